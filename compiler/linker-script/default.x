@@ -1,14 +1,21 @@
 ENTRY(_start)
 
+/* The ${...} placeholders are substituted by compiler::MemoryLayout */
 MEMORY
 {
     /* Define different memory regions for code and data memory */
-    FLASH (rx)  : ORIGIN = 0x00000000, LENGTH = 256K  /* Code memory */
-    RAM   (rwx) : ORIGIN = 0x40000, LENGTH = 4K /* Data memory */
+    FLASH (rx)  : ORIGIN = 0x00000000, LENGTH = ${ROM_SIZE}  /* Code memory */
+    RAM   (rwx) : ORIGIN = ${RAM_ORIGIN}, LENGTH = ${RAM_SIZE} /* Data memory */
+${RODATA_MEMORY}
 }
 
 SECTIONS
 {
+  /* bounds of the RAM, read by Phantom::from_elf to size the VM memory */
+  _ram_start = ORIGIN(RAM);
+  _ram_end = ORIGIN(RAM) + LENGTH(RAM);
+${RODATA_SYMBOLS}
+
   /* stack pointer is set to max memory limit */
   _stack_start = ORIGIN(RAM) + LENGTH(RAM);
 
@@ -43,7 +50,7 @@ SECTIONS
        This is required by LLD to ensure the LMA of the following .data
        section will have the correct alignment. */
     . = ALIGN(4);
-  } > RAM AT> RAM
+  } > ${RODATA_REGION} AT> ${RODATA_REGION}
 
   .data : ALIGN(4)
   {
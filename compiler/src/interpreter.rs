@@ -1,16 +1,30 @@
 use elf::{
-    abi::{PF_R, PF_W, PF_X, PT_LOAD},
+    abi::{EF_RISCV_RVC, EF_RISCV_RVE, PF_R, PF_W, PF_X, PT_LOAD},
+    endian::LittleEndian,
     segment::ProgramHeader,
+    ElfBytes,
+};
+use std::{
+    fmt,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
 };
 use tqdm::tqdm;
 
 use fhevm::{
+    input::EncryptedInput,
     instructions::{Instruction, InstructionsParser},
-    keys::{VMKeys, VMKeysPrepared},
+    keys::{VMKeys, VMKeysPrepared, VMPublicKey, VMPublicKeyPrepared},
     parameters::CryptographicParameters,
-    Interpreter,
+    rodata::rodata_offset,
+    serialization::{invalid_data, read_u64, write_u64, Header},
+    sources::{os_seed, Sources},
+    CustomInstructions, HaltStatus, InstructionSet, Interpreter, OpSet,
 };
 
+use crate::MemoryLayout;
+
 #[cfg(all(
     target_arch = "x86_64",
     target_feature = "avx2",
@@ -32,13 +46,11 @@ use poulpy_hal::{
     source::Source,
 };
 use poulpy_schemes::bin_fhe::blind_rotation::CGGI;
-use testvm::TestVM;
+pub use testvm::{ExitStatus, HostCall, TestVM, SYS_ABORT, SYS_EXIT, SYS_PRINT};
 
+mod rvc;
 mod testvm;
 
-// RAM size default to 4KB
-const RAM_SIZE: usize = 4 * 1024;
-
 mod macros {
     macro_rules! verbose_println {
     ($($arg:tt)*) => {
@@ -50,6 +62,7 @@ mod macros {
     pub(crate) use verbose_println;
 }
 
+#[derive(Clone)]
 pub struct BootMemory {
     data: Vec<u8>,
     offset: usize,
@@ -106,316 +119,1767 @@ impl OutputInfo {
     }
 }
 
-pub struct EncryptedVM {
-    params: CryptographicParameters<BackendImpl>,
-    sk_prepared: GLWESecretPrepared<Vec<u8>, BackendImpl>,
-    key_prepared: VMKeysPrepared<Vec<u8>, CGGI, BackendImpl>,
-    interpreter: Interpreter<BackendImpl>,
-    output_info: OutputInfo,
+/// Plaintext layout of an [EncryptedProgram].
+///
+/// Sizes are expressed in 32-bit words and addresses in bytes.
+#[derive(Clone)]
+pub struct ProgramLayout {
+    rom_size: usize,
     ram_offset: usize,
-    max_cycles: usize,
-    phantom_debug: bool,
+    ram_size: usize,
+    rodata_size: usize,
+    input_info: InputInfo,
+    output_info: OutputInfo,
+    instruction_set: InstructionSet,
+    rv32e: bool,
+    op_set: Option<OpSet>,
 }
 
-impl EncryptedVM {
-    pub fn execute(&mut self) {
-        let mut scratch: ScratchOwned<BackendImpl> =
-            ScratchOwned::alloc((1 << 24) * self.interpreter.threads());
+impl ProgramLayout {
+    pub fn rom_size(&self) -> &usize {
+        &self.rom_size
+    }
 
-        for _current_cycle in tqdm(0..self.max_cycles) {
-            if self.phantom_debug {
-                self.interpreter.cycle_debug(
-                    self.params.module(),
-                    &self.key_prepared,
-                    &self.sk_prepared,
-                    scratch.borrow(),
-                );
-            } else {
-                self.interpreter
-                    .cycle(self.params.module(), &self.key_prepared, scratch.borrow());
-            }
-        }
+    pub fn ram_offset(&self) -> &usize {
+        &self.ram_offset
     }
 
-    pub fn output_tape(&mut self) -> Vec<u8> {
-        let mut scratch: ScratchOwned<BackendImpl> = ScratchOwned::alloc(1 << 24);
+    pub fn ram_size(&self) -> &usize {
+        &self.ram_size
+    }
 
-        let mut data_decrypted: Vec<u32> = vec![0u32; RAM_SIZE >> 2];
-        // let mem_bytes: Vec<u8> =
-        self.interpreter.ram_decrypt(
-            self.params.module(),
-            &mut data_decrypted,
-            &self.sk_prepared,
-            scratch.borrow(),
-        );
-        let mut mem_bytes = Vec::with_capacity(data_decrypted.len() * 4);
-        for word in &data_decrypted {
-            mem_bytes.push((*word & 0xFF) as u8);
-            mem_bytes.push(((*word >> 8) & 0xFF) as u8);
-            mem_bytes.push(((*word >> 16) & 0xFF) as u8);
-            mem_bytes.push(((*word >> 24) & 0xFF) as u8);
+    /// Size of the read-only data bank, 0 if there is none.
+    pub fn rodata_size(&self) -> &usize {
+        &self.rodata_size
+    }
+
+    pub fn input_info(&self) -> &InputInfo {
+        &self.input_info
+    }
+
+    pub fn output_info(&self) -> &OutputInfo {
+        &self.output_info
+    }
+
+    pub fn instruction_set(&self) -> &InstructionSet {
+        &self.instruction_set
+    }
+
+    /// Returns true if the program only uses the 16 registers of RV32E.
+    pub fn rv32e(&self) -> bool {
+        self.rv32e
+    }
+
+    /// Operations the program is evaluated with, if pruned, see
+    /// [Phantom::with_op_set_pruning].
+    pub fn op_set(&self) -> Option<&OpSet> {
+        self.op_set.as_ref()
+    }
+
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        for value in [
+            self.rom_size,
+            self.ram_offset,
+            self.ram_size,
+            self.rodata_size,
+            self.input_info.start_addr,
+            self.input_info.size,
+            self.output_info.start_addr,
+            self.output_info.size,
+        ] {
+            write_u64(writer, value as u64)?;
+        }
+        write_u64(writer, instruction_set_id(self.instruction_set))?;
+        write_u64(writer, self.rv32e as u64)?;
+        write_u64(writer, self.op_set.is_some() as u64)?;
+        match &self.op_set {
+            Some(op_set) => op_set.write_to(writer),
+            None => Ok(()),
         }
-        // assert!(mem_bytes.len() == RAM_SIZE);
+    }
 
-        let mut output = Vec::with_capacity(self.output_info.size);
-        for i in 0..self.output_info.size {
-            output.push(mem_bytes[(self.output_info.start_addr + i - self.ram_offset) % RAM_SIZE]);
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut values: [usize; 8] = [0; 8];
+        for value in values.iter_mut() {
+            *value = read_u64(reader)? as usize;
         }
-        output
-        // vec![]
+        let instruction_set: InstructionSet = match read_u64(reader)? {
+            0 => InstructionSet::RV32I,
+            1 => InstructionSet::RV32M,
+            2 => InstructionSet::RV32IZbb,
+            3 => InstructionSet::RV32MZbb,
+            id => return Err(invalid_data(format!("unknown instruction set: {id}"))),
+        };
+        let rv32e: bool = match read_u64(reader)? {
+            0 => false,
+            1 => true,
+            flag => return Err(invalid_data(format!("invalid RV32E flag: {flag}"))),
+        };
+        let op_set: Option<OpSet> = match read_u64(reader)? {
+            0 => None,
+            1 => Some(OpSet::read_from(reader)?),
+            flag => return Err(invalid_data(format!("invalid op set flag: {flag}"))),
+        };
+        Ok(ProgramLayout {
+            rom_size: values[0],
+            ram_offset: values[1],
+            ram_size: values[2],
+            rodata_size: values[3],
+            input_info: InputInfo {
+                start_addr: values[4],
+                size: values[5],
+            },
+            output_info: OutputInfo {
+                start_addr: values[6],
+                size: values[7],
+            },
+            instruction_set,
+            rv32e,
+            op_set,
+        })
     }
 }
 
-/// Phantom VM: Encrypted Risc-v
-pub struct Phantom {
-    boot_rom: BootMemory,
-    boot_ram: BootMemory,
-    output_info: OutputInfo,
-    input_info: InputInfo,
-    _elf_bytes: Option<Vec<u8>>,
+fn instruction_set_id(instruction_set: InstructionSet) -> u64 {
+    match instruction_set {
+        InstructionSet::RV32I => 0,
+        InstructionSet::RV32M => 1,
+        InstructionSet::RV32IZbb => 2,
+        InstructionSet::RV32MZbb => 3,
+    }
 }
 
-impl Phantom {
-    pub fn from_elf(elf_bytes: Vec<u8>) -> Self {
-        let elf = elf::ElfBytes::<elf::endian::LittleEndian>::minimal_parse(&elf_bytes).unwrap();
+/// Returns true if `inst` is an instruction of the M extension.
+fn is_rv32m(inst: u32) -> bool {
+    inst & 0x7F == 0b0110011 && inst >> 25 == 0b0000001
+}
 
-        let phdrs: Vec<ProgramHeader> = elf
-            .segments()
-            .unwrap()
-            .iter()
-            .filter(|ph| ph.p_type == PT_LOAD)
-            .collect();
+/// Returns true if `inst` is an instruction of the Zbb extension.
+fn is_zbb(inst: u32) -> bool {
+    let funct3: u32 = (inst >> 12) & 0x7;
+    let funct7: u32 = inst >> 25;
+    let rs2: u32 = (inst >> 20) & 0x1F;
+    match inst & 0x7F {
+        0b0110011 => matches!(
+            (funct7, funct3),
+            (0b0100000, 0b100 | 0b110 | 0b111)
+                | (0b0000101, _)
+                | (0b0110000, 0b001 | 0b101)
+                | (0b0000100, 0b100)
+        ),
+        0b0010011 => matches!(
+            (funct7, funct3, rs2),
+            (0b0110000, 0b001, 0b00000..=0b00010 | 0b00100 | 0b00101)
+                | (0b0110000, 0b101, _)
+                | (0b0110100, 0b101, 0b11000)
+                | (0b0010100, 0b101, 0b00111)
+        ),
+        _ => false,
+    }
+}
 
-        // .text section: +rx
-        let txthdr = phdrs
-            .iter()
-            .find(|p| p.p_flags == PF_R + PF_X)
-            .expect("Program header for .text not found");
-        assert!(
-            txthdr.p_filesz == txthdr.p_memsz,
-            ".text phdr: contains uninitiliased values"
-        );
-        assert!(
-            txthdr.p_vaddr == 0,
-            ".text phdr: .text section must start from 0 offset"
-        );
-        let boot_rom = BootMemory::new(
-            txthdr.p_vaddr as usize,
-            txthdr.p_memsz as usize,
-            elf_bytes[txthdr.p_offset as usize..(txthdr.p_offset + txthdr.p_memsz) as usize]
-                .to_vec(),
-        );
-        // macros::verbose_println!("ROM SIZE: {} bytes", txthdr.p_memsz);
+/// Magic bytes of a serialized [EncryptedProgram].
+pub const ENCRYPTED_PROGRAM_MAGIC: [u8; 4] = *b"PZEP";
 
-        // load all +r/+rw headers
-        let hdrs: Vec<&ProgramHeader> = phdrs
-            .iter()
-            .filter(|p| p.p_flags == PF_R || p.p_flags == PF_R + PF_W)
-            .collect();
-        let mut ram_offset = 0;
-        let mut boot_ram_data = vec![0u8; RAM_SIZE];
-        if hdrs.len() > 0 {
-            ram_offset = hdrs[0].p_vaddr as usize;
-            // load ram with .inpdata,.rodata,.data.,etc.
-            hdrs.iter().for_each(|ph| {
-                // assert!(
-                //     ph.p_filesz == ph.p_memsz,
-                //     "Header contains uninitialised values (most probably .bss exists)"
-                // );
-                if ph.p_memsz > 0 && ph.p_filesz == ph.p_memsz {
-                    let vaddr = ph.p_vaddr as usize;
-                    boot_ram_data
-                        [(vaddr - ram_offset)..(vaddr + (ph.p_memsz as usize) - ram_offset)]
-                        .copy_from_slice(
-                            &elf_bytes[ph.p_offset as usize..(ph.p_memsz + ph.p_offset) as usize],
-                        );
-                }
-            });
-        }
-        let boot_ram = BootMemory::new(ram_offset, RAM_SIZE, boot_ram_data);
-        // println!("RAM OFFSET: {}", ram_offset);
+/// Version of the serialized [EncryptedProgram] layout.
+pub const ENCRYPTED_PROGRAM_VERSION: u32 = 5;
 
-        // gather input information
-        let inpdata_sec = elf
-            .section_header_by_name(".inpdata")
-            .expect(".inpdata section does not exist")
-            .expect(".inpdata section does not exist");
-        let input_info = InputInfo {
-            start_addr: inpdata_sec.sh_addr as usize,
-            size: inpdata_sec.sh_size as usize,
-        };
+/// Magic bytes of a seed-compressed serialized [EncryptedProgram].
+pub const ENCRYPTED_PROGRAM_COMPRESSED_MAGIC: [u8; 4] = *b"PZEC";
 
-        // gather output information
-        let outdata_sec = elf
-            .section_header_by_name(".outdata")
-            .expect(".outdata section does not exist")
-            .expect(".outdata section does not exist");
-        let output_info = OutputInfo {
-            start_addr: outdata_sec.sh_addr as usize,
-            size: outdata_sec.sh_size as usize,
-        };
+/// Encrypted version of a program that can be made public.
+///
+/// Holds the seven encrypted ROMs and the encrypted read-only data bank, if
+/// any, along with the plaintext layout of the boot RAM, so that it can be
+/// evaluated without the ELF.
+pub struct EncryptedProgram {
+    layout: ProgramLayout,
+    interpreter: Interpreter<BackendImpl>,
+}
 
-        // println!(
-        //     ".inpdata section: size={}, flag={}, v_addr={}, values={:?}",
-        //     inpdata_sec.sh_size,
-        //     inpdata_sec.sh_flags,
-        //     inpdata_sec.sh_addr,
-        //     &elf_bytes[inpdata_sec.sh_offset as usize
-        //         ..(inpdata_sec.sh_offset + inpdata_sec.sh_size) as usize]
-        // );
+impl EncryptedProgram {
+    pub fn layout(&self) -> &ProgramLayout {
+        &self.layout
+    }
 
-        // println!(
-        //     ".outdata section: size={}, flag={}, v_addr={}, values={:?}",
-        //     outdata_sec.sh_size,
-        //     outdata_sec.sh_flags,
-        //     outdata_sec.sh_addr,
-        //     &elf_bytes[outdata_sec.sh_offset as usize
-        //         ..(outdata_sec.sh_offset + outdata_sec.sh_size) as usize]
-        // );
+    /// Returns an [Interpreter] loaded with the encrypted ROM.
+    /// The RAM still has to be provided by the data owner.
+    pub fn into_interpreter(self) -> Interpreter<BackendImpl> {
+        self.interpreter
+    }
 
-        Phantom {
-            boot_rom,
-            boot_ram,
-            output_info,
-            input_info,
-            _elf_bytes: Some(elf_bytes),
-        }
+    /// Serializes the program as: [Header] | [ProgramLayout] | encrypted ROMs
+    /// | encrypted read-only data bank.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let params: CryptographicParameters<BackendImpl> = CryptographicParameters::new();
+        Header::new(
+            ENCRYPTED_PROGRAM_MAGIC,
+            ENCRYPTED_PROGRAM_VERSION,
+            params.fingerprint(),
+        )
+        .write_to(writer)?;
+        self.layout.write_to(writer)?;
+        self.interpreter.rom_write_to(writer)
     }
 
-    pub fn boot_rom(&self) -> &BootMemory {
-        &self.boot_rom
+    /// Loads a program written by [EncryptedProgram::write_to] into a fresh [Interpreter].
+    ///
+    /// Custom instructions are not serialized: they must be registered again on
+    /// the interpreter with [Interpreter::set_custom_instructions].
+    pub fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let params: CryptographicParameters<BackendImpl> = CryptographicParameters::new();
+        Header::read_from(reader)?.check(&Header::new(
+            ENCRYPTED_PROGRAM_MAGIC,
+            ENCRYPTED_PROGRAM_VERSION,
+            params.fingerprint(),
+        ))?;
+        let layout: ProgramLayout = ProgramLayout::read_from(reader)?;
+        let mut interpreter: Interpreter<BackendImpl> =
+            Interpreter::new(&params, layout.rom_size, layout.ram_size);
+        interpreter.set_instruction_set(layout.instruction_set);
+        interpreter.set_op_set(layout.op_set);
+        interpreter.set_rv32e(layout.rv32e);
+        interpreter.set_rodata_size(layout.rodata_size);
+        interpreter.rom_read_from(reader)?;
+        Ok(EncryptedProgram {
+            layout,
+            interpreter,
+        })
     }
 
-    pub fn boot_ram(&self) -> &BootMemory {
-        &self.boot_ram
+    /// Loads and decompresses a program written by
+    /// [PhantomClient::encrypt_rom_compressed_to] into a fresh [Interpreter].
+    ///
+    /// As with [EncryptedProgram::read_from], custom instructions must be
+    /// registered again on the interpreter.
+    pub fn read_compressed_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let params: CryptographicParameters<BackendImpl> = CryptographicParameters::new();
+        Header::read_from(reader)?.check(&Header::new(
+            ENCRYPTED_PROGRAM_COMPRESSED_MAGIC,
+            ENCRYPTED_PROGRAM_VERSION,
+            params.fingerprint(),
+        ))?;
+        let layout: ProgramLayout = ProgramLayout::read_from(reader)?;
+        let mut interpreter: Interpreter<BackendImpl> =
+            Interpreter::new(&params, layout.rom_size, layout.ram_size);
+        interpreter.set_instruction_set(layout.instruction_set);
+        interpreter.set_op_set(layout.op_set);
+        interpreter.set_rv32e(layout.rv32e);
+        interpreter.set_rodata_size(layout.rodata_size);
+        interpreter.rom_read_compressed_from(params.module(), reader)?;
+        Ok(EncryptedProgram {
+            layout,
+            interpreter,
+        })
     }
+}
 
-    pub fn input_info(&self) -> &InputInfo {
-        &self.input_info
+/// Data owner side of Phantom.
+///
+/// Holds the GLWE/LWE secrets and is responsible for key generation,
+/// encryption of the program and its input, and decryption of the output.
+pub struct PhantomClient {
+    params: CryptographicParameters<BackendImpl>,
+    sk_glwe: GLWESecret<Vec<u8>>,
+    sk_lwe: LWESecret<Vec<u8>>,
+    sk_prepared: GLWESecretPrepared<Vec<u8>, BackendImpl>,
+    sk_seed: [u8; 32],
+    sources: Sources,
+    boot_rom: BootMemory,
+    boot_ram: BootMemory,
+    boot_rodata: Option<BootMemory>,
+    input_info: InputInfo,
+    output_info: OutputInfo,
+    instruction_set: InstructionSet,
+    custom_instructions: CustomInstructions,
+    rv32e: bool,
+    op_set_pruning: bool,
+    // PHANTOM_DEBUG: checks each cycle against a plaintext execution, only in
+    // an [EncryptedVM], as the debug interpreter holds the plaintext
+    phantom_debug: bool,
+}
+
+impl PhantomClient {
+    /// Generates a fresh secret-key for the program loaded in `phantom`,
+    /// with randomness drawn from the OS entropy.
+    pub fn new(phantom: &Phantom) -> Self {
+        Self::with_sources(phantom, os_seed(), Sources::from_os_entropy())
     }
 
-    pub fn output_info(&self) -> &OutputInfo {
-        &self.output_info
+    /// Same as [PhantomClient::new] but with all randomness derived from `seed`.
+    ///
+    /// INSECURE: the secret-key can be recovered from `seed`.
+    /// Only meant for tests and reproducible benchmarks.
+    pub fn with_insecure_seed(phantom: &Phantom, seed: [u8; 32]) -> Self {
+        let mut sources: Sources = Sources::insecure_from_seed(seed);
+        let sk_seed: [u8; 32] = sources.xs.new_seed();
+        Self::with_sources(phantom, sk_seed, sources)
     }
 
-    pub fn encrypted_vm(&self, input_tape: &[u8], max_cycles: usize) -> EncryptedVM {
-        // map .text section to collection of Instructions
-        // boot_rom always has offset = 0
-        assert!(self.boot_rom.data.len() % 4 == 0);
-        let mut parser = InstructionsParser::new();
-        self.boot_rom
-            .data
-            .chunks_exact(4)
-            .map(|four_bytes| {
-                let mut inst = 0u32;
-                for i in 0..4 {
-                    inst += (four_bytes[i] as u32) << (i * 8);
-                }
-                Instruction::new(inst)
-            })
-            .for_each(|i| parser.add(i));
+    /// Restores the client whose secret-key was generated from `sk_seed`,
+    /// see [PhantomClient::secret_seed].
+    pub fn from_secret_seed(phantom: &Phantom, sk_seed: [u8; 32]) -> Self {
+        Self::with_sources(phantom, sk_seed, Sources::from_os_entropy())
+    }
 
-        // // setup RAM
-        let ram_offset: usize = self.boot_ram.offset;
-        let mut ram_with_input: Vec<u8> = self.boot_ram.data.clone();
-        // read input tape
-        assert!(input_tape.len() == self.input_info.size);
-        ram_with_input[(self.input_info.start_addr - ram_offset)
-            ..(self.input_info.start_addr + self.input_info.size - ram_offset)]
-            .copy_from_slice(input_tape);
-        // RAM: byte vector -> u32 vec
-        let ram_data_u32 = ram_with_input
-            .chunks_exact(4)
-            .map(|four_bytes| {
-                let mut date_u32 = 0u32;
-                for i in 0..4 {
-                    date_u32 += (four_bytes[i] as u32) << (i * 8);
-                }
-                date_u32
-            })
-            .collect::<Vec<u32>>();
+    /// Seed from which the secret-key is derived.
+    ///
+    /// SECRET: it must be stored with the same care as the secret-key itself.
+    pub fn secret_seed(&self) -> [u8; 32] {
+        self.sk_seed
+    }
 
-        let verbose_timings = std::env::var("PHANTOM_VERBOSE_TIMINGS")
-            .map(|val| val == "1" || val.eq_ignore_ascii_case("true"))
-            .unwrap_or(false);
-        let default_threads = num_cpus::get_physical();
-        let threads = std::env::var("PHANTOM_THREADS")
-            .map(|val| val.parse::<usize>().unwrap_or(default_threads))
-            .unwrap_or(default_threads);
+    fn with_sources(phantom: &Phantom, sk_seed: [u8; 32], sources: Sources) -> Self {
         let phantom_debug = std::env::var("PHANTOM_DEBUG")
             .map(|val| val == "1" || val.eq_ignore_ascii_case("true"))
             .unwrap_or(false);
 
         // Initializing cryptographic parameters
         let params = CryptographicParameters::<BackendImpl>::new();
-        let mut source_xs: Source = Source::new([0u8; 32]);
-        let mut source_xa: Source = Source::new([0u8; 32]);
-        let mut source_xe: Source = Source::new([0u8; 32]);
-        let mut scratch: ScratchOwned<BackendImpl> = ScratchOwned::alloc(1 << 24);
 
-        // Generates a new secret-key along with the public evaluation keys.
+        // Generates a new secret-key.
+        let mut source_xs: Source = Source::new(sk_seed);
         let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
         sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
 
         let mut sk_lwe: LWESecret<Vec<u8>> = LWESecret::alloc(params.n_lwe());
         sk_lwe.fill_binary_block(params.lwe_block_size(), &mut source_xs);
 
-        let mut interpreter: Interpreter<BackendImpl> = if phantom_debug {
-            Interpreter::new_with_debug(&params, self.boot_rom.size >> 2, self.boot_ram.size >> 2)
-        } else {
-            Interpreter::new(&params, self.boot_rom.size >> 2, self.boot_ram.size >> 2)
-        };
-
         let mut sk_prepared: GLWESecretPrepared<Vec<u8>, BackendImpl> =
             GLWESecretPrepared::alloc(params.module(), params.rank());
         sk_prepared.prepare(params.module(), &sk_glwe);
 
+        PhantomClient {
+            params,
+            sk_glwe,
+            sk_lwe,
+            sk_prepared,
+            sk_seed,
+            sources,
+            boot_rom: phantom.boot_rom.clone(),
+            boot_ram: phantom.boot_ram.clone(),
+            boot_rodata: phantom.boot_rodata.clone(),
+            input_info: phantom.input_info.clone(),
+            output_info: phantom.output_info.clone(),
+            instruction_set: phantom.instruction_set,
+            custom_instructions: phantom.custom_instructions.clone(),
+            rv32e: phantom.rv32e,
+            op_set_pruning: phantom.op_set_pruning,
+            phantom_debug,
+        }
+    }
+
+    /// Public evaluation keys to be handed to the [PhantomServer].
+    pub fn evaluation_keys(&mut self) -> VMKeys<Vec<u8>, CGGI> {
+        VMKeys::encrypt_sk(
+            &self.params,
+            &self.sk_lwe,
+            &self.sk_glwe,
+            &mut self.sources.xa,
+            &mut self.sources.xe,
+        )
+    }
+
+    /// Public encryption key to be handed to input providers, see [PhantomInputEncryptor].
+    pub fn public_key(&mut self) -> VMPublicKey<Vec<u8>> {
+        VMPublicKey::generate(
+            &self.params,
+            &self.sk_prepared,
+            &mut self.sources.xa,
+            &mut self.sources.xe,
+        )
+    }
+
+    /// Encrypts the program ROM and the boot RAM (with `input_tape` written
+    /// into .inpdata) into a fresh [Interpreter] ready to be evaluated.
+    pub fn encrypt_program(&mut self, input_tape: &[u8]) -> Interpreter<BackendImpl> {
+        self.encrypt_program_internal::<false>(input_tape)
+    }
+
+    // With DEBUG, the interpreter also holds the plaintext ROM and RAM, so it
+    // must never leave the client: only [EncryptedVM] runs it.
+    fn encrypt_program_internal<const DEBUG: bool>(
+        &mut self,
+        input_tape: &[u8],
+    ) -> Interpreter<BackendImpl> {
+        let mut interpreter: Interpreter<BackendImpl> =
+            self.encrypt_rom_internal::<DEBUG>().into_interpreter();
+        self.encrypt_ram(&mut interpreter, input_tape);
+        interpreter
+    }
+
+    /// Encrypts the program ROM and the read-only data bank, if any, into a
+    /// publishable [EncryptedProgram].
+    pub fn encrypt_rom(&mut self) -> EncryptedProgram {
+        self.encrypt_rom_internal::<false>()
+    }
+
+    fn encrypt_rom_internal<const DEBUG: bool>(&mut self) -> EncryptedProgram {
+        let parser: InstructionsParser = self.instructions();
+        let layout: ProgramLayout = self.layout();
+
+        let params: &CryptographicParameters<BackendImpl> = &self.params;
+        let mut scratch: ScratchOwned<BackendImpl> = ScratchOwned::alloc(1 << 24);
+
+        let mut interpreter: Interpreter<BackendImpl> = if DEBUG {
+            Interpreter::new_with_debug(params, layout.rom_size, layout.ram_size)
+        } else {
+            Interpreter::new(params, layout.rom_size, layout.ram_size)
+        };
+        interpreter.set_instruction_set(layout.instruction_set);
+        interpreter.set_custom_instructions(&self.custom_instructions);
+        interpreter.set_op_set(layout.op_set);
+        interpreter.set_rv32e(layout.rv32e);
+        interpreter.set_rodata_size(layout.rodata_size);
+
         interpreter.instructions_encrypt_sk(
             params.module(),
             &parser,
-            &sk_prepared,
-            &mut source_xa,
-            &mut source_xe,
+            &self.sk_prepared,
+            &mut self.sources.xa,
+            &mut self.sources.xe,
             scratch.borrow(),
         );
-        let key: VMKeys<Vec<u8>, CGGI> =
-            VMKeys::encrypt_sk(&params, &sk_lwe, &sk_glwe, &mut source_xa, &mut source_xe);
 
-        let mut key_prepared: VMKeysPrepared<Vec<u8>, CGGI, BackendImpl> =
-            VMKeysPrepared::alloc(&params);
-        key_prepared.prepare(params.module(), &key, scratch.borrow());
+        if let Some(boot_rodata) = &self.boot_rodata {
+            interpreter.rodata_encrypt_sk(
+                params.module(),
+                &words(&boot_rodata.data),
+                &self.sk_prepared,
+                &mut self.sources.xa,
+                &mut self.sources.xe,
+                scratch.borrow(),
+            );
+        }
 
-        interpreter.ram_encrypt_sk(
+        EncryptedProgram {
+            layout,
+            interpreter,
+        }
+    }
+
+    /// Same as [PhantomClient::encrypt_rom] but directly writes the program with
+    /// seed-compressed ciphertexts, as: [Header] | [ProgramLayout] | compressed ROMs
+    /// | compressed read-only data bank.
+    ///
+    /// Load it back with [EncryptedProgram::read_compressed_from].
+    pub fn encrypt_rom_compressed_to<W: Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        let parser: InstructionsParser = self.instructions();
+        let layout: ProgramLayout = self.layout();
+
+        let params: &CryptographicParameters<BackendImpl> = &self.params;
+        let mut scratch: ScratchOwned<BackendImpl> = ScratchOwned::alloc(1 << 24);
+
+        Header::new(
+            ENCRYPTED_PROGRAM_COMPRESSED_MAGIC,
+            ENCRYPTED_PROGRAM_VERSION,
+            params.fingerprint(),
+        )
+        .write_to(writer)?;
+        layout.write_to(writer)?;
+
+        let mut interpreter: Interpreter<BackendImpl> =
+            Interpreter::new(params, layout.rom_size, layout.ram_size);
+        interpreter.set_rv32e(layout.rv32e);
+        interpreter.set_rodata_size(layout.rodata_size);
+        interpreter.instructions_encrypt_sk_compressed_to(
             params.module(),
-            &ram_data_u32,
-            &sk_prepared,
-            &mut source_xa,
-            &mut source_xe,
+            &parser,
+            &self.sk_prepared,
+            &mut self.sources.xa,
+            &mut self.sources.xe,
             scratch.borrow(),
-        );
-
-        interpreter.set_verbose_timings(verbose_timings);
-        interpreter.set_threads(threads);
+            writer,
+        )?;
 
-        EncryptedVM {
-            params,
-            sk_prepared,
-            key_prepared,
-            interpreter,
-            output_info: self.output_info.clone(),
-            ram_offset: self.boot_ram.offset,
-            max_cycles,
-            phantom_debug,
+        match &self.boot_rodata {
+            Some(boot_rodata) => interpreter.rodata_encrypt_sk_compressed_to(
+                params.module(),
+                &words(&boot_rodata.data),
+                &self.sk_prepared,
+                &mut self.sources.xa,
+                &mut self.sources.xe,
+                scratch.borrow(),
+                writer,
+            ),
+            None => Ok(()),
         }
     }
 
-    pub fn test_vm(&self, max_cycles: usize) -> TestVM {
-        TestVM::init(
-            &self.boot_rom,
-            &self.boot_ram,
-            &self.input_info,
-            &self.output_info,
-            max_cycles,
+    /// Same as [PhantomClient::encrypt_ram] but writes the boot RAM with
+    /// seed-compressed ciphertexts instead of loading it into `interpreter`.
+    ///
+    /// Load it back with [PhantomServer::ram_read_compressed_from].
+    pub fn encrypt_ram_compressed_to<W: Write>(
+        &mut self,
+        interpreter: &Interpreter<BackendImpl>,
+        input_tape: &[u8],
+        writer: &mut W,
+    ) -> io::Result<()> {
+        let ram_data_u32: Vec<u32> = ram_data(&self.boot_ram, &self.input_info, input_tape);
+
+        let mut scratch: ScratchOwned<BackendImpl> = ScratchOwned::alloc(1 << 24);
+
+        interpreter.ram_encrypt_sk_compressed_to(
+            self.params.module(),
+            &ram_data_u32,
+            &self.sk_prepared,
+            &mut self.sources.xa,
+            &mut self.sources.xe,
+            scratch.borrow(),
+            writer,
         )
     }
+
+    /// Encrypts the boot RAM, with `input_tape` written into .inpdata, into `interpreter`.
+    pub fn encrypt_ram(&mut self, interpreter: &mut Interpreter<BackendImpl>, input_tape: &[u8]) {
+        let ram_data_u32: Vec<u32> = ram_data(&self.boot_ram, &self.input_info, input_tape);
+
+        let mut scratch: ScratchOwned<BackendImpl> = ScratchOwned::alloc(1 << 24);
+
+        interpreter.ram_encrypt_sk(
+            self.params.module(),
+            &ram_data_u32,
+            &self.sk_prepared,
+            &mut self.sources.xa,
+            &mut self.sources.xe,
+            scratch.borrow(),
+        );
+    }
+
+    /// Encrypts the boot RAM with a zeroed .inpdata into `interpreter`.
+    ///
+    /// The resulting RAM can be reused across inputs, each encrypted separately
+    /// with [PhantomClient::encrypt_input] or [PhantomInputEncryptor::encrypt_input]
+    /// and injected with [PhantomServer::inject_input].
+    pub fn encrypt_boot_ram(&mut self, interpreter: &mut Interpreter<BackendImpl>) {
+        let zero_tape: Vec<u8> = vec![0u8; self.input_info.size];
+        self.encrypt_ram(interpreter, &zero_tape);
+    }
+
+    /// Encrypts `input_tape` alone, to be injected into .inpdata of an encrypted boot RAM.
+    pub fn encrypt_input(&mut self, input_tape: &[u8]) -> EncryptedInput {
+        let (word_offset, words) = input_data(self.boot_ram.offset, &self.input_info, input_tape);
+
+        let mut scratch: ScratchOwned<BackendImpl> = ScratchOwned::alloc(1 << 24);
+
+        let mut input: EncryptedInput =
+            EncryptedInput::alloc(&self.params, word_offset, words.len());
+        input.encrypt_sk(
+            self.params.module(),
+            &words,
+            &self.sk_prepared,
+            &mut self.sources.xa,
+            &mut self.sources.xe,
+            scratch.borrow(),
+        );
+        input
+    }
+
+    fn instructions(&self) -> InstructionsParser {
+        // map .text section to collection of Instructions
+        // boot_rom always has offset = 0
+        assert!(self.boot_rom.data.len() % 4 == 0);
+        let mut parser =
+            InstructionsParser::new().with_custom_instructions(&self.custom_instructions);
+        if self.rv32e {
+            parser = parser.with_rv32e();
+        }
+        self.boot_rom
+            .data
+            .chunks_exact(4)
+            .map(|four_bytes| {
+                let mut inst = 0u32;
+                for i in 0..4 {
+                    inst += (four_bytes[i] as u32) << (i * 8);
+                }
+                Instruction::new(inst)
+            })
+            .for_each(|i| parser.add(i));
+        parser
+    }
+
+    fn layout(&self) -> ProgramLayout {
+        ProgramLayout {
+            rom_size: self.boot_rom.size >> 2,
+            ram_offset: self.boot_ram.offset,
+            ram_size: self.boot_ram.size >> 2,
+            rodata_size: self
+                .boot_rodata
+                .as_ref()
+                .map_or(0, |rodata| rodata.size >> 2),
+            input_info: self.input_info.clone(),
+            output_info: self.output_info.clone(),
+            instruction_set: self.instruction_set,
+            rv32e: self.rv32e,
+            op_set: self
+                .op_set_pruning
+                .then(|| OpSet::from_instructions(&self.instructions())),
+        }
+    }
+
+    /// Decrypts the words of the RAM of `interpreter` covering .outdata and
+    /// returns the content of .outdata.
+    pub fn output_tape(&self, interpreter: &Interpreter<BackendImpl>) -> Vec<u8> {
+        let mut scratch: ScratchOwned<BackendImpl> = ScratchOwned::alloc(1 << 24);
+
+        let start: usize = self.output_info.start_addr - self.boot_ram.offset;
+        let end: usize = start + self.output_info.size;
+        let word_offset: usize = start >> 2;
+
+        let mut data_decrypted: Vec<u32> = vec![0u32; end.div_ceil(4) - word_offset];
+        interpreter.ram_decrypt_range(
+            self.params.module(),
+            word_offset,
+            &mut data_decrypted,
+            &self.sk_prepared,
+            scratch.borrow(),
+        );
+
+        let mem_bytes: Vec<u8> = data_decrypted
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect();
+        mem_bytes[start - (word_offset << 2)..end - (word_offset << 2)].to_vec()
+    }
+
+    /// Decrypts the halt status of `interpreter`: whether the program exited
+    /// within the executed cycles and, if so, the cycle of the exit instruction.
+    pub fn halt_status(&self, interpreter: &Interpreter<BackendImpl>) -> HaltStatus {
+        let mut scratch: ScratchOwned<BackendImpl> = ScratchOwned::alloc(1 << 24);
+        interpreter.halt_status_decrypt(self.params.module(), &self.sk_prepared, scratch.borrow())
+    }
+}
+
+/// Boot RAM as words, with `input_tape` written into .inpdata.
+fn ram_data(boot_ram: &BootMemory, input_info: &InputInfo, input_tape: &[u8]) -> Vec<u32> {
+    // // setup RAM
+    let ram_offset: usize = boot_ram.offset;
+    let mut ram_with_input: Vec<u8> = boot_ram.data.clone();
+    // read input tape
+    assert!(input_tape.len() == input_info.size);
+    ram_with_input[(input_info.start_addr - ram_offset)
+        ..(input_info.start_addr + input_info.size - ram_offset)]
+        .copy_from_slice(input_tape);
+    // RAM: byte vector -> u32 vec
+    words(&ram_with_input)
+}
+
+/// Little-endian words of `bytes`.
+fn words(bytes: &[u8]) -> Vec<u32> {
+    bytes
+        .chunks_exact(4)
+        .map(|four_bytes| {
+            let mut date_u32 = 0u32;
+            for i in 0..4 {
+                date_u32 += (four_bytes[i] as u32) << (i * 8);
+            }
+            date_u32
+        })
+        .collect::<Vec<u32>>()
+}
+
+/// Words of the RAM covering .inpdata, with `input_tape` written at its position and the
+/// remaining bytes set to zero, along with the index of the first word in the RAM.
+fn input_data(ram_offset: usize, input_info: &InputInfo, input_tape: &[u8]) -> (usize, Vec<u32>) {
+    assert!(input_tape.len() == input_info.size);
+    let start: usize = input_info.start_addr - ram_offset;
+    let end: usize = start + input_info.size;
+    let word_offset: usize = start >> 2;
+    let mut words: Vec<u32> = vec![0u32; end.div_ceil(4) - word_offset];
+    for (i, byte) in input_tape.iter().enumerate() {
+        let addr: usize = start + i - (word_offset << 2);
+        words[addr >> 2] |= (*byte as u32) << ((addr & 3) * 8);
+    }
+    (word_offset, words)
+}
+
+/// Input provider side of Phantom.
+///
+/// Encrypts input tapes with the [VMPublicKey] of a [PhantomClient], so that
+/// inputs can be supplied by parties that do not hold the secret-key.
+pub struct PhantomInputEncryptor {
+    params: CryptographicParameters<BackendImpl>,
+    pk_prepared: VMPublicKeyPrepared<Vec<u8>, BackendImpl>,
+    sources: Sources,
+    boot_ram: BootMemory,
+    input_info: InputInfo,
+}
+
+impl PhantomInputEncryptor {
+    /// Creates an encryptor for the program loaded in `phantom`, with
+    /// randomness drawn from the OS entropy.
+    pub fn new(phantom: &Phantom, pk: &VMPublicKey<Vec<u8>>) -> Self {
+        Self::with_sources(phantom, pk, Sources::from_os_entropy())
+    }
+
+    /// Same as [PhantomInputEncryptor::new] but with all randomness derived from `seed`.
+    ///
+    /// INSECURE: only meant for tests and reproducible benchmarks.
+    pub fn with_insecure_seed(
+        phantom: &Phantom,
+        pk: &VMPublicKey<Vec<u8>>,
+        seed: [u8; 32],
+    ) -> Self {
+        Self::with_sources(phantom, pk, Sources::insecure_from_seed(seed))
+    }
+
+    fn with_sources(phantom: &Phantom, pk: &VMPublicKey<Vec<u8>>, sources: Sources) -> Self {
+        let params = CryptographicParameters::<BackendImpl>::new();
+        let mut pk_prepared: VMPublicKeyPrepared<Vec<u8>, BackendImpl> =
+            VMPublicKeyPrepared::alloc(&params);
+        pk_prepared.prepare(params.module(), pk);
+        PhantomInputEncryptor {
+            params,
+            pk_prepared,
+            sources,
+            boot_ram: phantom.boot_ram.clone(),
+            input_info: phantom.input_info.clone(),
+        }
+    }
+
+    /// Encrypts the boot RAM, with `input_tape` written into .inpdata, into `interpreter`.
+    pub fn encrypt_ram(&mut self, interpreter: &mut Interpreter<BackendImpl>, input_tape: &[u8]) {
+        let ram_data_u32: Vec<u32> = ram_data(&self.boot_ram, &self.input_info, input_tape);
+
+        let mut scratch: ScratchOwned<BackendImpl> = ScratchOwned::alloc(1 << 24);
+
+        interpreter.ram_encrypt_pk(
+            self.params.module(),
+            &ram_data_u32,
+            &self.pk_prepared,
+            &mut self.sources.xs,
+            &mut self.sources.xe,
+            scratch.borrow(),
+        );
+    }
+
+    /// Encrypts `input_tape` alone, to be injected into .inpdata of an encrypted boot RAM
+    /// with [PhantomServer::inject_input].
+    pub fn encrypt_input(&mut self, input_tape: &[u8]) -> EncryptedInput {
+        let (word_offset, words) = input_data(self.boot_ram.offset, &self.input_info, input_tape);
+
+        let mut scratch: ScratchOwned<BackendImpl> = ScratchOwned::alloc(1 << 24);
+
+        let mut input: EncryptedInput =
+            EncryptedInput::alloc(&self.params, word_offset, words.len());
+        input.encrypt_pk(
+            self.params.module(),
+            &words,
+            &self.pk_prepared,
+            &mut self.sources.xs,
+            &mut self.sources.xe,
+            scratch.borrow(),
+        );
+        input
+    }
+}
+
+/// Evaluator side of Phantom.
+///
+/// Only holds the prepared evaluation keys and the encrypted [Interpreter]
+/// state: it never has access to the secret-key.
+pub struct PhantomServer {
+    params: CryptographicParameters<BackendImpl>,
+    key_prepared: VMKeysPrepared<Vec<u8>, CGGI, BackendImpl>,
+    interpreter: Interpreter<BackendImpl>,
+    checkpoint: Option<(PathBuf, usize)>,
+}
+
+impl PhantomServer {
+    pub fn new(
+        key_prepared: VMKeysPrepared<Vec<u8>, CGGI, BackendImpl>,
+        mut interpreter: Interpreter<BackendImpl>,
+    ) -> Self {
+        let verbose_timings = std::env::var("PHANTOM_VERBOSE_TIMINGS")
+            .map(|val| val == "1" || val.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+        let default_threads = num_cpus::get_physical();
+        let threads = std::env::var("PHANTOM_THREADS")
+            .map(|val| val.parse::<usize>().unwrap_or(default_threads))
+            .unwrap_or(default_threads);
+
+        interpreter.set_verbose_timings(verbose_timings);
+        interpreter.set_threads(threads);
+
+        PhantomServer {
+            params: CryptographicParameters::<BackendImpl>::new(),
+            key_prepared,
+            interpreter,
+            checkpoint: None,
+        }
+    }
+
+    /// Prepares the evaluation keys received from the [PhantomClient].
+    pub fn from_keys(keys: &VMKeys<Vec<u8>, CGGI>, interpreter: Interpreter<BackendImpl>) -> Self {
+        let params = CryptographicParameters::<BackendImpl>::new();
+        let mut scratch: ScratchOwned<BackendImpl> = ScratchOwned::alloc(1 << 24);
+        let mut key_prepared: VMKeysPrepared<Vec<u8>, CGGI, BackendImpl> =
+            VMKeysPrepared::alloc(&params);
+        key_prepared.prepare(params.module(), keys, scratch.borrow());
+        Self::new(key_prepared, interpreter)
+    }
+
+    /// Resumes the execution from a checkpoint written by [PhantomServer::checkpoint_to].
+    ///
+    /// The checkpoint only holds the encrypted state, so the [PhantomClient] must
+    /// provide its evaluation keys and the encrypted program again: `interpreter`
+    /// must be loaded with the ROM, e.g. from [PhantomClient::encrypt_rom] or
+    /// [EncryptedProgram::read_from].
+    pub fn resume_from<P: AsRef<Path>>(
+        keys: &VMKeys<Vec<u8>, CGGI>,
+        mut interpreter: Interpreter<BackendImpl>,
+        path: P,
+    ) -> io::Result<Self> {
+        let params = CryptographicParameters::<BackendImpl>::new();
+        let mut reader = BufReader::new(File::open(path)?);
+        interpreter.checkpoint_read_from(&params, &mut reader)?;
+        Ok(Self::from_keys(keys, interpreter))
+    }
+
+    /// Executes the remaining cycles up to `max_cycles`, writing a checkpoint
+    /// every time the configured number of cycles has been executed,
+    /// see [PhantomServer::set_checkpoint].
+    ///
+    /// A checkpoint that cannot be written is reported on stderr and the
+    /// execution goes on, the previous checkpoint, if any, being left intact.
+    pub fn execute(&mut self, max_cycles: usize) {
+        self.execute_internal(max_cycles, None);
+    }
+
+    // With `sk`, checks each cycle against the debug VM of the interpreter, if any.
+    fn execute_internal(
+        &mut self,
+        max_cycles: usize,
+        sk: Option<&GLWESecretPrepared<Vec<u8>, BackendImpl>>,
+    ) {
+        let mut scratch: ScratchOwned<BackendImpl> =
+            ScratchOwned::alloc((1 << 24) * self.interpreter.threads());
+
+        let start: usize = self.interpreter.cycle_count();
+        for current_cycle in tqdm(start..max_cycles) {
+            match sk {
+                Some(sk) => self.interpreter.cycle_debug(
+                    self.params.module(),
+                    &self.key_prepared,
+                    sk,
+                    scratch.borrow(),
+                ),
+                None => self.interpreter.cycle(
+                    self.params.module(),
+                    &self.key_prepared,
+                    scratch.borrow(),
+                ),
+            }
+
+            if let Some((path, every)) = &self.checkpoint {
+                if (current_cycle + 1) % every == 0 {
+                    if let Err(err) = self.checkpoint_to(path) {
+                        eprintln!(
+                            "failed to write checkpoint {path:?} at cycle {}: {err}",
+                            current_cycle + 1
+                        );
+                    }
+                }
+            }
+        }
+        self.interpreter.halt_status_update(
+            self.params.module(),
+            &self.key_prepared,
+            scratch.borrow(),
+        );
+    }
+
+    /// Enables an automatic checkpoint to `path` every `every` cycles.
+    pub fn set_checkpoint<P: AsRef<Path>>(&mut self, path: P, every: usize) {
+        assert!(every > 0);
+        self.checkpoint = Some((path.as_ref().to_path_buf(), every));
+    }
+
+    /// Writes the [Interpreter] checkpoint of the current state to `path`.
+    ///
+    /// It only holds the encrypted registers, RAM, PC and halt status, along
+    /// with the cycle count and measurements: neither the keys nor the ROM.
+    /// Resume it with [PhantomServer::resume_from].
+    pub fn checkpoint_to<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        // Written and synced to a temporary file first, then renamed, so that
+        // neither a crash nor a power loss leaves a truncated checkpoint behind.
+        let path: &Path = path.as_ref();
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        {
+            let mut writer = BufWriter::new(File::create(&tmp_path)?);
+            self.interpreter
+                .checkpoint_write_to(&self.params, &mut writer)?;
+            writer
+                .into_inner()
+                .map_err(io::IntoInnerError::into_error)?
+                .sync_all()?;
+        }
+        fs::rename(&tmp_path, path)?;
+        // Makes the rename itself durable
+        let dir: &Path = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        File::open(dir)?.sync_all()
+    }
+
+    /// Loads the boot RAM written by [PhantomClient::encrypt_ram_compressed_to].
+    pub fn ram_read_compressed_from<R: Read>(&mut self, reader: &mut R) -> io::Result<()> {
+        self.interpreter
+            .ram_read_compressed_from(self.params.module(), reader)
+    }
+
+    /// Injects an input encrypted by [PhantomClient::encrypt_input] or
+    /// [PhantomInputEncryptor::encrypt_input] into .inpdata.
+    ///
+    /// The RAM must have been encrypted with [PhantomClient::encrypt_boot_ram].
+    pub fn inject_input(&mut self, input: &EncryptedInput) {
+        let mut scratch: ScratchOwned<BackendImpl> = ScratchOwned::alloc(1 << 24);
+        self.interpreter
+            .ram_inject_input(self.params.module(), input, scratch.borrow());
+    }
+
+    pub fn interpreter(&self) -> &Interpreter<BackendImpl> {
+        &self.interpreter
+    }
+
+    pub fn into_interpreter(self) -> Interpreter<BackendImpl> {
+        self.interpreter
+    }
+}
+
+/// Client and server running in the same process.
+///
+/// Convenient for testing, but the secret-key lives next to the evaluator:
+/// use [PhantomClient] and [PhantomServer] for actual deployments.
+pub struct EncryptedVM {
+    client: PhantomClient,
+    server: PhantomServer,
+    max_cycles: usize,
+}
+
+impl EncryptedVM {
+    /// Executes the remaining cycles up to `max_cycles`, writing a checkpoint
+    /// every time the configured number of cycles has been executed,
+    /// see [EncryptedVM::set_checkpoint].
+    pub fn execute(&mut self) {
+        let sk: Option<&GLWESecretPrepared<Vec<u8>, BackendImpl>> = self
+            .client
+            .phantom_debug
+            .then_some(&self.client.sk_prepared);
+        self.server.execute_internal(self.max_cycles, sk);
+    }
+
+    /// Enables an automatic checkpoint to `path` every `every` cycles,
+    /// see [PhantomServer::set_checkpoint].
+    pub fn set_checkpoint<P: AsRef<Path>>(&mut self, path: P, every: usize) {
+        self.server.set_checkpoint(path, every);
+    }
+
+    /// Writes the server state to `path`, see [PhantomServer::checkpoint_to].
+    ///
+    /// The secret-key is not part of it: resuming with
+    /// [Phantom::resume_encrypted_vm] requires the same [PhantomClient], e.g.
+    /// restored from [PhantomClient::secret_seed].
+    pub fn checkpoint_to<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.server.checkpoint_to(path)
+    }
+
+    /// Client holding the secret-key of the VM.
+    pub fn client(&self) -> &PhantomClient {
+        &self.client
+    }
+
+    pub fn output_tape(&mut self) -> Vec<u8> {
+        self.client.output_tape(&self.server.interpreter)
+    }
+
+    /// Returns whether the program exited within the executed cycles, and at which cycle.
+    pub fn halt_status(&self) -> HaltStatus {
+        self.client.halt_status(&self.server.interpreter)
+    }
+}
+
+// Minimum number of bytes between the end of the data (and of the heap start,
+// `_end`) and the initial stack pointer `_stack_start`, same as the gap kept
+// by the allocator of the runtime.
+const MIN_STACK_HEADROOM: usize = 0x100;
+
+/// Error returned by [Phantom::try_from_elf] when the ELF is malformed or
+/// when the program does not fit in its RAM.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ElfLoadError {
+    /// The ELF cannot be parsed, for the given reason.
+    InvalidElf(String),
+    /// The `size` bytes of a segment at `offset` in the file are beyond its end `file_size`.
+    SegmentOutOfFile {
+        offset: usize,
+        size: usize,
+        file_size: usize,
+    },
+    /// The loadable segment [start, end) is not within the RAM [ram_start, ram_end).
+    SegmentOutOfRam {
+        start: usize,
+        end: usize,
+        ram_start: usize,
+        ram_end: usize,
+    },
+    /// The `region` [start, end) given by the symbols of the linker script
+    /// is not the one of a [MemoryLayout].
+    InvalidLayout {
+        region: &'static str,
+        start: usize,
+        end: usize,
+    },
+    /// The compressed instructions of .text cannot be expanded, for the given
+    /// reason, see [rvc].
+    RvcExpansion(String),
+    /// The data and .bss end at `end`, which leaves less than
+    /// `min_headroom` bytes to the heap and the stack starting at `stack_start`.
+    NoStackHeadroom {
+        end: usize,
+        stack_start: usize,
+        min_headroom: usize,
+    },
+}
+
+impl fmt::Display for ElfLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ElfLoadError::InvalidElf(reason) => write!(f, "invalid ELF: {reason}"),
+            ElfLoadError::SegmentOutOfFile {
+                offset,
+                size,
+                file_size,
+            } => write!(
+                f,
+                "segment of {size:#x} bytes at offset {offset:#x} exceeds the ELF of {file_size:#x} bytes"
+            ),
+            ElfLoadError::SegmentOutOfRam {
+                start,
+                end,
+                ram_start,
+                ram_end,
+            } => write!(
+                f,
+                "segment [{start:#x}, {end:#x}) does not fit in the RAM [{ram_start:#x}, {ram_end:#x}): \
+                 increase the RAM size of the MemoryLayout"
+            ),
+            ElfLoadError::RvcExpansion(reason) => {
+                write!(f, "cannot expand the compressed instructions: {reason}")
+            }
+            ElfLoadError::InvalidLayout { region, start, end } => write!(
+                f,
+                "invalid {region} region [{start:#x}, {end:#x}): link with the linker script of a MemoryLayout"
+            ),
+            ElfLoadError::NoStackHeadroom {
+                end,
+                stack_start,
+                min_headroom,
+            } => write!(
+                f,
+                "data ends at {end:#x}, less than {min_headroom:#x} bytes below the stack at {stack_start:#x}: \
+                 increase the RAM size of the MemoryLayout"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ElfLoadError {}
+
+/// Returns the `size` bytes of `elf_bytes` of the segment `ph`.
+fn segment_bytes<'a>(
+    elf_bytes: &'a [u8],
+    ph: &ProgramHeader,
+    size: usize,
+) -> Result<&'a [u8], ElfLoadError> {
+    let offset: usize = ph.p_offset as usize;
+    offset
+        .checked_add(size)
+        .and_then(|end| elf_bytes.get(offset..end))
+        .ok_or(ElfLoadError::SegmentOutOfFile {
+            offset,
+            size,
+            file_size: elf_bytes.len(),
+        })
+}
+
+// Returns the value of the symbol `name` of `elf`, if any.
+fn elf_symbol(elf: &ElfBytes<LittleEndian>, name: &str) -> Option<usize> {
+    let (symbols, strings) = elf.symbol_table().ok()??;
+    symbols
+        .iter()
+        .find(|sym| strings.get(sym.st_name as usize).ok() == Some(name))
+        .map(|sym| sym.st_value as usize)
+}
+
+/// Returns the (start address, size) of the RAM of `elf`, given by the
+/// `_ram_start` and `_ram_end` symbols of the linker script generated by
+/// [MemoryLayout]. ELFs without them are assumed to use the default layout,
+/// with the RAM starting at the first data segment `hdrs`.
+fn ram_region(
+    elf: &ElfBytes<LittleEndian>,
+    hdrs: &[&ProgramHeader],
+) -> Result<(usize, usize), ElfLoadError> {
+    match (elf_symbol(elf, "_ram_start"), elf_symbol(elf, "_ram_end")) {
+        (Some(start), Some(end)) => {
+            if start != MemoryLayout::RAM_ORIGIN
+                || start >= end
+                || !(end - start).is_power_of_two()
+                || !end.is_multiple_of(4)
+            {
+                return Err(ElfLoadError::InvalidLayout {
+                    region: "RAM",
+                    start,
+                    end,
+                });
+            }
+            Ok((start, end - start))
+        }
+        _ => Ok((
+            hdrs.first().map_or(0, |ph| ph.p_vaddr as usize),
+            MemoryLayout::default().ram_size(),
+        )),
+    }
+}
+
+/// Returns the (start address, size) of the read-only data bank of `elf`,
+/// given by the `_rodata_start` and `_rodata_end` symbols of the linker script
+/// generated by a [MemoryLayout] with a read-only data bank, if any.
+fn rodata_region(
+    elf: &ElfBytes<LittleEndian>,
+    ram_size: usize,
+) -> Result<Option<(usize, usize)>, ElfLoadError> {
+    let (Some(start), Some(end)) = (
+        elf_symbol(elf, "_rodata_start"),
+        elf_symbol(elf, "_rodata_end"),
+    ) else {
+        return Ok(None);
+    };
+    let size: usize = end.wrapping_sub(start);
+    if start >= end
+        || !size.is_multiple_of(4)
+        || start != MemoryLayout::RAM_ORIGIN + rodata_offset(ram_size >> 2, size >> 2)
+    {
+        return Err(ElfLoadError::InvalidLayout {
+            region: "read-only data",
+            start,
+            end,
+        });
+    }
+    Ok(Some((start, size)))
+}
+
+/// Phantom VM: Encrypted Risc-v
+pub struct Phantom {
+    boot_rom: BootMemory,
+    boot_ram: BootMemory,
+    boot_rodata: Option<BootMemory>,
+    output_info: OutputInfo,
+    input_info: InputInfo,
+    instruction_set: InstructionSet,
+    custom_instructions: CustomInstructions,
+    rv32e: bool,
+    op_set_pruning: bool,
+    _elf_bytes: Option<Vec<u8>>,
+}
+
+impl Phantom {
+    /// Loads the program `elf_bytes`.
+    ///
+    /// Panics if the program does not fit in its RAM, see [Phantom::try_from_elf].
+    pub fn from_elf(elf_bytes: Vec<u8>) -> Self {
+        Self::try_from_elf(elf_bytes).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Same as [Phantom::from_elf], returning an [ElfLoadError] if the ELF
+    /// cannot be parsed or lacks .text, .inpdata or .outdata, if its RAM or
+    /// read-only data bank is not the one of a [MemoryLayout], if a
+    /// segment does not fit in the RAM, or if the data leaves no room to the
+    /// stack.
+    pub fn try_from_elf(elf_bytes: Vec<u8>) -> Result<Self, ElfLoadError> {
+        let elf = elf::ElfBytes::<elf::endian::LittleEndian>::minimal_parse(&elf_bytes)
+            .map_err(|err| ElfLoadError::InvalidElf(err.to_string()))?;
+
+        let phdrs: Vec<ProgramHeader> = elf
+            .segments()
+            .ok_or_else(|| ElfLoadError::InvalidElf("no program headers".to_string()))?
+            .iter()
+            .filter(|ph| ph.p_type == PT_LOAD)
+            .collect();
+
+        // .text section: +rx
+        let txthdr = phdrs
+            .iter()
+            .find(|p| p.p_flags == PF_R + PF_X)
+            .ok_or_else(|| ElfLoadError::InvalidElf("no .text program header".to_string()))?;
+        if txthdr.p_filesz != txthdr.p_memsz {
+            return Err(ElfLoadError::InvalidElf(
+                ".text program header contains uninitialized values".to_string(),
+            ));
+        }
+        if txthdr.p_vaddr != 0 {
+            return Err(ElfLoadError::InvalidElf(format!(
+                ".text must start at 0, not {:#x}",
+                txthdr.p_vaddr
+            )));
+        }
+        let mut text: Vec<u8> =
+            segment_bytes(&elf_bytes, txthdr, txthdr.p_memsz as usize)?.to_vec();
+
+        // load all +r/+rw headers
+        let hdrs: Vec<&ProgramHeader> = phdrs
+            .iter()
+            .filter(|p| p.p_flags == PF_R || p.p_flags == PF_R + PF_W)
+            .collect();
+        let (ram_offset, ram_size) = ram_region(&elf, &hdrs)?;
+        let mut boot_ram_data = vec![0u8; ram_size];
+        // .rodata is loaded in the read-only data bank, if the layout has one
+        let rodata_region: Option<(usize, usize)> = rodata_region(&elf, ram_size)?;
+        let mut boot_rodata_data = vec![0u8; rodata_region.map_or(0, |(_, size)| size)];
+        // load ram with .inpdata,.rodata,.data.,etc. Bytes beyond the file
+        // size of a segment (.bss) are zero-filled.
+        for ph in hdrs.iter() {
+            let end: u64 = ph
+                .p_vaddr
+                .checked_add(ph.p_memsz)
+                .filter(|end| *end <= 1 << 32)
+                .ok_or_else(|| {
+                    ElfLoadError::InvalidElf(format!(
+                        "segment of {:#x} bytes at {:#x} exceeds the address space",
+                        ph.p_memsz, ph.p_vaddr
+                    ))
+                })?;
+            let (start, end) = (ph.p_vaddr as usize, end as usize);
+            let (bank, bank_offset): (&mut [u8], usize) = match rodata_region {
+                Some((rodata_offset, rodata_size))
+                    if start >= rodata_offset && end <= rodata_offset + rodata_size =>
+                {
+                    (&mut boot_rodata_data, rodata_offset)
+                }
+                _ => (&mut boot_ram_data, ram_offset),
+            };
+            if start < bank_offset || end > bank_offset + bank.len() {
+                return Err(ElfLoadError::SegmentOutOfRam {
+                    start,
+                    end,
+                    ram_start: ram_offset,
+                    ram_end: ram_offset + ram_size,
+                });
+            }
+            let filesz: usize = ph.p_filesz.min(ph.p_memsz) as usize;
+            let segment: &mut [u8] = &mut bank[start - bank_offset..end - bank_offset];
+            segment[..filesz].copy_from_slice(segment_bytes(&elf_bytes, ph, filesz)?);
+            segment[filesz..].fill(0);
+        }
+
+        // the heap starts at _end and grows towards the stack
+        if let (Some(end), Some(stack_start)) =
+            (elf_symbol(&elf, "_end"), elf_symbol(&elf, "_stack_start"))
+        {
+            if end + MIN_STACK_HEADROOM > stack_start {
+                return Err(ElfLoadError::NoStackHeadroom {
+                    end,
+                    stack_start,
+                    min_headroom: MIN_STACK_HEADROOM,
+                });
+            }
+        }
+
+        // RV32C: compressed instructions are expanded, which moves the code,
+        // and the references to code addresses are remapped accordingly
+        if elf.ehdr.e_flags & EF_RISCV_RVC != 0 {
+            let mut expanded = rvc::ExpandedText::new(&text)?;
+            let rodata_offset: usize = rodata_region.map_or(0, |(offset, _)| offset);
+            expanded.relocate(
+                &elf,
+                &mut [
+                    (ram_offset, &mut boot_ram_data),
+                    (rodata_offset, &mut boot_rodata_data),
+                ],
+            )?;
+            text = expanded.to_bytes();
+        }
+        let boot_rom = BootMemory::new(txthdr.p_vaddr as usize, text.len(), text);
+        // macros::verbose_println!("ROM SIZE: {} bytes", txthdr.p_memsz);
+
+        let boot_ram = BootMemory::new(ram_offset, ram_size, boot_ram_data);
+        // println!("RAM OFFSET: {}", ram_offset);
+
+        let boot_rodata =
+            rodata_region.map(|(offset, size)| BootMemory::new(offset, size, boot_rodata_data));
+
+        // gather input information
+        let inpdata_sec = elf
+            .section_header_by_name(".inpdata")
+            .ok()
+            .flatten()
+            .ok_or_else(|| ElfLoadError::InvalidElf("no .inpdata section".to_string()))?;
+        let input_info = InputInfo {
+            start_addr: inpdata_sec.sh_addr as usize,
+            size: inpdata_sec.sh_size as usize,
+        };
+
+        // gather output information
+        let outdata_sec = elf
+            .section_header_by_name(".outdata")
+            .ok()
+            .flatten()
+            .ok_or_else(|| ElfLoadError::InvalidElf("no .outdata section".to_string()))?;
+        let output_info = OutputInfo {
+            start_addr: outdata_sec.sh_addr as usize,
+            size: outdata_sec.sh_size as usize,
+        };
+
+        // println!(
+        //     ".inpdata section: size={}, flag={}, v_addr={}, values={:?}",
+        //     inpdata_sec.sh_size,
+        //     inpdata_sec.sh_flags,
+        //     inpdata_sec.sh_addr,
+        //     &elf_bytes[inpdata_sec.sh_offset as usize
+        //         ..(inpdata_sec.sh_offset + inpdata_sec.sh_size) as usize]
+        // );
+
+        // println!(
+        //     ".outdata section: size={}, flag={}, v_addr={}, values={:?}",
+        //     outdata_sec.sh_size,
+        //     outdata_sec.sh_flags,
+        //     outdata_sec.sh_addr,
+        //     &elf_bytes[outdata_sec.sh_offset as usize
+        //         ..(outdata_sec.sh_offset + outdata_sec.sh_size) as usize]
+        // );
+
+        // RV32M and Zbb are only selected if .text contains instructions of the extension
+        let instructions: Vec<u32> = boot_rom
+            .data
+            .chunks_exact(4)
+            .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+        let instruction_set: InstructionSet = match (
+            instructions.iter().any(|&inst| is_rv32m(inst)),
+            instructions.iter().any(|&inst| is_zbb(inst)),
+        ) {
+            (false, false) => InstructionSet::RV32I,
+            (true, false) => InstructionSet::RV32M,
+            (false, true) => InstructionSet::RV32IZbb,
+            (true, true) => InstructionSet::RV32MZbb,
+        };
+
+        Ok(Phantom {
+            boot_rom,
+            boot_ram,
+            boot_rodata,
+            output_info,
+            input_info,
+            instruction_set,
+            custom_instructions: CustomInstructions::new(),
+            // RV32E binaries only use x0-x15, so the register file is halved
+            rv32e: elf.ehdr.e_flags & EF_RISCV_RVE != 0,
+            op_set_pruning: false,
+            _elf_bytes: Some(elf_bytes),
+        })
+    }
+
+    /// Overrides the instruction set detected from the ELF.
+    pub fn with_instruction_set(mut self, instruction_set: InstructionSet) -> Self {
+        self.instruction_set = instruction_set;
+        self
+    }
+
+    pub fn instruction_set(&self) -> &InstructionSet {
+        &self.instruction_set
+    }
+
+    /// Registers the custom-0/custom-1 instructions used by the program.
+    pub fn with_custom_instructions(mut self, custom_instructions: CustomInstructions) -> Self {
+        self.custom_instructions = custom_instructions;
+        self
+    }
+
+    pub fn custom_instructions(&self) -> &CustomInstructions {
+        &self.custom_instructions
+    }
+
+    /// Restricts the register and RAM update operations evaluated each cycle
+    /// to the ones present in .text, see [Interpreter::set_op_set].
+    ///
+    /// Disabled by default. Enabling it makes the cycles cheaper, but the op
+    /// set is stored in clear in the [ProgramLayout], and so reveals which
+    /// operations the program uses (e.g. that it never divides or only stores
+    /// words), though not where or how often.
+    pub fn with_op_set_pruning(mut self, op_set_pruning: bool) -> Self {
+        self.op_set_pruning = op_set_pruning;
+        self
+    }
+
+    pub fn op_set_pruning(&self) -> bool {
+        self.op_set_pruning
+    }
+
+    /// Returns true if the ELF targets RV32E, in which case the encrypted VM
+    /// runs with a 16-register file, see [Interpreter::set_rv32e].
+    pub fn rv32e(&self) -> bool {
+        self.rv32e
+    }
+
+    pub fn boot_rom(&self) -> &BootMemory {
+        &self.boot_rom
+    }
+
+    pub fn boot_ram(&self) -> &BootMemory {
+        &self.boot_ram
+    }
+
+    /// Read-only data bank of the program, if it was linked with one, see
+    /// [MemoryLayout::with_rodata_size].
+    pub fn boot_rodata(&self) -> Option<&BootMemory> {
+        self.boot_rodata.as_ref()
+    }
+
+    pub fn input_info(&self) -> &InputInfo {
+        &self.input_info
+    }
+
+    pub fn output_info(&self) -> &OutputInfo {
+        &self.output_info
+    }
+
+    /// Returns a [PhantomClient] with a freshly generated secret-key for this program.
+    pub fn client(&self) -> PhantomClient {
+        PhantomClient::new(self)
+    }
+
+    pub fn encrypted_vm(&self, input_tape: &[u8], max_cycles: usize) -> EncryptedVM {
+        let mut client: PhantomClient = self.client();
+        // PHANTOM_DEBUG only applies in process, where the secret-key is at hand
+        let interpreter: Interpreter<BackendImpl> = if client.phantom_debug {
+            client.encrypt_program_internal::<true>(input_tape)
+        } else {
+            client.encrypt_program(input_tape)
+        };
+        let keys: VMKeys<Vec<u8>, CGGI> = client.evaluation_keys();
+        let server: PhantomServer = PhantomServer::from_keys(&keys, interpreter);
+
+        EncryptedVM {
+            client,
+            server,
+            max_cycles,
+        }
+    }
+
+    /// Resumes an [EncryptedVM] from a checkpoint written by [EncryptedVM::checkpoint_to].
+    ///
+    /// `client` must hold the secret-key of the checkpointed VM, see
+    /// [PhantomClient::from_secret_seed]: it re-encrypts the ROM and regenerates
+    /// the evaluation keys, then the execution continues from the checkpointed
+    /// cycle up to `max_cycles`. PHANTOM_DEBUG checks do not resume, as the
+    /// checkpoint does not hold the plaintext of the debug VM.
+    pub fn resume_encrypted_vm<P: AsRef<Path>>(
+        &self,
+        mut client: PhantomClient,
+        path: P,
+        max_cycles: usize,
+    ) -> io::Result<EncryptedVM> {
+        let interpreter: Interpreter<BackendImpl> = client.encrypt_rom().into_interpreter();
+        let keys: VMKeys<Vec<u8>, CGGI> = client.evaluation_keys();
+        let server: PhantomServer = PhantomServer::resume_from(&keys, interpreter, path)?;
+
+        Ok(EncryptedVM {
+            client,
+            server,
+            max_cycles,
+        })
+    }
+
+    pub fn test_vm(&self, max_cycles: usize) -> TestVM {
+        TestVM::init(
+            &self.boot_rom,
+            &self.boot_ram,
+            self.boot_rodata.as_ref(),
+            &self.input_info,
+            &self.output_info,
+            &self.custom_instructions,
+            self.rv32e,
+            max_cycles,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Linked with the default MemoryLayout: 12 bytes of .text, .inpdata,
+    // .outdata and .data words at the start of the RAM and 64 bytes of .bss
+    const LOAD_ELF: &[u8] = include_bytes!("interpreter/testdata/load.elf");
+
+    // Linked with a MemoryLayout with 64 bytes of read-only data, holding a
+    // .rodata word
+    const RODATA_ELF: &[u8] = include_bytes!("interpreter/testdata/rodata.elf");
+
+    fn load_phdrs() -> Vec<ProgramHeader> {
+        let elf = ElfBytes::<LittleEndian>::minimal_parse(LOAD_ELF).unwrap();
+        elf.segments()
+            .unwrap()
+            .iter()
+            .filter(|ph| ph.p_type == PT_LOAD)
+            .collect()
+    }
+
+    /// Returns [LOAD_ELF] with the word at `offset` replaced by `value`.
+    fn patched(offset: usize, value: u32) -> Vec<u8> {
+        let mut bytes: Vec<u8> = LOAD_ELF.to_vec();
+        bytes[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+        bytes
+    }
+
+    /// Returns the offset in [LOAD_ELF] of the field at `field` of the program header `idx`.
+    fn phdr_field(idx: usize, field: usize) -> usize {
+        let elf = ElfBytes::<LittleEndian>::minimal_parse(LOAD_ELF).unwrap();
+        elf.ehdr.e_phoff as usize + idx * elf.ehdr.e_phentsize as usize + field
+    }
+
+    #[test]
+    fn test_load() {
+        let phantom: Phantom = Phantom::try_from_elf(LOAD_ELF.to_vec()).unwrap();
+        let ram: &BootMemory = &phantom.boot_ram;
+        assert_eq!(ram.offset, MemoryLayout::RAM_ORIGIN);
+        assert_eq!(ram.size, MemoryLayout::default().ram_size());
+        assert_eq!(phantom.boot_rom.data.len(), 12);
+        assert_eq!(phantom.input_info.start_addr, MemoryLayout::RAM_ORIGIN);
+        assert_eq!(phantom.input_info.size, 4);
+        assert_eq!(phantom.output_info.start_addr, MemoryLayout::RAM_ORIGIN + 4);
+        // value: .word 0x12345678
+        assert_eq!(ram.data[8..12], 0x1234_5678u32.to_le_bytes());
+    }
+
+    #[test]
+    fn test_load_bss() {
+        // .bss has no file bytes: the ones at its offset belong to other sections
+        let bss: ProgramHeader = load_phdrs()[2];
+        assert_eq!((bss.p_filesz, bss.p_memsz), (0, 64));
+        let file_offset: usize = bss.p_offset as usize;
+        assert!(LOAD_ELF[file_offset..file_offset + 64]
+            .iter()
+            .any(|&b| b != 0));
+
+        let phantom: Phantom = Phantom::try_from_elf(LOAD_ELF.to_vec()).unwrap();
+        let start: usize = bss.p_vaddr as usize - MemoryLayout::RAM_ORIGIN;
+        assert!(phantom.boot_ram.data[start..start + 64]
+            .iter()
+            .all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_segment_out_of_ram() {
+        // .bss of 4KB, past the end of the RAM
+        let bytes: Vec<u8> = patched(phdr_field(2, 20), 0x1000);
+        assert_eq!(
+            Phantom::try_from_elf(bytes).err(),
+            Some(ElfLoadError::SegmentOutOfRam {
+                start: 0x4000c,
+                end: 0x4100c,
+                ram_start: 0x40000,
+                ram_end: 0x41000,
+            })
+        );
+    }
+
+    /// Returns the offset in `elf_bytes` of the value of the symbol `name`.
+    fn symbol_value(elf_bytes: &[u8], name: &str) -> usize {
+        let elf = ElfBytes::<LittleEndian>::minimal_parse(elf_bytes).unwrap();
+        let symtab = elf.section_header_by_name(".symtab").unwrap().unwrap();
+        let (symbols, strings) = elf.symbol_table().unwrap().unwrap();
+        let idx: usize = symbols
+            .iter()
+            .position(|sym| strings.get(sym.st_name as usize).unwrap() == name)
+            .unwrap();
+        // st_value
+        symtab.sh_offset as usize + idx * symtab.sh_entsize as usize + 4
+    }
+
+    #[test]
+    fn test_no_stack_headroom() {
+        // _end 128 bytes below the stack
+        let bytes: Vec<u8> = patched(symbol_value(LOAD_ELF, "_end"), 0x41000 - 0x80);
+        assert_eq!(
+            Phantom::try_from_elf(bytes).err(),
+            Some(ElfLoadError::NoStackHeadroom {
+                end: 0x41000 - 0x80,
+                stack_start: 0x41000,
+                min_headroom: MIN_STACK_HEADROOM,
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_ram_region() {
+        // 3KB of RAM
+        let bytes: Vec<u8> = patched(symbol_value(LOAD_ELF, "_ram_end"), 0x40c00);
+        assert_eq!(
+            Phantom::try_from_elf(bytes).err(),
+            Some(ElfLoadError::InvalidLayout {
+                region: "RAM",
+                start: 0x40000,
+                end: 0x40c00,
+            })
+        );
+
+        // RAM not at RAM_ORIGIN
+        let bytes: Vec<u8> = patched(symbol_value(LOAD_ELF, "_ram_start"), 0x30000);
+        assert_eq!(
+            Phantom::try_from_elf(bytes).err(),
+            Some(ElfLoadError::InvalidLayout {
+                region: "RAM",
+                start: 0x30000,
+                end: 0x41000,
+            })
+        );
+    }
+
+    #[test]
+    fn test_segment_out_of_file() {
+        // .text file offset 8 bytes before the end of the ELF
+        let offset: usize = LOAD_ELF.len() - 8;
+        let bytes: Vec<u8> = patched(phdr_field(0, 4), offset as u32);
+        assert_eq!(
+            Phantom::try_from_elf(bytes).err(),
+            Some(ElfLoadError::SegmentOutOfFile {
+                offset,
+                size: 12,
+                file_size: LOAD_ELF.len(),
+            })
+        );
+
+        // data segment
+        let bytes: Vec<u8> = patched(phdr_field(1, 4), u32::MAX);
+        assert_eq!(
+            Phantom::try_from_elf(bytes).err(),
+            Some(ElfLoadError::SegmentOutOfFile {
+                offset: u32::MAX as usize,
+                size: 12,
+                file_size: LOAD_ELF.len(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_load_rodata() {
+        let phantom: Phantom = Phantom::try_from_elf(RODATA_ELF.to_vec()).unwrap();
+        let rodata: &BootMemory = phantom.boot_rodata.as_ref().unwrap();
+        assert_eq!((rodata.offset, rodata.size), (0x41000, 64));
+        // table: .word 0x89abcdef
+        assert_eq!(rodata.data[..4], 0x89ab_cdefu32.to_le_bytes());
+    }
+
+    #[test]
+    fn test_invalid_rodata_region() {
+        // Bank not at the offset of the read-only data of a MemoryLayout
+        let mut bytes: Vec<u8> = RODATA_ELF.to_vec();
+        let offset: usize = symbol_value(RODATA_ELF, "_rodata_start");
+        bytes[offset..offset + 4].copy_from_slice(&0x41004u32.to_le_bytes());
+        assert_eq!(
+            Phantom::try_from_elf(bytes).err(),
+            Some(ElfLoadError::InvalidLayout {
+                region: "read-only data",
+                start: 0x41004,
+                end: 0x41040,
+            })
+        );
+
+        // Empty bank
+        let mut bytes: Vec<u8> = RODATA_ELF.to_vec();
+        let offset: usize = symbol_value(RODATA_ELF, "_rodata_end");
+        bytes[offset..offset + 4].copy_from_slice(&0x41000u32.to_le_bytes());
+        assert_eq!(
+            Phantom::try_from_elf(bytes).err(),
+            Some(ElfLoadError::InvalidLayout {
+                region: "read-only data",
+                start: 0x41000,
+                end: 0x41000,
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_text() {
+        // .text not executable
+        let bytes: Vec<u8> = patched(phdr_field(0, 24), PF_R);
+        assert_eq!(
+            Phantom::try_from_elf(bytes).err(),
+            Some(ElfLoadError::InvalidElf(
+                "no .text program header".to_string()
+            ))
+        );
+
+        // .text with 4 bytes of .bss
+        let bytes: Vec<u8> = patched(phdr_field(0, 20), 16);
+        assert_eq!(
+            Phantom::try_from_elf(bytes).err(),
+            Some(ElfLoadError::InvalidElf(
+                ".text program header contains uninitialized values".to_string()
+            ))
+        );
+
+        // .text at 0x100
+        let bytes: Vec<u8> = patched(phdr_field(0, 8), 0x100);
+        assert_eq!(
+            Phantom::try_from_elf(bytes).err(),
+            Some(ElfLoadError::InvalidElf(
+                ".text must start at 0, not 0x100".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_segment_overflow() {
+        // .bss ending past 2^32
+        let mut bytes: Vec<u8> = patched(phdr_field(2, 8), u32::MAX);
+        bytes[phdr_field(2, 20)..phdr_field(2, 24)].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            Phantom::try_from_elf(bytes).err(),
+            Some(ElfLoadError::InvalidElf(
+                "segment of 0xffffffff bytes at 0xffffffff exceeds the address space".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_missing_io_sections() {
+        let elf = ElfBytes::<LittleEndian>::minimal_parse(LOAD_ELF).unwrap();
+        let (shdrs, strtab) = elf.section_headers_with_strtab().unwrap();
+        for (name, renamed) in [(".inpdata", ".inpdatx"), (".outdata", ".outdatx")] {
+            let shdr = shdrs
+                .unwrap()
+                .iter()
+                .find(|shdr| strtab.unwrap().get(shdr.sh_name as usize).unwrap() == name)
+                .unwrap();
+            let offset: usize = elf
+                .section_header_by_name(".shstrtab")
+                .unwrap()
+                .unwrap()
+                .sh_offset as usize
+                + shdr.sh_name as usize;
+            let mut bytes: Vec<u8> = LOAD_ELF.to_vec();
+            bytes[offset..offset + renamed.len()].copy_from_slice(renamed.as_bytes());
+            assert_eq!(
+                Phantom::try_from_elf(bytes).err(),
+                Some(ElfLoadError::InvalidElf(format!("no {name} section")))
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_elf() {
+        assert!(matches!(
+            Phantom::try_from_elf(LOAD_ELF[..16].to_vec()),
+            Err(ElfLoadError::InvalidElf(_))
+        ));
+        assert!(matches!(
+            Phantom::try_from_elf(b"not an ELF".to_vec()),
+            Err(ElfLoadError::InvalidElf(_))
+        ));
+    }
 }
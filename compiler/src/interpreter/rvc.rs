@@ -0,0 +1,812 @@
+//! Load-time expansion of RV32C compressed instructions.
+//!
+//! The ROM of the VM is word addressed and the PC circuits assume that every
+//! instruction is 4 bytes long. Each 16-bit instruction of .text is therefore
+//! expanded into its 32-bit equivalent and .text is laid out again with one
+//! instruction per word. Every reference to a code address is then remapped
+//! to the new layout:
+//! - branch (B-type) and JAL offsets are re-encoded,
+//! - AUIPC and its %pcrel_lo consumers (ADDI, loads, stores, JALR) are re-encoded
+//!   from the PCREL_HI20/PCREL_LO12 and CALL relocations kept with `--emit-relocs`,
+//! - absolute references into .text (LUI/ADDI pairs, jump tables and vtables in
+//!   data) are rewritten from the relocations kept with `--emit-relocs`.
+//!
+//! Return addresses written by JAL/JALR are `pc + 4` in the new layout and
+//! thus consistent with every remapped code address.
+//!
+//! Programs that cannot be expanded, e.g. with floating point instructions,
+//! without `--emit-relocs` or with a branch out of range once expanded, are
+//! reported as [ElfLoadError::RvcExpansion].
+
+use std::collections::HashMap;
+
+use elf::{
+    abi::{
+        R_RISCV_32, R_RISCV_CALL, R_RISCV_CALL_PLT, R_RISCV_GOT_HI20, R_RISCV_HI20, R_RISCV_LO12_I,
+        R_RISCV_LO12_S, R_RISCV_PCREL_HI20, R_RISCV_PCREL_LO12_I, R_RISCV_PCREL_LO12_S,
+        R_RISCV_RVC_LUI, SHF_ALLOC, SHT_RELA,
+    },
+    endian::LittleEndian,
+    ElfBytes,
+};
+
+use super::ElfLoadError;
+
+const OPCODE_LOAD: u32 = 0b0000011;
+const OPCODE_OP_IMM: u32 = 0b0010011;
+const OPCODE_AUIPC: u32 = 0b0010111;
+const OPCODE_STORE: u32 = 0b0100011;
+const OPCODE_OP: u32 = 0b0110011;
+const OPCODE_LUI: u32 = 0b0110111;
+const OPCODE_BRANCH: u32 = 0b1100011;
+const OPCODE_JALR: u32 = 0b1100111;
+const OPCODE_JAL: u32 = 0b1101111;
+
+// lld emits its internal relocations for LUI/ADDI pairs relaxed to an x0-based
+// immediate (X0REL_I/X0REL_S) truncated to R_RISCV_64/R_RISCV_RELATIVE
+const LLD_X0REL_I: u32 = 2;
+const LLD_X0REL_S: u32 = 3;
+
+const EBREAK: u32 = 0x00100073;
+// `unimp` (csrrw x0, cycle, x0), the 32-bit counterpart of the illegal 0x0000
+const UNIMP: u32 = 0xc0001073;
+
+fn error(reason: String) -> ElfLoadError {
+    ElfLoadError::RvcExpansion(reason)
+}
+
+/// .text with every compressed instruction expanded.
+pub(crate) struct ExpandedText {
+    instructions: Vec<u32>,
+    /// Original address of each instruction
+    old_addrs: Vec<u32>,
+    /// Maps the original address of an instruction to its address after expansion
+    new_addrs: HashMap<u32, u32>,
+    old_size: u32,
+}
+
+impl ExpandedText {
+    /// Expands the compressed instructions of `text`, which starts at address 0,
+    /// and re-encodes the branches and jumps to the new layout.
+    pub(crate) fn new(text: &[u8]) -> Result<Self, ElfLoadError> {
+        let mut instructions: Vec<u32> = Vec::new();
+        let mut old_addrs: Vec<u32> = Vec::new();
+        let mut new_addrs: HashMap<u32, u32> = HashMap::new();
+
+        let mut pc: usize = 0;
+        while pc + 2 <= text.len() {
+            let half: u16 = u16::from_le_bytes([text[pc], text[pc + 1]]);
+            let (inst, len) = if half & 0b11 != 0b11 {
+                (expand(half, pc as u32)?, 2)
+            } else {
+                let bytes: &[u8] = text
+                    .get(pc..pc + 4)
+                    .ok_or_else(|| error(format!("truncated instruction at {pc:#x}")))?;
+                (u32::from_le_bytes(bytes.try_into().unwrap()), 4)
+            };
+            new_addrs.insert(pc as u32, (instructions.len() << 2) as u32);
+            old_addrs.push(pc as u32);
+            instructions.push(inst);
+            pc += len;
+        }
+        // end of .text (e.g. linker symbols placed right after the last instruction)
+        new_addrs.insert(pc as u32, (instructions.len() << 2) as u32);
+
+        let mut expanded: ExpandedText = ExpandedText {
+            instructions,
+            old_addrs,
+            new_addrs,
+            old_size: pc as u32,
+        };
+        expanded.remap_branches()?;
+        Ok(expanded)
+    }
+
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        self.instructions
+            .iter()
+            .flat_map(|inst| inst.to_le_bytes())
+            .collect()
+    }
+
+    fn contains(&self, addr: i64) -> bool {
+        0 <= addr && addr <= self.old_size as i64
+    }
+
+    /// Returns the address after expansion of the instruction at `addr` in the original .text.
+    fn remap(&self, addr: i64) -> Result<i64, ElfLoadError> {
+        self.new_addrs
+            .get(&(addr as u32))
+            .map(|&new_addr| new_addr as i64)
+            .ok_or_else(|| {
+                error(format!(
+                    "code address {addr:#x} does not point to an instruction boundary"
+                ))
+            })
+    }
+
+    /// Returns the address of `addr` after expansion if it points into .text, else `addr`.
+    fn remap_if_code(&self, addr: i64) -> Result<i64, ElfLoadError> {
+        if self.contains(addr) {
+            self.remap(addr)
+        } else {
+            Ok(addr)
+        }
+    }
+
+    fn index_of(&self, old_addr: u64) -> Result<usize, ElfLoadError> {
+        Ok((self.remap(old_addr as i64)? >> 2) as usize)
+    }
+
+    fn remap_branches(&mut self) -> Result<(), ElfLoadError> {
+        for i in 0..self.instructions.len() {
+            let inst: u32 = self.instructions[i];
+            let old_pc: i64 = self.old_addrs[i] as i64;
+            let new_pc: i64 = (i << 2) as i64;
+            match opcode(inst) {
+                OPCODE_JAL => {
+                    let offset: i64 = self.remap(old_pc + j_imm(inst) as i64)? - new_pc;
+                    self.instructions[i] = set_j_imm(inst, offset, old_pc)?;
+                }
+                OPCODE_BRANCH => {
+                    let offset: i64 = self.remap(old_pc + b_imm(inst) as i64)? - new_pc;
+                    self.instructions[i] = set_b_imm(inst, offset, old_pc)?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Re-encodes the AUIPCs of `pcrel_hi`, given by index, and their %pcrel_lo
+    /// consumers of `pcrel_lo`, given as (original address, original address of
+    /// the AUIPC), from the original encodings `old`.
+    fn remap_pcrel(
+        &mut self,
+        old: &[u32],
+        pcrel_hi: &[usize],
+        pcrel_lo: &[(u64, i64)],
+    ) -> Result<(), ElfLoadError> {
+        let mut new_hi: HashMap<usize, i64> = HashMap::new();
+        for &(lo_addr, hi_addr) in pcrel_lo {
+            let i: usize = self.index_of(hi_addr as u64)?;
+            let j: usize = self.index_of(lo_addr)?;
+            if !pcrel_hi.contains(&i) {
+                return Err(error(format!(
+                    "%pcrel_lo at {lo_addr:#x} does not refer to an auipc"
+                )));
+            }
+
+            let (auipc, inst): (u32, u32) = (old[i], old[j]);
+            let is_store: bool = opcode(inst) == OPCODE_STORE;
+            let old_hi: i64 = (auipc & 0xfffff000) as i32 as i64;
+            let old_lo: i64 = if is_store { s_imm(inst) } else { i_imm(inst) } as i64;
+            let offset: i64 = self.remap_if_code(hi_addr + old_hi + old_lo)? - ((i << 2) as i64);
+            let hi: i64 = *new_hi.entry(i).or_insert((offset + 0x800) & !0xfff);
+            let lo: i64 = offset - hi;
+            if !(-2048..2048).contains(&lo) {
+                return Err(error(format!(
+                    "%pcrel_lo at {lo_addr:#x} out of range after expansion"
+                )));
+            }
+            self.instructions[j] = if is_store {
+                set_s_imm(inst, lo)
+            } else {
+                set_i_imm(inst, lo)
+            };
+        }
+
+        for &i in pcrel_hi {
+            let hi: i64 = *new_hi.get(&i).ok_or_else(|| {
+                error(format!(
+                    "auipc at {:#x} has no %pcrel_lo consumer: cannot remap it after expansion",
+                    self.old_addrs[i]
+                ))
+            })?;
+            self.instructions[i] = (old[i] & 0xfff) | (hi as u32 & 0xfffff000);
+        }
+        Ok(())
+    }
+
+    /// Rewrites the absolute references into .text recorded in the relocation
+    /// sections of `elf`, which must have been linked with `--emit-relocs`.
+    /// Data words are patched in the memory `banks`, given as (start address, bytes).
+    pub(crate) fn relocate(
+        &mut self,
+        elf: &ElfBytes<LittleEndian>,
+        banks: &mut [(usize, &mut [u8])],
+    ) -> Result<(), ElfLoadError> {
+        let invalid = |err: elf::ParseError| ElfLoadError::InvalidElf(err.to_string());
+        let shdrs = elf
+            .section_headers()
+            .ok_or_else(|| ElfLoadError::InvalidElf("no section headers".to_string()))?;
+        let symtab = elf.symbol_table().map_err(invalid)?;
+
+        // AUIPCs are re-encoded once all their consumers are known
+        let old: Vec<u32> = self.instructions.clone();
+        let mut pcrel_hi: Vec<usize> = Vec::new();
+        let mut pcrel_lo: Vec<(u64, i64)> = Vec::new();
+
+        let mut relocations_found: bool = false;
+        for shdr in shdrs.iter().filter(|shdr| shdr.sh_type == SHT_RELA) {
+            relocations_found = true;
+
+            // skip relocations of sections that are not loaded
+            let target_shdr = shdrs.get(shdr.sh_info as usize).map_err(invalid)?;
+            if target_shdr.sh_flags & SHF_ALLOC as u64 == 0 {
+                continue;
+            }
+
+            for rela in elf.section_data_as_relas(&shdr).map_err(invalid)? {
+                let sym_value: i64 = if rela.r_sym == 0 {
+                    0
+                } else {
+                    let (symbols, _) = symtab.as_ref().ok_or_else(|| {
+                        ElfLoadError::InvalidElf("relocations without symbol table".to_string())
+                    })?;
+                    symbols.get(rela.r_sym as usize).map_err(invalid)?.st_value as i64
+                };
+                let target: i64 = sym_value + rela.r_addend;
+
+                // The target of a PC-relative reference is read from its encoding, as
+                // the PC moves even if the target does not. The linker may have relaxed
+                // the AUIPC away, in which case the reference is left as it is.
+                match rela.r_type {
+                    R_RISCV_PCREL_HI20 | R_RISCV_GOT_HI20 | R_RISCV_CALL | R_RISCV_CALL_PLT => {
+                        let i: usize = self.index_of(rela.r_offset)?;
+                        if opcode(old[i]) == OPCODE_AUIPC {
+                            pcrel_hi.push(i);
+                            // the JALR of a call is its %pcrel_lo consumer
+                            if matches!(rela.r_type, R_RISCV_CALL | R_RISCV_CALL_PLT) {
+                                pcrel_lo.push((rela.r_offset + 4, rela.r_offset as i64));
+                            }
+                        }
+                        continue;
+                    }
+                    // the symbol of a %pcrel_lo is the AUIPC of its %pcrel_hi
+                    R_RISCV_PCREL_LO12_I | R_RISCV_PCREL_LO12_S => {
+                        pcrel_lo.push((rela.r_offset, target));
+                        continue;
+                    }
+                    _ => {}
+                }
+
+                if !self.contains(target) {
+                    continue;
+                }
+                let new_target: i64 = self.remap(target)?;
+
+                match rela.r_type {
+                    R_RISCV_32 => {
+                        let (bank, offset): (&mut [u8], usize) = banks
+                            .iter_mut()
+                            .find_map(|(bank_offset, bank)| {
+                                (rela.r_offset as usize)
+                                    .checked_sub(*bank_offset)
+                                    .filter(|offset| offset + 4 <= bank.len())
+                                    .map(|offset| (&mut **bank, offset))
+                            })
+                            .ok_or_else(|| {
+                                error(format!(
+                                    "R_RISCV_32 at {:#x} does not point into the RAM or the read-only data",
+                                    rela.r_offset
+                                ))
+                            })?;
+                        bank[offset..offset + 4]
+                            .copy_from_slice(&(new_target as u32).to_le_bytes());
+                    }
+                    R_RISCV_HI20 | R_RISCV_RVC_LUI => {
+                        let i: usize = self.index_of(rela.r_offset)?;
+                        let inst: u32 = self.instructions[i];
+                        self.instructions[i] =
+                            (inst & 0xfff) | (((new_target + 0x800) & !0xfff) as u32);
+                    }
+                    R_RISCV_LO12_I => {
+                        let i: usize = self.index_of(rela.r_offset)?;
+                        self.instructions[i] = set_i_imm(self.instructions[i], lo12(new_target));
+                    }
+                    R_RISCV_LO12_S => {
+                        let i: usize = self.index_of(rela.r_offset)?;
+                        self.instructions[i] = set_s_imm(self.instructions[i], lo12(new_target));
+                    }
+                    LLD_X0REL_I | LLD_X0REL_S => {
+                        if new_target >= 2048 {
+                            return Err(error(format!(
+                                "relaxed reference to {:#x} at {:#x} out of range after expansion: \
+                                 link with --no-relax",
+                                target, rela.r_offset
+                            )));
+                        }
+                        let i: usize = self.index_of(rela.r_offset)?;
+                        self.instructions[i] = if rela.r_type == LLD_X0REL_I {
+                            set_i_imm(self.instructions[i], new_target)
+                        } else {
+                            set_s_imm(self.instructions[i], new_target)
+                        };
+                    }
+                    // PC-relative references are remapped when expanding
+                    _ => {}
+                }
+            }
+        }
+
+        if !relocations_found {
+            return Err(error(
+                "compressed .text requires the ELF to be linked with --emit-relocs".to_string(),
+            ));
+        }
+        self.remap_pcrel(&old, &pcrel_hi, &pcrel_lo)
+    }
+}
+
+/// Expands the compressed instruction `inst` located at `pc` into its 32-bit equivalent.
+/// PC-relative offsets are kept as they are.
+pub(crate) fn expand(inst: u16, pc: u32) -> Result<u32, ElfLoadError> {
+    let c: u32 = inst as u32;
+    let funct3: u32 = bits(c, 15, 13);
+    // registers x8-x15 of the 3-bit fields
+    let rd_p: u32 = bits(c, 4, 2) + 8;
+    let rs1_p: u32 = bits(c, 9, 7) + 8;
+    let rd: u32 = bits(c, 11, 7);
+    let rs2: u32 = bits(c, 6, 2);
+    // imm[5] = inst[12], imm[4:0] = inst[6:2]
+    let imm6: i32 = sext(bits(c, 12, 12) << 5 | bits(c, 6, 2), 6);
+
+    let unsupported = || -> Result<u32, ElfLoadError> {
+        Err(error(format!(
+            "unsupported compressed instruction at {pc:#x}: {inst:016b}"
+        )))
+    };
+
+    Ok(match (c & 0b11, funct3) {
+        _ if inst == 0 => UNIMP,
+        // C.ADDI4SPN: addi rd', x2, nzuimm
+        (0b00, 0b000) => {
+            let imm: u32 = bits(c, 12, 11) << 4
+                | bits(c, 10, 7) << 6
+                | bits(c, 6, 6) << 2
+                | bits(c, 5, 5) << 3;
+            if imm == 0 {
+                return unsupported();
+            }
+            i_type(imm as i32, 2, 0b000, rd_p, OPCODE_OP_IMM)
+        }
+        // C.LW: lw rd', uimm(rs1')
+        (0b00, 0b010) => i_type(cl_offset(c), rs1_p, 0b010, rd_p, OPCODE_LOAD),
+        // C.SW: sw rs2', uimm(rs1')
+        (0b00, 0b110) => s_type(cl_offset(c), rd_p, rs1_p, 0b010),
+        // C.ADDI (C.NOP if rd = x0): addi rd, rd, imm
+        (0b01, 0b000) => i_type(imm6, rd, 0b000, rd, OPCODE_OP_IMM),
+        // C.JAL: jal x1, offset
+        (0b01, 0b001) => set_j_imm(1 << 7 | OPCODE_JAL, cj_offset(c), pc as i64)?,
+        // C.LI: addi rd, x0, imm
+        (0b01, 0b010) => i_type(imm6, 0, 0b000, rd, OPCODE_OP_IMM),
+        // C.ADDI16SP: addi x2, x2, nzimm
+        (0b01, 0b011) if rd == 2 => {
+            let imm: i32 = sext(
+                bits(c, 12, 12) << 9
+                    | bits(c, 6, 6) << 4
+                    | bits(c, 5, 5) << 6
+                    | bits(c, 4, 3) << 7
+                    | bits(c, 2, 2) << 5,
+                10,
+            );
+            if imm == 0 {
+                return unsupported();
+            }
+            i_type(imm, 2, 0b000, 2, OPCODE_OP_IMM)
+        }
+        // C.LUI: lui rd, nzimm
+        (0b01, 0b011) => {
+            if imm6 == 0 || rd == 0 {
+                return unsupported();
+            }
+            ((imm6 as u32) << 12) | rd << 7 | OPCODE_LUI
+        }
+        (0b01, 0b100) => match bits(c, 11, 10) {
+            // C.SRLI, C.SRAI: shamt[5] must be zero on RV32
+            0b00 | 0b01 if bits(c, 12, 12) != 0 => return unsupported(),
+            0b00 => i_type(rs2 as i32, rs1_p, 0b101, rs1_p, OPCODE_OP_IMM),
+            0b01 => i_type(
+                (0b0100000 << 5 | rs2) as i32,
+                rs1_p,
+                0b101,
+                rs1_p,
+                OPCODE_OP_IMM,
+            ),
+            // C.ANDI: andi rd', rd', imm
+            0b10 => i_type(imm6, rs1_p, 0b111, rs1_p, OPCODE_OP_IMM),
+            _ => {
+                if bits(c, 12, 12) != 0 {
+                    return unsupported();
+                }
+                let (funct7, funct3): (u32, u32) = match bits(c, 6, 5) {
+                    0b00 => (0b0100000, 0b000), // C.SUB
+                    0b01 => (0b0000000, 0b100), // C.XOR
+                    0b10 => (0b0000000, 0b110), // C.OR
+                    _ => (0b0000000, 0b111),    // C.AND
+                };
+                r_type(funct7, rd_p, rs1_p, funct3, rs1_p)
+            }
+        },
+        // C.J: jal x0, offset
+        (0b01, 0b101) => set_j_imm(OPCODE_JAL, cj_offset(c), pc as i64)?,
+        // C.BEQZ, C.BNEZ: beq/bne rs1', x0, offset
+        (0b01, 0b110) | (0b01, 0b111) => {
+            let offset: i64 = sext(
+                bits(c, 12, 12) << 8
+                    | bits(c, 11, 10) << 3
+                    | bits(c, 6, 5) << 6
+                    | bits(c, 4, 3) << 1
+                    | bits(c, 2, 2) << 5,
+                9,
+            ) as i64;
+            set_b_imm(
+                rs1_p << 15 | (funct3 & 1) << 12 | OPCODE_BRANCH,
+                offset,
+                pc as i64,
+            )?
+        }
+        // C.SLLI: slli rd, rd, shamt
+        (0b10, 0b000) => {
+            if bits(c, 12, 12) != 0 {
+                return unsupported();
+            }
+            i_type(rs2 as i32, rd, 0b001, rd, OPCODE_OP_IMM)
+        }
+        // C.LWSP: lw rd, uimm(x2)
+        (0b10, 0b010) => {
+            if rd == 0 {
+                return unsupported();
+            }
+            let imm: u32 = bits(c, 12, 12) << 5 | bits(c, 6, 4) << 2 | bits(c, 3, 2) << 6;
+            i_type(imm as i32, 2, 0b010, rd, OPCODE_LOAD)
+        }
+        (0b10, 0b100) => match (bits(c, 12, 12), rd, rs2) {
+            (0, 0, 0) => return unsupported(),
+            // C.JR: jalr x0, 0(rs1)
+            (0, rs1, 0) => i_type(0, rs1, 0b000, 0, OPCODE_JALR),
+            // C.MV: add rd, x0, rs2
+            (0, rd, rs2) => r_type(0b0000000, rs2, 0, 0b000, rd),
+            // C.EBREAK
+            (_, 0, 0) => EBREAK,
+            // C.JALR: jalr x1, 0(rs1)
+            (_, rs1, 0) => i_type(0, rs1, 0b000, 1, OPCODE_JALR),
+            // C.ADD: add rd, rd, rs2
+            (_, rd, rs2) => r_type(0b0000000, rs2, rd, 0b000, rd),
+        },
+        // C.SWSP: sw rs2, uimm(x2)
+        (0b10, 0b110) => {
+            let imm: u32 = bits(c, 12, 9) << 2 | bits(c, 8, 7) << 6;
+            s_type(imm as i32, rs2, 2, 0b010)
+        }
+        // floating point loads/stores and reserved encodings
+        _ => return unsupported(),
+    })
+}
+
+/// Returns inst[hi:lo]
+fn bits(inst: u32, hi: u32, lo: u32) -> u32 {
+    (inst >> lo) & ((1 << (hi - lo + 1)) - 1)
+}
+
+/// Sign extends the `n` lower bits of `x`
+fn sext(x: u32, n: u32) -> i32 {
+    ((x << (32 - n)) as i32) >> (32 - n)
+}
+
+/// Offset of C.LW/C.SW: uimm[5:3] = inst[12:10], uimm[2] = inst[6], uimm[6] = inst[5]
+fn cl_offset(c: u32) -> i32 {
+    (bits(c, 12, 10) << 3 | bits(c, 6, 6) << 2 | bits(c, 5, 5) << 6) as i32
+}
+
+/// Offset of C.J/C.JAL: offset[11|4|9:8|10|6|7|3:1|5] = inst[12:2]
+fn cj_offset(c: u32) -> i64 {
+    sext(
+        bits(c, 12, 12) << 11
+            | bits(c, 11, 11) << 4
+            | bits(c, 10, 9) << 8
+            | bits(c, 8, 8) << 10
+            | bits(c, 7, 7) << 6
+            | bits(c, 6, 6) << 7
+            | bits(c, 5, 3) << 1
+            | bits(c, 2, 2) << 5,
+        12,
+    ) as i64
+}
+
+fn opcode(inst: u32) -> u32 {
+    inst & 0x7f
+}
+
+fn r_type(funct7: u32, rs2: u32, rs1: u32, funct3: u32, rd: u32) -> u32 {
+    funct7 << 25 | rs2 << 20 | rs1 << 15 | funct3 << 12 | rd << 7 | OPCODE_OP
+}
+
+fn i_type(imm: i32, rs1: u32, funct3: u32, rd: u32, opcode: u32) -> u32 {
+    set_i_imm(rs1 << 15 | funct3 << 12 | rd << 7 | opcode, imm as i64)
+}
+
+fn s_type(imm: i32, rs2: u32, rs1: u32, funct3: u32) -> u32 {
+    set_s_imm(
+        rs2 << 20 | rs1 << 15 | funct3 << 12 | OPCODE_STORE,
+        imm as i64,
+    )
+}
+
+/// Low 12 bits of `addr` as split by %hi/%lo
+fn lo12(addr: i64) -> i64 {
+    addr - ((addr + 0x800) & !0xfff)
+}
+
+fn i_imm(inst: u32) -> i32 {
+    (inst as i32) >> 20
+}
+
+fn set_i_imm(inst: u32, imm: i64) -> u32 {
+    (inst & 0x000fffff) | ((imm as u32 & 0xfff) << 20)
+}
+
+fn s_imm(inst: u32) -> i32 {
+    ((inst & 0xfe000000) as i32) >> 20 | bits(inst, 11, 7) as i32
+}
+
+fn set_s_imm(inst: u32, imm: i64) -> u32 {
+    let imm: u32 = imm as u32;
+    (inst & 0x01fff07f) | bits(imm, 11, 5) << 25 | bits(imm, 4, 0) << 7
+}
+
+fn b_imm(inst: u32) -> i32 {
+    sext(
+        bits(inst, 31, 31) << 12
+            | bits(inst, 7, 7) << 11
+            | bits(inst, 30, 25) << 5
+            | bits(inst, 11, 8) << 1,
+        13,
+    )
+}
+
+fn set_b_imm(inst: u32, imm: i64, pc: i64) -> Result<u32, ElfLoadError> {
+    if !(-(1 << 12)..(1 << 12)).contains(&imm) {
+        return Err(error(format!(
+            "branch at {pc:#x} out of range after expansion"
+        )));
+    }
+    let imm: u32 = imm as u32;
+    Ok((inst & 0x01fff07f)
+        | bits(imm, 12, 12) << 31
+        | bits(imm, 10, 5) << 25
+        | bits(imm, 4, 1) << 8
+        | bits(imm, 11, 11) << 7)
+}
+
+fn j_imm(inst: u32) -> i32 {
+    sext(
+        bits(inst, 31, 31) << 20
+            | bits(inst, 19, 12) << 12
+            | bits(inst, 20, 20) << 11
+            | bits(inst, 30, 21) << 1,
+        21,
+    )
+}
+
+fn set_j_imm(inst: u32, imm: i64, pc: i64) -> Result<u32, ElfLoadError> {
+    if !(-(1 << 20)..(1 << 20)).contains(&imm) {
+        return Err(error(format!(
+            "jump at {pc:#x} out of range after expansion"
+        )));
+    }
+    let imm: u32 = imm as u32;
+    Ok((inst & 0x00000fff)
+        | bits(imm, 20, 20) << 31
+        | bits(imm, 10, 1) << 21
+        | bits(imm, 11, 11) << 20
+        | bits(imm, 19, 12) << 12)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (compressed, expanded) encodings, as assembled by llvm-mc with and without +c
+    const EXPANSIONS: [(u16, u32, &str); 49] = [
+        (0x1fe0, 0x3fc1_0413, "c.addi4spn s0, sp, 1020"),
+        (0x005c, 0x0041_0793, "c.addi4spn a5, sp, 4"),
+        (0x5ce8, 0x07c4_a503, "c.lw a0, 124(s1)"),
+        (0x4380, 0x0007_a403, "c.lw s0, 0(a5)"),
+        (0xdc7c, 0x06f4_2e23, "c.sw a5, 124(s0)"),
+        (0xc1a8, 0x04a5_a023, "c.sw a0, 64(a1)"),
+        (0x0001, 0x0000_0013, "c.nop"),
+        (0x1501, 0xfe05_0513, "c.addi a0, -32"),
+        (0x0ffd, 0x01ff_8f93, "c.addi t6, 31"),
+        (0x3001, 0x801f_f0ef, "c.jal -2048"),
+        (0x2ffd, 0x7fe0_00ef, "c.jal 2046"),
+        (0x2b91, 0x5540_00ef, "c.jal 0x554"),
+        (0x5501, 0xfe00_0513, "c.li a0, -32"),
+        (0x42fd, 0x01f0_0293, "c.li t0, 31"),
+        (0x7101, 0xe001_0113, "c.addi16sp sp, -512"),
+        (0x617d, 0x1f01_0113, "c.addi16sp sp, 496"),
+        (0x6141, 0x0101_0113, "c.addi16sp sp, 16"),
+        (0x6505, 0x0000_1537, "c.lui a0, 1"),
+        (0x7d81, 0xfffe_0db7, "c.lui s11, 0xfffe0"),
+        (0x62fd, 0x0001_f2b7, "c.lui t0, 0x1f"),
+        (0x817d, 0x01f5_5513, "c.srli a0, 31"),
+        (0x8485, 0x4014_d493, "c.srai s1, 1"),
+        (0x9bfd, 0xfff7_f793, "c.andi a5, -1"),
+        (0x8855, 0x0154_7413, "c.andi s0, 21"),
+        (0x8c1d, 0x40f4_0433, "c.sub s0, a5"),
+        (0x8d2d, 0x00b5_4533, "c.xor a0, a1"),
+        (0x8cd1, 0x00c4_e4b3, "c.or s1, a2"),
+        (0x8ef9, 0x00e6_f6b3, "c.and a3, a4"),
+        (0xbffd, 0xffff_f06f, "c.j -2"),
+        (0xa46d, 0x2aa0_006f, "c.j 0x2aa"),
+        (0xd001, 0xf004_00e3, "c.beqz s0, -256"),
+        (0xcffd, 0x0e07_8f63, "c.beqz a5, 254"),
+        (0xe54d, 0x0a05_1563, "c.bnez a0, 0xaa"),
+        (0xfcfd, 0xfe04_9fe3, "c.bnez s1, -2"),
+        (0x037e, 0x01f3_1313, "c.slli t1, 31"),
+        (0x0406, 0x0014_1413, "c.slli s0, 1"),
+        (0x50fe, 0x0fc1_2083, "c.lwsp ra, 252(sp)"),
+        (0x4f82, 0x0001_2f83, "c.lwsp t6, 0(sp)"),
+        (0x8082, 0x0000_8067, "c.jr ra"),
+        (0x8f82, 0x000f_8067, "c.jr t6"),
+        (0x857e, 0x01f0_0533, "c.mv a0, t6"),
+        (0x8406, 0x0010_0433, "c.mv s0, ra"),
+        (0x9002, 0x0010_0073, "c.ebreak"),
+        (0x9502, 0x0005_00e7, "c.jalr a0"),
+        (0x9f82, 0x000f_80e7, "c.jalr t6"),
+        (0x952e, 0x00b5_0533, "c.add a0, a1"),
+        (0x9f86, 0x001f_8fb3, "c.add t6, ra"),
+        (0xdffe, 0x0ff1_2e23, "c.swsp t6, 252(sp)"),
+        (0xc006, 0x0011_2023, "c.swsp ra, 0(sp)"),
+    ];
+
+    #[test]
+    fn test_expand() {
+        for (compressed, expanded, asm) in EXPANSIONS {
+            let have: u32 = expand(compressed, 0x100).unwrap();
+            assert_eq!(
+                have, expanded,
+                "{asm}: got {have:#010x}, want {expanded:#010x}"
+            );
+        }
+        assert_eq!(expand(0x0000, 0), Ok(UNIMP));
+    }
+
+    #[test]
+    fn test_expand_unsupported() {
+        // c.addi4spn with nzuimm = 0
+        assert_eq!(
+            expand(0x0004, 6),
+            Err(ElfLoadError::RvcExpansion(
+                "unsupported compressed instruction at 0x6: 0000000000000100".to_string()
+            ))
+        );
+        // c.flw fa0, 0(a0)
+        assert_eq!(
+            expand(0x6108, 6),
+            Err(ElfLoadError::RvcExpansion(
+                "unsupported compressed instruction at 0x6: 0110000100001000".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_branch_out_of_range() {
+        // beq x0, x0 to the end of .text, over `nops` c.nop, twice as far once expanded
+        let text = |nops: usize| -> Vec<u8> {
+            let beq: u32 = set_b_imm(OPCODE_BRANCH, 4 + 2 * nops as i64, 0).unwrap();
+            beq.to_le_bytes()
+                .into_iter()
+                .chain(std::iter::repeat_n(0x0001u16, nops).flat_map(|c| c.to_le_bytes()))
+                .collect()
+        };
+        assert!(ExpandedText::new(&text(1000)).is_ok());
+        assert_eq!(
+            ExpandedText::new(&text(2000)).err(),
+            Some(ElfLoadError::RvcExpansion(
+                "branch at 0x0 out of range after expansion".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_expanded_text() {
+        // c.li a0, 0; c.j +4; c.addi a0, 1; addi a0, a0, 2; c.beqz a0, -8
+        let text: Vec<u8> = [0x4501u16, 0xa011, 0x0505]
+            .iter()
+            .flat_map(|c| c.to_le_bytes())
+            .chain(0x0025_0513u32.to_le_bytes())
+            .chain(0xdd65u16.to_le_bytes())
+            .collect();
+        let expanded: ExpandedText = ExpandedText::new(&text).unwrap();
+
+        assert_eq!(expanded.old_addrs, [0, 2, 4, 6, 10]);
+        assert_eq!(expanded.remap(12), Ok(20));
+        assert_eq!(
+            expanded.instructions,
+            [
+                0x0000_0513,
+                // c.j to the addi, now 8 bytes ahead
+                0x0080_006f,
+                0x0015_0513,
+                0x0025_0513,
+                // c.beqz to the c.addi, now 12 bytes back
+                0xfe05_0ae3,
+            ]
+        );
+    }
+
+    /// Returns the address of the symbol `name` of `elf`.
+    fn symbol(elf: &ElfBytes<LittleEndian>, name: &str) -> i64 {
+        let (symbols, strings) = elf.symbol_table().unwrap().unwrap();
+        symbols
+            .iter()
+            .find(|sym| strings.get(sym.st_name as usize).unwrap() == name)
+            .unwrap()
+            .st_value as i64
+    }
+
+    #[test]
+    fn test_relocate_without_relocs() {
+        // linked without --emit-relocs
+        let bytes: &[u8] = include_bytes!("testdata/load.elf");
+        let elf: ElfBytes<LittleEndian> = ElfBytes::minimal_parse(bytes).unwrap();
+        let mut expanded: ExpandedText = ExpandedText::new(&[0x01, 0x00]).unwrap();
+        assert_eq!(
+            expanded.relocate(&elf, &mut []),
+            Err(ElfLoadError::RvcExpansion(
+                "compressed .text requires the ELF to be linked with --emit-relocs".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_relocate() {
+        let bytes: &[u8] = include_bytes!("testdata/rvc.elf");
+        let elf: ElfBytes<LittleEndian> = ElfBytes::minimal_parse(bytes).unwrap();
+        let section = |name: &str| {
+            let shdr = elf.section_header_by_name(name).unwrap().unwrap();
+            (
+                shdr.sh_addr as usize,
+                elf.section_data(&shdr).unwrap().0.to_vec(),
+            )
+        };
+        let (_, text) = section(".text");
+        let (data_offset, mut data) = section(".data");
+
+        let mut expanded: ExpandedText = ExpandedText::new(&text).unwrap();
+        expanded
+            .relocate(&elf, &mut [(data_offset, &mut data)])
+            .unwrap();
+        let inst = |addr: i64| expanded.instructions[(addr >> 2) as usize];
+        let new = |name: &str| expanded.remap(symbol(&elf, name)).unwrap();
+        let pcrel = |hi_addr: i64, lo_addr: i64, lo: fn(u32) -> i32| {
+            hi_addr + (inst(hi_addr) & 0xfffff000) as i32 as i64 + lo(inst(lo_addr)) as i64
+        };
+
+        // bne a0, a1, loop; beqz a0, end
+        assert_eq!(new("loop"), 8);
+        assert_eq!(12 + b_imm(inst(12)) as i64, new("loop"));
+        assert_eq!(16 + b_imm(inst(16)) as i64, new("end"));
+        // auipc a2, %pcrel_hi(value); lw a3, %pcrel_lo(..)(a2)
+        assert_eq!(pcrel(20, 24, i_imm), symbol(&elf, "value"));
+        // auipc a4, %pcrel_hi(table); beqz a0, skip; addi a4, a4, %pcrel_lo(..)
+        assert_eq!(32 + b_imm(inst(32)) as i64, new("skip"));
+        assert_eq!(pcrel(28, new("skip"), i_imm), symbol(&elf, "table"));
+        // auipc a5, %pcrel_hi(func); addi a5, a5, %pcrel_lo(..)
+        assert_eq!(pcrel(40, 44, i_imm), new("func"));
+        // auipc a2, %pcrel_hi(value); sw a1, %pcrel_lo(..)(a2)
+        assert_eq!(pcrel(48, 52, s_imm), symbol(&elf, "value"));
+        // call func; j end
+        assert_eq!(pcrel(56, 60, i_imm), new("func"));
+        assert_eq!(64 + j_imm(inst(64)) as i64, new("end"));
+        // .word func
+        let table: usize = symbol(&elf, "table") as usize - data_offset;
+        assert_eq!(
+            u32::from_le_bytes(data[table..table + 4].try_into().unwrap()) as i64,
+            new("func")
+        );
+        assert_eq!(inst(new("end")), EBREAK);
+    }
+}
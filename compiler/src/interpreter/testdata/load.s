@@ -0,0 +1,26 @@
+# Fixture of the ELF loading tests, linked with the linker script of
+# MemoryLayout::default(), written to link.x:
+#   llvm-mc -triple=riscv32 -mattr=-relax -filetype=obj load.s -o load.o
+#   rust-lld -flavor gnu -z max-page-size=16 -T link.x load.o -o load.elf
+    .section .init, "ax"
+    .globl _start
+_start:
+    lui a0, %hi(value)
+    lw a1, %lo(value)(a0)
+    ebreak
+
+    .section .inpdata, "aw"
+    .word 0
+
+    .section .outdata, "aw"
+    .word 0
+
+    .data
+    .globl value
+value:
+    .word 0x12345678
+
+    .bss
+    .globl buf
+buf:
+    .zero 64
@@ -0,0 +1,21 @@
+# Fixture of the ELF loading tests, linked with the linker script of
+# MemoryLayout::new().with_rodata_size(64), written to link.x:
+#   llvm-mc -triple=riscv32 -mattr=-relax -filetype=obj rodata.s -o rodata.o
+#   rust-lld -flavor gnu -z max-page-size=16 -T link.x rodata.o -o rodata.elf
+    .section .init, "ax"
+    .globl _start
+_start:
+    lui a0, %hi(table)
+    lw a1, %lo(table)(a0)
+    ebreak
+
+    .section .inpdata, "aw"
+    .word 0
+
+    .section .outdata, "aw"
+    .word 0
+
+    .section .rodata, "a"
+    .globl table
+table:
+    .word 0x89abcdef
@@ -0,0 +1,42 @@
+# Fixture of the rvc tests: branches, %pcrel_hi/%pcrel_lo pairs, a call and a
+# code address in data across compressed instructions. rvc.elf is built with:
+#   llvm-mc -triple=riscv32 -mattr=+c,-relax -filetype=obj rvc.s -o rvc.o
+#   rust-lld -flavor gnu --emit-relocs --no-relax -N --image-base=0 \
+#       -Ttext=0 -Tdata=0x100 -e _start rvc.o -o rvc.elf
+    .text
+    .globl _start, loop, func, end, skip
+_start:
+    c.li a0, 0
+    c.li a1, 10
+loop:
+    c.addi a0, 1
+    bne a0, a1, loop
+    c.beqz a0, end
+.Lpcrel_hi0:
+    auipc a2, %pcrel_hi(value)
+    lw a3, %pcrel_lo(.Lpcrel_hi0)(a2)
+.Lpcrel_hi1:
+    auipc a4, %pcrel_hi(table)
+    c.beqz a0, skip
+skip:
+    addi a4, a4, %pcrel_lo(.Lpcrel_hi1)
+.Lpcrel_hi2:
+    auipc a5, %pcrel_hi(func)
+    addi a5, a5, %pcrel_lo(.Lpcrel_hi2)
+.Lpcrel_hi3:
+    auipc a2, %pcrel_hi(value)
+    sw a1, %pcrel_lo(.Lpcrel_hi3)(a2)
+    call func
+    c.j end
+func:
+    c.addi a0, 2
+    c.jr ra
+end:
+    c.ebreak
+
+    .data
+    .globl value, table
+value:
+    .word 0x1234
+table:
+    .word func
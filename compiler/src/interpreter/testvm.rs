@@ -1,7 +1,60 @@
 use super::{macros::verbose_println, BootMemory, InputInfo, OutputInfo};
-use std::fmt;
+use fhevm::CustomInstructions;
+use std::{collections::HashMap, fmt};
 use utils::{extract_bits, sign_extend};
 
+/// Prints the UTF-8 string at address a0 of length a1.
+pub const SYS_PRINT: u32 = 1;
+/// Terminates the program with exit code a0.
+pub const SYS_EXIT: u32 = -1i32 as u32;
+/// Terminates the program abnormally.
+pub const SYS_ABORT: u32 = -2i32 as u32;
+
+/// Register holding the syscall number of an ECALL (a7).
+const SYSCALL_REGISTER: RegisterIndex = RegisterIndex(17);
+
+/// Register holding the syscall number of an ECALL in RV32E, which has no a7 (t0).
+const SYSCALL_REGISTER_RV32E: RegisterIndex = RegisterIndex(5);
+
+/// Number of registers of RV32E.
+const RV32E_REGISTERS: u32 = 16;
+
+/// Reason for which a [TestVM] stopped before `max_cycles`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    /// [SYS_EXIT] with the given exit code.
+    Exit(u32),
+    /// [SYS_ABORT].
+    Abort,
+    /// EBREAK.
+    Breakpoint,
+    /// Halfword or word load/store at the given misaligned address.
+    MisalignedAccess(u32),
+}
+
+/// Host call handler of the [TestVM], invoked on ECALL with the syscall number in a7 (t0 in RV32E).
+///
+/// Returns an [ExitStatus] if the program must terminate. Handlers of negative
+/// syscall numbers must terminate, as the encrypted VM freezes on them.
+pub type HostCall = fn(&mut TestVM) -> Option<ExitStatus>;
+
+fn sys_print(vm: &mut TestVM) -> Option<ExitStatus> {
+    let addr: usize = vm.register_value(10) as usize;
+    let len: usize = vm.register_value(11) as usize;
+    let bytes: Vec<u8> = vm.ram_bytes(addr, len);
+    print!("{}", String::from_utf8_lossy(&bytes));
+    vm.stdout.extend_from_slice(&bytes);
+    None
+}
+
+fn sys_exit(vm: &mut TestVM) -> Option<ExitStatus> {
+    Some(ExitStatus::Exit(vm.register_value(10)))
+}
+
+fn sys_abort(_vm: &mut TestVM) -> Option<ExitStatus> {
+    Some(ExitStatus::Abort)
+}
+
 struct Memory {
     data: Vec<u8>,
     offset: usize,
@@ -112,6 +165,26 @@ enum Inst {
     REM(RegisterIndex, RegisterIndex, RegisterIndex),
     REMU(RegisterIndex, RegisterIndex, RegisterIndex),
 
+    // Zbb extension
+    ANDN(RegisterIndex, RegisterIndex, RegisterIndex),
+    ORN(RegisterIndex, RegisterIndex, RegisterIndex),
+    XNOR(RegisterIndex, RegisterIndex, RegisterIndex),
+    MAX(RegisterIndex, RegisterIndex, RegisterIndex),
+    MAXU(RegisterIndex, RegisterIndex, RegisterIndex),
+    MIN(RegisterIndex, RegisterIndex, RegisterIndex),
+    MINU(RegisterIndex, RegisterIndex, RegisterIndex),
+    ROL(RegisterIndex, RegisterIndex, RegisterIndex),
+    ROR(RegisterIndex, RegisterIndex, RegisterIndex),
+    CLZ(RegisterIndex, RegisterIndex),
+    CTZ(RegisterIndex, RegisterIndex),
+    CPOP(RegisterIndex, RegisterIndex),
+    SEXTB(RegisterIndex, RegisterIndex),
+    SEXTH(RegisterIndex, RegisterIndex),
+    ZEXTH(RegisterIndex, RegisterIndex),
+    REV8(RegisterIndex, RegisterIndex),
+    ORCB(RegisterIndex, RegisterIndex),
+    RORI(RegisterIndex, RegisterIndex, u32),
+
     LUI(RegisterIndex, u32),
     AUIPC(RegisterIndex, u32),
 
@@ -138,9 +211,13 @@ enum Inst {
     SB(RegisterIndex, RegisterIndex, u32),
     SH(RegisterIndex, RegisterIndex, u32),
     SW(RegisterIndex, RegisterIndex, u32),
-    // ECALL
-    // ECALL,
 
+    // Custom instructions: rs1, rs2, rd, imm, raw instruction
+    CUSTOM(RegisterIndex, RegisterIndex, RegisterIndex, u32, u32),
+
+    // System instructions
+    ECALL,
+    EBREAK,
     // UNIMP
     // UNIMP,
 }
@@ -168,6 +245,8 @@ pub struct TestVM {
     rom: Memory,
     /// RAM
     ram: Memory,
+    /// Read-only data bank, if any
+    rodata: Option<Memory>,
     /// program counter
     pc: u32,
     /// Input info
@@ -176,14 +255,28 @@ pub struct TestVM {
     output_info: OutputInfo,
     /// Max cycles to run for
     max_cycles: usize,
+    /// Host calls by syscall number
+    host_calls: HashMap<u32, HostCall>,
+    /// Set once the program has terminated
+    exit_status: Option<ExitStatus>,
+    /// Bytes printed by the program
+    stdout: Vec<u8>,
+    /// Custom instructions, executed with their plaintext semantics
+    custom_instructions: CustomInstructions,
+    /// Only x0-x15 are available (RV32E)
+    rv32e: bool,
 }
 
 impl TestVM {
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn init(
         boot_rom: &BootMemory,
         boot_ram: &BootMemory,
+        boot_rodata: Option<&BootMemory>,
         input_info: &InputInfo,
         output_info: &OutputInfo,
+        custom_instructions: &CustomInstructions,
+        rv32e: bool,
         max_cycles: usize,
     ) -> Self {
         let mut rom = Memory::new(boot_rom.offset, boot_rom.size, false);
@@ -192,20 +285,102 @@ impl TestVM {
         let mut ram = Memory::new(boot_ram.offset, boot_ram.size, true);
         ram.load_memory(boot_ram.offset, &boot_ram.data);
 
+        let rodata = boot_rodata.map(|boot_rodata| {
+            let mut rodata = Memory::new(boot_rodata.offset, boot_rodata.size, false);
+            rodata.load_memory(boot_rodata.offset, &boot_rodata.data);
+            rodata
+        });
+
         Self {
             registers: [0u32; 32],
             ram,
+            rodata,
             rom,
             pc: 0,
             input_info: input_info.clone(),
             output_info: output_info.clone(),
             max_cycles,
+            host_calls: HashMap::from([
+                (SYS_PRINT, sys_print as HostCall),
+                (SYS_EXIT, sys_exit as HostCall),
+                (SYS_ABORT, sys_abort as HostCall),
+            ]),
+            exit_status: None,
+            stdout: Vec::new(),
+            custom_instructions: custom_instructions.clone(),
+            rv32e,
+        }
+    }
+
+    /// Registers `host_call` for the syscall number `id`, replacing any existing one.
+    ///
+    /// Syscalls without host call are no-ops, as in the encrypted VM, except
+    /// the terminating ones (negative numbers), which panic.
+    pub fn set_host_call(&mut self, id: u32, host_call: HostCall) {
+        self.host_calls.insert(id, host_call);
+    }
+
+    /// Returns the [ExitStatus] if the program has terminated.
+    pub fn exit_status(&self) -> Option<ExitStatus> {
+        self.exit_status
+    }
+
+    /// Returns the bytes printed by the program with [SYS_PRINT].
+    pub fn stdout(&self) -> &[u8] {
+        &self.stdout
+    }
+
+    /// Returns the value of the register x`index`.
+    pub fn register_value(&self, index: u32) -> u32 {
+        self.register(RegisterIndex::from(index))
+    }
+
+    /// Returns the `len` bytes of the RAM starting at `addr`.
+    pub fn ram_bytes(&self, addr: usize, len: usize) -> Vec<u8> {
+        (addr..addr + len)
+            .map(|addr| self.ram.read_byte(addr))
+            .collect()
+    }
+
+    /// Returns true if `addr` is a multiple of `size`. Otherwise terminates with
+    /// [ExitStatus::MisalignedAccess], leaving the registers, the RAM and the PC
+    /// untouched, as the encrypted VM does.
+    fn check_alignment(&mut self, addr: u32, size: u32) -> bool {
+        let aligned: bool = addr.is_multiple_of(size);
+        if !aligned {
+            verbose_println!("misaligned access: addr={}", addr);
+            self.exit_status = Some(ExitStatus::MisalignedAccess(addr));
+        }
+        aligned
+    }
+
+    /// Returns true if `addr` is within the read-only data bank.
+    fn in_rodata(&self, addr: u32) -> bool {
+        self.rodata.as_ref().is_some_and(|rodata| {
+            (rodata.offset..rodata.offset + rodata.size).contains(&(addr as usize))
+        })
+    }
+
+    /// Memory read by a load at `addr`: the read-only data bank if `addr` is
+    /// within it, the RAM otherwise.
+    fn memory(&self, addr: u32) -> &Memory {
+        match &self.rodata {
+            Some(rodata) if self.in_rodata(addr) => rodata,
+            _ => &self.ram,
         }
     }
 
     fn decode_inst(&self, inst: u32) -> Inst {
         let opcode = extract_bits(inst, 7);
 
+        if self.custom_instructions.get(inst).is_some() {
+            let rd = RegisterIndex::from(extract_bits(inst >> 7, 5));
+            let rs1 = RegisterIndex::from(extract_bits(inst >> 15, 5));
+            let rs2 = RegisterIndex::from(extract_bits(inst >> 20, 5));
+            let imm = sign_extend(extract_bits(inst >> 20, 12), 12);
+            return Inst::CUSTOM(rs1, rs2, rd, imm, inst);
+        }
+
         if opcode == 0b0010011 {
             // Integer register-immediate instructions
 
@@ -232,13 +407,35 @@ impl TestVM {
 
             // Constant shifts
             let shift = extract_bits(inst >> 20, 5);
+            let funct7 = inst >> 25;
             if funct3 == 0b001 {
-                return Inst::SLLI(rs1, rd, shift);
+                if funct7 == 0b0000000 {
+                    return Inst::SLLI(rs1, rd, shift);
+                } else if funct7 == 0b0110000 {
+                    // Zbb unary operations, selected by the shamt field
+                    if shift == 0b00000 {
+                        return Inst::CLZ(rs1, rd);
+                    } else if shift == 0b00001 {
+                        return Inst::CTZ(rs1, rd);
+                    } else if shift == 0b00010 {
+                        return Inst::CPOP(rs1, rd);
+                    } else if shift == 0b00100 {
+                        return Inst::SEXTB(rs1, rd);
+                    } else if shift == 0b00101 {
+                        return Inst::SEXTH(rs1, rd);
+                    }
+                }
             } else if funct3 == 0b101 {
-                if (inst >> 30) & 1 == 0 {
+                if funct7 == 0b0000000 {
                     return Inst::SRLI(rs1, rd, shift);
-                } else {
+                } else if funct7 == 0b0100000 {
                     return Inst::SRAI(rs1, rd, shift);
+                } else if funct7 == 0b0110000 {
+                    return Inst::RORI(rs1, rd, shift);
+                } else if funct7 == 0b0110100 && shift == 0b11000 {
+                    return Inst::REV8(rs1, rd);
+                } else if funct7 == 0b0010100 && shift == 0b00111 {
+                    return Inst::ORCB(rs1, rd);
                 }
             }
         } else if opcode == 0b0110111 {
@@ -255,8 +452,9 @@ impl TestVM {
             let rs2 = RegisterIndex::from(extract_bits(inst >> 20, 5));
 
             let func3 = extract_bits(inst >> 12, 3);
+            let funct7 = inst >> 25;
 
-            if inst >> 25 & 1 == 0 {
+            if funct7 == 0b0000000 || (funct7 == 0b0100000 && (func3 == 0b000 || func3 == 0b101)) {
                 // Integer register register
                 if func3 == 0b000 {
                     if (inst >> 30) & 1 == 0 {
@@ -283,7 +481,7 @@ impl TestVM {
                         return Inst::SRA(rs1, rs2, rd);
                     }
                 }
-            } else {
+            } else if funct7 == 0b0000001 {
                 // M extension
                 if func3 == 0b000 {
                     return Inst::MUL(rs1, rs2, rd);
@@ -302,6 +500,35 @@ impl TestVM {
                 } else if func3 == 0b111 {
                     return Inst::REMU(rs1, rs2, rd);
                 }
+            } else if funct7 == 0b0100000 {
+                // Zbb logical with negate
+                if func3 == 0b111 {
+                    return Inst::ANDN(rs1, rs2, rd);
+                } else if func3 == 0b110 {
+                    return Inst::ORN(rs1, rs2, rd);
+                } else if func3 == 0b100 {
+                    return Inst::XNOR(rs1, rs2, rd);
+                }
+            } else if funct7 == 0b0000101 {
+                // Zbb integer minimum/maximum
+                if func3 == 0b110 {
+                    return Inst::MAX(rs1, rs2, rd);
+                } else if func3 == 0b111 {
+                    return Inst::MAXU(rs1, rs2, rd);
+                } else if func3 == 0b100 {
+                    return Inst::MIN(rs1, rs2, rd);
+                } else if func3 == 0b101 {
+                    return Inst::MINU(rs1, rs2, rd);
+                }
+            } else if funct7 == 0b0110000 {
+                // Zbb rotations
+                if func3 == 0b001 {
+                    return Inst::ROL(rs1, rs2, rd);
+                } else if func3 == 0b101 {
+                    return Inst::ROR(rs1, rs2, rd);
+                }
+            } else if funct7 == 0b0000100 && func3 == 0b100 && rs2 == RegisterIndex(0) {
+                return Inst::ZEXTH(rs1, rd);
             }
         } else if opcode == 0b1101111 {
             let rd = RegisterIndex::from(extract_bits(inst >> 7, 5));
@@ -379,10 +606,15 @@ impl TestVM {
             } else if func3 == 0b010 {
                 return Inst::SW(rs1, rs2, imm);
             }
+        } else if opcode == 0b1110011 && extract_bits(inst >> 7, 13) == 0 {
+            let funct12 = inst >> 20;
+
+            if funct12 == 0 {
+                return Inst::ECALL;
+            } else if funct12 == 1 {
+                return Inst::EBREAK;
+            }
         }
-        //  else if opcode == 0b1110011 && ((inst >> 20) == 0) {
-        //     return Inst::ECALL;
-        // }
         // else if inst == 3221229683 {
         //     return Inst::UNIMP;
         // }
@@ -391,6 +623,7 @@ impl TestVM {
     }
 
     fn register(&self, index: RegisterIndex) -> u32 {
+        self.check_register(index);
         self.registers[index.0 as usize]
     }
 
@@ -398,12 +631,29 @@ impl TestVM {
         // if index.0 == 15 {
         //     println!("Access 15");
         // }
+        self.check_register(index);
         &mut self.registers[index.0 as usize]
     }
 
+    fn check_register(&self, index: RegisterIndex) {
+        assert!(
+            !self.rv32e || index.0 < RV32E_REGISTERS,
+            "register {index} is not available in RV32E"
+        );
+    }
+
+    fn syscall_register(&self) -> RegisterIndex {
+        if self.rv32e {
+            SYSCALL_REGISTER_RV32E
+        } else {
+            SYSCALL_REGISTER
+        }
+    }
+
+    /// Runs until `max_cycles` or until the program terminates, see [TestVM::exit_status].
     pub fn execute(&mut self) {
         let mut counter = 0;
-        while counter < self.max_cycles {
+        while counter < self.max_cycles && self.exit_status.is_none() {
             self.run();
             counter += 1;
             //println!("ram: {:?}", cast_mut::<u8,u32>(&self.ram.data));
@@ -862,7 +1112,7 @@ impl TestVM {
 
                 let addr = self.register(rs1).wrapping_add(imm);
 
-                let mut value = self.ram.read_byte(addr as usize) as u32;
+                let mut value = self.memory(addr).read_byte(addr as usize) as u32;
                 value = sign_extend(value, 8);
                 *self.register_mut(rd) = value;
 
@@ -879,16 +1129,13 @@ impl TestVM {
                 );
 
                 let addr = self.register(rs1).wrapping_add(imm);
-                assert!(
-                    (addr % 4 == 0) || ((addr - 1) % 4 == 0) || ((addr - 2) % 4 == 0),
-                    "LH addr={addr} is not 4-byte aligned"
-                );
+                if self.check_alignment(addr, 2) {
+                    let mut value = self.memory(addr).read_half(addr as usize) as u32;
+                    value = sign_extend(value, 16);
+                    *self.register_mut(rd) = value;
 
-                let mut value = self.ram.read_half(addr as usize) as u32;
-                value = sign_extend(value, 16);
-                *self.register_mut(rd) = value;
-
-                self.pc += 4;
+                    self.pc += 4;
+                }
             }
             Inst::LW(rs1, rd, imm) => {
                 verbose_println!(
@@ -901,11 +1148,11 @@ impl TestVM {
                 );
 
                 let addr = self.register(rs1).wrapping_add(imm);
-                assert!(addr % 4 == 0, "LW addr={addr} is not 4-byte aligned");
-
-                *self.register_mut(rd) = self.ram.read_word(addr as usize);
+                if self.check_alignment(addr, 4) {
+                    *self.register_mut(rd) = self.memory(addr).read_word(addr as usize);
 
-                self.pc += 4;
+                    self.pc += 4;
+                }
             }
             Inst::LBU(rs1, rd, imm) => {
                 verbose_println!(
@@ -918,7 +1165,7 @@ impl TestVM {
                 );
 
                 let addr = self.register(rs1).wrapping_add(imm);
-                *self.register_mut(rd) = self.ram.read_byte(addr as usize) as u32;
+                *self.register_mut(rd) = self.memory(addr).read_byte(addr as usize) as u32;
                 self.pc += 4;
             }
             Inst::LHU(rs1, rd, imm) => {
@@ -932,13 +1179,10 @@ impl TestVM {
                 );
 
                 let addr = self.register(rs1).wrapping_add(imm);
-                assert!(
-                    (addr % 4 == 0) || ((addr - 1) % 4 == 0) || ((addr - 2) % 4 == 0),
-                    "LHU addr={addr} is not 4-byte aligned"
-                );
-
-                *self.register_mut(rd) = self.ram.read_half(addr as usize) as u32;
-                self.pc += 4;
+                if self.check_alignment(addr, 2) {
+                    *self.register_mut(rd) = self.memory(addr).read_half(addr as usize) as u32;
+                    self.pc += 4;
+                }
             }
             Inst::SB(rs1, rs2, imm) => {
                 verbose_println!(
@@ -951,8 +1195,11 @@ impl TestVM {
                 );
 
                 let addr = self.register(rs1).wrapping_add(imm);
-                self.ram
-                    .write_byte(addr as usize, extract_bits(self.register(rs2), 8) as u8);
+                // stores to the read-only data bank are dropped
+                if !self.in_rodata(addr) {
+                    self.ram
+                        .write_byte(addr as usize, extract_bits(self.register(rs2), 8) as u8);
+                }
 
                 self.pc += 4;
             }
@@ -967,15 +1214,16 @@ impl TestVM {
                 );
 
                 let addr = self.register(rs1).wrapping_add(imm);
-                assert!(
-                    (addr % 4 == 0) || ((addr - 1) % 4 == 0) || ((addr - 2) % 4 == 0),
-                    "SH addr={addr} is not 4-byte aligned"
-                );
-
-                self.ram
-                    .write_half(addr as usize, (extract_bits(self.register(rs2), 16)) as u16);
+                if self.check_alignment(addr, 2) {
+                    if !self.in_rodata(addr) {
+                        self.ram.write_half(
+                            addr as usize,
+                            (extract_bits(self.register(rs2), 16)) as u16,
+                        );
+                    }
 
-                self.pc += 4;
+                    self.pc += 4;
+                }
             }
             Inst::SW(rs1, rs2, imm) => {
                 verbose_println!(
@@ -988,11 +1236,13 @@ impl TestVM {
                 );
 
                 let addr = self.register(rs1).wrapping_add(imm);
-                assert!(addr % 4 == 0, "SW addr={addr} is not 4-byte aligned");
-
-                self.ram.write_word(addr as usize, self.register(rs2));
+                if self.check_alignment(addr, 4) {
+                    if !self.in_rodata(addr) {
+                        self.ram.write_word(addr as usize, self.register(rs2));
+                    }
 
-                self.pc += 4;
+                    self.pc += 4;
+                }
             }
             Inst::MUL(rs1, rs2, rd) => {
                 verbose_println!(
@@ -1075,8 +1325,11 @@ impl TestVM {
                     self.register(rd)
                 );
 
-                *self.register_mut(rd) =
-                    ((self.register(rs1) as i32) / (self.register(rs2) as i32)) as u32;
+                // Division by zero returns -1 and overflow returns rs1
+                *self.register_mut(rd) = match self.register(rs2) {
+                    0 => u32::MAX,
+                    rs2 => (self.register(rs1) as i32).wrapping_div(rs2 as i32) as u32,
+                };
 
                 self.pc += 4;
             }
@@ -1091,7 +1344,10 @@ impl TestVM {
                     self.register(rd)
                 );
 
-                *self.register_mut(rd) = self.register(rs1) / self.register(rs2);
+                *self.register_mut(rd) = self
+                    .register(rs1)
+                    .checked_div(self.register(rs2))
+                    .unwrap_or(u32::MAX);
 
                 self.pc += 4;
             }
@@ -1106,8 +1362,11 @@ impl TestVM {
                     self.register(rd)
                 );
 
-                *self.register_mut(rd) =
-                    (self.register(rs1) as i32 % self.register(rs2) as i32) as u32;
+                // Division by zero returns rs1 and overflow returns 0
+                *self.register_mut(rd) = match self.register(rs2) {
+                    0 => self.register(rs1),
+                    rs2 => (self.register(rs1) as i32).wrapping_rem(rs2 as i32) as u32,
+                };
 
                 self.pc += 4;
             }
@@ -1122,23 +1381,303 @@ impl TestVM {
                     self.register(rd)
                 );
 
-                *self.register_mut(rd) = self.register(rs1) % self.register(rs2);
+                *self.register_mut(rd) = self
+                    .register(rs1)
+                    .checked_rem(self.register(rs2))
+                    .unwrap_or(self.register(rs1));
 
                 self.pc += 4;
-            } // Inst::ECALL => {
-              //     // a0 stores v_addrs, a1 stores v_len
-              //     let addr = self.register(RegisterIndex::from(10));
-              //     let len = self.register(RegisterIndex::from(11)) as usize;
-              //     let mut out_bytes = Vec::with_capacity(len);
-              //     for i in 0..len {
-              //         out_bytes.push(self.ram.read_byte((addr as usize) + i));
-              //     }
-              //     let s = std::str::from_utf8(&out_bytes).unwrap();
-              //     println!("[TestVM log] {s}");
-
-              //     self.pc += 4;
-              // }
-              // Inst::UNIMP => {
+            }
+            Inst::ANDN(rs1, rs2, rd) => {
+                verbose_println!(
+                    "ANDN: rs1={}({}) rs2={}({}) rd={}({})",
+                    rs1,
+                    self.register(rs1),
+                    rs2,
+                    self.register(rs2),
+                    rd,
+                    self.register(rd)
+                );
+
+                *self.register_mut(rd) = self.register(rs1) & !self.register(rs2);
+
+                self.pc += 4;
+            }
+            Inst::ORN(rs1, rs2, rd) => {
+                verbose_println!(
+                    "ORN: rs1={}({}) rs2={}({}) rd={}({})",
+                    rs1,
+                    self.register(rs1),
+                    rs2,
+                    self.register(rs2),
+                    rd,
+                    self.register(rd)
+                );
+
+                *self.register_mut(rd) = self.register(rs1) | !self.register(rs2);
+
+                self.pc += 4;
+            }
+            Inst::XNOR(rs1, rs2, rd) => {
+                verbose_println!(
+                    "XNOR: rs1={}({}) rs2={}({}) rd={}({})",
+                    rs1,
+                    self.register(rs1),
+                    rs2,
+                    self.register(rs2),
+                    rd,
+                    self.register(rd)
+                );
+
+                *self.register_mut(rd) = !(self.register(rs1) ^ self.register(rs2));
+
+                self.pc += 4;
+            }
+            Inst::MAX(rs1, rs2, rd) => {
+                verbose_println!(
+                    "MAX: rs1={}({}) rs2={}({}) rd={}({})",
+                    rs1,
+                    self.register(rs1),
+                    rs2,
+                    self.register(rs2),
+                    rd,
+                    self.register(rd)
+                );
+
+                *self.register_mut(rd) =
+                    (self.register(rs1) as i32).max(self.register(rs2) as i32) as u32;
+
+                self.pc += 4;
+            }
+            Inst::MAXU(rs1, rs2, rd) => {
+                verbose_println!(
+                    "MAXU: rs1={}({}) rs2={}({}) rd={}({})",
+                    rs1,
+                    self.register(rs1),
+                    rs2,
+                    self.register(rs2),
+                    rd,
+                    self.register(rd)
+                );
+
+                *self.register_mut(rd) = self.register(rs1).max(self.register(rs2));
+
+                self.pc += 4;
+            }
+            Inst::MIN(rs1, rs2, rd) => {
+                verbose_println!(
+                    "MIN: rs1={}({}) rs2={}({}) rd={}({})",
+                    rs1,
+                    self.register(rs1),
+                    rs2,
+                    self.register(rs2),
+                    rd,
+                    self.register(rd)
+                );
+
+                *self.register_mut(rd) =
+                    (self.register(rs1) as i32).min(self.register(rs2) as i32) as u32;
+
+                self.pc += 4;
+            }
+            Inst::MINU(rs1, rs2, rd) => {
+                verbose_println!(
+                    "MINU: rs1={}({}) rs2={}({}) rd={}({})",
+                    rs1,
+                    self.register(rs1),
+                    rs2,
+                    self.register(rs2),
+                    rd,
+                    self.register(rd)
+                );
+
+                *self.register_mut(rd) = self.register(rs1).min(self.register(rs2));
+
+                self.pc += 4;
+            }
+            Inst::ROL(rs1, rs2, rd) => {
+                verbose_println!(
+                    "ROL: rs1={}({}) rs2={}({}) rd={}({})",
+                    rs1,
+                    self.register(rs1),
+                    rs2,
+                    self.register(rs2),
+                    rd,
+                    self.register(rd)
+                );
+
+                *self.register_mut(rd) = self.register(rs1).rotate_left(self.register(rs2) & 0x1F);
+
+                self.pc += 4;
+            }
+            Inst::ROR(rs1, rs2, rd) => {
+                verbose_println!(
+                    "ROR: rs1={}({}) rs2={}({}) rd={}({})",
+                    rs1,
+                    self.register(rs1),
+                    rs2,
+                    self.register(rs2),
+                    rd,
+                    self.register(rd)
+                );
+
+                *self.register_mut(rd) = self.register(rs1).rotate_right(self.register(rs2) & 0x1F);
+
+                self.pc += 4;
+            }
+            Inst::CLZ(rs1, rd) => {
+                verbose_println!(
+                    "CLZ: rs1={rs1}({}), rd={rd}({})",
+                    self.register(rs1),
+                    self.register(rd)
+                );
+
+                let rs1v = self.register(rs1);
+                *self.register_mut(rd) = rs1v.leading_zeros();
+
+                self.pc += 4;
+            }
+            Inst::CTZ(rs1, rd) => {
+                verbose_println!(
+                    "CTZ: rs1={rs1}({}), rd={rd}({})",
+                    self.register(rs1),
+                    self.register(rd)
+                );
+
+                let rs1v = self.register(rs1);
+                *self.register_mut(rd) = rs1v.trailing_zeros();
+
+                self.pc += 4;
+            }
+            Inst::CPOP(rs1, rd) => {
+                verbose_println!(
+                    "CPOP: rs1={rs1}({}), rd={rd}({})",
+                    self.register(rs1),
+                    self.register(rd)
+                );
+
+                let rs1v = self.register(rs1);
+                *self.register_mut(rd) = rs1v.count_ones();
+
+                self.pc += 4;
+            }
+            Inst::SEXTB(rs1, rd) => {
+                verbose_println!(
+                    "SEXT.B: rs1={rs1}({}), rd={rd}({})",
+                    self.register(rs1),
+                    self.register(rd)
+                );
+
+                let rs1v = self.register(rs1);
+                *self.register_mut(rd) = rs1v as i8 as i32 as u32;
+
+                self.pc += 4;
+            }
+            Inst::SEXTH(rs1, rd) => {
+                verbose_println!(
+                    "SEXT.H: rs1={rs1}({}), rd={rd}({})",
+                    self.register(rs1),
+                    self.register(rd)
+                );
+
+                let rs1v = self.register(rs1);
+                *self.register_mut(rd) = rs1v as i16 as i32 as u32;
+
+                self.pc += 4;
+            }
+            Inst::ZEXTH(rs1, rd) => {
+                verbose_println!(
+                    "ZEXT.H: rs1={rs1}({}), rd={rd}({})",
+                    self.register(rs1),
+                    self.register(rd)
+                );
+
+                let rs1v = self.register(rs1);
+                *self.register_mut(rd) = rs1v & 0xFFFF;
+
+                self.pc += 4;
+            }
+            Inst::REV8(rs1, rd) => {
+                verbose_println!(
+                    "REV8: rs1={rs1}({}), rd={rd}({})",
+                    self.register(rs1),
+                    self.register(rd)
+                );
+
+                let rs1v = self.register(rs1);
+                *self.register_mut(rd) = rs1v.swap_bytes();
+
+                self.pc += 4;
+            }
+            Inst::ORCB(rs1, rd) => {
+                verbose_println!(
+                    "ORC.B: rs1={rs1}({}), rd={rd}({})",
+                    self.register(rs1),
+                    self.register(rd)
+                );
+
+                let rs1v = self.register(rs1);
+                *self.register_mut(rd) =
+                    u32::from_le_bytes(
+                        rs1v.to_le_bytes()
+                            .map(|byte| if byte != 0 { 0xFF } else { 0 }),
+                    );
+
+                self.pc += 4;
+            }
+            Inst::RORI(rs1, rd, shift) => {
+                verbose_println!(
+                    "RORI: rs1={rs1}({}), rd={rd}({}), shamt={shift}",
+                    self.register(rs1),
+                    self.register(rd)
+                );
+
+                let rs1v = self.register(rs1);
+                *self.register_mut(rd) = rs1v.rotate_right(shift);
+
+                self.pc += 4;
+            }
+            Inst::CUSTOM(rs1, rs2, rd, imm, inst) => {
+                verbose_println!(
+                    "CUSTOM({inst:08x}): rs1={rs1}({}), rs2={rs2}({}), rd={rd}({}), imm={imm}",
+                    self.register(rs1),
+                    self.register(rs2),
+                    self.register(rd)
+                );
+
+                let rdv = self.custom_instructions.get(inst).unwrap().eval_plain(
+                    self.register(rs1),
+                    self.register(rs2),
+                    imm,
+                );
+                *self.register_mut(rd) = rdv;
+
+                self.pc += 4;
+            }
+            Inst::ECALL => {
+                let id = self.register(self.syscall_register());
+                verbose_println!("ECALL: {}={}", self.syscall_register(), id);
+
+                match self.host_calls.get(&id) {
+                    Some(&host_call) => {
+                        // Terminating host calls leave the PC on the ECALL, as the encrypted VM does
+                        self.exit_status = host_call(self);
+                        if self.exit_status.is_none() {
+                            self.pc += 4;
+                        }
+                    }
+                    // Other syscalls are no-ops, as in the encrypted VM
+                    None if (id as i32) >= 0 => {
+                        verbose_println!("ECALL: no host call {id}, ignored");
+                        self.pc += 4;
+                    }
+                    None => panic!("ECALL: unsupported terminating host call {}", id as i32),
+                }
+            }
+            Inst::EBREAK => {
+                verbose_println!("EBREAK");
+                self.exit_status = Some(ExitStatus::Breakpoint);
+            } // Inst::UNIMP => {
               //     verbose_println!("UNIMP");
               //     // halt vm
               //     self.state = VMState::HALT
@@ -1188,3 +1727,343 @@ mod utils {
         return out_v;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Base address of the RAM, as in the fhevm tests (LUI 1<<6).
+    const RAM_OFFSET: usize = 1 << 18;
+
+    const ECALL: u32 = 0x0000_0073;
+    const EBREAK: u32 = 0x0010_0073;
+
+    fn i_type(opcode: u32, funct3: u32, rd: u32, rs1: u32, imm: i32) -> u32 {
+        ((imm as u32 & 0xFFF) << 20) | (rs1 << 15) | (funct3 << 12) | (rd << 7) | opcode
+    }
+
+    fn s_type(funct3: u32, rs1: u32, rs2: u32, imm: i32) -> u32 {
+        let imm: u32 = imm as u32 & 0xFFF;
+        ((imm >> 5) << 25)
+            | (rs2 << 20)
+            | (rs1 << 15)
+            | (funct3 << 12)
+            | ((imm & 0x1F) << 7)
+            | 0b0100011
+    }
+
+    fn lui(rd: u32, imm: u32) -> u32 {
+        (imm << 12) | (rd << 7) | 0b0110111
+    }
+
+    fn addi(rd: u32, rs1: u32, imm: i32) -> u32 {
+        i_type(0b0010011, 0b000, rd, rs1, imm)
+    }
+
+    fn add(rd: u32, rs1: u32, rs2: u32) -> u32 {
+        (rs2 << 20) | (rs1 << 15) | (rd << 7) | 0b0110011
+    }
+
+    fn sw(rs1: u32, rs2: u32, imm: i32) -> u32 {
+        s_type(0b010, rs1, rs2, imm)
+    }
+
+    fn sh(rs1: u32, rs2: u32, imm: i32) -> u32 {
+        s_type(0b001, rs1, rs2, imm)
+    }
+
+    fn load(funct3: u32, rd: u32, rs1: u32, imm: i32) -> u32 {
+        i_type(0b0000011, funct3, rd, rs1, imm)
+    }
+
+    /// Returns a [TestVM] with `rom` at address 0 and `ram` at [RAM_OFFSET].
+    fn test_vm(rom: &[u32], ram: &[u32], rv32e: bool, max_cycles: usize) -> TestVM {
+        let bytes =
+            |words: &[u32]| -> Vec<u8> { words.iter().flat_map(|w| w.to_le_bytes()).collect() };
+        let boot_rom: BootMemory = BootMemory::new(0, rom.len() * 4, bytes(rom));
+        let boot_ram: BootMemory = BootMemory::new(RAM_OFFSET, ram.len() * 4, bytes(ram));
+        let input_info: InputInfo = InputInfo {
+            start_addr: RAM_OFFSET,
+            size: 0,
+        };
+        let output_info: OutputInfo = OutputInfo {
+            start_addr: RAM_OFFSET,
+            size: 0,
+        };
+        TestVM::init(
+            &boot_rom,
+            &boot_ram,
+            None,
+            &input_info,
+            &output_info,
+            &CustomInstructions::new(),
+            rv32e,
+            max_cycles,
+        )
+    }
+
+    fn ram_words(vm: &TestVM, words: usize) -> Vec<u32> {
+        vm.ram_bytes(RAM_OFFSET, words * 4)
+            .chunks_exact(4)
+            .map(|w| u32::from_le_bytes(w.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_sys_exit() {
+        let rom: Vec<u32> = vec![
+            // a0 <- 42
+            addi(10, 0, 42),
+            // a7 <- SYS_EXIT
+            addi(17, 0, -1),
+            ECALL,
+            // a0 <- 7, never executed
+            addi(10, 0, 7),
+        ];
+        let mut vm: TestVM = test_vm(&rom, &[0; 4], false, 16);
+        vm.execute();
+
+        assert_eq!(vm.exit_status(), Some(ExitStatus::Exit(42)));
+        // Terminating host calls freeze the PC on the ECALL
+        assert_eq!(vm.pc, 8);
+        assert_eq!(vm.register_value(10), 42);
+        assert_eq!(vm.register_value(17), SYS_EXIT);
+    }
+
+    #[test]
+    fn test_sys_abort() {
+        let rom: Vec<u32> = vec![addi(17, 0, -2), ECALL, addi(10, 0, 7)];
+        let mut vm: TestVM = test_vm(&rom, &[0; 4], false, 16);
+        vm.execute();
+
+        assert_eq!(vm.exit_status(), Some(ExitStatus::Abort));
+        assert_eq!(vm.pc, 4);
+        assert_eq!(vm.register_value(10), 0);
+    }
+
+    #[test]
+    fn test_sys_print() {
+        let rom: Vec<u32> = vec![
+            // a0 <- 1<<18
+            lui(10, 1 << 6),
+            // a1 <- 5
+            addi(11, 0, 5),
+            // a7 <- SYS_PRINT
+            addi(17, 0, SYS_PRINT as i32),
+            ECALL,
+            // Non-terminating host calls fall through
+            addi(17, 0, -1),
+            ECALL,
+        ];
+        let ram: [u32; 2] = [u32::from_le_bytes(*b"hell"), u32::from_le_bytes(*b"o!!!")];
+        let mut vm: TestVM = test_vm(&rom, &ram, false, 16);
+        vm.execute();
+
+        assert_eq!(vm.stdout(), b"hello");
+        assert_eq!(vm.exit_status(), Some(ExitStatus::Exit(RAM_OFFSET as u32)));
+        assert_eq!(vm.pc, 20);
+        assert_eq!(ram_words(&vm, 2), ram);
+    }
+
+    #[test]
+    fn test_set_host_call() {
+        fn store_a0(vm: &mut TestVM) -> Option<ExitStatus> {
+            let a0: u32 = vm.register_value(10);
+            vm.ram.write_word(RAM_OFFSET, a0);
+            None
+        }
+
+        let rom: Vec<u32> = vec![
+            addi(10, 0, 0x123),
+            addi(17, 0, 3),
+            ECALL,
+            // Replaces the default SYS_EXIT
+            addi(17, 0, -1),
+            ECALL,
+            addi(10, 0, 7),
+        ];
+        let mut vm: TestVM = test_vm(&rom, &[0; 4], false, 6);
+        vm.set_host_call(3, store_a0);
+        vm.set_host_call(SYS_EXIT, |_| None);
+        vm.execute();
+
+        assert_eq!(vm.exit_status(), None);
+        assert_eq!(vm.pc, 24);
+        assert_eq!(vm.register_value(10), 7);
+        assert_eq!(ram_words(&vm, 4), [0x123, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_unsupported_host_call() {
+        // A no-op, as in the encrypted VM
+        let rom: Vec<u32> = vec![addi(17, 0, 3), ECALL, addi(10, 0, 7)];
+        let mut vm: TestVM = test_vm(&rom, &[0; 4], false, 3);
+        vm.execute();
+
+        assert_eq!(vm.exit_status(), None);
+        assert_eq!(vm.pc, 12);
+        assert_eq!(vm.register_value(10), 7);
+    }
+
+    #[test]
+    #[should_panic(expected = "ECALL: unsupported terminating host call -3")]
+    fn test_unsupported_terminating_host_call() {
+        let rom: Vec<u32> = vec![addi(17, 0, -3), ECALL];
+        let mut vm: TestVM = test_vm(&rom, &[0; 4], false, 16);
+        vm.execute();
+    }
+
+    #[test]
+    fn test_ebreak() {
+        // As in the fhevm halt test, EBREAK freezes the PC, the registers and the RAM
+        let rom: Vec<u32> = vec![
+            addi(1, 0, 5),
+            lui(31, 1 << 6),
+            sw(31, 1, 4),
+            EBREAK,
+            // Never executed
+            addi(2, 0, 7),
+        ];
+        let mut vm: TestVM = test_vm(&rom, &[0; 4], false, 16);
+        vm.execute();
+
+        assert_eq!(vm.exit_status(), Some(ExitStatus::Breakpoint));
+        assert_eq!(vm.pc, 12);
+        assert_eq!(vm.register_value(1), 5);
+        assert_eq!(vm.register_value(2), 0);
+        assert_eq!(ram_words(&vm, 4), [0, 5, 0, 0]);
+
+        // Stays frozen if run past the breakpoint
+        vm.run();
+        assert_eq!(vm.pc, 12);
+        assert_eq!(vm.register_value(2), 0);
+    }
+
+    #[test]
+    fn test_misaligned_load() {
+        // Mirrors the fhevm misaligned interpreter test
+        let rom: Vec<u32> = vec![
+            // x31 <- 1<<18
+            lui(31, 1 << 6),
+            // x1 <- sext(RAM[1<<18 + 1][7:0])
+            load(0b000, 1, 31, 1),
+            // x2 <- RAM[1<<18 + 2][15:0]
+            load(0b101, 2, 31, 2),
+            // RAM[1<<18 + 6][15:0] <- x2
+            sh(31, 2, 6),
+            // x3 <- RAM[1<<18 + 7][7:0]
+            load(0b100, 3, 31, 7),
+            // Misaligned, traps
+            load(0b010, 4, 31, 2),
+            // Never executed
+            addi(5, 0, 7),
+        ];
+        let mut ram: Vec<u32> = vec![0; 64];
+        ram[0] = 0x8382_8180;
+        let mut vm: TestVM = test_vm(&rom, &ram, false, 16);
+        vm.execute();
+
+        assert_eq!(
+            vm.exit_status(),
+            Some(ExitStatus::MisalignedAccess(RAM_OFFSET as u32 + 2))
+        );
+        assert_eq!(vm.pc, 20);
+        assert_eq!(vm.register_value(1), 0xFFFF_FF81);
+        assert_eq!(vm.register_value(2), 0x8382);
+        assert_eq!(vm.register_value(3), 0x83);
+        assert_eq!(vm.register_value(4), 0);
+        assert_eq!(vm.register_value(5), 0);
+        assert_eq!(ram_words(&vm, 2), [0x8382_8180, 0x8382_0000]);
+    }
+
+    #[test]
+    fn test_misaligned_store() {
+        let rom: Vec<u32> = vec![
+            lui(31, 1 << 6),
+            addi(1, 0, -1),
+            // Aligned halfword store
+            sh(31, 1, 2),
+            // Misaligned, traps and leaves the RAM untouched
+            sw(31, 1, 6),
+            addi(2, 0, 7),
+        ];
+        let mut vm: TestVM = test_vm(&rom, &[0; 4], false, 16);
+        vm.execute();
+
+        assert_eq!(
+            vm.exit_status(),
+            Some(ExitStatus::MisalignedAccess(RAM_OFFSET as u32 + 6))
+        );
+        assert_eq!(vm.pc, 12);
+        assert_eq!(vm.register_value(2), 0);
+        assert_eq!(ram_words(&vm, 4), [0xFFFF_0000, 0, 0, 0]);
+
+        // Stays frozen if run past the trap
+        vm.run();
+        assert_eq!(vm.pc, 12);
+        assert_eq!(ram_words(&vm, 4), [0xFFFF_0000, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_rv32e() {
+        // Mirrors the fhevm RV32E interpreter test
+        let rom: Vec<u32> = vec![
+            addi(1, 0, 5),
+            // x15, the last register of RV32E
+            add(15, 1, 1),
+            // t0 <- SYS_EXIT, as a7 does not exist
+            addi(5, 0, -1),
+            ECALL,
+            // Never executed
+            addi(2, 0, 7),
+        ];
+        let mut vm: TestVM = test_vm(&rom, &[0; 4], true, 16);
+        vm.execute();
+
+        assert_eq!(vm.exit_status(), Some(ExitStatus::Exit(0)));
+        assert_eq!(vm.pc, 12);
+        assert_eq!(vm.register_value(1), 5);
+        assert_eq!(vm.register_value(15), 10);
+        assert_eq!(vm.register_value(5), u32::MAX);
+        assert_eq!(vm.register_value(2), 0);
+    }
+
+    #[test]
+    fn test_rv32i_syscall_register() {
+        // Outside of RV32E, t0 is an ordinary register and a7 holds the syscall number
+        let rom: Vec<u32> = vec![addi(5, 0, -2), addi(17, 0, -1), ECALL];
+        let mut vm: TestVM = test_vm(&rom, &[0; 4], false, 16);
+        vm.execute();
+
+        assert_eq!(vm.exit_status(), Some(ExitStatus::Exit(0)));
+        assert_eq!(vm.pc, 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "register r16 is not available in RV32E")]
+    fn test_rv32e_write_x16() {
+        let rom: Vec<u32> = vec![addi(16, 0, 1)];
+        let mut vm: TestVM = test_vm(&rom, &[0; 4], true, 16);
+        vm.execute();
+    }
+
+    #[test]
+    #[should_panic(expected = "register r31 is not available in RV32E")]
+    fn test_rv32e_read_x31() {
+        let rom: Vec<u32> = vec![add(1, 31, 0)];
+        let mut vm: TestVM = test_vm(&rom, &[0; 4], true, 16);
+        vm.execute();
+    }
+
+    #[test]
+    fn test_max_cycles() {
+        let rom: Vec<u32> = vec![addi(1, 1, 1), add(2, 1, 1), addi(1, 1, 1), add(2, 1, 1)];
+        let mut vm: TestVM = test_vm(&rom, &[0; 4], false, 3);
+        vm.execute();
+
+        assert_eq!(vm.exit_status(), None);
+        assert_eq!(vm.pc, 12);
+        assert_eq!(vm.register_value(1), 2);
+        assert_eq!(vm.register_value(2), 2);
+    }
+}
@@ -0,0 +1,250 @@
+use fhevm::rodata::rodata_offset;
+
+/// Code (ROM) and data (RAM) memory regions of a guest.
+///
+/// The ROM starts at address 0 and holds `.text`. The RAM holds the input and
+/// output tapes, the read-only and mutable data, the heap and the stack, which
+/// starts at the end of the RAM. [crate::CompileOpts] links the guest with the
+/// linker script generated from the layout, and [crate::Phantom::from_elf]
+/// reads the RAM bounds back from the ELF.
+///
+/// The read-only data can instead be placed in a separate read-only bank, see
+/// [MemoryLayout::with_rodata_size].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryLayout {
+    rom_size: usize,
+    ram_size: usize,
+    rodata_size: usize,
+}
+
+// Template of the linker script, see [MemoryLayout::linker_script].
+const LINKER_SCRIPT: &str = include_str!("../linker-script/default.x");
+
+impl Default for MemoryLayout {
+    /// 256KB of ROM and 4KB of RAM, starting right after the ROM, and no
+    /// read-only data bank.
+    fn default() -> Self {
+        Self {
+            rom_size: 256 * 1024,
+            ram_size: 4 * 1024,
+            rodata_size: 0,
+        }
+    }
+}
+
+impl MemoryLayout {
+    /// Address of the start of the RAM. The encrypted VM subtracts it from
+    /// the load and store addresses, so it cannot be changed.
+    pub const RAM_ORIGIN: usize = 1 << 18;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum size, in bytes, of `.text`, at most
+    /// [MemoryLayout::RAM_ORIGIN].
+    pub fn with_rom_size(mut self, rom_size: usize) -> Self {
+        self.rom_size = rom_size;
+        self
+    }
+
+    /// Sets the size, in bytes, of the RAM, a power of two of at least 4.
+    ///
+    /// The encrypted VM reduces the addresses to the RAM modulo the next power
+    /// of two, so other sizes would not wrap as in the plaintext VM.
+    ///
+    /// Every cycle of the encrypted VM reads and writes the whole RAM, so its
+    /// cost grows with this size.
+    pub fn with_ram_size(mut self, ram_size: usize) -> Self {
+        self.ram_size = ram_size;
+        self
+    }
+
+    /// Sets the size, in bytes, of the read-only data bank holding `.rodata`,
+    /// or keeps `.rodata` in the RAM if 0.
+    ///
+    /// The bank is never written and is read as the ROM, so its cost is much
+    /// lower than the one of the RAM: it suits large constant tables. It is
+    /// mapped after the RAM, at [fhevm::rodata::rodata_offset].
+    pub fn with_rodata_size(mut self, rodata_size: usize) -> Self {
+        self.rodata_size = rodata_size;
+        self
+    }
+
+    pub fn rom_size(&self) -> usize {
+        self.rom_size
+    }
+
+    pub fn ram_size(&self) -> usize {
+        self.ram_size
+    }
+
+    pub fn rodata_size(&self) -> usize {
+        self.rodata_size
+    }
+
+    /// Address of the start of the read-only data bank.
+    pub fn rodata_origin(&self) -> usize {
+        Self::RAM_ORIGIN + rodata_offset(self.ram_size >> 2, self.rodata_size >> 2)
+    }
+
+    /// Panics if the layout is invalid: empty or non word-aligned regions, a RAM
+    /// size that is not a power of two, overlapping regions or a RAM or read-only data bank beyond the 32-bit
+    /// address space.
+    pub fn check(&self) {
+        assert!(
+            self.rom_size > 0 && self.rom_size.is_multiple_of(4),
+            "ROM size must be a non-zero multiple of 4: {}",
+            self.rom_size
+        );
+        assert!(
+            self.ram_size >= 4 && self.ram_size.is_power_of_two(),
+            "RAM size must be a power of two of at least 4: {}",
+            self.ram_size
+        );
+        assert!(
+            self.rom_size <= Self::RAM_ORIGIN,
+            "ROM [0, {:#x}) overlaps RAM starting at {:#x}",
+            self.rom_size,
+            Self::RAM_ORIGIN
+        );
+        assert!(
+            Self::RAM_ORIGIN + self.ram_size <= 1 << 32,
+            "RAM [{:#x}, {:#x}) exceeds the 32-bit address space",
+            Self::RAM_ORIGIN,
+            Self::RAM_ORIGIN + self.ram_size
+        );
+        assert!(
+            self.rodata_size.is_multiple_of(4),
+            "read-only data size must be a multiple of 4: {}",
+            self.rodata_size
+        );
+        assert!(
+            self.rodata_origin() + self.rodata_size <= 1 << 32,
+            "read-only data [{:#x}, {:#x}) exceeds the 32-bit address space",
+            self.rodata_origin(),
+            self.rodata_origin() + self.rodata_size
+        );
+    }
+
+    /// Returns the linker script of the layout.
+    pub fn linker_script(&self) -> String {
+        self.check();
+        let (rodata_memory, rodata_symbols, rodata_region) = if self.rodata_size > 0 {
+            (
+                format!(
+                    "    RODATA (r)  : ORIGIN = {:#x}, LENGTH = {:#x} /* Read-only data memory */",
+                    self.rodata_origin(),
+                    self.rodata_size
+                ),
+                "  _rodata_start = ORIGIN(RODATA);\n  _rodata_end = ORIGIN(RODATA) + LENGTH(RODATA);"
+                    .to_string(),
+                "RODATA",
+            )
+        } else {
+            (String::new(), String::new(), "RAM")
+        };
+        LINKER_SCRIPT
+            .replace("${ROM_SIZE}", &format!("{:#x}", self.rom_size))
+            .replace("${RAM_ORIGIN}", &format!("{:#x}", Self::RAM_ORIGIN))
+            .replace("${RAM_SIZE}", &format!("{:#x}", self.ram_size))
+            .replace("${RODATA_MEMORY}", &rodata_memory)
+            .replace("${RODATA_SYMBOLS}", &rodata_symbols)
+            .replace("${RODATA_REGION}", rodata_region)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLACEHOLDERS: [&str; 6] = [
+        "${ROM_SIZE}",
+        "${RAM_ORIGIN}",
+        "${RAM_SIZE}",
+        "${RODATA_MEMORY}",
+        "${RODATA_SYMBOLS}",
+        "${RODATA_REGION}",
+    ];
+
+    #[test]
+    fn test_linker_script() {
+        let script: String = MemoryLayout::new().linker_script();
+        for placeholder in PLACEHOLDERS {
+            assert!(!script.contains(placeholder), "{placeholder} in {script}");
+        }
+        assert!(script.contains("LENGTH = 0x40000  /* Code memory */"));
+        assert!(script.contains("ORIGIN = 0x40000, LENGTH = 0x1000 /* Data memory */"));
+        assert!(script.contains("} > RAM AT> RAM\n\n  .data"));
+        assert!(!script.contains("RODATA"));
+        assert!(!script.contains("_rodata_start"));
+    }
+
+    #[test]
+    fn test_linker_script_rodata() {
+        let layout: MemoryLayout = MemoryLayout::new()
+            .with_rom_size(0x800)
+            .with_ram_size(0x2000)
+            .with_rodata_size(0x100);
+        let script: String = layout.linker_script();
+        for placeholder in PLACEHOLDERS {
+            assert!(!script.contains(placeholder), "{placeholder} in {script}");
+        }
+        assert!(script.contains("LENGTH = 0x800  /* Code memory */"));
+        assert!(script.contains("ORIGIN = 0x40000, LENGTH = 0x2000 /* Data memory */"));
+        assert!(script.contains(&format!(
+            "RODATA (r)  : ORIGIN = {:#x}, LENGTH = 0x100",
+            layout.rodata_origin()
+        )));
+        assert!(script.contains("_rodata_start = ORIGIN(RODATA);"));
+        assert!(script.contains("} > RODATA AT> RODATA"));
+    }
+
+    #[test]
+    fn test_check() {
+        MemoryLayout::new().check();
+        MemoryLayout::new()
+            .with_rom_size(MemoryLayout::RAM_ORIGIN)
+            .with_ram_size(4)
+            .with_rodata_size(12)
+            .check();
+    }
+
+    #[test]
+    #[should_panic(expected = "RAM size must be a power of two of at least 4: 12288")]
+    fn test_check_ram_not_power_of_two() {
+        MemoryLayout::new().with_ram_size(12 * 1024).check();
+    }
+
+    #[test]
+    #[should_panic(expected = "RAM size must be a power of two of at least 4: 0")]
+    fn test_check_empty_ram() {
+        MemoryLayout::new().with_ram_size(0).check();
+    }
+
+    #[test]
+    #[should_panic(expected = "ROM size must be a non-zero multiple of 4: 6")]
+    fn test_check_misaligned_rom() {
+        MemoryLayout::new().with_rom_size(6).check();
+    }
+
+    #[test]
+    #[should_panic(expected = "ROM [0, 0x40004) overlaps RAM starting at 0x40000")]
+    fn test_check_rom_overlaps_ram() {
+        MemoryLayout::new()
+            .with_rom_size(MemoryLayout::RAM_ORIGIN + 4)
+            .check();
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the 32-bit address space")]
+    fn test_check_ram_too_large() {
+        MemoryLayout::new().with_ram_size(1 << 32).check();
+    }
+
+    #[test]
+    #[should_panic(expected = "read-only data size must be a multiple of 4: 6")]
+    fn test_check_misaligned_rodata() {
+        MemoryLayout::new().with_rodata_size(6).check();
+    }
+}
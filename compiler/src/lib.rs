@@ -1,16 +1,25 @@
 use std::{
     fs,
     io::{self, Write},
-    path::Path,
+    path::{Path, PathBuf},
     process::Command,
 };
 
 pub mod interpreter;
+mod layout;
 
-pub use interpreter::Phantom;
+pub use fhevm::{HaltStatus, InstructionSet};
+pub use interpreter::{
+    ElfLoadError, EncryptedProgram, Phantom, PhantomClient, PhantomInputEncryptor, PhantomServer,
+};
+pub use layout::MemoryLayout;
 
 pub struct CompileOpts {
     program: String,
+    instruction_set: InstructionSet,
+    compressed: bool,
+    rv32e: bool,
+    memory_layout: MemoryLayout,
 }
 
 impl CompileOpts {
@@ -18,32 +27,94 @@ impl CompileOpts {
     pub fn new(program: &str) -> CompileOpts {
         CompileOpts {
             program: program.to_string(),
+            instruction_set: InstructionSet::RV32I,
+            compressed: false,
+            rv32e: false,
+            memory_layout: MemoryLayout::default(),
         }
     }
 
+    /// Compiles the guest for `instruction_set` instead of RV32I.
+    pub fn with_instruction_set(mut self, instruction_set: InstructionSet) -> CompileOpts {
+        self.instruction_set = instruction_set;
+        self
+    }
+
+    /// Compiles the guest with compressed (RV32C) instructions, which are
+    /// expanded when the ELF is loaded.
+    pub fn with_compressed_instructions(mut self) -> CompileOpts {
+        self.compressed = true;
+        self
+    }
+
+    /// Compiles the guest for RV32E, which only has the registers x0-x15, so
+    /// that the encrypted VM runs with a smaller register file.
+    ///
+    /// The RV32E targets are not distributed prebuilt: this requires a nightly
+    /// toolchain with the `rust-src` component, to build `core` and `alloc`.
+    pub fn with_rv32e(mut self) -> CompileOpts {
+        self.rv32e = true;
+        self
+    }
+
+    /// Links the guest with the memory regions of `memory_layout` instead of
+    /// the default 4KB of RAM.
+    pub fn with_memory_layout(mut self, memory_layout: MemoryLayout) -> CompileOpts {
+        memory_layout.check();
+        self.memory_layout = memory_layout;
+        self
+    }
+
     pub fn build(&self, destination_name: &str) -> Vec<u8> {
-        // set compilation target to riscv32i
-        let target = "riscv32i-unknown-none-elf";
+        // set compilation target to riscv32i, riscv32im or riscv32imc,
+        // or their RV32E counterpart
+        let target = match (self.rv32e, self.instruction_set.has_m(), self.compressed) {
+            (false, true, true) => "riscv32imc-unknown-none-elf",
+            (false, true, false) => "riscv32im-unknown-none-elf",
+            (false, false, _) => "riscv32i-unknown-none-elf",
+            (true, true, true) => "riscv32emc-unknown-none-elf",
+            (true, true, false) => "riscv32em-unknown-none-elf",
+            (true, false, _) => "riscv32e-unknown-none-elf",
+        };
         let profile = "release";
 
-        // Direct path to linker file for the rust compiler
-        let linker_path = {
-            let manifest_dir = env!("CARGO_MANIFEST_DIR");
-            fs::canonicalize(Path::new(manifest_dir).join("linker-script/default.x")).unwrap()
+        // Destination for outputs
+        let destination = format!("/tmp/vm-experiments/{}", destination_name);
+
+        // Linker script generated from the memory layout. It is named after
+        // the layout so that a new layout changes the rustflags, which makes
+        // cargo link the guest again.
+        let linker_path: PathBuf = {
+            let layout = &self.memory_layout;
+            fs::create_dir_all(&destination).unwrap();
+            let path = Path::new(&destination).join(format!(
+                "memory-{:x}-{:x}-{:x}.x",
+                layout.rom_size(),
+                layout.ram_size(),
+                layout.rodata_size()
+            ));
+            fs::write(&path, layout.linker_script()).unwrap();
+            fs::canonicalize(path).unwrap()
         };
 
-        let rust_flags = [
+        let link_arg = format!("link-arg=-T{}", linker_path.to_str().unwrap());
+        let mut rust_flags = vec![
             "-C",
             // specify the linker path
-            &format!("link-arg=-T{}", linker_path.to_str().unwrap()),
+            &link_arg,
             "-C",
             "panic=abort",
         ];
+        if self.instruction_set.has_zbb() {
+            rust_flags.extend(["-C", "target-feature=+zbb"]);
+        }
+        if self.compressed {
+            // relocations are required to remap code addresses once the
+            // compressed instructions are expanded
+            rust_flags.extend(["-C", "target-feature=+c", "-C", "link-arg=--emit-relocs"]);
+        }
         let envs = vec![("CARGO_ENCODED_RUSTFLAGS", rust_flags.join("\x1f"))];
 
-        // Destination for outputs
-        let destination = format!("/tmp/vm-experiments/{}", destination_name);
-
         let cargo_bin = std::env::var("CARGO").unwrap();
         let mut cmd = Command::new(cargo_bin);
         // Compile /guest/main using cargo
@@ -61,6 +132,9 @@ impl CompileOpts {
             destination.as_str(),
             // "--verbose",
         ]);
+        if self.rv32e {
+            cmd.args(["-Zbuild-std=core,alloc"]);
+        }
         let out = cmd.output().unwrap();
 
         if !out.status.success() {
@@ -6,6 +6,7 @@ use fhevm::{
     keys::{VMKeys, VMKeysPrepared},
     parameters::CryptographicParameters,
     prepare::PrepareMultiple,
+    sources::Sources,
 };
 use std::env;
 
@@ -33,7 +34,6 @@ use poulpy_core::{
 use poulpy_hal::{
     api::{ModuleN, ModuleNew, ScratchOwnedAlloc, ScratchOwnedBorrow},
     layouts::{Backend, Module, Scratch, ScratchOwned},
-    source::Source,
 };
 use poulpy_schemes::bin_fhe::{
     bdd_arithmetic::{
@@ -109,17 +109,16 @@ where
     let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
     let module: &Module<BE> = params.module();
 
-    let mut source_xs: Source = Source::new([0u8; 32]);
-    let mut source_xa: Source = Source::new([0u8; 32]);
-    let mut source_xe: Source = Source::new([0u8; 32]);
+    // Deterministic seed to keep the benchmark reproducible.
+    let mut sources: Sources = Sources::insecure_from_seed([0u8; 32]);
 
     let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 24);
 
     // Generates a new secret-key along with the public evaluation keys.
     let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
-    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    sk_glwe.fill_ternary_prob(0.5, &mut sources.xs);
     let mut sk_lwe: LWESecret<Vec<u8>> = LWESecret::alloc(params.n_lwe());
-    sk_lwe.fill_binary_block(params.lwe_block_size(), &mut source_xs);
+    sk_lwe.fill_binary_block(params.lwe_block_size(), &mut sources.xs);
 
     let mut interpreter: Interpreter<BE> =
         Interpreter::new_with_debug(&params, rom.len(), ram.len());
@@ -137,8 +136,8 @@ where
         module,
         &instructions,
         &sk_glwe_prepared,
-        &mut source_xa,
-        &mut source_xe,
+        &mut sources.xa,
+        &mut sources.xe,
         scratch.borrow(),
     );
 
@@ -146,15 +145,15 @@ where
         module,
         &ram,
         &sk_glwe_prepared,
-        &mut source_xa,
-        &mut source_xe,
+        &mut sources.xa,
+        &mut sources.xe,
         scratch.borrow(),
     );
 
     let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 28);
 
     let key: VMKeys<Vec<u8>, BRA> =
-        VMKeys::encrypt_sk(&params, &sk_lwe, &sk_glwe, &mut source_xa, &mut source_xe);
+        VMKeys::encrypt_sk(&params, &sk_lwe, &sk_glwe, &mut sources.xa, &mut sources.xe);
 
     let mut key_prepared: VMKeysPrepared<Vec<u8>, BRA, BE> = VMKeysPrepared::alloc(&params);
     key_prepared.prepare(module, &key, scratch.borrow());
@@ -0,0 +1,166 @@
+//! Alignment of the RAM accesses of the [crate::Interpreter].
+//!
+//! The RAM is word addressed: a cycle reads the word holding the accessed
+//! address and writes it back. Byte accesses and halfword accesses at offset 0
+//! or 2 stay within that word, the loaded value being moved to the low bits
+//! by [align_loaded]. Halfword accesses at an odd address and word accesses
+//! at an address which is not a multiple of 4 would span two words: they trap
+//! instead. A trapping instruction writes neither rd nor the RAM and leaves
+//! the PC on itself, so that the program halts at the next cycle (see
+//! [crate::halt]).
+
+use std::collections::HashMap;
+
+use poulpy_core::{
+    layouts::{GGLWEInfos, GGLWEPreparedToRef, GLWEAutomorphismKeyHelper, GetGaloisElement},
+    GLWEAdd, GLWECopy, GLWEPacking, GLWERotate, GLWESub, GLWETrace, ScratchTakeCore,
+};
+use poulpy_hal::{
+    api::ModuleLogN,
+    layouts::{Backend, DataMut, DataRef, Scratch},
+};
+use poulpy_schemes::bin_fhe::bdd_arithmetic::{
+    ExecuteBDDCircuit, FheUint, FheUintPrepared, GLWEBlinSelection,
+};
+
+use crate::{
+    bdd::{BDDBuilder, BDDInputs, BDDRef, DynamicCircuit, FALSE},
+    RAM_UPDATE, RD_UPDATE,
+};
+
+// Number of bits of the RAM update operation ids.
+const RAM_OP_BITS: usize = 2;
+
+/// Returns true if the load `rdu` or the store `mu` at `addr` is misaligned.
+pub(crate) fn is_misaligned(rdu: u32, mu: u32, addr: u32) -> bool {
+    let half: bool =
+        rdu == RD_UPDATE::LH.id() || rdu == RD_UPDATE::LHU.id() || mu == RAM_UPDATE::SH.id();
+    let word: bool = rdu == RD_UPDATE::LW.id() || mu == RAM_UPDATE::SW.id();
+    (half && addr & 1 != 0) || (word && addr & 3 != 0)
+}
+
+/// Moves the byte, or the halfword, at `offset` of the loaded word `ram` to
+/// its low bits, as the loads only read the low bits of the word.
+pub(crate) fn align_loaded(ram: u32, offset: u32) -> u32 {
+    match offset {
+        0 => ram,
+        1 => (ram & 0xFFFF_FF00) | ((ram >> 8) & 0xFF),
+        2 => (ram & 0xFFFF_0000) | (ram >> 16),
+        3 => (ram & 0xFFFF_FF00) | (ram >> 24),
+        _ => unreachable!("invalid offset: {offset}"),
+    }
+}
+
+// Returns the BDD of `bits == id`, FALSE if `id` is not representable on
+// `bits`, i.e. if the operation is not in the operation set.
+fn is_op(b: &mut BDDBuilder, bits: &[BDDRef], id: u32) -> BDDRef {
+    if (id as u64) >> bits.len() == 0 {
+        b.eq_const(bits, id)
+    } else {
+        FALSE
+    }
+}
+
+/// Circuit [addr[2], mu[2], rdu[rd_op_bits]] -> [misaligned] implementing
+/// [is_misaligned].
+pub(crate) fn misaligned_circuit(rd_op_bits: usize) -> DynamicCircuit {
+    let mut b: BDDBuilder = BDDBuilder::new();
+
+    let a0: BDDRef = b.var(0);
+    let a1: BDDRef = b.var(1);
+    let mu: Vec<BDDRef> = (0..RAM_OP_BITS).map(|i| b.var(2 + i)).collect();
+    let rdu: Vec<BDDRef> = (0..rd_op_bits)
+        .map(|i| b.var(2 + RAM_OP_BITS + i))
+        .collect();
+
+    let lh: BDDRef = is_op(&mut b, &rdu, RD_UPDATE::LH.id());
+    let lhu: BDDRef = is_op(&mut b, &rdu, RD_UPDATE::LHU.id());
+    let sh: BDDRef = is_op(&mut b, &mu, RAM_UPDATE::SH.id());
+    let lw: BDDRef = is_op(&mut b, &rdu, RD_UPDATE::LW.id());
+    let sw: BDDRef = is_op(&mut b, &mu, RAM_UPDATE::SW.id());
+
+    let half: BDDRef = b.or(lh, lhu);
+    let half: BDDRef = b.or(half, sh);
+    let word: BDDRef = b.or(lw, sw);
+
+    let odd: BDDRef = b.and(half, a0);
+    let unaligned: BDDRef = b.or(a0, a1);
+    let unaligned: BDDRef = b.and(word, unaligned);
+    let misaligned: BDDRef = b.or(odd, unaligned);
+
+    b.circuit(2 + RAM_OP_BITS + rd_op_bits, &[misaligned])
+}
+
+/// Evaluates `circuit` (see [misaligned_circuit]) and writes the misaligned
+/// flag in the bit 0 of `res`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn eval_misaligned<R, A, U, D, H, K, M, BE: Backend>(
+    threads: usize,
+    module: &M,
+    res: &mut FheUint<R, u32>,
+    addr: &FheUintPrepared<A, u32, BE>,
+    mu: &FheUintPrepared<U, u32, BE>,
+    rdu: &FheUintPrepared<D, u32, BE>,
+    rd_op_bits: usize,
+    circuit: &DynamicCircuit,
+    keys: &H,
+    scratch: &mut Scratch<BE>,
+) where
+    R: DataMut,
+    A: DataRef,
+    U: DataRef,
+    D: DataRef,
+    H: GLWEAutomorphismKeyHelper<K, BE>,
+    K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+    M: ModuleLogN + GLWEPacking<BE> + GLWECopy + ExecuteBDDCircuit<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+{
+    let inputs: BDDInputs<'_, BE> = BDDInputs::new()
+        .word(addr, 2)
+        .word(mu, RAM_OP_BITS)
+        .word(rdu, rd_op_bits);
+
+    let (mut out_bits, scratch_1) = scratch.take_glwe_slice(1, res);
+    module.execute_bdd_circuit_multi_thread(threads, &mut out_bits, &inputs, circuit, scratch_1);
+    res.pack(module, out_bits, keys, scratch_1);
+}
+
+/// Encrypted [align_loaded], with the offset given by the bits [0, 2) of `addr`.
+pub(crate) fn eval_align_loaded<R, B, A, H, K, M, BE: Backend>(
+    module: &M,
+    res: &mut FheUint<R, u32>,
+    loaded: &FheUint<B, u32>,
+    addr: &FheUintPrepared<A, u32, BE>,
+    keys: &H,
+    scratch: &mut Scratch<BE>,
+) where
+    R: DataMut,
+    B: DataRef,
+    A: DataRef,
+    H: GLWEAutomorphismKeyHelper<K, BE>,
+    K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+    M: ModuleLogN
+        + GLWEBlinSelection<u32, BE>
+        + GLWERotate<BE>
+        + GLWETrace<BE>
+        + GLWESub
+        + GLWEAdd
+        + GLWECopy,
+    Scratch<BE>: ScratchTakeCore<BE>,
+{
+    let mut cts: HashMap<usize, FheUint<Vec<u8>, u32>> = HashMap::new();
+    for offset in 0..4 {
+        let mut tmp: FheUint<Vec<u8>, u32> = FheUint::alloc_from_infos(res);
+        match offset {
+            0 => module.glwe_copy(&mut tmp, loaded),
+            2 => tmp.splice_u16(module, 0, 1, loaded, loaded, keys, scratch),
+            _ => tmp.splice_u8(module, 0, offset, loaded, loaded, keys, scratch),
+        }
+        cts.insert(offset, tmp);
+    }
+    let mut cts_ref: HashMap<usize, &mut FheUint<Vec<u8>, u32>> = HashMap::new();
+    for (key, object) in cts.iter_mut() {
+        cts_ref.insert(*key, object);
+    }
+    module.glwe_blind_selection(res, cts_ref, addr, 0, 2, scratch);
+}
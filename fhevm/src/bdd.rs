@@ -0,0 +1,341 @@
+use std::collections::{HashMap, HashSet};
+
+use poulpy_core::layouts::GGSWPrepared;
+use poulpy_hal::layouts::Backend;
+use poulpy_schemes::bin_fhe::bdd_arithmetic::{BitSize, GetBitCircuitInfo, GetGGSWBit, Node};
+
+/// Reference to a node of a [BDDBuilder].
+pub type BDDRef = usize;
+
+/// Constant false terminal.
+pub const FALSE: BDDRef = 0;
+
+/// Constant true terminal.
+pub const TRUE: BDDRef = 1;
+
+// Variable of the terminals, ordered after all the declared variables.
+const TERMINAL: usize = usize::MAX;
+
+/// Builds reduced ordered BDDs and lays them out as circuits for
+/// [poulpy_schemes::bin_fhe::bdd_arithmetic::ExecuteBDDCircuit].
+///
+/// Unlike the circuits of [crate::codegen], which are generated offline, these
+/// are built at runtime from a description of the operation as boolean gates.
+///
+/// Variables are ordered from the root to the leaves in the order they are
+/// declared with [BDDBuilder::var]. Circuits are evaluated from the leaves to
+/// the root, so bits that propagate state (e.g. the low bits of an addition)
+/// should be declared last to keep the circuits narrow.
+pub struct BDDBuilder {
+    // Input bit bound to each variable.
+    inputs: Vec<usize>,
+    // (variable, hi, lo) of each node, starting with the two terminals.
+    nodes: Vec<(usize, BDDRef, BDDRef)>,
+    unique: HashMap<(usize, BDDRef, BDDRef), BDDRef>,
+    ite_cache: HashMap<(BDDRef, BDDRef, BDDRef), BDDRef>,
+}
+
+impl Default for BDDBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BDDBuilder {
+    pub fn new() -> Self {
+        Self {
+            inputs: Vec::new(),
+            nodes: vec![(TERMINAL, FALSE, FALSE), (TERMINAL, TRUE, TRUE)],
+            unique: HashMap::new(),
+            ite_cache: HashMap::new(),
+        }
+    }
+
+    /// Declares the next variable of the order, bound to the input bit `input`.
+    pub fn var(&mut self, input: usize) -> BDDRef {
+        self.inputs.push(input);
+        self.node(self.inputs.len() - 1, TRUE, FALSE)
+    }
+
+    fn node(&mut self, var: usize, hi: BDDRef, lo: BDDRef) -> BDDRef {
+        if hi == lo {
+            return hi;
+        }
+        if let Some(&node) = self.unique.get(&(var, hi, lo)) {
+            return node;
+        }
+        self.nodes.push((var, hi, lo));
+        let node: BDDRef = self.nodes.len() - 1;
+        self.unique.insert((var, hi, lo), node);
+        node
+    }
+
+    fn cofactors(&self, f: BDDRef, var: usize) -> (BDDRef, BDDRef) {
+        let (f_var, hi, lo) = self.nodes[f];
+        if f_var == var {
+            (hi, lo)
+        } else {
+            (f, f)
+        }
+    }
+
+    /// if f { g } else { h }
+    pub fn ite(&mut self, f: BDDRef, g: BDDRef, h: BDDRef) -> BDDRef {
+        if f == TRUE || g == h {
+            return g;
+        }
+        if f == FALSE {
+            return h;
+        }
+        if g == TRUE && h == FALSE {
+            return f;
+        }
+        if let Some(&res) = self.ite_cache.get(&(f, g, h)) {
+            return res;
+        }
+
+        let var: usize = self.nodes[f].0.min(self.nodes[g].0).min(self.nodes[h].0);
+        let (f_hi, f_lo) = self.cofactors(f, var);
+        let (g_hi, g_lo) = self.cofactors(g, var);
+        let (h_hi, h_lo) = self.cofactors(h, var);
+        let hi: BDDRef = self.ite(f_hi, g_hi, h_hi);
+        let lo: BDDRef = self.ite(f_lo, g_lo, h_lo);
+        let res: BDDRef = self.node(var, hi, lo);
+
+        self.ite_cache.insert((f, g, h), res);
+        res
+    }
+
+    pub fn not(&mut self, a: BDDRef) -> BDDRef {
+        self.ite(a, FALSE, TRUE)
+    }
+
+    pub fn and(&mut self, a: BDDRef, b: BDDRef) -> BDDRef {
+        self.ite(a, b, FALSE)
+    }
+
+    pub fn or(&mut self, a: BDDRef, b: BDDRef) -> BDDRef {
+        self.ite(a, TRUE, b)
+    }
+
+    pub fn xor(&mut self, a: BDDRef, b: BDDRef) -> BDDRef {
+        let not_b: BDDRef = self.not(b);
+        self.ite(a, not_b, b)
+    }
+
+    /// Bitwise if s { t } else { f }.
+    pub fn mux(&mut self, s: BDDRef, t: &[BDDRef], f: &[BDDRef]) -> Vec<BDDRef> {
+        assert_eq!(t.len(), f.len());
+        t.iter().zip(f).map(|(&t, &f)| self.ite(s, t, f)).collect()
+    }
+
+    /// Returns a + b + carry on a.len() + 1 bits (little endian).
+    pub fn add(&mut self, a: &[BDDRef], b: &[BDDRef], mut carry: BDDRef) -> Vec<BDDRef> {
+        assert_eq!(a.len(), b.len());
+        let mut res: Vec<BDDRef> = Vec::with_capacity(a.len() + 1);
+        for (&a, &b) in a.iter().zip(b) {
+            let a_xor_b: BDDRef = self.xor(a, b);
+            res.push(self.xor(a_xor_b, carry));
+            let a_and_b: BDDRef = self.and(a, b);
+            let propagate: BDDRef = self.and(a_xor_b, carry);
+            carry = self.or(a_and_b, propagate);
+        }
+        res.push(carry);
+        res
+    }
+
+    /// Returns a - b on a.len() + 1 bits, the last bit being set iff a >= b.
+    pub fn sub(&mut self, a: &[BDDRef], b: &[BDDRef]) -> Vec<BDDRef> {
+        let not_b: Vec<BDDRef> = b.iter().map(|&b| self.not(b)).collect();
+        self.add(a, &not_b, TRUE)
+    }
+
+    /// Returns -a on a.len() bits.
+    pub fn neg(&mut self, a: &[BDDRef]) -> Vec<BDDRef> {
+        let not_a: Vec<BDDRef> = a.iter().map(|&a| self.not(a)).collect();
+        let zero: Vec<BDDRef> = vec![FALSE; a.len()];
+        let mut res: Vec<BDDRef> = self.add(&not_a, &zero, TRUE);
+        res.truncate(a.len());
+        res
+    }
+
+    /// Returns true iff the word `a` is equal to `value`.
+    pub fn eq_const(&mut self, a: &[BDDRef], value: u32) -> BDDRef {
+        a.iter().enumerate().fold(TRUE, |acc, (i, &a)| {
+            let bit: BDDRef = if (value >> i) & 1 == 1 {
+                a
+            } else {
+                self.not(a)
+            };
+            self.and(acc, bit)
+        })
+    }
+
+    /// Lays out `outputs` as a circuit over `input_size` input bits.
+    pub fn circuit(&self, input_size: usize, outputs: &[BDDRef]) -> DynamicCircuit {
+        assert!(self.inputs.iter().all(|&input| input < input_size));
+        DynamicCircuit {
+            input_size,
+            circuits: outputs.iter().map(|&root| self.layout(root)).collect(),
+        }
+    }
+
+    // Lays out the BDD of `root` as levels of [Node], one per branching variable,
+    // from the leaves to the root. Each node keeps the same slot of the state for
+    // its whole lifetime, slots 0 and 1 initially holding the terminals.
+    fn layout(&self, root: BDDRef) -> (Vec<Node>, usize) {
+        match root {
+            FALSE => return (Vec::new(), 0),
+            TRUE => return (vec![Node::Cmux(0, TRUE, TRUE), Node::None], 2),
+            _ => {}
+        }
+
+        let mut reachable: Vec<BDDRef> = Vec::new();
+        let mut visited: HashSet<BDDRef> = HashSet::from([FALSE, TRUE]);
+        let mut stack: Vec<BDDRef> = vec![root];
+        while let Some(node) = stack.pop() {
+            if visited.insert(node) {
+                reachable.push(node);
+                let (_, hi, lo) = self.nodes[node];
+                stack.push(hi);
+                stack.push(lo);
+            }
+        }
+
+        // Evaluation step of each branching variable, from the leaves to the root.
+        let mut vars: Vec<usize> = reachable.iter().map(|&node| self.nodes[node].0).collect();
+        vars.sort_unstable_by(|a, b| b.cmp(a));
+        vars.dedup();
+        let steps: usize = vars.len();
+        let step_of: HashMap<usize, usize> = vars
+            .iter()
+            .enumerate()
+            .map(|(i, &var)| (var, i + 1))
+            .collect();
+
+        let mut created: Vec<Vec<BDDRef>> = vec![Vec::new(); steps + 1];
+        let mut last_use: HashMap<BDDRef, usize> = HashMap::new();
+        reachable.sort_unstable();
+        for &node in &reachable {
+            let (var, hi, lo) = self.nodes[node];
+            let step: usize = step_of[&var];
+            created[step].push(node);
+            for child in [hi, lo] {
+                let entry: &mut usize = last_use.entry(child).or_default();
+                *entry = (*entry).max(step);
+            }
+        }
+
+        let mut slot: HashMap<BDDRef, usize> = HashMap::from([(FALSE, 0), (TRUE, 1)]);
+        let mut state: Vec<Option<BDDRef>> = vec![Some(FALSE), Some(TRUE)];
+        let mut levels: Vec<Vec<Node>> = Vec::with_capacity(steps);
+
+        for (step, nodes) in created.iter().enumerate().take(steps).skip(1) {
+            // Frees the slots of the nodes that are not needed after this step.
+            // Nodes last read at this step are read from the previous state, so
+            // their slot can already be written.
+            for entry in state.iter_mut() {
+                if entry.is_some_and(|node| last_use.get(&node).copied().unwrap_or(0) <= step) {
+                    *entry = None;
+                }
+            }
+
+            let mut level: Vec<Node> = state
+                .iter()
+                .map(|entry| match entry {
+                    Some(_) => Node::Copy,
+                    None => Node::None,
+                })
+                .collect();
+
+            for &node in nodes {
+                let (var, hi, lo) = self.nodes[node];
+                let cmux: Node = Node::Cmux(self.inputs[var], slot[&hi], slot[&lo]);
+                let j: usize = match state.iter().position(Option::is_none) {
+                    Some(j) => j,
+                    None => {
+                        state.push(None);
+                        level.push(Node::None);
+                        state.len() - 1
+                    }
+                };
+                state[j] = Some(node);
+                level[j] = cmux;
+                slot.insert(node, j);
+            }
+
+            levels.push(level);
+        }
+
+        let state_size: usize = levels.iter().map(Vec::len).max().unwrap_or(0).max(2);
+
+        // The last level is [CMUX, NONE, ..., NONE], the root being written to the output.
+        let (var, hi, lo) = self.nodes[root];
+        levels.push(vec![Node::Cmux(self.inputs[var], slot[&hi], slot[&lo])]);
+
+        let mut nodes: Vec<Node> = Vec::with_capacity(state_size * levels.len());
+        for mut level in levels {
+            level.resize_with(state_size, || Node::None);
+            nodes.extend(level);
+        }
+
+        (nodes, state_size)
+    }
+}
+
+/// Circuit built with a [BDDBuilder].
+pub struct DynamicCircuit {
+    input_size: usize,
+    circuits: Vec<(Vec<Node>, usize)>,
+}
+
+impl GetBitCircuitInfo for DynamicCircuit {
+    fn input_size(&self) -> usize {
+        self.input_size
+    }
+
+    fn output_size(&self) -> usize {
+        self.circuits.len()
+    }
+
+    fn get_circuit(&self, bit: usize) -> (&[Node], usize) {
+        let (nodes, state_size) = &self.circuits[bit];
+        (nodes, *state_size)
+    }
+}
+
+/// Binds the input bits of a [DynamicCircuit] to bits of prepared ciphertexts.
+pub(crate) struct BDDInputs<'a, BE: Backend> {
+    bits: Vec<(&'a dyn GetGGSWBit<BE>, usize)>,
+}
+
+impl<'a, BE: Backend> BDDInputs<'a, BE> {
+    pub(crate) fn new() -> Self {
+        Self { bits: Vec::new() }
+    }
+
+    /// Appends the bits [0, size) of `word`.
+    pub(crate) fn word(mut self, word: &'a dyn GetGGSWBit<BE>, size: usize) -> Self {
+        self.bits.extend((0..size).map(|i| (word, i)));
+        self
+    }
+
+    /// Appends the bit `bit` of `word`.
+    pub(crate) fn bit(mut self, word: &'a dyn GetGGSWBit<BE>, bit: usize) -> Self {
+        self.bits.push((word, bit));
+        self
+    }
+}
+
+impl<BE: Backend> GetGGSWBit<BE> for BDDInputs<'_, BE> {
+    fn get_bit(&self, bit: usize) -> GGSWPrepared<&[u8], BE> {
+        let (word, i) = self.bits[bit];
+        word.get_bit(i)
+    }
+}
+
+impl<BE: Backend> BitSize for BDDInputs<'_, BE> {
+    fn bit_size(&self) -> usize {
+        self.bits.len()
+    }
+}
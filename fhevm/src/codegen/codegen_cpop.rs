@@ -0,0 +1,1803 @@
+use poulpy_schemes::bin_fhe::bdd_arithmetic::{
+    BitCircuit, BitCircuitFamily, BitCircuitInfo, Circuit, Node,
+};
+pub(crate) enum AnyBitCircuit {
+    B0(BitCircuit<64>),
+    B1(BitCircuit<128>),
+    B2(BitCircuit<256>),
+    B3(BitCircuit<512>),
+    B4(BitCircuit<576>),
+    B5(BitCircuit<64>),
+    B6(BitCircuit<0>),
+    B7(BitCircuit<0>),
+    B8(BitCircuit<0>),
+    B9(BitCircuit<0>),
+    B10(BitCircuit<0>),
+    B11(BitCircuit<0>),
+    B12(BitCircuit<0>),
+    B13(BitCircuit<0>),
+    B14(BitCircuit<0>),
+    B15(BitCircuit<0>),
+    B16(BitCircuit<0>),
+    B17(BitCircuit<0>),
+    B18(BitCircuit<0>),
+    B19(BitCircuit<0>),
+    B20(BitCircuit<0>),
+    B21(BitCircuit<0>),
+    B22(BitCircuit<0>),
+    B23(BitCircuit<0>),
+    B24(BitCircuit<0>),
+    B25(BitCircuit<0>),
+    B26(BitCircuit<0>),
+    B27(BitCircuit<0>),
+    B28(BitCircuit<0>),
+    B29(BitCircuit<0>),
+    B30(BitCircuit<0>),
+    B31(BitCircuit<0>),
+}
+impl BitCircuitInfo for AnyBitCircuit {
+    fn info(&self) -> (&[Node], usize) {
+        match self {
+            AnyBitCircuit::B0(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B1(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B2(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B3(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B4(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B5(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B6(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B7(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B8(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B9(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B10(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B11(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B12(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B13(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B14(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B15(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B16(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B17(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B18(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B19(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B20(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B21(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B22(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B23(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B24(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B25(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B26(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B27(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B28(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B29(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B30(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B31(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+        }
+    }
+}
+
+impl BitCircuitFamily for AnyBitCircuit {
+    const INPUT_BITS: usize = 32;
+    const OUTPUT_BITS: usize = 32;
+}
+
+pub(crate) static OUTPUT_CIRCUITS: Circuit<AnyBitCircuit, 32usize> = Circuit([
+    AnyBitCircuit::B0(BitCircuit::new(
+        [
+            Node::Cmux(31, 1, 0),
+            Node::Cmux(31, 0, 1),
+            Node::Cmux(30, 1, 0),
+            Node::Cmux(30, 0, 1),
+            Node::Cmux(29, 0, 1),
+            Node::Cmux(29, 1, 0),
+            Node::Cmux(28, 0, 1),
+            Node::Cmux(28, 1, 0),
+            Node::Cmux(27, 0, 1),
+            Node::Cmux(27, 1, 0),
+            Node::Cmux(26, 0, 1),
+            Node::Cmux(26, 1, 0),
+            Node::Cmux(25, 0, 1),
+            Node::Cmux(25, 1, 0),
+            Node::Cmux(24, 0, 1),
+            Node::Cmux(24, 1, 0),
+            Node::Cmux(23, 0, 1),
+            Node::Cmux(23, 1, 0),
+            Node::Cmux(22, 0, 1),
+            Node::Cmux(22, 1, 0),
+            Node::Cmux(21, 0, 1),
+            Node::Cmux(21, 1, 0),
+            Node::Cmux(20, 0, 1),
+            Node::Cmux(20, 1, 0),
+            Node::Cmux(19, 0, 1),
+            Node::Cmux(19, 1, 0),
+            Node::Cmux(18, 0, 1),
+            Node::Cmux(18, 1, 0),
+            Node::Cmux(17, 0, 1),
+            Node::Cmux(17, 1, 0),
+            Node::Cmux(16, 0, 1),
+            Node::Cmux(16, 1, 0),
+            Node::Cmux(15, 0, 1),
+            Node::Cmux(15, 1, 0),
+            Node::Cmux(14, 0, 1),
+            Node::Cmux(14, 1, 0),
+            Node::Cmux(13, 0, 1),
+            Node::Cmux(13, 1, 0),
+            Node::Cmux(12, 0, 1),
+            Node::Cmux(12, 1, 0),
+            Node::Cmux(11, 0, 1),
+            Node::Cmux(11, 1, 0),
+            Node::Cmux(10, 0, 1),
+            Node::Cmux(10, 1, 0),
+            Node::Cmux(9, 0, 1),
+            Node::Cmux(9, 1, 0),
+            Node::Cmux(8, 0, 1),
+            Node::Cmux(8, 1, 0),
+            Node::Cmux(7, 0, 1),
+            Node::Cmux(7, 1, 0),
+            Node::Cmux(6, 0, 1),
+            Node::Cmux(6, 1, 0),
+            Node::Cmux(5, 0, 1),
+            Node::Cmux(5, 1, 0),
+            Node::Cmux(4, 0, 1),
+            Node::Cmux(4, 1, 0),
+            Node::Cmux(3, 0, 1),
+            Node::Cmux(3, 1, 0),
+            Node::Cmux(2, 0, 1),
+            Node::Cmux(2, 1, 0),
+            Node::Cmux(1, 0, 1),
+            Node::Cmux(1, 1, 0),
+            Node::Cmux(0, 0, 1),
+            Node::None,
+        ],
+        2,
+    )),
+    AnyBitCircuit::B1(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(31, 1, 0),
+            Node::Cmux(31, 0, 1),
+            Node::Cmux(30, 2, 0),
+            Node::Cmux(30, 3, 1),
+            Node::Cmux(30, 1, 2),
+            Node::Cmux(30, 0, 3),
+            Node::Cmux(29, 1, 2),
+            Node::Cmux(29, 2, 0),
+            Node::Cmux(29, 0, 3),
+            Node::Cmux(29, 3, 1),
+            Node::Cmux(28, 0, 1),
+            Node::Cmux(28, 1, 2),
+            Node::Cmux(28, 2, 3),
+            Node::Cmux(28, 3, 0),
+            Node::Cmux(27, 0, 1),
+            Node::Cmux(27, 1, 2),
+            Node::Cmux(27, 2, 3),
+            Node::Cmux(27, 3, 0),
+            Node::Cmux(26, 0, 1),
+            Node::Cmux(26, 1, 2),
+            Node::Cmux(26, 2, 3),
+            Node::Cmux(26, 3, 0),
+            Node::Cmux(25, 0, 1),
+            Node::Cmux(25, 1, 2),
+            Node::Cmux(25, 2, 3),
+            Node::Cmux(25, 3, 0),
+            Node::Cmux(24, 0, 1),
+            Node::Cmux(24, 1, 2),
+            Node::Cmux(24, 2, 3),
+            Node::Cmux(24, 3, 0),
+            Node::Cmux(23, 0, 1),
+            Node::Cmux(23, 1, 2),
+            Node::Cmux(23, 2, 3),
+            Node::Cmux(23, 3, 0),
+            Node::Cmux(22, 0, 1),
+            Node::Cmux(22, 1, 2),
+            Node::Cmux(22, 2, 3),
+            Node::Cmux(22, 3, 0),
+            Node::Cmux(21, 0, 1),
+            Node::Cmux(21, 1, 2),
+            Node::Cmux(21, 2, 3),
+            Node::Cmux(21, 3, 0),
+            Node::Cmux(20, 0, 1),
+            Node::Cmux(20, 1, 2),
+            Node::Cmux(20, 2, 3),
+            Node::Cmux(20, 3, 0),
+            Node::Cmux(19, 0, 1),
+            Node::Cmux(19, 1, 2),
+            Node::Cmux(19, 2, 3),
+            Node::Cmux(19, 3, 0),
+            Node::Cmux(18, 0, 1),
+            Node::Cmux(18, 1, 2),
+            Node::Cmux(18, 2, 3),
+            Node::Cmux(18, 3, 0),
+            Node::Cmux(17, 0, 1),
+            Node::Cmux(17, 1, 2),
+            Node::Cmux(17, 2, 3),
+            Node::Cmux(17, 3, 0),
+            Node::Cmux(16, 0, 1),
+            Node::Cmux(16, 1, 2),
+            Node::Cmux(16, 2, 3),
+            Node::Cmux(16, 3, 0),
+            Node::Cmux(15, 0, 1),
+            Node::Cmux(15, 1, 2),
+            Node::Cmux(15, 2, 3),
+            Node::Cmux(15, 3, 0),
+            Node::Cmux(14, 0, 1),
+            Node::Cmux(14, 1, 2),
+            Node::Cmux(14, 2, 3),
+            Node::Cmux(14, 3, 0),
+            Node::Cmux(13, 0, 1),
+            Node::Cmux(13, 1, 2),
+            Node::Cmux(13, 2, 3),
+            Node::Cmux(13, 3, 0),
+            Node::Cmux(12, 0, 1),
+            Node::Cmux(12, 1, 2),
+            Node::Cmux(12, 2, 3),
+            Node::Cmux(12, 3, 0),
+            Node::Cmux(11, 0, 1),
+            Node::Cmux(11, 1, 2),
+            Node::Cmux(11, 2, 3),
+            Node::Cmux(11, 3, 0),
+            Node::Cmux(10, 0, 1),
+            Node::Cmux(10, 1, 2),
+            Node::Cmux(10, 2, 3),
+            Node::Cmux(10, 3, 0),
+            Node::Cmux(9, 0, 1),
+            Node::Cmux(9, 1, 2),
+            Node::Cmux(9, 2, 3),
+            Node::Cmux(9, 3, 0),
+            Node::Cmux(8, 0, 1),
+            Node::Cmux(8, 1, 2),
+            Node::Cmux(8, 2, 3),
+            Node::Cmux(8, 3, 0),
+            Node::Cmux(7, 0, 1),
+            Node::Cmux(7, 1, 2),
+            Node::Cmux(7, 2, 3),
+            Node::Cmux(7, 3, 0),
+            Node::Cmux(6, 0, 1),
+            Node::Cmux(6, 1, 2),
+            Node::Cmux(6, 2, 3),
+            Node::Cmux(6, 3, 0),
+            Node::Cmux(5, 0, 1),
+            Node::Cmux(5, 1, 2),
+            Node::Cmux(5, 2, 3),
+            Node::Cmux(5, 3, 0),
+            Node::Cmux(4, 0, 1),
+            Node::Cmux(4, 1, 2),
+            Node::Cmux(4, 2, 3),
+            Node::Cmux(4, 3, 0),
+            Node::Cmux(3, 0, 1),
+            Node::Cmux(3, 1, 2),
+            Node::Cmux(3, 2, 3),
+            Node::Cmux(3, 3, 0),
+            Node::Cmux(2, 0, 1),
+            Node::Cmux(2, 1, 2),
+            Node::Cmux(2, 2, 3),
+            Node::None,
+            Node::Cmux(1, 0, 1),
+            Node::Cmux(1, 1, 2),
+            Node::None,
+            Node::None,
+            Node::Cmux(0, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B2(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(31, 1, 0),
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(30, 2, 0),
+            Node::Cmux(30, 3, 1),
+            Node::Cmux(30, 1, 2),
+            Node::Cmux(30, 0, 3),
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(29, 4, 2),
+            Node::Cmux(29, 5, 3),
+            Node::Cmux(29, 2, 0),
+            Node::Cmux(29, 3, 1),
+            Node::Cmux(29, 1, 4),
+            Node::Cmux(29, 0, 5),
+            Node::Cmux(28, 4, 0),
+            Node::Cmux(28, 5, 1),
+            Node::Cmux(28, 1, 6),
+            Node::Cmux(28, 6, 2),
+            Node::Cmux(28, 2, 4),
+            Node::Cmux(28, 0, 7),
+            Node::Cmux(28, 7, 3),
+            Node::Cmux(28, 3, 5),
+            Node::Cmux(27, 1, 2),
+            Node::Cmux(27, 2, 3),
+            Node::Cmux(27, 3, 4),
+            Node::Cmux(27, 4, 0),
+            Node::Cmux(27, 0, 5),
+            Node::Cmux(27, 5, 6),
+            Node::Cmux(27, 6, 7),
+            Node::Cmux(27, 7, 1),
+            Node::Cmux(26, 0, 1),
+            Node::Cmux(26, 1, 2),
+            Node::Cmux(26, 2, 3),
+            Node::Cmux(26, 3, 4),
+            Node::Cmux(26, 4, 5),
+            Node::Cmux(26, 5, 6),
+            Node::Cmux(26, 6, 7),
+            Node::Cmux(26, 7, 0),
+            Node::Cmux(25, 0, 1),
+            Node::Cmux(25, 1, 2),
+            Node::Cmux(25, 2, 3),
+            Node::Cmux(25, 3, 4),
+            Node::Cmux(25, 4, 5),
+            Node::Cmux(25, 5, 6),
+            Node::Cmux(25, 6, 7),
+            Node::Cmux(25, 7, 0),
+            Node::Cmux(24, 0, 1),
+            Node::Cmux(24, 1, 2),
+            Node::Cmux(24, 2, 3),
+            Node::Cmux(24, 3, 4),
+            Node::Cmux(24, 4, 5),
+            Node::Cmux(24, 5, 6),
+            Node::Cmux(24, 6, 7),
+            Node::Cmux(24, 7, 0),
+            Node::Cmux(23, 0, 1),
+            Node::Cmux(23, 1, 2),
+            Node::Cmux(23, 2, 3),
+            Node::Cmux(23, 3, 4),
+            Node::Cmux(23, 4, 5),
+            Node::Cmux(23, 5, 6),
+            Node::Cmux(23, 6, 7),
+            Node::Cmux(23, 7, 0),
+            Node::Cmux(22, 0, 1),
+            Node::Cmux(22, 1, 2),
+            Node::Cmux(22, 2, 3),
+            Node::Cmux(22, 3, 4),
+            Node::Cmux(22, 4, 5),
+            Node::Cmux(22, 5, 6),
+            Node::Cmux(22, 6, 7),
+            Node::Cmux(22, 7, 0),
+            Node::Cmux(21, 0, 1),
+            Node::Cmux(21, 1, 2),
+            Node::Cmux(21, 2, 3),
+            Node::Cmux(21, 3, 4),
+            Node::Cmux(21, 4, 5),
+            Node::Cmux(21, 5, 6),
+            Node::Cmux(21, 6, 7),
+            Node::Cmux(21, 7, 0),
+            Node::Cmux(20, 0, 1),
+            Node::Cmux(20, 1, 2),
+            Node::Cmux(20, 2, 3),
+            Node::Cmux(20, 3, 4),
+            Node::Cmux(20, 4, 5),
+            Node::Cmux(20, 5, 6),
+            Node::Cmux(20, 6, 7),
+            Node::Cmux(20, 7, 0),
+            Node::Cmux(19, 0, 1),
+            Node::Cmux(19, 1, 2),
+            Node::Cmux(19, 2, 3),
+            Node::Cmux(19, 3, 4),
+            Node::Cmux(19, 4, 5),
+            Node::Cmux(19, 5, 6),
+            Node::Cmux(19, 6, 7),
+            Node::Cmux(19, 7, 0),
+            Node::Cmux(18, 0, 1),
+            Node::Cmux(18, 1, 2),
+            Node::Cmux(18, 2, 3),
+            Node::Cmux(18, 3, 4),
+            Node::Cmux(18, 4, 5),
+            Node::Cmux(18, 5, 6),
+            Node::Cmux(18, 6, 7),
+            Node::Cmux(18, 7, 0),
+            Node::Cmux(17, 0, 1),
+            Node::Cmux(17, 1, 2),
+            Node::Cmux(17, 2, 3),
+            Node::Cmux(17, 3, 4),
+            Node::Cmux(17, 4, 5),
+            Node::Cmux(17, 5, 6),
+            Node::Cmux(17, 6, 7),
+            Node::Cmux(17, 7, 0),
+            Node::Cmux(16, 0, 1),
+            Node::Cmux(16, 1, 2),
+            Node::Cmux(16, 2, 3),
+            Node::Cmux(16, 3, 4),
+            Node::Cmux(16, 4, 5),
+            Node::Cmux(16, 5, 6),
+            Node::Cmux(16, 6, 7),
+            Node::Cmux(16, 7, 0),
+            Node::Cmux(15, 0, 1),
+            Node::Cmux(15, 1, 2),
+            Node::Cmux(15, 2, 3),
+            Node::Cmux(15, 3, 4),
+            Node::Cmux(15, 4, 5),
+            Node::Cmux(15, 5, 6),
+            Node::Cmux(15, 6, 7),
+            Node::Cmux(15, 7, 0),
+            Node::Cmux(14, 0, 1),
+            Node::Cmux(14, 1, 2),
+            Node::Cmux(14, 2, 3),
+            Node::Cmux(14, 3, 4),
+            Node::Cmux(14, 4, 5),
+            Node::Cmux(14, 5, 6),
+            Node::Cmux(14, 6, 7),
+            Node::Cmux(14, 7, 0),
+            Node::Cmux(13, 0, 1),
+            Node::Cmux(13, 1, 2),
+            Node::Cmux(13, 2, 3),
+            Node::Cmux(13, 3, 4),
+            Node::Cmux(13, 4, 5),
+            Node::Cmux(13, 5, 6),
+            Node::Cmux(13, 6, 7),
+            Node::Cmux(13, 7, 0),
+            Node::Cmux(12, 0, 1),
+            Node::Cmux(12, 1, 2),
+            Node::Cmux(12, 2, 3),
+            Node::Cmux(12, 3, 4),
+            Node::Cmux(12, 4, 5),
+            Node::Cmux(12, 5, 6),
+            Node::Cmux(12, 6, 7),
+            Node::Cmux(12, 7, 0),
+            Node::Cmux(11, 0, 1),
+            Node::Cmux(11, 1, 2),
+            Node::Cmux(11, 2, 3),
+            Node::Cmux(11, 3, 4),
+            Node::Cmux(11, 4, 5),
+            Node::Cmux(11, 5, 6),
+            Node::Cmux(11, 6, 7),
+            Node::Cmux(11, 7, 0),
+            Node::Cmux(10, 0, 1),
+            Node::Cmux(10, 1, 2),
+            Node::Cmux(10, 2, 3),
+            Node::Cmux(10, 3, 4),
+            Node::Cmux(10, 4, 5),
+            Node::Cmux(10, 5, 6),
+            Node::Cmux(10, 6, 7),
+            Node::Cmux(10, 7, 0),
+            Node::Cmux(9, 0, 1),
+            Node::Cmux(9, 1, 2),
+            Node::Cmux(9, 2, 3),
+            Node::Cmux(9, 3, 4),
+            Node::Cmux(9, 4, 5),
+            Node::Cmux(9, 5, 6),
+            Node::Cmux(9, 6, 7),
+            Node::Cmux(9, 7, 0),
+            Node::Cmux(8, 0, 1),
+            Node::Cmux(8, 1, 2),
+            Node::Cmux(8, 2, 3),
+            Node::Cmux(8, 3, 4),
+            Node::Cmux(8, 4, 5),
+            Node::Cmux(8, 5, 6),
+            Node::Cmux(8, 6, 7),
+            Node::Cmux(8, 7, 0),
+            Node::Cmux(7, 0, 1),
+            Node::Cmux(7, 1, 2),
+            Node::Cmux(7, 2, 3),
+            Node::Cmux(7, 3, 4),
+            Node::Cmux(7, 4, 5),
+            Node::Cmux(7, 5, 6),
+            Node::Cmux(7, 6, 7),
+            Node::Cmux(7, 7, 0),
+            Node::Cmux(6, 0, 1),
+            Node::Cmux(6, 1, 2),
+            Node::Cmux(6, 2, 3),
+            Node::Cmux(6, 3, 4),
+            Node::Cmux(6, 4, 5),
+            Node::Cmux(6, 5, 6),
+            Node::Cmux(6, 6, 7),
+            Node::None,
+            Node::Cmux(5, 0, 1),
+            Node::Cmux(5, 1, 2),
+            Node::Cmux(5, 2, 3),
+            Node::Cmux(5, 3, 4),
+            Node::Cmux(5, 4, 5),
+            Node::Cmux(5, 5, 6),
+            Node::None,
+            Node::None,
+            Node::Cmux(4, 0, 1),
+            Node::Cmux(4, 1, 2),
+            Node::Cmux(4, 2, 3),
+            Node::Cmux(4, 3, 4),
+            Node::Cmux(4, 4, 5),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(3, 0, 1),
+            Node::Cmux(3, 1, 2),
+            Node::Cmux(3, 2, 3),
+            Node::Cmux(3, 3, 4),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(2, 0, 1),
+            Node::Cmux(2, 1, 2),
+            Node::Cmux(2, 2, 3),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(1, 0, 1),
+            Node::Cmux(1, 1, 2),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(0, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        8,
+    )),
+    AnyBitCircuit::B3(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(31, 1, 0),
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(30, 2, 0),
+            Node::Cmux(30, 3, 1),
+            Node::Cmux(30, 1, 2),
+            Node::Cmux(30, 0, 3),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(29, 4, 2),
+            Node::Cmux(29, 5, 3),
+            Node::Cmux(29, 2, 0),
+            Node::Cmux(29, 3, 1),
+            Node::Cmux(29, 1, 4),
+            Node::Cmux(29, 0, 5),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(28, 4, 0),
+            Node::Cmux(28, 5, 1),
+            Node::Cmux(28, 1, 6),
+            Node::Cmux(28, 6, 2),
+            Node::Cmux(28, 2, 4),
+            Node::Cmux(28, 0, 7),
+            Node::Cmux(28, 7, 3),
+            Node::Cmux(28, 3, 5),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(27, 4, 5),
+            Node::Cmux(27, 5, 6),
+            Node::Cmux(27, 6, 2),
+            Node::Cmux(27, 7, 8),
+            Node::Cmux(27, 8, 9),
+            Node::Cmux(27, 9, 3),
+            Node::Cmux(27, 2, 0),
+            Node::Cmux(27, 3, 1),
+            Node::Cmux(27, 1, 4),
+            Node::Cmux(27, 0, 7),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(26, 2, 3),
+            Node::Cmux(26, 3, 4),
+            Node::Cmux(26, 5, 6),
+            Node::Cmux(26, 6, 7),
+            Node::Cmux(26, 8, 0),
+            Node::Cmux(26, 9, 1),
+            Node::Cmux(26, 1, 10),
+            Node::Cmux(26, 10, 2),
+            Node::Cmux(26, 4, 8),
+            Node::Cmux(26, 0, 11),
+            Node::Cmux(26, 11, 5),
+            Node::Cmux(26, 7, 9),
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(25, 2, 3),
+            Node::Cmux(25, 4, 5),
+            Node::Cmux(25, 6, 0),
+            Node::Cmux(25, 7, 1),
+            Node::Cmux(25, 1, 8),
+            Node::Cmux(25, 8, 9),
+            Node::Cmux(25, 9, 2),
+            Node::Cmux(25, 3, 10),
+            Node::Cmux(25, 10, 6),
+            Node::Cmux(25, 0, 11),
+            Node::Cmux(25, 11, 12),
+            Node::Cmux(25, 12, 4),
+            Node::Cmux(25, 5, 13),
+            Node::Cmux(25, 13, 7),
+            Node::Cmux(24, 4, 0),
+            Node::Cmux(24, 5, 1),
+            Node::Cmux(24, 1, 6),
+            Node::Cmux(24, 6, 7),
+            Node::Cmux(24, 7, 8),
+            Node::Cmux(24, 8, 2),
+            Node::Cmux(24, 2, 9),
+            Node::Cmux(24, 9, 10),
+            Node::Cmux(24, 10, 4),
+            Node::Cmux(24, 0, 11),
+            Node::Cmux(24, 11, 12),
+            Node::Cmux(24, 12, 13),
+            Node::Cmux(24, 13, 3),
+            Node::Cmux(24, 3, 14),
+            Node::Cmux(24, 14, 15),
+            Node::Cmux(24, 15, 5),
+            Node::Cmux(23, 1, 2),
+            Node::Cmux(23, 2, 3),
+            Node::Cmux(23, 3, 4),
+            Node::Cmux(23, 4, 5),
+            Node::Cmux(23, 5, 6),
+            Node::Cmux(23, 6, 7),
+            Node::Cmux(23, 7, 8),
+            Node::Cmux(23, 8, 0),
+            Node::Cmux(23, 0, 9),
+            Node::Cmux(23, 9, 10),
+            Node::Cmux(23, 10, 11),
+            Node::Cmux(23, 11, 12),
+            Node::Cmux(23, 12, 13),
+            Node::Cmux(23, 13, 14),
+            Node::Cmux(23, 14, 15),
+            Node::Cmux(23, 15, 1),
+            Node::Cmux(22, 0, 1),
+            Node::Cmux(22, 1, 2),
+            Node::Cmux(22, 2, 3),
+            Node::Cmux(22, 3, 4),
+            Node::Cmux(22, 4, 5),
+            Node::Cmux(22, 5, 6),
+            Node::Cmux(22, 6, 7),
+            Node::Cmux(22, 7, 8),
+            Node::Cmux(22, 8, 9),
+            Node::Cmux(22, 9, 10),
+            Node::Cmux(22, 10, 11),
+            Node::Cmux(22, 11, 12),
+            Node::Cmux(22, 12, 13),
+            Node::Cmux(22, 13, 14),
+            Node::Cmux(22, 14, 15),
+            Node::Cmux(22, 15, 0),
+            Node::Cmux(21, 0, 1),
+            Node::Cmux(21, 1, 2),
+            Node::Cmux(21, 2, 3),
+            Node::Cmux(21, 3, 4),
+            Node::Cmux(21, 4, 5),
+            Node::Cmux(21, 5, 6),
+            Node::Cmux(21, 6, 7),
+            Node::Cmux(21, 7, 8),
+            Node::Cmux(21, 8, 9),
+            Node::Cmux(21, 9, 10),
+            Node::Cmux(21, 10, 11),
+            Node::Cmux(21, 11, 12),
+            Node::Cmux(21, 12, 13),
+            Node::Cmux(21, 13, 14),
+            Node::Cmux(21, 14, 15),
+            Node::Cmux(21, 15, 0),
+            Node::Cmux(20, 0, 1),
+            Node::Cmux(20, 1, 2),
+            Node::Cmux(20, 2, 3),
+            Node::Cmux(20, 3, 4),
+            Node::Cmux(20, 4, 5),
+            Node::Cmux(20, 5, 6),
+            Node::Cmux(20, 6, 7),
+            Node::Cmux(20, 7, 8),
+            Node::Cmux(20, 8, 9),
+            Node::Cmux(20, 9, 10),
+            Node::Cmux(20, 10, 11),
+            Node::Cmux(20, 11, 12),
+            Node::Cmux(20, 12, 13),
+            Node::Cmux(20, 13, 14),
+            Node::Cmux(20, 14, 15),
+            Node::Cmux(20, 15, 0),
+            Node::Cmux(19, 0, 1),
+            Node::Cmux(19, 1, 2),
+            Node::Cmux(19, 2, 3),
+            Node::Cmux(19, 3, 4),
+            Node::Cmux(19, 4, 5),
+            Node::Cmux(19, 5, 6),
+            Node::Cmux(19, 6, 7),
+            Node::Cmux(19, 7, 8),
+            Node::Cmux(19, 8, 9),
+            Node::Cmux(19, 9, 10),
+            Node::Cmux(19, 10, 11),
+            Node::Cmux(19, 11, 12),
+            Node::Cmux(19, 12, 13),
+            Node::Cmux(19, 13, 14),
+            Node::Cmux(19, 14, 15),
+            Node::Cmux(19, 15, 0),
+            Node::Cmux(18, 0, 1),
+            Node::Cmux(18, 1, 2),
+            Node::Cmux(18, 2, 3),
+            Node::Cmux(18, 3, 4),
+            Node::Cmux(18, 4, 5),
+            Node::Cmux(18, 5, 6),
+            Node::Cmux(18, 6, 7),
+            Node::Cmux(18, 7, 8),
+            Node::Cmux(18, 8, 9),
+            Node::Cmux(18, 9, 10),
+            Node::Cmux(18, 10, 11),
+            Node::Cmux(18, 11, 12),
+            Node::Cmux(18, 12, 13),
+            Node::Cmux(18, 13, 14),
+            Node::Cmux(18, 14, 15),
+            Node::Cmux(18, 15, 0),
+            Node::Cmux(17, 0, 1),
+            Node::Cmux(17, 1, 2),
+            Node::Cmux(17, 2, 3),
+            Node::Cmux(17, 3, 4),
+            Node::Cmux(17, 4, 5),
+            Node::Cmux(17, 5, 6),
+            Node::Cmux(17, 6, 7),
+            Node::Cmux(17, 7, 8),
+            Node::Cmux(17, 8, 9),
+            Node::Cmux(17, 9, 10),
+            Node::Cmux(17, 10, 11),
+            Node::Cmux(17, 11, 12),
+            Node::Cmux(17, 12, 13),
+            Node::Cmux(17, 13, 14),
+            Node::Cmux(17, 14, 15),
+            Node::Cmux(17, 15, 0),
+            Node::Cmux(16, 0, 1),
+            Node::Cmux(16, 1, 2),
+            Node::Cmux(16, 2, 3),
+            Node::Cmux(16, 3, 4),
+            Node::Cmux(16, 4, 5),
+            Node::Cmux(16, 5, 6),
+            Node::Cmux(16, 6, 7),
+            Node::Cmux(16, 7, 8),
+            Node::Cmux(16, 8, 9),
+            Node::Cmux(16, 9, 10),
+            Node::Cmux(16, 10, 11),
+            Node::Cmux(16, 11, 12),
+            Node::Cmux(16, 12, 13),
+            Node::Cmux(16, 13, 14),
+            Node::Cmux(16, 14, 15),
+            Node::Cmux(16, 15, 0),
+            Node::Cmux(15, 0, 1),
+            Node::Cmux(15, 1, 2),
+            Node::Cmux(15, 2, 3),
+            Node::Cmux(15, 3, 4),
+            Node::Cmux(15, 4, 5),
+            Node::Cmux(15, 5, 6),
+            Node::Cmux(15, 6, 7),
+            Node::Cmux(15, 7, 8),
+            Node::Cmux(15, 8, 9),
+            Node::Cmux(15, 9, 10),
+            Node::Cmux(15, 10, 11),
+            Node::Cmux(15, 11, 12),
+            Node::Cmux(15, 12, 13),
+            Node::Cmux(15, 13, 14),
+            Node::Cmux(15, 14, 15),
+            Node::Cmux(15, 15, 0),
+            Node::Cmux(14, 0, 1),
+            Node::Cmux(14, 1, 2),
+            Node::Cmux(14, 2, 3),
+            Node::Cmux(14, 3, 4),
+            Node::Cmux(14, 4, 5),
+            Node::Cmux(14, 5, 6),
+            Node::Cmux(14, 6, 7),
+            Node::Cmux(14, 7, 8),
+            Node::Cmux(14, 8, 9),
+            Node::Cmux(14, 9, 10),
+            Node::Cmux(14, 10, 11),
+            Node::Cmux(14, 11, 12),
+            Node::Cmux(14, 12, 13),
+            Node::Cmux(14, 13, 14),
+            Node::Cmux(14, 14, 15),
+            Node::None,
+            Node::Cmux(13, 0, 1),
+            Node::Cmux(13, 1, 2),
+            Node::Cmux(13, 2, 3),
+            Node::Cmux(13, 3, 4),
+            Node::Cmux(13, 4, 5),
+            Node::Cmux(13, 5, 6),
+            Node::Cmux(13, 6, 7),
+            Node::Cmux(13, 7, 8),
+            Node::Cmux(13, 8, 9),
+            Node::Cmux(13, 9, 10),
+            Node::Cmux(13, 10, 11),
+            Node::Cmux(13, 11, 12),
+            Node::Cmux(13, 12, 13),
+            Node::Cmux(13, 13, 14),
+            Node::None,
+            Node::None,
+            Node::Cmux(12, 0, 1),
+            Node::Cmux(12, 1, 2),
+            Node::Cmux(12, 2, 3),
+            Node::Cmux(12, 3, 4),
+            Node::Cmux(12, 4, 5),
+            Node::Cmux(12, 5, 6),
+            Node::Cmux(12, 6, 7),
+            Node::Cmux(12, 7, 8),
+            Node::Cmux(12, 8, 9),
+            Node::Cmux(12, 9, 10),
+            Node::Cmux(12, 10, 11),
+            Node::Cmux(12, 11, 12),
+            Node::Cmux(12, 12, 13),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(11, 0, 1),
+            Node::Cmux(11, 1, 2),
+            Node::Cmux(11, 2, 3),
+            Node::Cmux(11, 3, 4),
+            Node::Cmux(11, 4, 5),
+            Node::Cmux(11, 5, 6),
+            Node::Cmux(11, 6, 7),
+            Node::Cmux(11, 7, 8),
+            Node::Cmux(11, 8, 9),
+            Node::Cmux(11, 9, 10),
+            Node::Cmux(11, 10, 11),
+            Node::Cmux(11, 11, 12),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(10, 0, 1),
+            Node::Cmux(10, 1, 2),
+            Node::Cmux(10, 2, 3),
+            Node::Cmux(10, 3, 4),
+            Node::Cmux(10, 4, 5),
+            Node::Cmux(10, 5, 6),
+            Node::Cmux(10, 6, 7),
+            Node::Cmux(10, 7, 8),
+            Node::Cmux(10, 8, 9),
+            Node::Cmux(10, 9, 10),
+            Node::Cmux(10, 10, 11),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(9, 0, 1),
+            Node::Cmux(9, 1, 2),
+            Node::Cmux(9, 2, 3),
+            Node::Cmux(9, 3, 4),
+            Node::Cmux(9, 4, 5),
+            Node::Cmux(9, 5, 6),
+            Node::Cmux(9, 6, 7),
+            Node::Cmux(9, 7, 8),
+            Node::Cmux(9, 8, 9),
+            Node::Cmux(9, 9, 10),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(8, 0, 1),
+            Node::Cmux(8, 1, 2),
+            Node::Cmux(8, 2, 3),
+            Node::Cmux(8, 3, 4),
+            Node::Cmux(8, 4, 5),
+            Node::Cmux(8, 5, 6),
+            Node::Cmux(8, 6, 7),
+            Node::Cmux(8, 7, 8),
+            Node::Cmux(8, 8, 9),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(7, 0, 1),
+            Node::Cmux(7, 1, 2),
+            Node::Cmux(7, 2, 3),
+            Node::Cmux(7, 3, 4),
+            Node::Cmux(7, 4, 5),
+            Node::Cmux(7, 5, 6),
+            Node::Cmux(7, 6, 7),
+            Node::Cmux(7, 7, 8),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(6, 0, 1),
+            Node::Cmux(6, 1, 2),
+            Node::Cmux(6, 2, 3),
+            Node::Cmux(6, 3, 4),
+            Node::Cmux(6, 4, 5),
+            Node::Cmux(6, 5, 6),
+            Node::Cmux(6, 6, 7),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(5, 0, 1),
+            Node::Cmux(5, 1, 2),
+            Node::Cmux(5, 2, 3),
+            Node::Cmux(5, 3, 4),
+            Node::Cmux(5, 4, 5),
+            Node::Cmux(5, 5, 6),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(4, 0, 1),
+            Node::Cmux(4, 1, 2),
+            Node::Cmux(4, 2, 3),
+            Node::Cmux(4, 3, 4),
+            Node::Cmux(4, 4, 5),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(3, 0, 1),
+            Node::Cmux(3, 1, 2),
+            Node::Cmux(3, 2, 3),
+            Node::Cmux(3, 3, 4),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(2, 0, 1),
+            Node::Cmux(2, 1, 2),
+            Node::Cmux(2, 2, 3),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(1, 0, 1),
+            Node::Cmux(1, 1, 2),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(0, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        16,
+    )),
+    AnyBitCircuit::B4(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(31, 1, 0),
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(30, 2, 0),
+            Node::Cmux(30, 3, 1),
+            Node::Cmux(30, 1, 2),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(29, 4, 2),
+            Node::Cmux(29, 2, 0),
+            Node::Cmux(29, 3, 1),
+            Node::Cmux(29, 1, 4),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(28, 3, 0),
+            Node::Cmux(28, 4, 1),
+            Node::Cmux(28, 1, 5),
+            Node::Cmux(28, 5, 2),
+            Node::Cmux(28, 2, 3),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(27, 4, 5),
+            Node::Cmux(27, 5, 6),
+            Node::Cmux(27, 6, 2),
+            Node::Cmux(27, 2, 0),
+            Node::Cmux(27, 3, 1),
+            Node::Cmux(27, 1, 4),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(26, 2, 3),
+            Node::Cmux(26, 3, 4),
+            Node::Cmux(26, 5, 0),
+            Node::Cmux(26, 6, 1),
+            Node::Cmux(26, 1, 7),
+            Node::Cmux(26, 7, 2),
+            Node::Cmux(26, 4, 5),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(25, 2, 3),
+            Node::Cmux(25, 4, 0),
+            Node::Cmux(25, 5, 1),
+            Node::Cmux(25, 1, 6),
+            Node::Cmux(25, 6, 7),
+            Node::Cmux(25, 7, 2),
+            Node::Cmux(25, 3, 8),
+            Node::Cmux(25, 8, 4),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(24, 3, 0),
+            Node::Cmux(24, 4, 1),
+            Node::Cmux(24, 1, 5),
+            Node::Cmux(24, 5, 6),
+            Node::Cmux(24, 6, 7),
+            Node::Cmux(24, 7, 2),
+            Node::Cmux(24, 2, 8),
+            Node::Cmux(24, 8, 9),
+            Node::Cmux(24, 9, 3),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(23, 4, 5),
+            Node::Cmux(23, 5, 6),
+            Node::Cmux(23, 6, 7),
+            Node::Cmux(23, 7, 8),
+            Node::Cmux(23, 8, 9),
+            Node::Cmux(23, 9, 10),
+            Node::Cmux(23, 10, 2),
+            Node::Cmux(23, 2, 0),
+            Node::Cmux(23, 3, 1),
+            Node::Cmux(23, 1, 4),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(22, 2, 3),
+            Node::Cmux(22, 3, 4),
+            Node::Cmux(22, 4, 5),
+            Node::Cmux(22, 5, 6),
+            Node::Cmux(22, 6, 7),
+            Node::Cmux(22, 7, 8),
+            Node::Cmux(22, 9, 0),
+            Node::Cmux(22, 10, 1),
+            Node::Cmux(22, 1, 11),
+            Node::Cmux(22, 11, 2),
+            Node::Cmux(22, 8, 9),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(21, 2, 3),
+            Node::Cmux(21, 3, 4),
+            Node::Cmux(21, 4, 5),
+            Node::Cmux(21, 5, 6),
+            Node::Cmux(21, 6, 7),
+            Node::Cmux(21, 8, 0),
+            Node::Cmux(21, 9, 1),
+            Node::Cmux(21, 1, 10),
+            Node::Cmux(21, 10, 11),
+            Node::Cmux(21, 11, 2),
+            Node::Cmux(21, 7, 12),
+            Node::Cmux(21, 12, 8),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(20, 2, 3),
+            Node::Cmux(20, 3, 4),
+            Node::Cmux(20, 4, 5),
+            Node::Cmux(20, 5, 6),
+            Node::Cmux(20, 7, 0),
+            Node::Cmux(20, 8, 1),
+            Node::Cmux(20, 1, 9),
+            Node::Cmux(20, 9, 10),
+            Node::Cmux(20, 10, 11),
+            Node::Cmux(20, 11, 2),
+            Node::Cmux(20, 6, 12),
+            Node::Cmux(20, 12, 13),
+            Node::Cmux(20, 13, 7),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(19, 2, 3),
+            Node::Cmux(19, 3, 4),
+            Node::Cmux(19, 4, 5),
+            Node::Cmux(19, 6, 0),
+            Node::Cmux(19, 7, 1),
+            Node::Cmux(19, 1, 8),
+            Node::Cmux(19, 8, 9),
+            Node::Cmux(19, 9, 10),
+            Node::Cmux(19, 10, 11),
+            Node::Cmux(19, 11, 2),
+            Node::Cmux(19, 5, 12),
+            Node::Cmux(19, 12, 13),
+            Node::Cmux(19, 13, 14),
+            Node::Cmux(19, 14, 6),
+            Node::None,
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(18, 2, 3),
+            Node::Cmux(18, 3, 4),
+            Node::Cmux(18, 5, 0),
+            Node::Cmux(18, 6, 1),
+            Node::Cmux(18, 1, 7),
+            Node::Cmux(18, 7, 8),
+            Node::Cmux(18, 8, 9),
+            Node::Cmux(18, 9, 10),
+            Node::Cmux(18, 10, 11),
+            Node::Cmux(18, 11, 2),
+            Node::Cmux(18, 4, 12),
+            Node::Cmux(18, 12, 13),
+            Node::Cmux(18, 13, 14),
+            Node::Cmux(18, 14, 15),
+            Node::Cmux(18, 15, 5),
+            Node::None,
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(17, 2, 3),
+            Node::Cmux(17, 4, 0),
+            Node::Cmux(17, 5, 1),
+            Node::Cmux(17, 1, 6),
+            Node::Cmux(17, 6, 7),
+            Node::Cmux(17, 7, 8),
+            Node::Cmux(17, 8, 9),
+            Node::Cmux(17, 9, 10),
+            Node::Cmux(17, 10, 11),
+            Node::Cmux(17, 11, 2),
+            Node::Cmux(17, 3, 12),
+            Node::Cmux(17, 12, 13),
+            Node::Cmux(17, 13, 14),
+            Node::Cmux(17, 14, 15),
+            Node::Cmux(17, 15, 16),
+            Node::Cmux(17, 16, 4),
+            Node::Cmux(16, 3, 0),
+            Node::Cmux(16, 4, 1),
+            Node::Cmux(16, 1, 5),
+            Node::Cmux(16, 5, 6),
+            Node::Cmux(16, 6, 7),
+            Node::Cmux(16, 7, 8),
+            Node::Cmux(16, 8, 9),
+            Node::Cmux(16, 9, 10),
+            Node::Cmux(16, 10, 11),
+            Node::Cmux(16, 11, 2),
+            Node::Cmux(16, 2, 12),
+            Node::Cmux(16, 12, 13),
+            Node::Cmux(16, 13, 14),
+            Node::Cmux(16, 14, 15),
+            Node::Cmux(16, 15, 16),
+            Node::Cmux(16, 16, 17),
+            Node::Cmux(16, 17, 3),
+            Node::None,
+            Node::Cmux(15, 1, 2),
+            Node::Cmux(15, 2, 3),
+            Node::Cmux(15, 3, 4),
+            Node::Cmux(15, 4, 5),
+            Node::Cmux(15, 5, 6),
+            Node::Cmux(15, 6, 7),
+            Node::Cmux(15, 7, 8),
+            Node::Cmux(15, 8, 9),
+            Node::Cmux(15, 9, 10),
+            Node::Cmux(15, 10, 11),
+            Node::Cmux(15, 11, 12),
+            Node::Cmux(15, 12, 13),
+            Node::Cmux(15, 13, 14),
+            Node::Cmux(15, 14, 15),
+            Node::Cmux(15, 15, 16),
+            Node::Cmux(15, 16, 0),
+            Node::None,
+            Node::None,
+            Node::Cmux(14, 0, 1),
+            Node::Cmux(14, 1, 2),
+            Node::Cmux(14, 2, 3),
+            Node::Cmux(14, 3, 4),
+            Node::Cmux(14, 4, 5),
+            Node::Cmux(14, 5, 6),
+            Node::Cmux(14, 6, 7),
+            Node::Cmux(14, 7, 8),
+            Node::Cmux(14, 8, 9),
+            Node::Cmux(14, 9, 10),
+            Node::Cmux(14, 10, 11),
+            Node::Cmux(14, 11, 12),
+            Node::Cmux(14, 12, 13),
+            Node::Cmux(14, 13, 14),
+            Node::Cmux(14, 14, 15),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(13, 0, 1),
+            Node::Cmux(13, 1, 2),
+            Node::Cmux(13, 2, 3),
+            Node::Cmux(13, 3, 4),
+            Node::Cmux(13, 4, 5),
+            Node::Cmux(13, 5, 6),
+            Node::Cmux(13, 6, 7),
+            Node::Cmux(13, 7, 8),
+            Node::Cmux(13, 8, 9),
+            Node::Cmux(13, 9, 10),
+            Node::Cmux(13, 10, 11),
+            Node::Cmux(13, 11, 12),
+            Node::Cmux(13, 12, 13),
+            Node::Cmux(13, 13, 14),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(12, 0, 1),
+            Node::Cmux(12, 1, 2),
+            Node::Cmux(12, 2, 3),
+            Node::Cmux(12, 3, 4),
+            Node::Cmux(12, 4, 5),
+            Node::Cmux(12, 5, 6),
+            Node::Cmux(12, 6, 7),
+            Node::Cmux(12, 7, 8),
+            Node::Cmux(12, 8, 9),
+            Node::Cmux(12, 9, 10),
+            Node::Cmux(12, 10, 11),
+            Node::Cmux(12, 11, 12),
+            Node::Cmux(12, 12, 13),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(11, 0, 1),
+            Node::Cmux(11, 1, 2),
+            Node::Cmux(11, 2, 3),
+            Node::Cmux(11, 3, 4),
+            Node::Cmux(11, 4, 5),
+            Node::Cmux(11, 5, 6),
+            Node::Cmux(11, 6, 7),
+            Node::Cmux(11, 7, 8),
+            Node::Cmux(11, 8, 9),
+            Node::Cmux(11, 9, 10),
+            Node::Cmux(11, 10, 11),
+            Node::Cmux(11, 11, 12),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(10, 0, 1),
+            Node::Cmux(10, 1, 2),
+            Node::Cmux(10, 2, 3),
+            Node::Cmux(10, 3, 4),
+            Node::Cmux(10, 4, 5),
+            Node::Cmux(10, 5, 6),
+            Node::Cmux(10, 6, 7),
+            Node::Cmux(10, 7, 8),
+            Node::Cmux(10, 8, 9),
+            Node::Cmux(10, 9, 10),
+            Node::Cmux(10, 10, 11),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(9, 0, 1),
+            Node::Cmux(9, 1, 2),
+            Node::Cmux(9, 2, 3),
+            Node::Cmux(9, 3, 4),
+            Node::Cmux(9, 4, 5),
+            Node::Cmux(9, 5, 6),
+            Node::Cmux(9, 6, 7),
+            Node::Cmux(9, 7, 8),
+            Node::Cmux(9, 8, 9),
+            Node::Cmux(9, 9, 10),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(8, 0, 1),
+            Node::Cmux(8, 1, 2),
+            Node::Cmux(8, 2, 3),
+            Node::Cmux(8, 3, 4),
+            Node::Cmux(8, 4, 5),
+            Node::Cmux(8, 5, 6),
+            Node::Cmux(8, 6, 7),
+            Node::Cmux(8, 7, 8),
+            Node::Cmux(8, 8, 9),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(7, 0, 1),
+            Node::Cmux(7, 1, 2),
+            Node::Cmux(7, 2, 3),
+            Node::Cmux(7, 3, 4),
+            Node::Cmux(7, 4, 5),
+            Node::Cmux(7, 5, 6),
+            Node::Cmux(7, 6, 7),
+            Node::Cmux(7, 7, 8),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(6, 0, 1),
+            Node::Cmux(6, 1, 2),
+            Node::Cmux(6, 2, 3),
+            Node::Cmux(6, 3, 4),
+            Node::Cmux(6, 4, 5),
+            Node::Cmux(6, 5, 6),
+            Node::Cmux(6, 6, 7),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(5, 0, 1),
+            Node::Cmux(5, 1, 2),
+            Node::Cmux(5, 2, 3),
+            Node::Cmux(5, 3, 4),
+            Node::Cmux(5, 4, 5),
+            Node::Cmux(5, 5, 6),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(4, 0, 1),
+            Node::Cmux(4, 1, 2),
+            Node::Cmux(4, 2, 3),
+            Node::Cmux(4, 3, 4),
+            Node::Cmux(4, 4, 5),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(3, 0, 1),
+            Node::Cmux(3, 1, 2),
+            Node::Cmux(3, 2, 3),
+            Node::Cmux(3, 3, 4),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(2, 0, 1),
+            Node::Cmux(2, 1, 2),
+            Node::Cmux(2, 2, 3),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(1, 0, 1),
+            Node::Cmux(1, 1, 2),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::Cmux(0, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        18,
+    )),
+    AnyBitCircuit::B5(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Cmux(31, 1, 0),
+            Node::Copy,
+            Node::Cmux(30, 1, 0),
+            Node::Copy,
+            Node::Cmux(29, 1, 0),
+            Node::Copy,
+            Node::Cmux(28, 1, 0),
+            Node::Copy,
+            Node::Cmux(27, 1, 0),
+            Node::Copy,
+            Node::Cmux(26, 1, 0),
+            Node::Copy,
+            Node::Cmux(25, 1, 0),
+            Node::Copy,
+            Node::Cmux(24, 1, 0),
+            Node::Copy,
+            Node::Cmux(23, 1, 0),
+            Node::Copy,
+            Node::Cmux(22, 1, 0),
+            Node::Copy,
+            Node::Cmux(21, 1, 0),
+            Node::Copy,
+            Node::Cmux(20, 1, 0),
+            Node::Copy,
+            Node::Cmux(19, 1, 0),
+            Node::Copy,
+            Node::Cmux(18, 1, 0),
+            Node::Copy,
+            Node::Cmux(17, 1, 0),
+            Node::Copy,
+            Node::Cmux(16, 1, 0),
+            Node::Copy,
+            Node::Cmux(15, 1, 0),
+            Node::Copy,
+            Node::Cmux(14, 1, 0),
+            Node::Copy,
+            Node::Cmux(13, 1, 0),
+            Node::Copy,
+            Node::Cmux(12, 1, 0),
+            Node::Copy,
+            Node::Cmux(11, 1, 0),
+            Node::Copy,
+            Node::Cmux(10, 1, 0),
+            Node::Copy,
+            Node::Cmux(9, 1, 0),
+            Node::Copy,
+            Node::Cmux(8, 1, 0),
+            Node::Copy,
+            Node::Cmux(7, 1, 0),
+            Node::Copy,
+            Node::Cmux(6, 1, 0),
+            Node::Copy,
+            Node::Cmux(5, 1, 0),
+            Node::Copy,
+            Node::Cmux(4, 1, 0),
+            Node::Copy,
+            Node::Cmux(3, 1, 0),
+            Node::Copy,
+            Node::Cmux(2, 1, 0),
+            Node::Copy,
+            Node::Cmux(1, 1, 0),
+            Node::Cmux(0, 1, 0),
+            Node::None,
+        ],
+        2,
+    )),
+    AnyBitCircuit::B6(BitCircuit::new([], 0)),
+    AnyBitCircuit::B7(BitCircuit::new([], 0)),
+    AnyBitCircuit::B8(BitCircuit::new([], 0)),
+    AnyBitCircuit::B9(BitCircuit::new([], 0)),
+    AnyBitCircuit::B10(BitCircuit::new([], 0)),
+    AnyBitCircuit::B11(BitCircuit::new([], 0)),
+    AnyBitCircuit::B12(BitCircuit::new([], 0)),
+    AnyBitCircuit::B13(BitCircuit::new([], 0)),
+    AnyBitCircuit::B14(BitCircuit::new([], 0)),
+    AnyBitCircuit::B15(BitCircuit::new([], 0)),
+    AnyBitCircuit::B16(BitCircuit::new([], 0)),
+    AnyBitCircuit::B17(BitCircuit::new([], 0)),
+    AnyBitCircuit::B18(BitCircuit::new([], 0)),
+    AnyBitCircuit::B19(BitCircuit::new([], 0)),
+    AnyBitCircuit::B20(BitCircuit::new([], 0)),
+    AnyBitCircuit::B21(BitCircuit::new([], 0)),
+    AnyBitCircuit::B22(BitCircuit::new([], 0)),
+    AnyBitCircuit::B23(BitCircuit::new([], 0)),
+    AnyBitCircuit::B24(BitCircuit::new([], 0)),
+    AnyBitCircuit::B25(BitCircuit::new([], 0)),
+    AnyBitCircuit::B26(BitCircuit::new([], 0)),
+    AnyBitCircuit::B27(BitCircuit::new([], 0)),
+    AnyBitCircuit::B28(BitCircuit::new([], 0)),
+    AnyBitCircuit::B29(BitCircuit::new([], 0)),
+    AnyBitCircuit::B30(BitCircuit::new([], 0)),
+    AnyBitCircuit::B31(BitCircuit::new([], 0)),
+]);
@@ -0,0 +1,747 @@
+use poulpy_schemes::bin_fhe::bdd_arithmetic::{
+    BitCircuit, BitCircuitFamily, BitCircuitInfo, Circuit, Node,
+};
+pub(crate) enum AnyBitCircuit {
+    B0(BitCircuit<96>),
+    B1(BitCircuit<96>),
+    B2(BitCircuit<96>),
+    B3(BitCircuit<96>),
+    B4(BitCircuit<96>),
+    B5(BitCircuit<64>),
+    B6(BitCircuit<0>),
+    B7(BitCircuit<0>),
+    B8(BitCircuit<0>),
+    B9(BitCircuit<0>),
+    B10(BitCircuit<0>),
+    B11(BitCircuit<0>),
+    B12(BitCircuit<0>),
+    B13(BitCircuit<0>),
+    B14(BitCircuit<0>),
+    B15(BitCircuit<0>),
+    B16(BitCircuit<0>),
+    B17(BitCircuit<0>),
+    B18(BitCircuit<0>),
+    B19(BitCircuit<0>),
+    B20(BitCircuit<0>),
+    B21(BitCircuit<0>),
+    B22(BitCircuit<0>),
+    B23(BitCircuit<0>),
+    B24(BitCircuit<0>),
+    B25(BitCircuit<0>),
+    B26(BitCircuit<0>),
+    B27(BitCircuit<0>),
+    B28(BitCircuit<0>),
+    B29(BitCircuit<0>),
+    B30(BitCircuit<0>),
+    B31(BitCircuit<0>),
+}
+impl BitCircuitInfo for AnyBitCircuit {
+    fn info(&self) -> (&[Node], usize) {
+        match self {
+            AnyBitCircuit::B0(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B1(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B2(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B3(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B4(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B5(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B6(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B7(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B8(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B9(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B10(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B11(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B12(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B13(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B14(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B15(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B16(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B17(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B18(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B19(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B20(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B21(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B22(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B23(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B24(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B25(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B26(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B27(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B28(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B29(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B30(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B31(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+        }
+    }
+}
+
+impl BitCircuitFamily for AnyBitCircuit {
+    const INPUT_BITS: usize = 32;
+    const OUTPUT_BITS: usize = 32;
+}
+
+pub(crate) static OUTPUT_CIRCUITS: Circuit<AnyBitCircuit, 32usize> = Circuit([
+    AnyBitCircuit::B0(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(31, 1, 0),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(30, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(29, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(28, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(27, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(26, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(25, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(24, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(23, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(22, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(21, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(20, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(19, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(18, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(17, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(16, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(15, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(14, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(13, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(12, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(11, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(10, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(9, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(8, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(7, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(6, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(5, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(4, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(3, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(2, 0, 2),
+            Node::Copy,
+            Node::Cmux(1, 1, 2),
+            Node::None,
+            Node::Cmux(0, 0, 1),
+            Node::None,
+            Node::None,
+        ],
+        3,
+    )),
+    AnyBitCircuit::B1(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(31, 1, 0),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(30, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(29, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(28, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(27, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(26, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(25, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(24, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(23, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(22, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(21, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(20, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(19, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(18, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(17, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(16, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(15, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(14, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(13, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(12, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(11, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(10, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(9, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(8, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(7, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(6, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(5, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(4, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(3, 1, 2),
+            Node::Copy,
+            Node::Cmux(2, 1, 2),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(1, 0, 1),
+            Node::None,
+            Node::Cmux(0, 0, 1),
+            Node::None,
+            Node::None,
+        ],
+        3,
+    )),
+    AnyBitCircuit::B2(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(31, 1, 0),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(30, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(29, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(28, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(27, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(26, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(25, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(24, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(23, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(22, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(21, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(20, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(19, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(18, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(17, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(16, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(15, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(14, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(13, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(12, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(11, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(10, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(9, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(8, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(7, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(6, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(5, 1, 2),
+            Node::Copy,
+            Node::Cmux(4, 1, 2),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(3, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(2, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(1, 0, 1),
+            Node::None,
+            Node::Cmux(0, 0, 1),
+            Node::None,
+            Node::None,
+        ],
+        3,
+    )),
+    AnyBitCircuit::B3(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(31, 1, 0),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(30, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(29, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(28, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(27, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(26, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(25, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(24, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(23, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(22, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(21, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(20, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(19, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(18, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(17, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(16, 0, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(15, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(14, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(13, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(12, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(11, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(10, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(9, 1, 2),
+            Node::Copy,
+            Node::Cmux(8, 1, 2),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(7, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(6, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(5, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(4, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(3, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(2, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(1, 0, 1),
+            Node::None,
+            Node::Cmux(0, 0, 1),
+            Node::None,
+            Node::None,
+        ],
+        3,
+    )),
+    AnyBitCircuit::B4(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(31, 1, 0),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(30, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(29, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(28, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(27, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(26, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(25, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(24, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(23, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(22, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(21, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(20, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(19, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(18, 1, 2),
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(17, 1, 2),
+            Node::Copy,
+            Node::Cmux(16, 1, 2),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(15, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(14, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(13, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(12, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(11, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(10, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(9, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(8, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(7, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(6, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(5, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(4, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(3, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(2, 0, 1),
+            Node::None,
+            Node::Copy,
+            Node::Cmux(1, 0, 1),
+            Node::None,
+            Node::Cmux(0, 0, 1),
+            Node::None,
+            Node::None,
+        ],
+        3,
+    )),
+    AnyBitCircuit::B5(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Cmux(31, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 0, 1),
+            Node::Copy,
+            Node::Cmux(17, 0, 1),
+            Node::Copy,
+            Node::Cmux(16, 0, 1),
+            Node::Copy,
+            Node::Cmux(15, 0, 1),
+            Node::Copy,
+            Node::Cmux(14, 0, 1),
+            Node::Copy,
+            Node::Cmux(13, 0, 1),
+            Node::Copy,
+            Node::Cmux(12, 0, 1),
+            Node::Copy,
+            Node::Cmux(11, 0, 1),
+            Node::Copy,
+            Node::Cmux(10, 0, 1),
+            Node::Copy,
+            Node::Cmux(9, 0, 1),
+            Node::Copy,
+            Node::Cmux(8, 0, 1),
+            Node::Copy,
+            Node::Cmux(7, 0, 1),
+            Node::Copy,
+            Node::Cmux(6, 0, 1),
+            Node::Copy,
+            Node::Cmux(5, 0, 1),
+            Node::Copy,
+            Node::Cmux(4, 0, 1),
+            Node::Copy,
+            Node::Cmux(3, 0, 1),
+            Node::Copy,
+            Node::Cmux(2, 0, 1),
+            Node::Copy,
+            Node::Cmux(1, 0, 1),
+            Node::Cmux(0, 0, 1),
+            Node::None,
+        ],
+        2,
+    )),
+    AnyBitCircuit::B6(BitCircuit::new([], 0)),
+    AnyBitCircuit::B7(BitCircuit::new([], 0)),
+    AnyBitCircuit::B8(BitCircuit::new([], 0)),
+    AnyBitCircuit::B9(BitCircuit::new([], 0)),
+    AnyBitCircuit::B10(BitCircuit::new([], 0)),
+    AnyBitCircuit::B11(BitCircuit::new([], 0)),
+    AnyBitCircuit::B12(BitCircuit::new([], 0)),
+    AnyBitCircuit::B13(BitCircuit::new([], 0)),
+    AnyBitCircuit::B14(BitCircuit::new([], 0)),
+    AnyBitCircuit::B15(BitCircuit::new([], 0)),
+    AnyBitCircuit::B16(BitCircuit::new([], 0)),
+    AnyBitCircuit::B17(BitCircuit::new([], 0)),
+    AnyBitCircuit::B18(BitCircuit::new([], 0)),
+    AnyBitCircuit::B19(BitCircuit::new([], 0)),
+    AnyBitCircuit::B20(BitCircuit::new([], 0)),
+    AnyBitCircuit::B21(BitCircuit::new([], 0)),
+    AnyBitCircuit::B22(BitCircuit::new([], 0)),
+    AnyBitCircuit::B23(BitCircuit::new([], 0)),
+    AnyBitCircuit::B24(BitCircuit::new([], 0)),
+    AnyBitCircuit::B25(BitCircuit::new([], 0)),
+    AnyBitCircuit::B26(BitCircuit::new([], 0)),
+    AnyBitCircuit::B27(BitCircuit::new([], 0)),
+    AnyBitCircuit::B28(BitCircuit::new([], 0)),
+    AnyBitCircuit::B29(BitCircuit::new([], 0)),
+    AnyBitCircuit::B30(BitCircuit::new([], 0)),
+    AnyBitCircuit::B31(BitCircuit::new([], 0)),
+]);
@@ -0,0 +1,4523 @@
+use poulpy_schemes::bin_fhe::bdd_arithmetic::{
+    BitCircuit, BitCircuitFamily, BitCircuitInfo, Circuit, Node,
+};
+pub(crate) enum AnyBitCircuit {
+    B0(BitCircuit<256>),
+    B1(BitCircuit<248>),
+    B2(BitCircuit<240>),
+    B3(BitCircuit<232>),
+    B4(BitCircuit<224>),
+    B5(BitCircuit<216>),
+    B6(BitCircuit<208>),
+    B7(BitCircuit<200>),
+    B8(BitCircuit<192>),
+    B9(BitCircuit<184>),
+    B10(BitCircuit<176>),
+    B11(BitCircuit<168>),
+    B12(BitCircuit<160>),
+    B13(BitCircuit<152>),
+    B14(BitCircuit<144>),
+    B15(BitCircuit<136>),
+    B16(BitCircuit<128>),
+    B17(BitCircuit<120>),
+    B18(BitCircuit<112>),
+    B19(BitCircuit<104>),
+    B20(BitCircuit<96>),
+    B21(BitCircuit<88>),
+    B22(BitCircuit<80>),
+    B23(BitCircuit<72>),
+    B24(BitCircuit<64>),
+    B25(BitCircuit<56>),
+    B26(BitCircuit<48>),
+    B27(BitCircuit<40>),
+    B28(BitCircuit<32>),
+    B29(BitCircuit<24>),
+    B30(BitCircuit<12>),
+    B31(BitCircuit<4>),
+}
+impl BitCircuitInfo for AnyBitCircuit {
+    fn info(&self) -> (&[Node], usize) {
+        match self {
+            AnyBitCircuit::B0(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B1(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B2(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B3(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B4(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B5(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B6(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B7(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B8(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B9(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B10(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B11(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B12(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B13(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B14(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B15(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B16(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B17(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B18(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B19(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B20(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B21(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B22(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B23(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B24(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B25(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B26(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B27(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B28(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B29(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B30(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+            AnyBitCircuit::B31(bit_circuit) => {
+                (bit_circuit.nodes.as_ref(), bit_circuit.max_inter_state)
+            }
+        }
+    }
+}
+
+impl BitCircuitFamily for AnyBitCircuit {
+    const INPUT_BITS: usize = 64;
+    const OUTPUT_BITS: usize = 32;
+}
+
+pub(crate) static OUTPUT_CIRCUITS: Circuit<AnyBitCircuit, 32usize> = Circuit([
+    AnyBitCircuit::B0(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(32, 1, 0),
+            Node::None,
+            Node::Cmux(0, 1, 0),
+            Node::Cmux(0, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(33, 1, 2),
+            Node::Copy,
+            Node::Cmux(33, 0, 1),
+            Node::Copy,
+            Node::Cmux(1, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(34, 1, 2),
+            Node::Copy,
+            Node::Cmux(34, 0, 1),
+            Node::Copy,
+            Node::Cmux(2, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(35, 1, 2),
+            Node::Copy,
+            Node::Cmux(35, 0, 1),
+            Node::Copy,
+            Node::Cmux(3, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(36, 1, 2),
+            Node::Copy,
+            Node::Cmux(36, 0, 1),
+            Node::Copy,
+            Node::Cmux(4, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(37, 1, 2),
+            Node::Copy,
+            Node::Cmux(37, 0, 1),
+            Node::Copy,
+            Node::Cmux(5, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(38, 1, 2),
+            Node::Copy,
+            Node::Cmux(38, 0, 1),
+            Node::Copy,
+            Node::Cmux(6, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(39, 1, 2),
+            Node::Copy,
+            Node::Cmux(39, 0, 1),
+            Node::Copy,
+            Node::Cmux(7, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(40, 1, 2),
+            Node::Copy,
+            Node::Cmux(40, 0, 1),
+            Node::Copy,
+            Node::Cmux(8, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(41, 1, 2),
+            Node::Copy,
+            Node::Cmux(41, 0, 1),
+            Node::Copy,
+            Node::Cmux(9, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(42, 1, 2),
+            Node::Copy,
+            Node::Cmux(42, 0, 1),
+            Node::Copy,
+            Node::Cmux(10, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(43, 1, 2),
+            Node::Copy,
+            Node::Cmux(43, 0, 1),
+            Node::Copy,
+            Node::Cmux(11, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(44, 1, 2),
+            Node::Copy,
+            Node::Cmux(44, 0, 1),
+            Node::Copy,
+            Node::Cmux(12, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(45, 1, 2),
+            Node::Copy,
+            Node::Cmux(45, 0, 1),
+            Node::Copy,
+            Node::Cmux(13, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(46, 1, 2),
+            Node::Copy,
+            Node::Cmux(46, 0, 1),
+            Node::Copy,
+            Node::Cmux(14, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(47, 1, 2),
+            Node::Copy,
+            Node::Cmux(47, 0, 1),
+            Node::Copy,
+            Node::Cmux(15, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(48, 1, 2),
+            Node::Copy,
+            Node::Cmux(48, 0, 1),
+            Node::Copy,
+            Node::Cmux(16, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(49, 1, 2),
+            Node::Copy,
+            Node::Cmux(49, 0, 1),
+            Node::Copy,
+            Node::Cmux(17, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(50, 1, 2),
+            Node::Copy,
+            Node::Cmux(50, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B1(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(33, 1, 0),
+            Node::None,
+            Node::Cmux(1, 1, 0),
+            Node::Cmux(1, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(34, 1, 2),
+            Node::Copy,
+            Node::Cmux(34, 0, 1),
+            Node::Copy,
+            Node::Cmux(2, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(35, 1, 2),
+            Node::Copy,
+            Node::Cmux(35, 0, 1),
+            Node::Copy,
+            Node::Cmux(3, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(36, 1, 2),
+            Node::Copy,
+            Node::Cmux(36, 0, 1),
+            Node::Copy,
+            Node::Cmux(4, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(37, 1, 2),
+            Node::Copy,
+            Node::Cmux(37, 0, 1),
+            Node::Copy,
+            Node::Cmux(5, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(38, 1, 2),
+            Node::Copy,
+            Node::Cmux(38, 0, 1),
+            Node::Copy,
+            Node::Cmux(6, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(39, 1, 2),
+            Node::Copy,
+            Node::Cmux(39, 0, 1),
+            Node::Copy,
+            Node::Cmux(7, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(40, 1, 2),
+            Node::Copy,
+            Node::Cmux(40, 0, 1),
+            Node::Copy,
+            Node::Cmux(8, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(41, 1, 2),
+            Node::Copy,
+            Node::Cmux(41, 0, 1),
+            Node::Copy,
+            Node::Cmux(9, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(42, 1, 2),
+            Node::Copy,
+            Node::Cmux(42, 0, 1),
+            Node::Copy,
+            Node::Cmux(10, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(43, 1, 2),
+            Node::Copy,
+            Node::Cmux(43, 0, 1),
+            Node::Copy,
+            Node::Cmux(11, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(44, 1, 2),
+            Node::Copy,
+            Node::Cmux(44, 0, 1),
+            Node::Copy,
+            Node::Cmux(12, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(45, 1, 2),
+            Node::Copy,
+            Node::Cmux(45, 0, 1),
+            Node::Copy,
+            Node::Cmux(13, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(46, 1, 2),
+            Node::Copy,
+            Node::Cmux(46, 0, 1),
+            Node::Copy,
+            Node::Cmux(14, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(47, 1, 2),
+            Node::Copy,
+            Node::Cmux(47, 0, 1),
+            Node::Copy,
+            Node::Cmux(15, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(48, 1, 2),
+            Node::Copy,
+            Node::Cmux(48, 0, 1),
+            Node::Copy,
+            Node::Cmux(16, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(49, 1, 2),
+            Node::Copy,
+            Node::Cmux(49, 0, 1),
+            Node::Copy,
+            Node::Cmux(17, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(50, 1, 2),
+            Node::Copy,
+            Node::Cmux(50, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B2(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(34, 1, 0),
+            Node::None,
+            Node::Cmux(2, 1, 0),
+            Node::Cmux(2, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(35, 1, 2),
+            Node::Copy,
+            Node::Cmux(35, 0, 1),
+            Node::Copy,
+            Node::Cmux(3, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(36, 1, 2),
+            Node::Copy,
+            Node::Cmux(36, 0, 1),
+            Node::Copy,
+            Node::Cmux(4, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(37, 1, 2),
+            Node::Copy,
+            Node::Cmux(37, 0, 1),
+            Node::Copy,
+            Node::Cmux(5, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(38, 1, 2),
+            Node::Copy,
+            Node::Cmux(38, 0, 1),
+            Node::Copy,
+            Node::Cmux(6, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(39, 1, 2),
+            Node::Copy,
+            Node::Cmux(39, 0, 1),
+            Node::Copy,
+            Node::Cmux(7, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(40, 1, 2),
+            Node::Copy,
+            Node::Cmux(40, 0, 1),
+            Node::Copy,
+            Node::Cmux(8, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(41, 1, 2),
+            Node::Copy,
+            Node::Cmux(41, 0, 1),
+            Node::Copy,
+            Node::Cmux(9, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(42, 1, 2),
+            Node::Copy,
+            Node::Cmux(42, 0, 1),
+            Node::Copy,
+            Node::Cmux(10, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(43, 1, 2),
+            Node::Copy,
+            Node::Cmux(43, 0, 1),
+            Node::Copy,
+            Node::Cmux(11, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(44, 1, 2),
+            Node::Copy,
+            Node::Cmux(44, 0, 1),
+            Node::Copy,
+            Node::Cmux(12, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(45, 1, 2),
+            Node::Copy,
+            Node::Cmux(45, 0, 1),
+            Node::Copy,
+            Node::Cmux(13, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(46, 1, 2),
+            Node::Copy,
+            Node::Cmux(46, 0, 1),
+            Node::Copy,
+            Node::Cmux(14, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(47, 1, 2),
+            Node::Copy,
+            Node::Cmux(47, 0, 1),
+            Node::Copy,
+            Node::Cmux(15, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(48, 1, 2),
+            Node::Copy,
+            Node::Cmux(48, 0, 1),
+            Node::Copy,
+            Node::Cmux(16, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(49, 1, 2),
+            Node::Copy,
+            Node::Cmux(49, 0, 1),
+            Node::Copy,
+            Node::Cmux(17, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(50, 1, 2),
+            Node::Copy,
+            Node::Cmux(50, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B3(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(35, 1, 0),
+            Node::None,
+            Node::Cmux(3, 1, 0),
+            Node::Cmux(3, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(36, 1, 2),
+            Node::Copy,
+            Node::Cmux(36, 0, 1),
+            Node::Copy,
+            Node::Cmux(4, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(37, 1, 2),
+            Node::Copy,
+            Node::Cmux(37, 0, 1),
+            Node::Copy,
+            Node::Cmux(5, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(38, 1, 2),
+            Node::Copy,
+            Node::Cmux(38, 0, 1),
+            Node::Copy,
+            Node::Cmux(6, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(39, 1, 2),
+            Node::Copy,
+            Node::Cmux(39, 0, 1),
+            Node::Copy,
+            Node::Cmux(7, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(40, 1, 2),
+            Node::Copy,
+            Node::Cmux(40, 0, 1),
+            Node::Copy,
+            Node::Cmux(8, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(41, 1, 2),
+            Node::Copy,
+            Node::Cmux(41, 0, 1),
+            Node::Copy,
+            Node::Cmux(9, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(42, 1, 2),
+            Node::Copy,
+            Node::Cmux(42, 0, 1),
+            Node::Copy,
+            Node::Cmux(10, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(43, 1, 2),
+            Node::Copy,
+            Node::Cmux(43, 0, 1),
+            Node::Copy,
+            Node::Cmux(11, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(44, 1, 2),
+            Node::Copy,
+            Node::Cmux(44, 0, 1),
+            Node::Copy,
+            Node::Cmux(12, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(45, 1, 2),
+            Node::Copy,
+            Node::Cmux(45, 0, 1),
+            Node::Copy,
+            Node::Cmux(13, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(46, 1, 2),
+            Node::Copy,
+            Node::Cmux(46, 0, 1),
+            Node::Copy,
+            Node::Cmux(14, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(47, 1, 2),
+            Node::Copy,
+            Node::Cmux(47, 0, 1),
+            Node::Copy,
+            Node::Cmux(15, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(48, 1, 2),
+            Node::Copy,
+            Node::Cmux(48, 0, 1),
+            Node::Copy,
+            Node::Cmux(16, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(49, 1, 2),
+            Node::Copy,
+            Node::Cmux(49, 0, 1),
+            Node::Copy,
+            Node::Cmux(17, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(50, 1, 2),
+            Node::Copy,
+            Node::Cmux(50, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B4(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(36, 1, 0),
+            Node::None,
+            Node::Cmux(4, 1, 0),
+            Node::Cmux(4, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(37, 1, 2),
+            Node::Copy,
+            Node::Cmux(37, 0, 1),
+            Node::Copy,
+            Node::Cmux(5, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(38, 1, 2),
+            Node::Copy,
+            Node::Cmux(38, 0, 1),
+            Node::Copy,
+            Node::Cmux(6, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(39, 1, 2),
+            Node::Copy,
+            Node::Cmux(39, 0, 1),
+            Node::Copy,
+            Node::Cmux(7, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(40, 1, 2),
+            Node::Copy,
+            Node::Cmux(40, 0, 1),
+            Node::Copy,
+            Node::Cmux(8, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(41, 1, 2),
+            Node::Copy,
+            Node::Cmux(41, 0, 1),
+            Node::Copy,
+            Node::Cmux(9, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(42, 1, 2),
+            Node::Copy,
+            Node::Cmux(42, 0, 1),
+            Node::Copy,
+            Node::Cmux(10, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(43, 1, 2),
+            Node::Copy,
+            Node::Cmux(43, 0, 1),
+            Node::Copy,
+            Node::Cmux(11, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(44, 1, 2),
+            Node::Copy,
+            Node::Cmux(44, 0, 1),
+            Node::Copy,
+            Node::Cmux(12, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(45, 1, 2),
+            Node::Copy,
+            Node::Cmux(45, 0, 1),
+            Node::Copy,
+            Node::Cmux(13, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(46, 1, 2),
+            Node::Copy,
+            Node::Cmux(46, 0, 1),
+            Node::Copy,
+            Node::Cmux(14, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(47, 1, 2),
+            Node::Copy,
+            Node::Cmux(47, 0, 1),
+            Node::Copy,
+            Node::Cmux(15, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(48, 1, 2),
+            Node::Copy,
+            Node::Cmux(48, 0, 1),
+            Node::Copy,
+            Node::Cmux(16, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(49, 1, 2),
+            Node::Copy,
+            Node::Cmux(49, 0, 1),
+            Node::Copy,
+            Node::Cmux(17, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(50, 1, 2),
+            Node::Copy,
+            Node::Cmux(50, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B5(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(37, 1, 0),
+            Node::None,
+            Node::Cmux(5, 1, 0),
+            Node::Cmux(5, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(38, 1, 2),
+            Node::Copy,
+            Node::Cmux(38, 0, 1),
+            Node::Copy,
+            Node::Cmux(6, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(39, 1, 2),
+            Node::Copy,
+            Node::Cmux(39, 0, 1),
+            Node::Copy,
+            Node::Cmux(7, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(40, 1, 2),
+            Node::Copy,
+            Node::Cmux(40, 0, 1),
+            Node::Copy,
+            Node::Cmux(8, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(41, 1, 2),
+            Node::Copy,
+            Node::Cmux(41, 0, 1),
+            Node::Copy,
+            Node::Cmux(9, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(42, 1, 2),
+            Node::Copy,
+            Node::Cmux(42, 0, 1),
+            Node::Copy,
+            Node::Cmux(10, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(43, 1, 2),
+            Node::Copy,
+            Node::Cmux(43, 0, 1),
+            Node::Copy,
+            Node::Cmux(11, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(44, 1, 2),
+            Node::Copy,
+            Node::Cmux(44, 0, 1),
+            Node::Copy,
+            Node::Cmux(12, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(45, 1, 2),
+            Node::Copy,
+            Node::Cmux(45, 0, 1),
+            Node::Copy,
+            Node::Cmux(13, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(46, 1, 2),
+            Node::Copy,
+            Node::Cmux(46, 0, 1),
+            Node::Copy,
+            Node::Cmux(14, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(47, 1, 2),
+            Node::Copy,
+            Node::Cmux(47, 0, 1),
+            Node::Copy,
+            Node::Cmux(15, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(48, 1, 2),
+            Node::Copy,
+            Node::Cmux(48, 0, 1),
+            Node::Copy,
+            Node::Cmux(16, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(49, 1, 2),
+            Node::Copy,
+            Node::Cmux(49, 0, 1),
+            Node::Copy,
+            Node::Cmux(17, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(50, 1, 2),
+            Node::Copy,
+            Node::Cmux(50, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B6(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(38, 1, 0),
+            Node::None,
+            Node::Cmux(6, 1, 0),
+            Node::Cmux(6, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(39, 1, 2),
+            Node::Copy,
+            Node::Cmux(39, 0, 1),
+            Node::Copy,
+            Node::Cmux(7, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(40, 1, 2),
+            Node::Copy,
+            Node::Cmux(40, 0, 1),
+            Node::Copy,
+            Node::Cmux(8, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(41, 1, 2),
+            Node::Copy,
+            Node::Cmux(41, 0, 1),
+            Node::Copy,
+            Node::Cmux(9, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(42, 1, 2),
+            Node::Copy,
+            Node::Cmux(42, 0, 1),
+            Node::Copy,
+            Node::Cmux(10, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(43, 1, 2),
+            Node::Copy,
+            Node::Cmux(43, 0, 1),
+            Node::Copy,
+            Node::Cmux(11, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(44, 1, 2),
+            Node::Copy,
+            Node::Cmux(44, 0, 1),
+            Node::Copy,
+            Node::Cmux(12, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(45, 1, 2),
+            Node::Copy,
+            Node::Cmux(45, 0, 1),
+            Node::Copy,
+            Node::Cmux(13, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(46, 1, 2),
+            Node::Copy,
+            Node::Cmux(46, 0, 1),
+            Node::Copy,
+            Node::Cmux(14, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(47, 1, 2),
+            Node::Copy,
+            Node::Cmux(47, 0, 1),
+            Node::Copy,
+            Node::Cmux(15, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(48, 1, 2),
+            Node::Copy,
+            Node::Cmux(48, 0, 1),
+            Node::Copy,
+            Node::Cmux(16, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(49, 1, 2),
+            Node::Copy,
+            Node::Cmux(49, 0, 1),
+            Node::Copy,
+            Node::Cmux(17, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(50, 1, 2),
+            Node::Copy,
+            Node::Cmux(50, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B7(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(39, 1, 0),
+            Node::None,
+            Node::Cmux(7, 1, 0),
+            Node::Cmux(7, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(40, 1, 2),
+            Node::Copy,
+            Node::Cmux(40, 0, 1),
+            Node::Copy,
+            Node::Cmux(8, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(41, 1, 2),
+            Node::Copy,
+            Node::Cmux(41, 0, 1),
+            Node::Copy,
+            Node::Cmux(9, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(42, 1, 2),
+            Node::Copy,
+            Node::Cmux(42, 0, 1),
+            Node::Copy,
+            Node::Cmux(10, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(43, 1, 2),
+            Node::Copy,
+            Node::Cmux(43, 0, 1),
+            Node::Copy,
+            Node::Cmux(11, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(44, 1, 2),
+            Node::Copy,
+            Node::Cmux(44, 0, 1),
+            Node::Copy,
+            Node::Cmux(12, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(45, 1, 2),
+            Node::Copy,
+            Node::Cmux(45, 0, 1),
+            Node::Copy,
+            Node::Cmux(13, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(46, 1, 2),
+            Node::Copy,
+            Node::Cmux(46, 0, 1),
+            Node::Copy,
+            Node::Cmux(14, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(47, 1, 2),
+            Node::Copy,
+            Node::Cmux(47, 0, 1),
+            Node::Copy,
+            Node::Cmux(15, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(48, 1, 2),
+            Node::Copy,
+            Node::Cmux(48, 0, 1),
+            Node::Copy,
+            Node::Cmux(16, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(49, 1, 2),
+            Node::Copy,
+            Node::Cmux(49, 0, 1),
+            Node::Copy,
+            Node::Cmux(17, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(50, 1, 2),
+            Node::Copy,
+            Node::Cmux(50, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B8(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(40, 1, 0),
+            Node::None,
+            Node::Cmux(8, 1, 0),
+            Node::Cmux(8, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(41, 1, 2),
+            Node::Copy,
+            Node::Cmux(41, 0, 1),
+            Node::Copy,
+            Node::Cmux(9, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(42, 1, 2),
+            Node::Copy,
+            Node::Cmux(42, 0, 1),
+            Node::Copy,
+            Node::Cmux(10, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(43, 1, 2),
+            Node::Copy,
+            Node::Cmux(43, 0, 1),
+            Node::Copy,
+            Node::Cmux(11, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(44, 1, 2),
+            Node::Copy,
+            Node::Cmux(44, 0, 1),
+            Node::Copy,
+            Node::Cmux(12, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(45, 1, 2),
+            Node::Copy,
+            Node::Cmux(45, 0, 1),
+            Node::Copy,
+            Node::Cmux(13, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(46, 1, 2),
+            Node::Copy,
+            Node::Cmux(46, 0, 1),
+            Node::Copy,
+            Node::Cmux(14, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(47, 1, 2),
+            Node::Copy,
+            Node::Cmux(47, 0, 1),
+            Node::Copy,
+            Node::Cmux(15, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(48, 1, 2),
+            Node::Copy,
+            Node::Cmux(48, 0, 1),
+            Node::Copy,
+            Node::Cmux(16, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(49, 1, 2),
+            Node::Copy,
+            Node::Cmux(49, 0, 1),
+            Node::Copy,
+            Node::Cmux(17, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(50, 1, 2),
+            Node::Copy,
+            Node::Cmux(50, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B9(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(41, 1, 0),
+            Node::None,
+            Node::Cmux(9, 1, 0),
+            Node::Cmux(9, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(42, 1, 2),
+            Node::Copy,
+            Node::Cmux(42, 0, 1),
+            Node::Copy,
+            Node::Cmux(10, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(43, 1, 2),
+            Node::Copy,
+            Node::Cmux(43, 0, 1),
+            Node::Copy,
+            Node::Cmux(11, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(44, 1, 2),
+            Node::Copy,
+            Node::Cmux(44, 0, 1),
+            Node::Copy,
+            Node::Cmux(12, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(45, 1, 2),
+            Node::Copy,
+            Node::Cmux(45, 0, 1),
+            Node::Copy,
+            Node::Cmux(13, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(46, 1, 2),
+            Node::Copy,
+            Node::Cmux(46, 0, 1),
+            Node::Copy,
+            Node::Cmux(14, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(47, 1, 2),
+            Node::Copy,
+            Node::Cmux(47, 0, 1),
+            Node::Copy,
+            Node::Cmux(15, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(48, 1, 2),
+            Node::Copy,
+            Node::Cmux(48, 0, 1),
+            Node::Copy,
+            Node::Cmux(16, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(49, 1, 2),
+            Node::Copy,
+            Node::Cmux(49, 0, 1),
+            Node::Copy,
+            Node::Cmux(17, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(50, 1, 2),
+            Node::Copy,
+            Node::Cmux(50, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B10(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(42, 1, 0),
+            Node::None,
+            Node::Cmux(10, 1, 0),
+            Node::Cmux(10, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(43, 1, 2),
+            Node::Copy,
+            Node::Cmux(43, 0, 1),
+            Node::Copy,
+            Node::Cmux(11, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(44, 1, 2),
+            Node::Copy,
+            Node::Cmux(44, 0, 1),
+            Node::Copy,
+            Node::Cmux(12, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(45, 1, 2),
+            Node::Copy,
+            Node::Cmux(45, 0, 1),
+            Node::Copy,
+            Node::Cmux(13, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(46, 1, 2),
+            Node::Copy,
+            Node::Cmux(46, 0, 1),
+            Node::Copy,
+            Node::Cmux(14, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(47, 1, 2),
+            Node::Copy,
+            Node::Cmux(47, 0, 1),
+            Node::Copy,
+            Node::Cmux(15, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(48, 1, 2),
+            Node::Copy,
+            Node::Cmux(48, 0, 1),
+            Node::Copy,
+            Node::Cmux(16, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(49, 1, 2),
+            Node::Copy,
+            Node::Cmux(49, 0, 1),
+            Node::Copy,
+            Node::Cmux(17, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(50, 1, 2),
+            Node::Copy,
+            Node::Cmux(50, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B11(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(43, 1, 0),
+            Node::None,
+            Node::Cmux(11, 1, 0),
+            Node::Cmux(11, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(44, 1, 2),
+            Node::Copy,
+            Node::Cmux(44, 0, 1),
+            Node::Copy,
+            Node::Cmux(12, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(45, 1, 2),
+            Node::Copy,
+            Node::Cmux(45, 0, 1),
+            Node::Copy,
+            Node::Cmux(13, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(46, 1, 2),
+            Node::Copy,
+            Node::Cmux(46, 0, 1),
+            Node::Copy,
+            Node::Cmux(14, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(47, 1, 2),
+            Node::Copy,
+            Node::Cmux(47, 0, 1),
+            Node::Copy,
+            Node::Cmux(15, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(48, 1, 2),
+            Node::Copy,
+            Node::Cmux(48, 0, 1),
+            Node::Copy,
+            Node::Cmux(16, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(49, 1, 2),
+            Node::Copy,
+            Node::Cmux(49, 0, 1),
+            Node::Copy,
+            Node::Cmux(17, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(50, 1, 2),
+            Node::Copy,
+            Node::Cmux(50, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B12(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(44, 1, 0),
+            Node::None,
+            Node::Cmux(12, 1, 0),
+            Node::Cmux(12, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(45, 1, 2),
+            Node::Copy,
+            Node::Cmux(45, 0, 1),
+            Node::Copy,
+            Node::Cmux(13, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(46, 1, 2),
+            Node::Copy,
+            Node::Cmux(46, 0, 1),
+            Node::Copy,
+            Node::Cmux(14, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(47, 1, 2),
+            Node::Copy,
+            Node::Cmux(47, 0, 1),
+            Node::Copy,
+            Node::Cmux(15, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(48, 1, 2),
+            Node::Copy,
+            Node::Cmux(48, 0, 1),
+            Node::Copy,
+            Node::Cmux(16, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(49, 1, 2),
+            Node::Copy,
+            Node::Cmux(49, 0, 1),
+            Node::Copy,
+            Node::Cmux(17, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(50, 1, 2),
+            Node::Copy,
+            Node::Cmux(50, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B13(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(45, 1, 0),
+            Node::None,
+            Node::Cmux(13, 1, 0),
+            Node::Cmux(13, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(46, 1, 2),
+            Node::Copy,
+            Node::Cmux(46, 0, 1),
+            Node::Copy,
+            Node::Cmux(14, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(47, 1, 2),
+            Node::Copy,
+            Node::Cmux(47, 0, 1),
+            Node::Copy,
+            Node::Cmux(15, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(48, 1, 2),
+            Node::Copy,
+            Node::Cmux(48, 0, 1),
+            Node::Copy,
+            Node::Cmux(16, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(49, 1, 2),
+            Node::Copy,
+            Node::Cmux(49, 0, 1),
+            Node::Copy,
+            Node::Cmux(17, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(50, 1, 2),
+            Node::Copy,
+            Node::Cmux(50, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B14(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(46, 1, 0),
+            Node::None,
+            Node::Cmux(14, 1, 0),
+            Node::Cmux(14, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(47, 1, 2),
+            Node::Copy,
+            Node::Cmux(47, 0, 1),
+            Node::Copy,
+            Node::Cmux(15, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(48, 1, 2),
+            Node::Copy,
+            Node::Cmux(48, 0, 1),
+            Node::Copy,
+            Node::Cmux(16, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(49, 1, 2),
+            Node::Copy,
+            Node::Cmux(49, 0, 1),
+            Node::Copy,
+            Node::Cmux(17, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(50, 1, 2),
+            Node::Copy,
+            Node::Cmux(50, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B15(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(47, 1, 0),
+            Node::None,
+            Node::Cmux(15, 1, 0),
+            Node::Cmux(15, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(48, 1, 2),
+            Node::Copy,
+            Node::Cmux(48, 0, 1),
+            Node::Copy,
+            Node::Cmux(16, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(49, 1, 2),
+            Node::Copy,
+            Node::Cmux(49, 0, 1),
+            Node::Copy,
+            Node::Cmux(17, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(50, 1, 2),
+            Node::Copy,
+            Node::Cmux(50, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B16(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(48, 1, 0),
+            Node::None,
+            Node::Cmux(16, 1, 0),
+            Node::Cmux(16, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(49, 1, 2),
+            Node::Copy,
+            Node::Cmux(49, 0, 1),
+            Node::Copy,
+            Node::Cmux(17, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(50, 1, 2),
+            Node::Copy,
+            Node::Cmux(50, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B17(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(49, 1, 0),
+            Node::None,
+            Node::Cmux(17, 1, 0),
+            Node::Cmux(17, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(50, 1, 2),
+            Node::Copy,
+            Node::Cmux(50, 0, 1),
+            Node::Copy,
+            Node::Cmux(18, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B18(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(50, 1, 0),
+            Node::None,
+            Node::Cmux(18, 1, 0),
+            Node::Cmux(18, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(51, 1, 2),
+            Node::Copy,
+            Node::Cmux(51, 0, 1),
+            Node::Copy,
+            Node::Cmux(19, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B19(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(51, 1, 0),
+            Node::None,
+            Node::Cmux(19, 1, 0),
+            Node::Cmux(19, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(52, 1, 2),
+            Node::Copy,
+            Node::Cmux(52, 0, 1),
+            Node::Copy,
+            Node::Cmux(20, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B20(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(52, 1, 0),
+            Node::None,
+            Node::Cmux(20, 1, 0),
+            Node::Cmux(20, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(53, 1, 2),
+            Node::Copy,
+            Node::Cmux(53, 0, 1),
+            Node::Copy,
+            Node::Cmux(21, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B21(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(53, 1, 0),
+            Node::None,
+            Node::Cmux(21, 1, 0),
+            Node::Cmux(21, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(54, 1, 2),
+            Node::Copy,
+            Node::Cmux(54, 0, 1),
+            Node::Copy,
+            Node::Cmux(22, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B22(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(54, 1, 0),
+            Node::None,
+            Node::Cmux(22, 1, 0),
+            Node::Cmux(22, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(55, 1, 2),
+            Node::Copy,
+            Node::Cmux(55, 0, 1),
+            Node::Copy,
+            Node::Cmux(23, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B23(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(55, 1, 0),
+            Node::None,
+            Node::Cmux(23, 1, 0),
+            Node::Cmux(23, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(56, 1, 2),
+            Node::Copy,
+            Node::Cmux(56, 0, 1),
+            Node::Copy,
+            Node::Cmux(24, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B24(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(56, 1, 0),
+            Node::None,
+            Node::Cmux(24, 1, 0),
+            Node::Cmux(24, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(57, 1, 2),
+            Node::Copy,
+            Node::Cmux(57, 0, 1),
+            Node::Copy,
+            Node::Cmux(25, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B25(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(57, 1, 0),
+            Node::None,
+            Node::Cmux(25, 1, 0),
+            Node::Cmux(25, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(58, 1, 2),
+            Node::Copy,
+            Node::Cmux(58, 0, 1),
+            Node::Copy,
+            Node::Cmux(26, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B26(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(58, 1, 0),
+            Node::None,
+            Node::Cmux(26, 1, 0),
+            Node::Cmux(26, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(59, 1, 2),
+            Node::Copy,
+            Node::Cmux(59, 0, 1),
+            Node::Copy,
+            Node::Cmux(27, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B27(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(59, 1, 0),
+            Node::None,
+            Node::Cmux(27, 1, 0),
+            Node::Cmux(27, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(60, 1, 2),
+            Node::Copy,
+            Node::Cmux(60, 0, 1),
+            Node::Copy,
+            Node::Cmux(28, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B28(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(60, 1, 0),
+            Node::None,
+            Node::Cmux(28, 1, 0),
+            Node::Cmux(28, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(61, 1, 2),
+            Node::Copy,
+            Node::Cmux(61, 0, 1),
+            Node::Copy,
+            Node::Cmux(29, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B29(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(61, 1, 0),
+            Node::None,
+            Node::Cmux(29, 1, 0),
+            Node::Cmux(29, 2, 0),
+            Node::Copy,
+            Node::None,
+            Node::Copy,
+            Node::Cmux(62, 1, 2),
+            Node::Copy,
+            Node::Cmux(62, 0, 1),
+            Node::Copy,
+            Node::Cmux(30, 1, 3),
+            Node::Copy,
+            Node::None,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+            Node::None,
+        ],
+        4,
+    )),
+    AnyBitCircuit::B30(BitCircuit::new(
+        [
+            Node::Copy,
+            Node::Copy,
+            Node::Cmux(62, 1, 0),
+            Node::Cmux(30, 1, 0),
+            Node::Cmux(30, 2, 0),
+            Node::Copy,
+            Node::Cmux(63, 1, 0),
+            Node::Cmux(63, 2, 1),
+            Node::None,
+            Node::Cmux(31, 0, 1),
+            Node::None,
+            Node::None,
+        ],
+        3,
+    )),
+    AnyBitCircuit::B31(BitCircuit::new(
+        [
+            Node::Cmux(63, 1, 0),
+            Node::Copy,
+            Node::Cmux(31, 1, 0),
+            Node::None,
+        ],
+        2,
+    )),
+]);
@@ -1,5 +1,25 @@
+// Generated circuits, whose AnyBitCircuit variants are sized by their number of nodes.
+#![allow(clippy::large_enum_variant)]
+
+pub(crate) mod codegen_andn;
 pub(crate) mod codegen_auipc;
+pub(crate) mod codegen_clz;
+pub(crate) mod codegen_cpop;
+pub(crate) mod codegen_ctz;
 pub(crate) mod codegen_jalr;
 pub(crate) mod codegen_lui;
+pub(crate) mod codegen_max;
+pub(crate) mod codegen_maxu;
+pub(crate) mod codegen_min;
+pub(crate) mod codegen_minu;
+pub(crate) mod codegen_orc_b;
+pub(crate) mod codegen_orn;
 pub(crate) mod codegen_pc_update;
 pub(crate) mod codegen_ram_offset;
+pub(crate) mod codegen_rev8;
+pub(crate) mod codegen_rol;
+pub(crate) mod codegen_ror;
+pub(crate) mod codegen_sext_b;
+pub(crate) mod codegen_sext_h;
+pub(crate) mod codegen_xnor;
+pub(crate) mod codegen_zext_h;
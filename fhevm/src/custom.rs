@@ -0,0 +1,215 @@
+//! Custom instructions evaluated by user-defined circuits.
+//!
+//! The custom-0 and custom-1 major opcodes of RISC-V are reserved for
+//! extensions. A [CustomInstruction] binds one of them, together with a
+//! funct3, to a circuit over (rs1, rs2, imm), e.g. a modular reduction or an
+//! S-box lookup. Registered instructions are decoded to the register update
+//! operations [crate::RD_UPDATE::CUSTOM], evaluated every cycle alongside the
+//! operations of the [crate::InstructionSet] and blind-selected like them.
+//!
+//! Custom instructions use the R-type register fields with a sign-extended
+//! 12-bit immediate overlapping funct7 and rs2:
+//!
+//! ```text
+//! imm[11:0] (funct7 | rs2) | rs1 | funct3 | rd | opcode
+//! ```
+//!
+//! so an instruction reads both x[rs1] and x[rs2] and the immediate, and the
+//! circuit decides which of them are meaningful.
+
+use std::sync::Arc;
+
+use poulpy_core::{
+    layouts::{GGLWEInfos, GGLWEPreparedToRef, GLWEAutomorphismKeyHelper, GetGaloisElement},
+    GLWECopy, GLWEPacking, ScratchTakeCore,
+};
+use poulpy_hal::{
+    api::ModuleLogN,
+    layouts::{Backend, DataMut, DataRef, Scratch},
+};
+use poulpy_schemes::bin_fhe::bdd_arithmetic::{
+    ExecuteBDDCircuit, FheUint, FheUintPrepared, GetBitCircuitInfo, Node,
+};
+
+use crate::{bdd::BDDInputs, Instruction, PC_UPDATE, RAM_UPDATE, RD_UPDATE};
+
+const WORD: usize = u32::BITS as usize;
+
+/// Major opcode custom-0.
+pub const CUSTOM_0: u32 = 0b0001011;
+
+/// Major opcode custom-1.
+pub const CUSTOM_1: u32 = 0b0101011;
+
+/// Number of bits of the inputs of a custom instruction circuit: [rs1, rs2, imm].
+pub const CUSTOM_INPUT_SIZE: usize = 3 * WORD;
+
+/// An instruction evaluated by a user-defined circuit.
+pub trait CustomInstruction: Send + Sync {
+    /// Major opcode of the instruction, [CUSTOM_0] or [CUSTOM_1].
+    fn opcode(&self) -> u32;
+
+    /// funct3 of the instruction.
+    fn funct3(&self) -> u32;
+
+    /// Circuit evaluating x[rd] from the [CUSTOM_INPUT_SIZE] bits
+    /// [x[rs1], x[rs2], sext(imm[11:0])], with 32 output bits.
+    ///
+    /// Circuits can be built with [crate::bdd::BDDBuilder].
+    fn circuit(&self) -> &dyn GetBitCircuitInfo;
+
+    /// Plaintext semantics of [CustomInstruction::circuit], used by the
+    /// debug interpreter and the test VM.
+    fn eval_plain(&self, rs1: u32, rs2: u32, imm: u32) -> u32;
+}
+
+/// Registry of the [CustomInstruction] of a program.
+///
+/// The registry is shared by the [crate::InstructionsParser], which decodes
+/// the custom opcodes, and by the [crate::Interpreter], which evaluates them,
+/// so both must be given the same registry, registered in the same order.
+#[derive(Clone, Default)]
+pub struct CustomInstructions(Vec<Arc<dyn CustomInstruction>>);
+
+impl CustomInstructions {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Registers `instruction`.
+    ///
+    /// Panics if its (opcode, funct3) is invalid or already registered, if its
+    /// circuit does not have the expected size or if [RD_UPDATE::CUSTOM] slots
+    /// are all taken.
+    pub fn register(&mut self, instruction: impl CustomInstruction + 'static) {
+        let (opcode, funct3) = (instruction.opcode(), instruction.funct3());
+        assert!(
+            opcode == CUSTOM_0 || opcode == CUSTOM_1,
+            "invalid custom opcode: {opcode:07b}"
+        );
+        assert!(funct3 < 8, "invalid funct3: {funct3}");
+        assert!(
+            self.find(opcode, funct3).is_none(),
+            "custom instruction already registered: opcode={opcode:07b} funct3={funct3:03b}"
+        );
+        assert!(
+            self.0.len() < RD_UPDATE::CUSTOM.len(),
+            "at most {} custom instructions can be registered",
+            RD_UPDATE::CUSTOM.len()
+        );
+
+        let circuit: &dyn GetBitCircuitInfo = instruction.circuit();
+        assert!(circuit.input_size() <= CUSTOM_INPUT_SIZE);
+        assert_eq!(circuit.output_size(), WORD);
+
+        self.0.push(Arc::new(instruction));
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the custom instruction matching the raw instruction `inst`, if any.
+    pub fn get(&self, inst: u32) -> Option<&dyn CustomInstruction> {
+        let instruction: Instruction = Instruction::new(inst);
+        match instruction.get_opcode() {
+            CUSTOM_0 | CUSTOM_1 => self
+                .find(instruction.get_opcode(), instruction.get_funct3())
+                .map(|slot| self.0[slot].as_ref()),
+            _ => None,
+        }
+    }
+
+    fn find(&self, opcode: u32, funct3: u32) -> Option<usize> {
+        self.0
+            .iter()
+            .position(|inst| inst.opcode() == opcode && inst.funct3() == funct3)
+    }
+
+    /// Same as [Instruction::get_opid], decoding the custom opcodes to their
+    /// slot of [RD_UPDATE::CUSTOM].
+    pub(crate) fn get_opid(&self, instruction: &Instruction) -> (RD_UPDATE, RAM_UPDATE, PC_UPDATE) {
+        match instruction.get_opcode() {
+            CUSTOM_0 | CUSTOM_1 => {
+                let slot: usize = self
+                    .find(instruction.get_opcode(), instruction.get_funct3())
+                    .unwrap_or_else(|| {
+                        panic!(
+                            "unregistered custom instruction: {:032b}",
+                            instruction.get()
+                        )
+                    });
+                (RD_UPDATE::CUSTOM[slot], RAM_UPDATE::NONE, PC_UPDATE::NONE)
+            }
+            _ => instruction.get_opid(),
+        }
+    }
+
+    /// Register update operations of the registered instructions.
+    pub(crate) fn ops(&self) -> &'static [RD_UPDATE] {
+        &RD_UPDATE::CUSTOM[..self.0.len()]
+    }
+
+    /// Returns the instruction evaluating `op`, or None if `op` is not a
+    /// registered custom operation.
+    pub(crate) fn instruction(&self, op: &RD_UPDATE) -> Option<&dyn CustomInstruction> {
+        op.custom_slot()
+            .and_then(|slot| self.0.get(slot))
+            .map(|inst| inst.as_ref())
+    }
+}
+
+// Sized view of a custom instruction circuit for [ExecuteBDDCircuit].
+struct CustomCircuit<'a>(&'a dyn GetBitCircuitInfo);
+
+impl GetBitCircuitInfo for CustomCircuit<'_> {
+    fn input_size(&self) -> usize {
+        self.0.input_size()
+    }
+
+    fn output_size(&self) -> usize {
+        self.0.output_size()
+    }
+
+    fn get_circuit(&self, bit: usize) -> (&[Node], usize) {
+        self.0.get_circuit(bit)
+    }
+}
+
+/// Evaluates the circuit of `instruction` and writes x[rd] in `res`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn eval_custom<R, A, B, I, H, K, M, BE: Backend>(
+    threads: usize,
+    module: &M,
+    instruction: &dyn CustomInstruction,
+    res: &mut FheUint<R, u32>,
+    rs1: &FheUintPrepared<A, u32, BE>,
+    rs2: &FheUintPrepared<B, u32, BE>,
+    imm: &FheUintPrepared<I, u32, BE>,
+    keys: &H,
+    scratch: &mut Scratch<BE>,
+) where
+    R: DataMut,
+    A: DataRef,
+    B: DataRef,
+    I: DataRef,
+    H: GLWEAutomorphismKeyHelper<K, BE>,
+    K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+    M: ModuleLogN + GLWEPacking<BE> + GLWECopy + ExecuteBDDCircuit<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+{
+    let circuit: CustomCircuit<'_> = CustomCircuit(instruction.circuit());
+
+    let inputs: BDDInputs<'_, BE> = BDDInputs::new()
+        .word(rs1, WORD)
+        .word(rs2, WORD)
+        .word(imm, WORD);
+
+    let (mut out_bits, scratch_1) = scratch.take_glwe_slice(WORD, res);
+    module.execute_bdd_circuit_multi_thread(threads, &mut out_bits, &inputs, &circuit, scratch_1);
+    res.pack(module, out_bits, keys, scratch_1);
+}
@@ -1,12 +1,20 @@
 use std::collections::HashMap;
 
-use crate::{InstructionsParser, PC_UPDATE_OP_LIST, RAM_UPDATE_OP_LIST, RD_UPDATE};
+use crate::{
+    alignment::{align_loaded, is_misaligned},
+    custom::CustomInstructions,
+    halt::HaltStatus,
+    rodata::rodata_bit,
+    InstructionsParser, PC_UPDATE_OP_LIST, RAM_UPDATE_OP_LIST, RD_UPDATE,
+};
 
 pub(crate) struct InterpreterDebug {
     pub(crate) ram_size: u32,
     #[allow(dead_code)]
     pub(crate) rom_size: u32,
     pub(crate) pc: u32,
+    pub(crate) pc_prev: u32,
+    pub(crate) status: HaltStatus,
     pub(crate) imm_rom: Vec<u32>,
     pub(crate) rs1_rom: Vec<u32>,
     pub(crate) rs2_rom: Vec<u32>,
@@ -16,11 +24,15 @@ pub(crate) struct InterpreterDebug {
     pub(crate) pcu_rom: Vec<u32>,
     pub(crate) registers: [u32; 32],
     pub(crate) ram: Vec<u32>,
+    pub(crate) rodata: Vec<u32>,
+    pub(crate) rodata_bit: Option<u32>,
     pub(crate) ram_addr: u32,
     pub(crate) imm: u32,
     pub(crate) rs1_val: u32,
     pub(crate) rs2_val: u32,
     pub(crate) ram_val: u32,
+    pub(crate) rodata_val: u32,
+    pub(crate) in_rodata: bool,
     pub(crate) rd_val: u32,
     pub(crate) rs1_addr: u32,
     pub(crate) rs2_addr: u32,
@@ -28,12 +40,15 @@ pub(crate) struct InterpreterDebug {
     pub(crate) rdu: u32,
     pub(crate) pcu: u32,
     pub(crate) mu: u32,
+    pub(crate) misaligned: bool,
 }
 
 impl InterpreterDebug {
     pub fn new(rom_size: usize, ram_size: usize) -> Self {
         Self {
             pc: 0,
+            pc_prev: 0,
+            status: HaltStatus::default(),
             ram_size: ram_size as u32,
             rom_size: rom_size as u32,
             imm_rom: vec![0u32; rom_size],
@@ -45,11 +60,15 @@ impl InterpreterDebug {
             pcu_rom: vec![0u32; rom_size],
             registers: [0u32; 32],
             ram: vec![0u32; ram_size],
+            rodata: Vec::new(),
+            rodata_bit: None,
             ram_addr: 0,
             imm: 0,
             rs1_val: 0,
             rs2_val: 0,
             ram_val: 0,
+            rodata_val: 0,
+            in_rodata: false,
             rd_val: 0,
             rs1_addr: 0,
             rs2_addr: 0,
@@ -57,6 +76,7 @@ impl InterpreterDebug {
             rdu: 0,
             pcu: 0,
             mu: 0,
+            misaligned: false,
         }
     }
 
@@ -64,11 +84,13 @@ impl InterpreterDebug {
         assert_eq!(self.imm_rom.len(), instructions.instructions.len());
         for i in 0..instructions.instructions.len() {
             self.imm_rom[i] = instructions.get_raw(i).get_imm() as u32;
-            let (rs2, rs1, rd) = instructions.get_raw(i).get_registers();
+            let (rs2, rs1, rd) = instructions.get_registers(i);
             self.rs1_rom[i] = rs1 as u32;
             self.rs2_rom[i] = rs2 as u32;
             self.rd_rom[i] = rd as u32;
-            let (rdu, mu, pcu) = instructions.get_raw(i).get_opid();
+            let (rdu, mu, pcu) = instructions
+                .custom_instructions
+                .get_opid(&instructions.get_raw(i));
             self.rdu_rom[i] = rdu as u32;
             self.mu_rom[i] = mu as u32;
             self.pcu_rom[i] = pcu as u32;
@@ -80,6 +102,17 @@ impl InterpreterDebug {
         self.ram.copy_from_slice(ram);
     }
 
+    pub fn set_rodata_size(&mut self, rodata_size: usize) {
+        self.rodata = vec![0u32; rodata_size];
+        self.rodata_bit =
+            (rodata_size > 0).then(|| rodata_bit(self.ram_size as usize, rodata_size) as u32);
+    }
+
+    pub fn set_rodata(&mut self, rodata: &[u32]) {
+        self.rodata.fill(0);
+        self.rodata[..rodata.len()].copy_from_slice(rodata);
+    }
+
     pub fn read_instructions(&mut self) {
         let pc: usize = (self.pc >> 2) as usize;
         self.imm = self.imm_rom[pc];
@@ -89,6 +122,14 @@ impl InterpreterDebug {
         self.rdu = self.rdu_rom[pc];
         self.pcu = self.pcu_rom[pc];
         self.mu = self.mu_rom[pc];
+        if self.status.halted {
+            self.rd_addr = 0;
+            self.mu = 0;
+        }
+    }
+
+    pub fn update_halt_status(&mut self) {
+        self.status = self.status.update(self.pc, self.pc_prev);
     }
 
     pub fn read_registers(&mut self) {
@@ -99,19 +140,42 @@ impl InterpreterDebug {
     pub fn read_ram(&mut self) {
         self.ram_addr = self.imm.wrapping_add(self.rs1_val).wrapping_sub(1 << 18);
         self.ram_val = self.ram[(self.ram_addr >> 2) as usize % self.ram_size as usize];
+        self.misaligned = is_misaligned(self.rdu, self.mu, self.ram_addr);
+        if self.misaligned {
+            self.rd_addr = 0;
+            self.mu = 0;
+        }
+        self.in_rodata = self
+            .rodata_bit
+            .is_some_and(|bit| (self.ram_addr >> bit) & 1 == 1);
+        if self.in_rodata {
+            let mask: usize = self.rodata.len().next_power_of_two() - 1;
+            let idx: usize = (self.ram_addr >> 2) as usize & mask;
+            self.rodata_val = self.rodata.get(idx).copied().unwrap_or(0);
+            self.mu = 0;
+        }
     }
 
-    pub fn update_registers(&mut self, ops: &[RD_UPDATE]) {
+    pub fn update_registers(&mut self, ops: &[RD_UPDATE], custom: &CustomInstructions) {
         let mut rd_map: HashMap<u32, u32> = HashMap::new();
 
         let imm: u32 = self.imm;
         let rs1: u32 = self.rs1_val;
         let rs2: u32 = self.rs2_val;
-        let ram: u32 = self.ram_val;
+        let loaded: u32 = if self.in_rodata {
+            self.rodata_val
+        } else {
+            self.ram_val
+        };
+        let ram: u32 = align_loaded(loaded, self.ram_addr & 0x3);
         let pc: u32 = self.pc;
 
         for op in ops {
-            rd_map.insert(op.id(), op.eval_plain(imm, rs1, rs2, pc, ram));
+            let rd: u32 = match custom.instruction(op) {
+                Some(inst) => inst.eval_plain(rs1, rs2, imm),
+                None => op.eval_plain(imm, rs1, rs2, pc, ram),
+            };
+            rd_map.insert(op.id(), rd);
         }
 
         self.rd_val = *rd_map.get(&self.rdu).unwrap();
@@ -145,6 +209,9 @@ impl InterpreterDebug {
             pc_map.insert(op.id(), op.eval_plain(imm, rs1, rs2, pc));
         }
 
-        self.pc = *pc_map.get(&self.pcu).unwrap()
+        self.pc_prev = pc;
+        if !self.status.halted && !self.misaligned {
+            self.pc = *pc_map.get(&self.pcu).unwrap()
+        }
     }
 }
@@ -0,0 +1,128 @@
+//! Encrypted halt flag of the [crate::Interpreter].
+//!
+//! A program halts when it executes an instruction that jumps to itself, which
+//! is how the exit instructions are lowered (see [crate::RV32I::ECALL] and
+//! [crate::RV32I::EBREAK]). The interpreter keeps an encrypted status word,
+//! updated at the start of each cycle by comparing the PC with the PC of the
+//! previous cycle, and freezes its state once the halt flag is set.
+
+use poulpy_core::{
+    layouts::{GGLWEInfos, GGLWEPreparedToRef, GLWEAutomorphismKeyHelper, GetGaloisElement},
+    GLWECopy, GLWEPacking, ScratchTakeCore,
+};
+use poulpy_hal::{
+    api::ModuleLogN,
+    layouts::{Backend, DataMut, DataRef, Scratch},
+};
+use poulpy_schemes::bin_fhe::bdd_arithmetic::{
+    ExecuteBDDCircuit, FheUint, FheUintPrepared, GetBitCircuitInfo,
+};
+
+use crate::bdd::{BDDBuilder, BDDInputs, BDDRef, DynamicCircuit, FALSE, TRUE};
+
+const WORD: usize = u32::BITS as usize;
+
+/// Status of an execution, as stored in the encrypted status word:
+/// bit 0 is the halt flag and bits [1, 32) the cycle counter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HaltStatus {
+    /// True if the program executed an exit instruction or a misaligned
+    /// load or store (see [crate::alignment]).
+    pub halted: bool,
+    /// Cycle at which the exit instruction was executed if halted, else the
+    /// number of cycles observed so far.
+    pub cycles: u32,
+}
+
+impl HaltStatus {
+    pub fn from_word(word: u32) -> Self {
+        Self {
+            halted: word & 1 == 1,
+            cycles: word >> 1,
+        }
+    }
+
+    pub fn to_word(&self) -> u32 {
+        (self.cycles << 1) | self.halted as u32
+    }
+
+    /// Returns the status after a cycle that moved the PC from `pc_prev` to `pc`.
+    pub(crate) fn update(&self, pc: u32, pc_prev: u32) -> Self {
+        let halted: bool = self.halted || pc == pc_prev;
+        Self {
+            halted,
+            cycles: if halted {
+                self.cycles
+            } else {
+                self.cycles.wrapping_add(1) & (u32::MAX >> 1)
+            },
+        }
+    }
+}
+
+/// Circuit [status, pc[pc_bits], pc_prev[pc_bits]] -> [status'] implementing
+/// [HaltStatus::update] over the first `pc_bits` bits of the PC.
+pub(crate) fn halt_circuit(pc_bits: usize) -> DynamicCircuit {
+    let mut b: BDDBuilder = BDDBuilder::new();
+
+    // The counter is declared last, from its high to its low bits, as the
+    // carry propagates from the low bits.
+    let halted: BDDRef = b.var(0);
+    let pcs: Vec<(BDDRef, BDDRef)> = (0..pc_bits)
+        .rev()
+        .map(|i| (b.var(WORD + i), b.var(WORD + pc_bits + i)))
+        .collect();
+    let mut count: Vec<BDDRef> = (1..WORD).rev().map(|i| b.var(i)).collect();
+    count.reverse();
+
+    let same_pc: BDDRef = pcs.iter().fold(TRUE, |acc, &(pc, pc_prev)| {
+        let diff: BDDRef = b.xor(pc, pc_prev);
+        let same: BDDRef = b.not(diff);
+        b.and(acc, same)
+    });
+    let halted_next: BDDRef = b.or(halted, same_pc);
+    let running: BDDRef = b.not(halted_next);
+
+    let zero: Vec<BDDRef> = vec![FALSE; count.len()];
+    let mut count_next: Vec<BDDRef> = b.add(&count, &zero, running);
+    count_next.truncate(count.len());
+
+    let mut outputs: Vec<BDDRef> = vec![halted_next];
+    outputs.extend(count_next);
+    b.circuit(WORD + 2 * pc_bits, &outputs)
+}
+
+/// Evaluates `circuit` (see [halt_circuit]) and writes the new status word in `res`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_halt_status<R, S, P, Q, H, K, M, BE: Backend>(
+    threads: usize,
+    module: &M,
+    res: &mut FheUint<R, u32>,
+    status: &FheUintPrepared<S, u32, BE>,
+    pc: &FheUintPrepared<P, u32, BE>,
+    pc_prev: &FheUintPrepared<Q, u32, BE>,
+    pc_bits: usize,
+    circuit: &DynamicCircuit,
+    keys: &H,
+    scratch: &mut Scratch<BE>,
+) where
+    R: DataMut,
+    S: DataRef,
+    P: DataRef,
+    Q: DataRef,
+    H: GLWEAutomorphismKeyHelper<K, BE>,
+    K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+    M: ModuleLogN + GLWEPacking<BE> + GLWECopy + ExecuteBDDCircuit<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+{
+    assert_eq!(circuit.output_size(), WORD);
+
+    let inputs: BDDInputs<'_, BE> = BDDInputs::new()
+        .word(status, WORD)
+        .word(pc, pc_bits)
+        .word(pc_prev, pc_bits);
+
+    let (mut out_bits, scratch_1) = scratch.take_glwe_slice(WORD, res);
+    module.execute_bdd_circuit_multi_thread(threads, &mut out_bits, &inputs, circuit, scratch_1);
+    res.pack(module, out_bits, keys, scratch_1);
+}
@@ -0,0 +1,236 @@
+use std::io::{Read, Result, Write};
+
+use poulpy_core::{
+    layouts::{GLWELayout, GLWEPlaintext, GLWESecretPreparedToRef, LWEInfos, TorusPrecision, GLWE},
+    GLWEDecrypt, GLWEEncryptPk, GLWEEncryptSk, ScratchTakeCore,
+};
+use poulpy_hal::{
+    api::{ModuleN, TakeSlice},
+    layouts::{Backend, DataRef, ReaderFrom, Scratch, WriterTo},
+    source::Source,
+};
+use poulpy_schemes::bin_fhe::bdd_arithmetic::ToBits;
+
+use crate::{
+    keys::VMPublicKeyPrepared,
+    parameters::CryptographicParameters,
+    serialization::{invalid_data, read_u64, write_u64, Header},
+};
+
+/// Magic bytes of a serialized [EncryptedInput].
+pub const ENCRYPTED_INPUT_MAGIC: [u8; 4] = *b"PZEI";
+
+/// Version of the serialized [EncryptedInput] layout.
+pub const ENCRYPTED_INPUT_VERSION: u32 = 1;
+
+/// Encrypted words covering the range [word_offset, word_offset + size) of the RAM,
+/// encrypted separately from the rest of the RAM.
+///
+/// Uses the same bit-sliced layout as the RAM, but only stores the ciphertexts
+/// overlapping the range, with all the slots outside of the range set to zero.
+/// It is injected into an encrypted RAM with [crate::Interpreter::ram_inject_input].
+pub struct EncryptedInput {
+    pub(crate) word_offset: usize,
+    pub(crate) size: usize,
+    pub(crate) bits: Vec<Vec<GLWE<Vec<u8>>>>,
+}
+
+impl EncryptedInput {
+    pub fn alloc<BE: Backend>(
+        params: &CryptographicParameters<BE>,
+        word_offset: usize,
+        size: usize,
+    ) -> Self {
+        assert!(size > 0);
+        let ram_infos: GLWELayout = params.ram_infos();
+        let n: usize = ram_infos.n.into();
+        let cts: usize = (word_offset + size - 1) / n - word_offset / n + 1;
+        Self {
+            word_offset,
+            size,
+            bits: (0..u32::BITS)
+                .map(|_| {
+                    (0..cts)
+                        .map(|_| GLWE::alloc_from_infos(&ram_infos))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// Index of the first word of the range in the RAM.
+    pub fn word_offset(&self) -> usize {
+        self.word_offset
+    }
+
+    /// Number of words of the range.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Index of the first RAM ciphertext (of each bit array) overlapped by the range.
+    pub(crate) fn ct_offset(&self) -> usize {
+        self.word_offset / self.n()
+    }
+
+    fn n(&self) -> usize {
+        self.bits[0][0].n().into()
+    }
+
+    pub fn encrypt_sk<M, S, BE: Backend>(
+        &mut self,
+        module: &M,
+        data: &[u32],
+        sk: &S,
+        source_xa: &mut Source,
+        source_xe: &mut Source,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: ModuleN + GLWEEncryptSk<BE>,
+        S: GLWESecretPreparedToRef<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        self.encrypt(module, data, scratch, |ct, pt, scratch| {
+            ct.encrypt_sk(module, pt, sk, source_xa, source_xe, scratch)
+        });
+    }
+
+    /// Same as [EncryptedInput::encrypt_sk] but with the public key `pk`.
+    pub fn encrypt_pk<M, D, BE: Backend>(
+        &mut self,
+        module: &M,
+        data: &[u32],
+        pk: &VMPublicKeyPrepared<D, BE>,
+        source_xu: &mut Source,
+        source_xe: &mut Source,
+        scratch: &mut Scratch<BE>,
+    ) where
+        D: DataRef,
+        M: ModuleN + GLWEEncryptPk<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        self.encrypt(module, data, scratch, |ct, pt, scratch| {
+            ct.encrypt_pk(module, pt, &pk.pk, source_xu, source_xe, scratch)
+        });
+    }
+
+    fn encrypt<M, F, BE: Backend>(
+        &mut self,
+        module: &M,
+        data: &[u32],
+        scratch: &mut Scratch<BE>,
+        mut encrypt: F,
+    ) where
+        M: ModuleN,
+        F: FnMut(&mut GLWE<Vec<u8>>, &GLWEPlaintext<&mut [u8]>, &mut Scratch<BE>),
+        Scratch<BE>: ScratchTakeCore<BE>,
+        u32: ToBits,
+    {
+        assert_eq!(data.len(), self.size);
+
+        let n: usize = module.n();
+        let word_offset: usize = self.word_offset;
+        let ct_offset: usize = self.ct_offset();
+
+        let (mut pt, scratch_1) = scratch.take_glwe_plaintext(&self.bits[0][0]);
+        let (data_i64, scratch_2) = scratch_1.take_slice(n);
+
+        for (i, cts) in self.bits.iter_mut().enumerate() {
+            for (j, ct) in cts.iter_mut().enumerate() {
+                data_i64.fill(0);
+
+                // Words of the range that fall into the j-th ciphertext.
+                let start: usize = ((ct_offset + j) * n).max(word_offset);
+                let end: usize = ((ct_offset + j + 1) * n).min(word_offset + data.len());
+                for addr in start..end {
+                    data_i64[addr % n] = data[addr - word_offset].bit(i) as i64;
+                }
+
+                pt.encode_vec_i64(data_i64, TorusPrecision(2));
+                encrypt(ct, &pt, scratch_2);
+            }
+        }
+    }
+
+    /// Decrypts the words of the range into `data`.
+    pub(crate) fn decrypt<M, S, BE: Backend>(
+        &self,
+        module: &M,
+        data: &mut [u32],
+        sk: &S,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: ModuleN + GLWEDecrypt<BE>,
+        S: GLWESecretPreparedToRef<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        assert_eq!(data.len(), self.size);
+
+        let n: usize = module.n();
+        let word_offset: usize = self.word_offset;
+        let ct_offset: usize = self.ct_offset();
+
+        let (mut pt, scratch_1) = scratch.take_glwe_plaintext(&self.bits[0][0]);
+        let (data_i64, scratch_2) = scratch_1.take_slice(n);
+
+        data.fill(0);
+        for (i, cts) in self.bits.iter().enumerate() {
+            for (j, ct) in cts.iter().enumerate() {
+                ct.decrypt(module, &mut pt, sk, scratch_2);
+                pt.decode_vec_i64(data_i64, TorusPrecision(2));
+
+                let start: usize = ((ct_offset + j) * n).max(word_offset);
+                let end: usize = ((ct_offset + j + 1) * n).min(word_offset + data.len());
+                for addr in start..end {
+                    data[addr - word_offset] |= ((data_i64[addr % n] & 1) as u32) << i;
+                }
+            }
+        }
+    }
+
+    /// Serializes the input as: [Header] | word offset | size | bit arrays.
+    pub fn write_to<W: Write, BE: Backend>(
+        &self,
+        params: &CryptographicParameters<BE>,
+        writer: &mut W,
+    ) -> Result<()> {
+        Header::new(
+            ENCRYPTED_INPUT_MAGIC,
+            ENCRYPTED_INPUT_VERSION,
+            params.fingerprint(),
+        )
+        .write_to(writer)?;
+        write_u64(writer, self.word_offset as u64)?;
+        write_u64(writer, self.size as u64)?;
+        for cts in &self.bits {
+            for ct in cts {
+                ct.write_to(writer)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Deserializes an input written by [EncryptedInput::write_to].
+    pub fn read_from<R: Read, BE: Backend>(
+        params: &CryptographicParameters<BE>,
+        reader: &mut R,
+    ) -> Result<Self> {
+        Header::read_from(reader)?.check(&Header::new(
+            ENCRYPTED_INPUT_MAGIC,
+            ENCRYPTED_INPUT_VERSION,
+            params.fingerprint(),
+        ))?;
+        let word_offset: usize = read_u64(reader)? as usize;
+        let size: usize = read_u64(reader)? as usize;
+        if size == 0 {
+            return Err(invalid_data("empty encrypted input".to_string()));
+        }
+        let mut input: Self = Self::alloc(params, word_offset, size);
+        for cts in &mut input.bits {
+            for ct in cts {
+                ct.read_from(reader)?;
+            }
+        }
+        Ok(input)
+    }
+}
@@ -43,6 +43,29 @@
 //! 35 |rem   | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = x[rs1]s % x[rs2]s
 //! 36 |remu  | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = x[rs1]u % x[rs2]u
 //!
+//! 37 |andn  | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = x[rs1] & ~x[rs2]
+//! 38 |orn   | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = x[rs1] | ~x[rs2]
+//! 39 |xnor  | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = ~(x[rs1] ^ x[rs2])
+//! 40 |clz   | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = leading zeros of x[rs1]
+//! 41 |ctz   | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = trailing zeros of x[rs1]
+//! 42 |cpop  | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = set bits of x[rs1]
+//! 43 |max   | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = max(x[rs1]s, x[rs2]s)
+//! 44 |maxu  | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = max(x[rs1]u, x[rs2]u)
+//! 45 |min   | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = min(x[rs1]s, x[rs2]s)
+//! 46 |minu  | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = min(x[rs1]u, x[rs2]u)
+//! 47 |sext.b| imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = sext(x[rs1][7:0])
+//! 48 |sext.h| imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = sext(x[rs1][15:0])
+//! 49 |zext.h| imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = x[rs1][15:0]
+//! 50 |rol   | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = x[rs1] rotl x[rs2][4:0]
+//! 51 |ror   | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = x[rs1] rotr x[rs2][4:0]
+//! 52 |rori  | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = x[rs1] rotr imm[4:0]
+//! 53 |rev8  | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = byte reverse of x[rs1]
+//! 54 |orc.b | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = per byte, 0xFF if the byte of x[rs1] is non-zero else 0
+//!
+//! 55 |custom0| imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = circuit(x[rs1], x[rs2], sext(imm[11:0]))
+//! .. |  ..  | ..
+//! 62 |custom7| imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | x[rd] = circuit(x[rs1], x[rs2], sext(imm[11:0]))
+//!
 //! # MEMORY UPDATE
 //! ID |  OP  |     4      |      4     |     4     |     4    |     4    |  5  |  5  |  5 |
 //!    |------|------------|------------|-----------|----------|----------|-----|-----|----|
@@ -64,8 +87,13 @@
 //!  2 |bltu  | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | if (x[rs1] <u  x[rs2]), pc += sext(imm[19:0])
 //! 10 |bgeu  | imm[19:16] | imm[15:12] | imm[11:8] | imm[7:4] | imm[3:0] | rs2 | rs1 | rd | if (x[rs1] >=u x[rs2]), pc += sext(imm[19:0])
 
+// Opcode literals are grouped by instruction field (funct7, rs2, rs1, funct3, rd, opcode).
+#![allow(clippy::unusual_byte_groupings)]
+
 use std::fmt::{self, Debug};
 
+use crate::custom::{CustomInstructions, CUSTOM_0, CUSTOM_1};
+
 pub(crate) mod b_type;
 pub(crate) mod i_type;
 pub(crate) mod j_type;
@@ -139,18 +167,82 @@ pub(crate) enum RV32I {
     // ────────────────────────────────────────────────
     // JALR — I-type but used for PC update
     JALR = 0b000000000000_00000_000_00000_1100111,
+
+    // ────────────────────────────────────────────────
+    // SYSTEM: funct12 | rs1 | funct3 | rd | opcode
+    ECALL = 0b000000000000_00000_000_00000_1110011,
+    EBREAK = 0b000000000001_00000_000_00000_1110011,
 }
 
 impl RV32I {
-    #[allow(dead_code)]
+    // Builds the instruction of the opcode, as in `RV32I::ADDI.new()`.
+    #[allow(dead_code, clippy::new_ret_no_self, clippy::wrong_self_convention)]
+    pub(crate) fn new(&self) -> Instruction {
+        Instruction(*self as u32)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[allow(dead_code)]
+pub(crate) enum RV32M {
+    // ────────────────────────────────────────────────
+    // R-type (M extension): funct7 | rs2 | rs1 | funct3 | rd | opcode
+    MUL = 0b0000001_00000_00000_000_00000_0110011,
+    MULH = 0b0000001_00000_00000_001_00000_0110011,
+    MULHSU = 0b0000001_00000_00000_010_00000_0110011,
+    MULHU = 0b0000001_00000_00000_011_00000_0110011,
+    DIV = 0b0000001_00000_00000_100_00000_0110011,
+    DIVU = 0b0000001_00000_00000_101_00000_0110011,
+    REM = 0b0000001_00000_00000_110_00000_0110011,
+    REMU = 0b0000001_00000_00000_111_00000_0110011,
+}
+
+impl RV32M {
+    // Builds the instruction of the opcode, as in `RV32M::MUL.new()`.
+    #[allow(dead_code, clippy::new_ret_no_self, clippy::wrong_self_convention)]
+    pub(crate) fn new(&self) -> Instruction {
+        Instruction(*self as u32)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[allow(dead_code)]
+pub(crate) enum Zbb {
+    // ────────────────────────────────────────────────
+    // R-type (Zbb extension): funct7 | rs2 | rs1 | funct3 | rd | opcode
+    ANDN = 0b0100000_00000_00000_111_00000_0110011,
+    ORN = 0b0100000_00000_00000_110_00000_0110011,
+    XNOR = 0b0100000_00000_00000_100_00000_0110011,
+    MAX = 0b0000101_00000_00000_110_00000_0110011,
+    MAXU = 0b0000101_00000_00000_111_00000_0110011,
+    MIN = 0b0000101_00000_00000_100_00000_0110011,
+    MINU = 0b0000101_00000_00000_101_00000_0110011,
+    ROL = 0b0110000_00000_00000_001_00000_0110011,
+    ROR = 0b0110000_00000_00000_101_00000_0110011,
+    ZEXTH = 0b0000100_00000_00000_100_00000_0110011,
+
+    // ────────────────────────────────────────────────
+    // I-type (Zbb extension): imm[11:0] | rs1 | funct3 | rd | opcode
+    CLZ = 0b0110000_00000_00000_001_00000_0010011,
+    CTZ = 0b0110000_00001_00000_001_00000_0010011,
+    CPOP = 0b0110000_00010_00000_001_00000_0010011,
+    SEXTB = 0b0110000_00100_00000_001_00000_0010011,
+    SEXTH = 0b0110000_00101_00000_001_00000_0010011,
+    RORI = 0b0110000_00000_00000_101_00000_0010011,
+    REV8 = 0b0110100_11000_00000_101_00000_0010011,
+    ORCB = 0b0010100_00111_00000_101_00000_0010011,
+}
+
+impl Zbb {
+    // Builds the instruction of the opcode, as in `Zbb::CLZ.new()`.
+    #[allow(dead_code, clippy::new_ret_no_self, clippy::wrong_self_convention)]
     pub(crate) fn new(&self) -> Instruction {
         Instruction(*self as u32)
     }
 }
 
 pub(crate) fn sext(x: u32, bits: u32) -> u32 {
-    (x << (u32::BITS - bits) >> (u32::BITS - bits))
-        | ((x >> bits) & 1) * (0xFFFF_FFFF & (0xFFFF_FFFF << bits))
+    (x << (u32::BITS - bits) >> (u32::BITS - bits)) | (((x >> bits) & 1) * (0xFFFF_FFFF << bits))
 }
 
 #[allow(non_camel_case_types)]
@@ -183,7 +275,7 @@ impl RAM_UPDATE {
             RAM_UPDATE::SH => match offset {
                 0 => (rs2 & 0xFFFF) | (ram & 0xFFFF_0000),
                 2 => ((rs2 & 0xFFFF) << 16) | (ram & 0x_FFFF),
-                _ => 0,
+                _ => 0, // Misaligned, trapped (see [crate::alignment])
             },
         }
     }
@@ -319,6 +411,32 @@ pub(crate) enum RD_UPDATE {
     DIVU = 34,
     REM = 35,
     REMU = 36,
+    ANDN = 37,
+    ORN = 38,
+    XNOR = 39,
+    CLZ = 40,
+    CTZ = 41,
+    CPOP = 42,
+    MAX = 43,
+    MAXU = 44,
+    MIN = 45,
+    MINU = 46,
+    SEXTB = 47,
+    SEXTH = 48,
+    ZEXTH = 49,
+    ROL = 50,
+    ROR = 51,
+    RORI = 52,
+    REV8 = 53,
+    ORCB = 54,
+    CUSTOM0 = 55,
+    CUSTOM1 = 56,
+    CUSTOM2 = 57,
+    CUSTOM3 = 58,
+    CUSTOM4 = 59,
+    CUSTOM5 = 60,
+    CUSTOM6 = 61,
+    CUSTOM7 = 62,
 }
 
 impl RD_UPDATE {
@@ -326,6 +444,31 @@ impl RD_UPDATE {
         *self as u32
     }
 
+    /// Returns true if the operation belongs to the M extension.
+    pub(crate) fn is_rv32m(&self) -> bool {
+        (RD_UPDATE::MUL.id()..=RD_UPDATE::REMU.id()).contains(&self.id())
+    }
+
+    /// Slots of the custom instructions, see [crate::custom::CustomInstructions].
+    pub(crate) const CUSTOM: [RD_UPDATE; 8] = [
+        RD_UPDATE::CUSTOM0,
+        RD_UPDATE::CUSTOM1,
+        RD_UPDATE::CUSTOM2,
+        RD_UPDATE::CUSTOM3,
+        RD_UPDATE::CUSTOM4,
+        RD_UPDATE::CUSTOM5,
+        RD_UPDATE::CUSTOM6,
+        RD_UPDATE::CUSTOM7,
+    ];
+
+    /// Returns the index of the custom instruction slot, or None if the
+    /// operation is not a custom instruction.
+    pub(crate) fn custom_slot(&self) -> Option<usize> {
+        self.id()
+            .checked_sub(RD_UPDATE::CUSTOM0.id())
+            .map(|slot| slot as usize)
+    }
+
     pub(crate) fn eval_plain(&self, imm: u32, rs1: u32, rs2: u32, pc: u32, ram: u32) -> u32 {
         match self {
             RD_UPDATE::NONE => 0,
@@ -387,38 +530,64 @@ impl RD_UPDATE {
             RD_UPDATE::MULHU => ((rs1 as i64).wrapping_mul(rs2 as i64) >> 32) as u32,
             RD_UPDATE::DIV => {
                 if rs2 == 0 {
-                    return i32::MAX as u32;
-                } else if (rs1 as i32 == i32::MIN) && (rs2 as i32 == -1) {
-                    return i32::MIN as u32;
+                    return u32::MAX;
                 }
-                (rs1 as i32 / rs2 as i32) as u32
+                (rs1 as i32).wrapping_div(rs2 as i32) as u32
             }
-            RD_UPDATE::DIVU => {
+            RD_UPDATE::DIVU => rs1.checked_div(rs2).unwrap_or(u32::MAX),
+            RD_UPDATE::REM => {
                 if rs2 == 0 {
                     return rs1;
                 }
-                rs1 / rs2
+                (rs1 as i32).wrapping_rem(rs2 as i32) as u32
             }
-            RD_UPDATE::REM => {
-                if rs1 == 0 {
-                    rs1
-                } else if rs1 as i32 == i32::MIN && rs2 as i32 == -1 {
-                    0
-                } else {
-                    (rs1 as i32 % rs2 as i32) as u32
-                }
+            RD_UPDATE::REMU => rs1.checked_rem(rs2).unwrap_or(rs1),
+            RD_UPDATE::ANDN => rs1 & !rs2,
+            RD_UPDATE::ORN => rs1 | !rs2,
+            RD_UPDATE::XNOR => !(rs1 ^ rs2),
+            RD_UPDATE::CLZ => rs1.leading_zeros(),
+            RD_UPDATE::CTZ => rs1.trailing_zeros(),
+            RD_UPDATE::CPOP => rs1.count_ones(),
+            RD_UPDATE::MAX => (rs1 as i32).max(rs2 as i32) as u32,
+            RD_UPDATE::MAXU => rs1.max(rs2),
+            RD_UPDATE::MIN => (rs1 as i32).min(rs2 as i32) as u32,
+            RD_UPDATE::MINU => rs1.min(rs2),
+            RD_UPDATE::SEXTB => sext(rs1 & 0xFF, 7),
+            RD_UPDATE::SEXTH => sext(rs1 & 0xFFFF, 15),
+            RD_UPDATE::ZEXTH => rs1 & 0xFFFF,
+            RD_UPDATE::ROL => rs1.rotate_left(rs2 & 0x1F),
+            RD_UPDATE::ROR => rs1.rotate_right(rs2 & 0x1F),
+            RD_UPDATE::RORI => rs1.rotate_right(imm & 0x1F),
+            RD_UPDATE::REV8 => rs1.swap_bytes(),
+            RD_UPDATE::ORCB => {
+                u32::from_le_bytes(
+                    rs1.to_le_bytes()
+                        .map(|byte| if byte != 0 { 0xFF } else { 0 }),
+                )
             }
-            RD_UPDATE::REMU => {
-                if rs2 == 0 {
-                    return rs1;
-                }
-                rs1 % rs2
+            RD_UPDATE::CUSTOM0
+            | RD_UPDATE::CUSTOM1
+            | RD_UPDATE::CUSTOM2
+            | RD_UPDATE::CUSTOM3
+            | RD_UPDATE::CUSTOM4
+            | RD_UPDATE::CUSTOM5
+            | RD_UPDATE::CUSTOM6
+            | RD_UPDATE::CUSTOM7 => {
+                panic!("{self:?} is evaluated by CustomInstruction::eval_plain")
             }
         }
     }
 }
 
-#[allow(dead_code)]
+/// Number of bits of the op id of the register update operations `ops`.
+///
+/// Op lists are not contiguous (e.g. RV32I with Zbb), so the size is given by
+/// the largest id rather than by the number of operations.
+pub(crate) fn rd_update_op_bit_size(ops: &[RD_UPDATE]) -> usize {
+    let max_id: u32 = ops.iter().map(RD_UPDATE::id).max().unwrap_or(0);
+    (u32::BITS - max_id.leading_zeros()) as usize
+}
+
 pub(crate) static RD_UPDATE_RV32M_OP_LIST: &[RD_UPDATE] = &[
     RD_UPDATE::NONE,
     RD_UPDATE::LUI,
@@ -491,10 +660,120 @@ pub(crate) static RD_UPDATE_RV32I_OP_LIST: &[RD_UPDATE] = &[
     RD_UPDATE::JALR,
 ];
 
+pub(crate) static RD_UPDATE_RV32M_ZBB_OP_LIST: &[RD_UPDATE] = &[
+    RD_UPDATE::NONE,
+    RD_UPDATE::LUI,
+    RD_UPDATE::AUIPC,
+    RD_UPDATE::ADDI,
+    RD_UPDATE::SLTI,
+    RD_UPDATE::SLTIU,
+    RD_UPDATE::XORI,
+    RD_UPDATE::ORI,
+    RD_UPDATE::ANDI,
+    RD_UPDATE::SLLI,
+    RD_UPDATE::SRLI,
+    RD_UPDATE::SRAI,
+    RD_UPDATE::ADD,
+    RD_UPDATE::SUB,
+    RD_UPDATE::SLL,
+    RD_UPDATE::SLT,
+    RD_UPDATE::SLTU,
+    RD_UPDATE::XOR,
+    RD_UPDATE::SRL,
+    RD_UPDATE::SRA,
+    RD_UPDATE::OR,
+    RD_UPDATE::AND,
+    RD_UPDATE::LB,
+    RD_UPDATE::LBU,
+    RD_UPDATE::LH,
+    RD_UPDATE::LHU,
+    RD_UPDATE::LW,
+    RD_UPDATE::JAL,
+    RD_UPDATE::JALR,
+    RD_UPDATE::MUL,
+    RD_UPDATE::MULH,
+    RD_UPDATE::MULHSU,
+    RD_UPDATE::MULHU,
+    RD_UPDATE::DIV,
+    RD_UPDATE::DIVU,
+    RD_UPDATE::REM,
+    RD_UPDATE::REMU,
+    RD_UPDATE::ANDN,
+    RD_UPDATE::ORN,
+    RD_UPDATE::XNOR,
+    RD_UPDATE::CLZ,
+    RD_UPDATE::CTZ,
+    RD_UPDATE::CPOP,
+    RD_UPDATE::MAX,
+    RD_UPDATE::MAXU,
+    RD_UPDATE::MIN,
+    RD_UPDATE::MINU,
+    RD_UPDATE::SEXTB,
+    RD_UPDATE::SEXTH,
+    RD_UPDATE::ZEXTH,
+    RD_UPDATE::ROL,
+    RD_UPDATE::ROR,
+    RD_UPDATE::RORI,
+    RD_UPDATE::REV8,
+    RD_UPDATE::ORCB,
+];
+
+pub(crate) static RD_UPDATE_RV32I_ZBB_OP_LIST: &[RD_UPDATE] = &[
+    RD_UPDATE::NONE,
+    RD_UPDATE::LUI,
+    RD_UPDATE::AUIPC,
+    RD_UPDATE::ADDI,
+    RD_UPDATE::SLTI,
+    RD_UPDATE::SLTIU,
+    RD_UPDATE::XORI,
+    RD_UPDATE::ORI,
+    RD_UPDATE::ANDI,
+    RD_UPDATE::SLLI,
+    RD_UPDATE::SRLI,
+    RD_UPDATE::SRAI,
+    RD_UPDATE::ADD,
+    RD_UPDATE::SUB,
+    RD_UPDATE::SLL,
+    RD_UPDATE::SLT,
+    RD_UPDATE::SLTU,
+    RD_UPDATE::XOR,
+    RD_UPDATE::SRL,
+    RD_UPDATE::SRA,
+    RD_UPDATE::OR,
+    RD_UPDATE::AND,
+    RD_UPDATE::LB,
+    RD_UPDATE::LBU,
+    RD_UPDATE::LH,
+    RD_UPDATE::LHU,
+    RD_UPDATE::LW,
+    RD_UPDATE::JAL,
+    RD_UPDATE::JALR,
+    RD_UPDATE::ANDN,
+    RD_UPDATE::ORN,
+    RD_UPDATE::XNOR,
+    RD_UPDATE::CLZ,
+    RD_UPDATE::CTZ,
+    RD_UPDATE::CPOP,
+    RD_UPDATE::MAX,
+    RD_UPDATE::MAXU,
+    RD_UPDATE::MIN,
+    RD_UPDATE::MINU,
+    RD_UPDATE::SEXTB,
+    RD_UPDATE::SEXTH,
+    RD_UPDATE::ZEXTH,
+    RD_UPDATE::ROL,
+    RD_UPDATE::ROR,
+    RD_UPDATE::RORI,
+    RD_UPDATE::REV8,
+    RD_UPDATE::ORCB,
+];
+
 pub struct InstructionsParser {
     pub imm: Vec<i64>,
     pub instructions: Vec<i64>,
     pub instructions_raw: Vec<Instruction>,
+    pub(crate) custom_instructions: CustomInstructions,
+    pub(crate) rv32e: bool,
 }
 
 impl InstructionsParser {
@@ -503,12 +782,44 @@ impl InstructionsParser {
             imm: Vec::new(),
             instructions: Vec::new(),
             instructions_raw: Vec::new(),
+            custom_instructions: CustomInstructions::new(),
+            rv32e: false,
         }
     }
 
+    /// Decodes the custom-0/custom-1 instructions with `custom_instructions`.
+    pub fn with_custom_instructions(mut self, custom_instructions: &CustomInstructions) -> Self {
+        self.custom_instructions = custom_instructions.clone();
+        self
+    }
+
+    /// Restricts the instructions to the [RV32E_REGISTERS] registers of RV32E,
+    /// where ECALL reads the syscall number from t0.
+    ///
+    /// The ROM must then be executed by an interpreter with the same register
+    /// file, see [crate::Interpreter::set_rv32e].
+    pub fn with_rv32e(mut self) -> Self {
+        self.rv32e = true;
+        self
+    }
+
     pub fn add(&mut self, instruction: Instruction) {
-        let (rs2, rs1, rd) = instruction.get_registers();
-        let (rd_w, mem_w, pc_w) = instruction.get_opid();
+        let (rs2, rs1, rd) = match instruction.get_registers() {
+            (rs2, _, rd) if self.rv32e && instruction.get() == RV32I::ECALL as u32 => {
+                (rs2, SYSCALL_REGISTER_RV32E, rd)
+            }
+            registers => registers,
+        };
+        if self.rv32e {
+            assert!(
+                [rs2, rs1, rd]
+                    .iter()
+                    .all(|&reg| (reg as usize) < RV32E_REGISTERS),
+                "instruction {instruction} uses a register beyond x{}, not available in RV32E",
+                RV32E_REGISTERS - 1
+            );
+        }
+        let (rd_w, mem_w, pc_w) = self.custom_instructions.get_opid(&instruction);
         self.imm.push(instruction.get_imm() as i64);
         self.instructions.push(
             (rs2 as i64) << 26
@@ -530,6 +841,16 @@ impl InstructionsParser {
         self.instructions_raw[idx]
     }
 
+    /// Returns the (rs2, rs1, rd) addresses of the instruction at `idx`.
+    pub(crate) fn get_registers(&self, idx: usize) -> (u32, u32, u32) {
+        let data: i64 = self.instructions[idx];
+        (
+            ((data >> 26) & 0x1F) as u32,
+            ((data >> 21) & 0x1F) as u32,
+            ((data >> 16) & 0x1F) as u32,
+        )
+    }
+
     pub(crate) fn get(&self, idx: usize) -> (i64, i64, i64, i64, i64, i64, i64) {
         assert!(self.imm.len() > idx);
         let data = self.instructions[idx];
@@ -544,6 +865,8 @@ impl InstructionsParser {
         )
     }
 
+    // Takes every field returned by `get`, so that tests spell out the expected encoding.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn assert_instruction(
         &self,
         idx: usize,
@@ -614,6 +937,15 @@ impl fmt::Debug for Instruction {
     }
 }
 
+/// Register holding the syscall number of an ECALL (a7).
+pub(crate) const SYSCALL_REGISTER: u32 = 17;
+
+/// Register holding the syscall number of an ECALL in RV32E, which has no a7 (t0).
+pub(crate) const SYSCALL_REGISTER_RV32E: u32 = 5;
+
+/// Number of registers of the RV32E base integer instruction set.
+pub const RV32E_REGISTERS: usize = 16;
+
 pub(crate) const RS1MASK: u32 = 0x000F_8000;
 pub(crate) const RS2MASK: u32 = 0x01F0_0000;
 pub(crate) const FUNCT3MASK: u32 = 0x0000_7000;
@@ -639,6 +971,9 @@ pub(crate) enum Type {
     B,
     U,
     J,
+    /// custom-0/custom-1: funct7 | rs2 | rs1 | funct3 | rd | opcode, with
+    /// imm[11:0] overlapping funct7 and rs2.
+    CUSTOM,
 }
 
 impl Instruction {
@@ -658,6 +993,7 @@ impl Instruction {
             0b1101111 => Type::J,
             0b1100011 => Type::B,
             0b1110011 => Type::NONE,
+            CUSTOM_0 | CUSTOM_1 => Type::CUSTOM,
             _ => panic!("unrecognized opcode: {:07b}", opcode),
         }
     }
@@ -731,7 +1067,7 @@ impl Instruction {
         #[cfg(debug_assertions)]
         {
             match self.get_type() {
-                Type::R | Type::I | Type::S | Type::B => {}
+                Type::R | Type::I | Type::S | Type::B | Type::CUSTOM => {}
                 _ => panic!(
                     "cannot get rs1 on Type::(U, J) instructions: {:032b}",
                     self.0
@@ -746,7 +1082,7 @@ impl Instruction {
         #[cfg(debug_assertions)]
         {
             match self.get_type() {
-                Type::R | Type::I | Type::S | Type::B => {}
+                Type::R | Type::I | Type::S | Type::B | Type::CUSTOM => {}
                 _ => panic!(
                     "cannot set rs1 on Type::(U, J) instructions: {:032b}",
                     self.0
@@ -762,7 +1098,7 @@ impl Instruction {
         #[cfg(debug_assertions)]
         {
             match self.get_type() {
-                Type::R | Type::S | Type::B => {}
+                Type::R | Type::S | Type::B | Type::CUSTOM => {}
                 _ => panic!(
                     "cannot get rs2 on Type::(I, U, J) instructions: {:032b}",
                     self.0
@@ -777,7 +1113,7 @@ impl Instruction {
         #[cfg(debug_assertions)]
         {
             match self.get_type() {
-                Type::R | Type::S | Type::B => {}
+                Type::R | Type::S | Type::B | Type::CUSTOM => {}
                 _ => panic!(
                     "cannot set rs2 on Type::(I, U, J) instructions: {:032b}",
                     self.0
@@ -793,7 +1129,7 @@ impl Instruction {
         #[cfg(debug_assertions)]
         {
             match self.get_type() {
-                Type::R | Type::I | Type::U | Type::J => {}
+                Type::R | Type::I | Type::U | Type::J | Type::CUSTOM => {}
                 _ => panic!(
                     "cannot get rd on Type::(S, B) instructions: {:032b}",
                     self.0
@@ -808,7 +1144,7 @@ impl Instruction {
         #[cfg(debug_assertions)]
         {
             match self.get_type() {
-                Type::R | Type::I | Type::U | Type::J => {}
+                Type::R | Type::I | Type::U | Type::J | Type::CUSTOM => {}
                 _ => panic!(
                     "cannot set rd on Type::(S, B) instructions: {:032b}",
                     self.0
@@ -881,6 +1217,7 @@ impl Instruction {
             Type::B => b_type::set_immediate(self, imm),
             Type::U => u_type::set_immediate(self, imm),
             Type::J => j_type::set_immediate(self, imm),
+            Type::CUSTOM => i_type::set_immediate(self, imm),
             Type::NONE => {
                 panic!("cannot encode immediate on type NONE instruction")
             }
@@ -899,6 +1236,7 @@ impl Instruction {
             Type::B => b_type::get_immediate(&self.0),
             Type::U => u_type::get_immediate(&self.0),
             Type::J => j_type::get_immediate(&self.0),
+            Type::CUSTOM => i_type::get_immediate(&self.0),
             Type::NONE => 0,
         }
     }
@@ -906,10 +1244,12 @@ impl Instruction {
     #[inline(always)]
     pub(crate) fn get_registers(&self) -> (u32, u32, u32) {
         match self.get_type() {
-            Type::R => (self.get_rs2(), self.get_rs1(), self.get_rd()),
+            Type::R | Type::CUSTOM => (self.get_rs2(), self.get_rs1(), self.get_rd()),
             Type::I => (0, self.get_rs1(), self.get_rd()),
             Type::S | Type::B => (self.get_rs2(), self.get_rs1(), 0),
             Type::U | Type::J => (0, 0, self.get_rd()),
+            // ECALL reads the syscall number from a7, see [Instruction::get_opid].
+            Type::NONE if self.0 == RV32I::ECALL as u32 => (0, SYSCALL_REGISTER, 0),
             Type::NONE => (0, 0, 0),
         }
     }
@@ -937,6 +1277,18 @@ impl Instruction {
                 (0b0000001, 0b101) => (RD_UPDATE::DIVU, RAM_UPDATE::NONE, PC_UPDATE::NONE),
                 (0b0000001, 0b110) => (RD_UPDATE::REM, RAM_UPDATE::NONE, PC_UPDATE::NONE),
                 (0b0000001, 0b111) => (RD_UPDATE::REMU, RAM_UPDATE::NONE, PC_UPDATE::NONE),
+                (0b0100000, 0b111) => (RD_UPDATE::ANDN, RAM_UPDATE::NONE, PC_UPDATE::NONE),
+                (0b0100000, 0b110) => (RD_UPDATE::ORN, RAM_UPDATE::NONE, PC_UPDATE::NONE),
+                (0b0100000, 0b100) => (RD_UPDATE::XNOR, RAM_UPDATE::NONE, PC_UPDATE::NONE),
+                (0b0000101, 0b110) => (RD_UPDATE::MAX, RAM_UPDATE::NONE, PC_UPDATE::NONE),
+                (0b0000101, 0b111) => (RD_UPDATE::MAXU, RAM_UPDATE::NONE, PC_UPDATE::NONE),
+                (0b0000101, 0b100) => (RD_UPDATE::MIN, RAM_UPDATE::NONE, PC_UPDATE::NONE),
+                (0b0000101, 0b101) => (RD_UPDATE::MINU, RAM_UPDATE::NONE, PC_UPDATE::NONE),
+                (0b0110000, 0b001) => (RD_UPDATE::ROL, RAM_UPDATE::NONE, PC_UPDATE::NONE),
+                (0b0110000, 0b101) => (RD_UPDATE::ROR, RAM_UPDATE::NONE, PC_UPDATE::NONE),
+                (0b0000100, 0b100) if self.get_rs2() == 0 => {
+                    (RD_UPDATE::ZEXTH, RAM_UPDATE::NONE, PC_UPDATE::NONE)
+                }
                 _ => panic!(
                     "invalid funct3 {:03b} or funct7 {:07b}: {:032b}",
                     self.get_funct3(),
@@ -954,11 +1306,36 @@ impl Instruction {
                         0b100 => (RD_UPDATE::XORI, RAM_UPDATE::NONE, PC_UPDATE::NONE),
                         0b110 => (RD_UPDATE::ORI, RAM_UPDATE::NONE, PC_UPDATE::NONE),
                         0b111 => (RD_UPDATE::ANDI, RAM_UPDATE::NONE, PC_UPDATE::NONE),
-                        0b001 => (RD_UPDATE::SLLI, RAM_UPDATE::NONE, PC_UPDATE::NONE),
-                        0b101 => match self.get_funct7() {
-                            0b0000000 => (RD_UPDATE::SRLI, RAM_UPDATE::NONE, PC_UPDATE::NONE),
-                            0b0100000 => (RD_UPDATE::SRAI, RAM_UPDATE::NONE, PC_UPDATE::NONE),
-                            _ => panic!("invalid funct7: {:032b}", self.0),
+                        0b001 => match (self.get_funct7(), self.get_shamt()) {
+                            (0b0000000, _) => (RD_UPDATE::SLLI, RAM_UPDATE::NONE, PC_UPDATE::NONE),
+                            (0b0110000, 0b00000) => {
+                                (RD_UPDATE::CLZ, RAM_UPDATE::NONE, PC_UPDATE::NONE)
+                            }
+                            (0b0110000, 0b00001) => {
+                                (RD_UPDATE::CTZ, RAM_UPDATE::NONE, PC_UPDATE::NONE)
+                            }
+                            (0b0110000, 0b00010) => {
+                                (RD_UPDATE::CPOP, RAM_UPDATE::NONE, PC_UPDATE::NONE)
+                            }
+                            (0b0110000, 0b00100) => {
+                                (RD_UPDATE::SEXTB, RAM_UPDATE::NONE, PC_UPDATE::NONE)
+                            }
+                            (0b0110000, 0b00101) => {
+                                (RD_UPDATE::SEXTH, RAM_UPDATE::NONE, PC_UPDATE::NONE)
+                            }
+                            _ => panic!("invalid funct7 or shamt: {:032b}", self.0),
+                        },
+                        0b101 => match (self.get_funct7(), self.get_shamt()) {
+                            (0b0000000, _) => (RD_UPDATE::SRLI, RAM_UPDATE::NONE, PC_UPDATE::NONE),
+                            (0b0100000, _) => (RD_UPDATE::SRAI, RAM_UPDATE::NONE, PC_UPDATE::NONE),
+                            (0b0110000, _) => (RD_UPDATE::RORI, RAM_UPDATE::NONE, PC_UPDATE::NONE),
+                            (0b0110100, 0b11000) => {
+                                (RD_UPDATE::REV8, RAM_UPDATE::NONE, PC_UPDATE::NONE)
+                            }
+                            (0b0010100, 0b00111) => {
+                                (RD_UPDATE::ORCB, RAM_UPDATE::NONE, PC_UPDATE::NONE)
+                            }
+                            _ => panic!("invalid funct7 or shamt: {:032b}", self.0),
                         },
                         _ => panic!("invalid funct3: {:032b}", self.0),
                     },
@@ -995,7 +1372,22 @@ impl Instruction {
                 _ => panic!("invalid instruction: {:032b}", self.0),
             },
             Type::J => (RD_UPDATE::JAL, RAM_UPDATE::NONE, PC_UPDATE::JAL),
-            Type::NONE => (RD_UPDATE::NONE, RAM_UPDATE::NONE, PC_UPDATE::NONE),
+            // ECALL is evaluated as BLT a7, x0, 0: the PC (and thus the whole
+            // machine state) is frozen if the syscall number is negative, and
+            // it is a no-op otherwise. EBREAK unconditionally freezes the PC.
+            Type::NONE => match self.0 {
+                inst if inst == RV32I::ECALL as u32 => {
+                    (RD_UPDATE::NONE, RAM_UPDATE::NONE, PC_UPDATE::BLT)
+                }
+                inst if inst == RV32I::EBREAK as u32 => {
+                    (RD_UPDATE::NONE, RAM_UPDATE::NONE, PC_UPDATE::JAL)
+                }
+                _ => (RD_UPDATE::NONE, RAM_UPDATE::NONE, PC_UPDATE::NONE),
+            },
+            Type::CUSTOM => panic!(
+                "custom instruction must be decoded with CustomInstructions::get_opid: {:032b}",
+                self.0
+            ),
         }
     }
 }
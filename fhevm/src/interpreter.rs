@@ -1,43 +1,107 @@
-use std::{collections::HashMap, time::Instant};
+use std::{
+    array,
+    collections::HashMap,
+    io::{Read, Result, Write},
+    ops::Range,
+    thread,
+    time::{Duration, Instant},
+};
 
 use crate::{
-    debug::InterpreterDebug, measure_duration, memory::Memory, parameters::CryptographicParameters,
-    prepare::PrepareMultiple, ram_offset::ram_offset, ram_update::Store, rd_update::Evaluate,
-    update_pc, Measurement, Measurements, RAM_UPDATE_OP_LIST, RD_UPDATE, RD_UPDATE_RV32I_OP_LIST,
+    alignment::{eval_align_loaded, eval_misaligned, misaligned_circuit},
+    bdd::DynamicCircuit,
+    custom::{eval_custom, CustomInstructions},
+    debug::InterpreterDebug,
+    halt::{halt_circuit, update_halt_status, HaltStatus},
+    input::EncryptedInput,
+    keys::VMPublicKeyPrepared,
+    measure_duration,
+    memory::{Memory, NoiseModel, RefreshSchedule},
+    parameters::CryptographicParameters,
+    pipeline::{CycleHook, Phase},
+    prepare::PrepareMultiple,
+    ram_offset::ram_offset,
+    ram_update::Store,
+    rd_update::Evaluate,
+    rd_update_op_bit_size,
+    rodata::rodata_bit,
+    rv32m::{eval_rv32m, RV32MResults},
+    scheduler::{split_scratch, split_threads, UPDATES, UPDATE_COSTS, UPDATE_PHASES},
+    serialization::{invalid_data, read_u32, read_u64, write_u32, write_u64, Header},
+    update_pc, Measurement, Measurements, OpSet, RAM_UPDATE, RAM_UPDATE_OP_LIST, RD_UPDATE,
+    RD_UPDATE_RV32I_OP_LIST, RD_UPDATE_RV32I_ZBB_OP_LIST, RD_UPDATE_RV32M_OP_LIST,
+    RD_UPDATE_RV32M_ZBB_OP_LIST, RV32E_REGISTERS,
 };
 
 use poulpy_hal::{
     api::{ModuleLogN, ModuleN},
-    layouts::{Backend, DataRef, Module, Scratch},
+    layouts::{Backend, DataRef, Module, ReaderFrom, Scratch, WriterTo},
     source::Source,
 };
 
 use poulpy_core::{
     layouts::{
-        GGLWEInfos, GGLWEPreparedToRef, GGSWLayout, GGSWPreparedFactory, GLWEAutomorphismKeyHelper,
-        GLWEInfos, GLWELayout, GLWESecretPrepared, GLWESecretPreparedToRef, GetGaloisElement,
+        GGLWEInfos, GGLWEPreparedToRef, GGSWLayout, GGSWPrepared, GGSWPreparedFactory,
+        GLWEAutomorphismKeyHelper, GLWEDecompress, GLWEInfos, GLWELayout, GLWESecretPrepared,
+        GLWESecretPreparedToRef, GLWEToMut, GLWEToRef, GetGaloisElement, GLWE,
     },
-    GGSWEncryptSk, GLWEAdd, GLWECopy, GLWEDecrypt, GLWEEncryptSk, GLWEExternalProduct, GLWENoise,
-    GLWENormalize, GLWEPackerOps, GLWEPacking, GLWERotate, GLWESub, GLWETrace, GetDistribution,
-    ScratchTakeCore,
+    GGSWEncryptSk, GLWEAdd, GLWECompressedEncryptSk, GLWECopy, GLWEDecrypt, GLWEEncryptPk,
+    GLWEEncryptSk, GLWEExternalProduct, GLWENoise, GLWENormalize, GLWEPackerOps, GLWEPacking,
+    GLWERotate, GLWESub, GLWETrace, GetDistribution, ScratchTakeCore,
 };
 use poulpy_schemes::bin_fhe::{
     bdd_arithmetic::{
         BDDKeyHelper, BDDKeyInfos, Cmux, ExecuteBDDCircuit, ExecuteBDDCircuit1WTo1W,
         ExecuteBDDCircuit2WTo1W, FheUint, FheUintPrepare, FheUintPrepared,
         FheUintPreparedEncryptSk, FheUintPreparedFactory, GGSWBlindRotation, GLWEBlinSelection,
-        GLWEBlindRetrieval, Identity,
+        GLWEBlindRetrieval, GetGGSWBit, Identity,
     },
     blind_rotation::BlindRotationAlgo,
 };
 
 use crate::instructions::InstructionsParser;
 
+/// Instruction set executed by the [Interpreter].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InstructionSet {
+    /// Base integer instruction set with the multiplication and division extension.
     RV32M,
+    /// Base integer instruction set.
     RV32I,
+    /// [InstructionSet::RV32I] with the basic bit-manipulation extension.
+    RV32IZbb,
+    /// [InstructionSet::RV32M] with the basic bit-manipulation extension.
+    RV32MZbb,
+}
+
+impl InstructionSet {
+    /// Returns true if the instruction set includes the M extension.
+    pub fn has_m(&self) -> bool {
+        matches!(self, InstructionSet::RV32M | InstructionSet::RV32MZbb)
+    }
+
+    /// Returns true if the instruction set includes the Zbb extension.
+    pub fn has_zbb(&self) -> bool {
+        matches!(self, InstructionSet::RV32IZbb | InstructionSet::RV32MZbb)
+    }
+
+    /// Returns the list of register update operations of the instruction set.
+    pub(crate) fn rd_update_op_list(&self) -> &'static [RD_UPDATE] {
+        match self {
+            InstructionSet::RV32M => RD_UPDATE_RV32M_OP_LIST,
+            InstructionSet::RV32I => RD_UPDATE_RV32I_OP_LIST,
+            InstructionSet::RV32IZbb => RD_UPDATE_RV32I_ZBB_OP_LIST,
+            InstructionSet::RV32MZbb => RD_UPDATE_RV32M_ZBB_OP_LIST,
+        }
+    }
 }
 
+/// Magic bytes of a serialized [Interpreter] checkpoint.
+pub const CHECKPOINT_MAGIC: [u8; 4] = *b"PZCK";
+
+/// Version of the serialized [Interpreter] checkpoint layout.
+pub const CHECKPOINT_VERSION: u32 = 4;
+
 pub struct Interpreter<BE: Backend> {
     pub(crate) cycle: u32,
     pub(crate) vm_debug: Option<InterpreterDebug>,
@@ -45,8 +109,17 @@ pub struct Interpreter<BE: Backend> {
     pub(crate) verbose_timings: bool,
     pub(crate) threads: usize,
     pub(crate) measurements: Measurements,
+    // Measurement and start time of the cycle being run (see [crate::pipeline])
+    pub(crate) cycle_in_progress: Option<(Measurement, Instant)>,
+    // Whether the updates of a cycle run concurrently (see [crate::scheduler])
+    pub(crate) concurrent_updates: bool,
+    // Threads the register, RAM and PC updates ran with at the last cycle
+    pub(crate) update_threads: [usize; UPDATES],
 
     pub(crate) instruction_set: InstructionSet,
+    pub(crate) custom_instructions: CustomInstructions,
+    // Operations evaluated each cycle, all of them if None (see [crate::op_set])
+    pub(crate) op_set: Option<OpSet>,
 
     // ROM
     pub(crate) rom_bits_size: usize,
@@ -69,13 +142,38 @@ pub struct Interpreter<BE: Backend> {
     pub(crate) ram: Memory,
     pub(crate) ram_addr_fhe_uint: FheUint<Vec<u8>, u32>,
     pub(crate) ram_val_fhe_uint: FheUint<Vec<u8>, u32>,
+    // Value stored by the RAM update, apart from the loaded one read by the
+    // register update
+    pub(crate) ram_store_fhe_uint: FheUint<Vec<u8>, u32>,
     pub(crate) ram_addr_fhe_uint_prepared: FheUintPrepared<Vec<u8>, u32, BE>,
     pub(crate) ram_val_fhe_uint_prepared: FheUintPrepared<Vec<u8>, u32, BE>,
+    // Noise model driving the refresh of the RAM words, None if disabled
+    pub(crate) ram_noise_model: Option<NoiseModel>,
+
+    // Read-only data bank (see [crate::rodata])
+    pub(crate) rodata: Option<Memory>,
+    pub(crate) rodata_val_fhe_uint: FheUint<Vec<u8>, u32>,
 
     // PC
     pub(crate) pc_fhe_uint: FheUint<Vec<u8>, u32>,
     pub(crate) pc_fhe_uint_prepared: FheUintPrepared<Vec<u8>, u32, BE>,
 
+    // Halt status
+    pub(crate) halt_circuit: DynamicCircuit,
+    pub(crate) pc_prev_fhe_uint: FheUint<Vec<u8>, u32>,
+    pub(crate) pc_prev_fhe_uint_prepared: FheUintPrepared<Vec<u8>, u32, BE>,
+    pub(crate) status_fhe_uint: FheUint<Vec<u8>, u32>,
+    pub(crate) status_fhe_uint_prepared: FheUintPrepared<Vec<u8>, u32, BE>,
+    // Cycle at which the status was last updated.
+    pub(crate) status_cycle: u32,
+    // Set when the prepared status and previous PC must be prepared again
+    // from their GLWE, e.g. after resuming from a checkpoint.
+    pub(crate) status_stale: bool,
+
+    // Misaligned RAM access flag of the current cycle
+    pub(crate) misaligned_fhe_uint: FheUint<Vec<u8>, u32>,
+    pub(crate) misaligned_fhe_uint_prepared: FheUintPrepared<Vec<u8>, u32, BE>,
+
     // RS1
     pub(crate) rs1_addr_fhe_uint: FheUint<Vec<u8>, u32>,
     pub(crate) rs1_addr_fhe_uint_prepared: FheUintPrepared<Vec<u8>, u32, BE>,
@@ -108,6 +206,144 @@ pub struct Interpreter<BE: Backend> {
 }
 
 impl<BE: Backend> Interpreter<BE> {
+    /// Sets the instruction set executed by the interpreter.
+    pub fn set_instruction_set(&mut self, instruction_set: InstructionSet) {
+        self.instruction_set = instruction_set
+    }
+
+    /// Returns the instruction set executed by the interpreter.
+    pub fn instruction_set(&self) -> InstructionSet {
+        self.instruction_set
+    }
+
+    /// Sets the custom instructions evaluated by the interpreter, which must be
+    /// the ones the ROM was decoded with (see
+    /// [InstructionsParser::with_custom_instructions]).
+    pub fn set_custom_instructions(&mut self, custom_instructions: &CustomInstructions) {
+        self.custom_instructions = custom_instructions.clone()
+    }
+
+    /// Returns the custom instructions evaluated by the interpreter.
+    pub fn custom_instructions(&self) -> &CustomInstructions {
+        &self.custom_instructions
+    }
+
+    /// Restricts the register and RAM update operations evaluated each cycle
+    /// to `op_set`, or evaluates all of them if None (the default).
+    ///
+    /// The ROM must only use operations of the set, see
+    /// [OpSet::from_instructions], otherwise the instructions outside of it
+    /// write 0 to rd or the RAM. Running with an op set reveals which
+    /// operations the program uses, see [crate::op_set].
+    pub fn set_op_set(&mut self, op_set: Option<OpSet>) {
+        self.op_set = op_set
+    }
+
+    /// Returns the op set the evaluated operations are restricted to, if any.
+    pub fn op_set(&self) -> Option<&OpSet> {
+        self.op_set.as_ref()
+    }
+
+    /// Restricts the register file to the [RV32E_REGISTERS] registers of RV32E,
+    /// addressed with 4 bits instead of 5, which makes the register reads and
+    /// writes cheaper. The ROM must be decoded accordingly, see
+    /// [InstructionsParser::with_rv32e].
+    ///
+    /// Discards the content of the registers, so it must be called before
+    /// [Interpreter::init_registers].
+    pub fn set_rv32e(&mut self, rv32e: bool) {
+        let size: usize = if rv32e { RV32E_REGISTERS } else { 32 };
+        if self.registers.size() != size {
+            self.registers.resize(size);
+        }
+        self.reg_bit_size = (usize::BITS - (size - 1).leading_zeros()) as usize;
+    }
+
+    /// Returns true if the register file is restricted to the registers of RV32E.
+    pub fn is_rv32e(&self) -> bool {
+        self.registers.size() == RV32E_REGISTERS
+    }
+
+    /// Allocates a read-only data bank of `size` words, mapped after the RAM
+    /// (see [crate::rodata]), or removes it if `size` is 0.
+    ///
+    /// Discards the content of the bank, so it must be called before
+    /// [Interpreter::rodata_encrypt_sk].
+    pub fn set_rodata_size(&mut self, size: usize) {
+        self.rodata = (size > 0).then(|| Memory::alloc(&self.imm_rom.glwe_layout(), 32, size));
+        if let Some(vm_debug) = &mut self.vm_debug {
+            vm_debug.set_rodata_size(size);
+        }
+    }
+
+    /// Returns the number of words of the read-only data bank, 0 if there is none.
+    pub fn rodata_size(&self) -> usize {
+        self.rodata.as_ref().map_or(0, Memory::size)
+    }
+
+    /// Sets the noise model of the RAM, from which the interpreter derives the
+    /// [RefreshSchedule] bootstrapping its words before they become too noisy
+    /// to decrypt, or disables the refresh if None.
+    ///
+    /// Disabled by default. Programs running for more cycles than
+    /// [NoiseModel::passes] need it, e.g. with [NoiseModel::default], which
+    /// matches the default parameters, to keep the words they do not write
+    /// decryptable.
+    pub fn set_ram_noise_model(&mut self, model: Option<NoiseModel>) {
+        self.ram_noise_model = model;
+    }
+
+    /// Returns the schedule of the refresh of the RAM, None if disabled.
+    pub fn ram_refresh_schedule(&self) -> Option<RefreshSchedule> {
+        self.ram_noise_model
+            .as_ref()
+            .map(|model| self.ram.refresh_schedule(model))
+    }
+
+    // Bit of the RAM address selecting the read-only data bank, if any.
+    fn rodata_bit(&self) -> Option<usize> {
+        self.rodata
+            .as_ref()
+            .map(|rodata| rodata_bit(self.ram_size, rodata.size()))
+    }
+
+    // Number of bits of the RAM address read by the RAM accesses.
+    fn ram_addr_bit_size(&self) -> usize {
+        self.rodata_bit()
+            .map_or(self.ram_bit_size + 2, |bit| bit + 1)
+    }
+
+    // Register update operations evaluated each cycle: the ones of the
+    // instruction set followed by the custom ones.
+    fn rd_update_ops(&self) -> Vec<RD_UPDATE> {
+        let mut ops: Vec<RD_UPDATE> = self.instruction_set.rd_update_op_list().to_vec();
+        ops.extend_from_slice(self.custom_instructions.ops());
+        ops
+    }
+
+    // Register update operations evaluated each cycle: the ones of
+    // [Interpreter::rd_update_ops] in the op set, if any. The op ids keep
+    // the bit size of the full list.
+    fn rd_update_evaluated_ops(&self) -> Vec<RD_UPDATE> {
+        let mut ops: Vec<RD_UPDATE> = self.rd_update_ops();
+        if let Some(op_set) = &self.op_set {
+            ops.retain(|op| op_set.contains_rd_update(op));
+        }
+        ops
+    }
+
+    // RAM update operations evaluated each cycle, restricted to the op set.
+    fn ram_update_evaluated_ops(&self) -> Vec<RAM_UPDATE> {
+        RAM_UPDATE_OP_LIST
+            .iter()
+            .filter(|op| {
+                self.op_set
+                    .is_none_or(|op_set| op_set.contains_ram_update(op))
+            })
+            .copied()
+            .collect()
+    }
+
     pub fn new(params: &CryptographicParameters<BE>, rom_size: usize, ram_size: usize) -> Self
     where
         Module<BE>: FheUintPreparedFactory<u32, BE>,
@@ -159,12 +395,19 @@ impl<BE: Backend> Interpreter<BE> {
             None
         };
 
+        let rom_bits_size: usize = (usize::BITS - (rom_size - 1).leading_zeros()) as usize;
+
         Self {
             vm_debug,
             verbose_timings: false,
             threads: 1,
             instruction_set: InstructionSet::RV32I,
+            custom_instructions: CustomInstructions::new(),
+            op_set: None,
             measurements: Measurements::new(),
+            cycle_in_progress: None,
+            concurrent_updates: false,
+            update_threads: [1; UPDATES],
             imm_rom,
             rs1_rom,
             rs2_rom,
@@ -178,7 +421,7 @@ impl<BE: Backend> Interpreter<BE> {
             rom_size,
             cycle: 0,
             ram_bit_size: (usize::BITS - (ram_size - 1).leading_zeros()) as usize,
-            rom_bits_size: (usize::BITS - (rom_size - 1).leading_zeros()) as usize,
+            rom_bits_size,
             reg_bit_size: 5,
             ram_addr_fhe_uint_prepared: FheUintPrepared::alloc_from_infos(
                 module,
@@ -186,6 +429,7 @@ impl<BE: Backend> Interpreter<BE> {
             ),
             rd_val_fhe_uint: FheUint::alloc_from_infos(fhe_uint_infos),
             ram_val_fhe_uint: FheUint::alloc_from_infos(fhe_uint_infos),
+            ram_store_fhe_uint: FheUint::alloc_from_infos(fhe_uint_infos),
             pcu_val_fhe_uint_prepared: FheUintPrepared::alloc_from_infos(
                 module,
                 fhe_uint_prepared_infos,
@@ -237,10 +481,31 @@ impl<BE: Backend> Interpreter<BE> {
                 module,
                 fhe_uint_prepared_infos,
             ),
+            halt_circuit: halt_circuit(rom_bits_size + 2),
+            pc_prev_fhe_uint: FheUint::alloc_from_infos(fhe_uint_infos),
+            pc_prev_fhe_uint_prepared: FheUintPrepared::alloc_from_infos(
+                module,
+                fhe_uint_prepared_infos,
+            ),
+            status_fhe_uint: FheUint::alloc_from_infos(fhe_uint_infos),
+            status_fhe_uint_prepared: FheUintPrepared::alloc_from_infos(
+                module,
+                fhe_uint_prepared_infos,
+            ),
+            status_cycle: 0,
+            status_stale: false,
+            misaligned_fhe_uint: FheUint::alloc_from_infos(fhe_uint_infos),
+            misaligned_fhe_uint_prepared: FheUintPrepared::alloc_from_infos(
+                module,
+                fhe_uint_prepared_infos,
+            ),
             ram_val_fhe_uint_prepared: FheUintPrepared::alloc_from_infos(
                 module,
                 fhe_uint_prepared_infos,
             ),
+            ram_noise_model: None,
+            rodata: None,
+            rodata_val_fhe_uint: FheUint::alloc_from_infos(fhe_uint_infos),
             rd_val_fhe_uint_prepared: FheUintPrepared::alloc_from_infos(
                 module,
                 fhe_uint_prepared_infos,
@@ -260,6 +525,170 @@ impl<BE: Backend> Interpreter<BE> {
         self.threads
     }
 
+    /// Runs the register, RAM and PC updates of each cycle at the same time,
+    /// each with a share of the threads, as they only depend on the phases
+    /// before them (see `doc/costs.md`). The refresh of the RAM runs right
+    /// after the RAM update. Has no effect with fewer than 3 threads, nor on
+    /// the phases run with [Interpreter::run_phase].
+    ///
+    /// The hooks of these phases are then not interleaved with them:
+    /// [CycleHook::before_phase] is called for [Phase::UpdateRegisters],
+    /// [Phase::UpdateRam], [Phase::UpdatePc] and [Phase::RefreshRam] before any
+    /// of them starts, then [CycleHook::after_phase] for the same phases, in the
+    /// same order, once they are all done. A hook timing the phases thus sees
+    /// the time of the whole group for each of them, while the timings
+    /// recorded by [Interpreter::end_cycle] are per update.
+    pub fn set_concurrent_updates(&mut self, concurrent_updates: bool) {
+        self.concurrent_updates = concurrent_updates;
+    }
+
+    pub fn concurrent_updates(&self) -> bool {
+        self.concurrent_updates
+    }
+
+    /// Number of cycles executed so far.
+    pub fn cycle_count(&self) -> usize {
+        self.cycle as usize
+    }
+
+    /// Updates the encrypted halt status with the last executed cycle.
+    ///
+    /// This is done at the start of each cycle, so it only needs to be called
+    /// after the last cycle, before [Interpreter::halt_status_decrypt].
+    /// Does nothing if the status is already up to date.
+    pub fn halt_status_update<M, DK, H, K, BRA>(
+        &mut self,
+        module: &M,
+        keys: &H,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: ModuleLogN
+            + GLWEPacking<BE>
+            + GLWECopy
+            + ExecuteBDDCircuit<BE>
+            + FheUintPrepare<BRA, BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+        BRA: BlindRotationAlgo,
+        DK: DataRef,
+        H: BDDKeyHelper<DK, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
+        K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+    {
+        self.update_halt_status(self.threads, module, keys, &mut Measurement::new(), scratch);
+    }
+
+    /// Decrypts the halt status, as of the last call to [Interpreter::halt_status_update].
+    pub fn halt_status_decrypt<M, S>(
+        &self,
+        module: &M,
+        sk_prepared: &S,
+        scratch: &mut Scratch<BE>,
+    ) -> HaltStatus
+    where
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos,
+        M: ModuleLogN + GLWEDecrypt<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        HaltStatus::from_word(self.status_fhe_uint.decrypt(module, sk_prepared, scratch))
+    }
+
+    // Prepares the PC of the current cycle and, if not already done, updates the
+    // status by comparing it with the PC of the previous cycle.
+    fn update_halt_status<M, DK, H, K, BRA>(
+        &mut self,
+        threads: usize,
+        module: &M,
+        keys: &H,
+        this_cycle_measurement: &mut Measurement,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: ModuleLogN
+            + GLWEPacking<BE>
+            + GLWECopy
+            + ExecuteBDDCircuit<BE>
+            + FheUintPrepare<BRA, BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+        BRA: BlindRotationAlgo,
+        DK: DataRef,
+        H: BDDKeyHelper<DK, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
+        K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+    {
+        // At the first cycle, the PC is prepared by the client and the status
+        // is a trivial encryption of zero.
+        if self.cycle == 0 || (self.status_cycle == self.cycle && !self.status_stale) {
+            return;
+        }
+
+        let pc_bits: usize = self.rom_bits_size + 2; // PC is 4bytes aligned
+
+        if self.status_stale {
+            self.pc_prev_fhe_uint_prepared.prepare_custom_multi_thread(
+                threads,
+                module,
+                &self.pc_prev_fhe_uint,
+                0,
+                pc_bits,
+                keys,
+                scratch,
+            );
+            self.status_fhe_uint_prepared.prepare_custom_multi_thread(
+                threads,
+                module,
+                &self.status_fhe_uint,
+                0,
+                u32::BITS as usize,
+                keys,
+                scratch,
+            );
+            self.status_stale = false;
+        } else {
+            std::mem::swap(
+                &mut self.pc_prev_fhe_uint_prepared,
+                &mut self.pc_fhe_uint_prepared,
+            );
+        }
+
+        this_cycle_measurement.time_prepare_pc = measure_duration(|| {
+            self.pc_fhe_uint_prepared.prepare_custom_multi_thread(
+                threads,
+                module,
+                &self.pc_fhe_uint,
+                0,
+                pc_bits,
+                keys,
+                scratch,
+            );
+        });
+
+        if self.status_cycle != self.cycle {
+            update_halt_status(
+                threads,
+                module,
+                &mut self.status_fhe_uint,
+                &self.status_fhe_uint_prepared,
+                &self.pc_fhe_uint_prepared,
+                &self.pc_prev_fhe_uint_prepared,
+                pc_bits,
+                &self.halt_circuit,
+                keys,
+                scratch,
+            );
+            self.status_fhe_uint_prepared.prepare_custom_multi_thread(
+                threads,
+                module,
+                &self.status_fhe_uint,
+                0,
+                u32::BITS as usize,
+                keys,
+                scratch,
+            );
+            self.status_cycle = self.cycle;
+
+            if let Some(vm_debug) = &mut self.vm_debug {
+                vm_debug.update_halt_status();
+            }
+        }
+    }
+
     pub fn instructions_encrypt_sk<M, S>(
         &mut self,
         module: &M,
@@ -277,27 +706,8 @@ impl<BE: Backend> Interpreter<BE> {
             vm_debug.set_instructions(instructions)
         }
 
-        let rom_size = self.rom_size;
-
-        let mut data_ram_rs1: Vec<u32> = vec![0u32; rom_size];
-        let mut data_ram_rs2: Vec<u32> = vec![0u32; rom_size];
-        let mut data_ram_rd: Vec<u32> = vec![0u32; rom_size];
-        let mut data_ram_imm: Vec<u32> = vec![0u32; rom_size];
-        let mut data_ram_rdu: Vec<u32> = vec![0u32; rom_size];
-        let mut data_ram_mu: Vec<u32> = vec![0u32; rom_size];
-        let mut data_ram_pcu: Vec<u32> = vec![0u32; rom_size];
-
-        for i in 0..instructions.instructions.len() {
-            data_ram_imm[i] = instructions.get_raw(i).get_imm() as u32;
-            let (rs2, rs1, rd) = instructions.get_raw(i).get_registers();
-            data_ram_rs1[i] = rs1 as u32;
-            data_ram_rs2[i] = rs2 as u32;
-            data_ram_rd[i] = rd as u32;
-            let (rdu, mu, pcu) = instructions.get_raw(i).get_opid();
-            data_ram_rdu[i] = rdu as u32;
-            data_ram_mu[i] = mu as u32;
-            data_ram_pcu[i] = pcu as u32;
-        }
+        let [data_ram_imm, data_ram_rs1, data_ram_rs2, data_ram_rd, data_ram_rdu, data_ram_mu, data_ram_pcu] =
+            self.rom_data(instructions);
 
         self.rs1_rom.encrypt_sk(
             module,
@@ -358,24 +768,115 @@ impl<BE: Backend> Interpreter<BE> {
         );
     }
 
-    pub fn init_registers<M, S>(
-        &mut self,
+    /// Splits the instructions into the plaintext content of the seven ROMs,
+    /// ordered as imm, rs1, rs2, rd, rdu, mu and pcu.
+    fn rom_data(&self, instructions: &InstructionsParser) -> [Vec<u32>; 7] {
+        assert_eq!(
+            instructions.rv32e,
+            self.is_rv32e(),
+            "the ROM and the register file of the interpreter must both be RV32E or not"
+        );
+
+        let rom_size = self.rom_size;
+
+        let mut data_ram_rs1: Vec<u32> = vec![0u32; rom_size];
+        let mut data_ram_rs2: Vec<u32> = vec![0u32; rom_size];
+        let mut data_ram_rd: Vec<u32> = vec![0u32; rom_size];
+        let mut data_ram_imm: Vec<u32> = vec![0u32; rom_size];
+        let mut data_ram_rdu: Vec<u32> = vec![0u32; rom_size];
+        let mut data_ram_mu: Vec<u32> = vec![0u32; rom_size];
+        let mut data_ram_pcu: Vec<u32> = vec![0u32; rom_size];
+
+        for i in 0..instructions.instructions.len() {
+            data_ram_imm[i] = instructions.get_raw(i).get_imm() as u32;
+            let (rs2, rs1, rd) = instructions.get_registers(i);
+            data_ram_rs1[i] = rs1 as u32;
+            data_ram_rs2[i] = rs2 as u32;
+            data_ram_rd[i] = rd as u32;
+            let (rdu, mu, pcu) = instructions
+                .custom_instructions
+                .get_opid(&instructions.get_raw(i));
+            data_ram_rdu[i] = rdu as u32;
+            data_ram_mu[i] = mu as u32;
+            data_ram_pcu[i] = pcu as u32;
+        }
+
+        [
+            data_ram_imm,
+            data_ram_rs1,
+            data_ram_rs2,
+            data_ram_rd,
+            data_ram_rdu,
+            data_ram_mu,
+            data_ram_pcu,
+        ]
+    }
+
+    /// Encrypts the instructions with seed-compressed ciphertexts and writes them
+    /// with the layout of [Interpreter::rom_write_to].
+    /// Load them back with [Interpreter::rom_read_compressed_from].
+    #[allow(clippy::too_many_arguments)]
+    pub fn instructions_encrypt_sk_compressed_to<W: Write, M, S>(
+        &self,
         module: &M,
-        data: &[u32],
+        instructions: &InstructionsParser,
         sk_prepared: &S,
         source_xa: &mut Source,
         source_xe: &mut Source,
         scratch: &mut Scratch<BE>,
-    ) where
+        writer: &mut W,
+    ) -> Result<()>
+    where
         S: GLWESecretPreparedToRef<BE> + GLWEInfos,
-        M: ModuleN + GLWEEncryptSk<BE>,
+        M: ModuleN + GLWECompressedEncryptSk<BE>,
         Scratch<BE>: ScratchTakeCore<BE>,
     {
-        self.registers
-            .encrypt_sk(module, data, sk_prepared, source_xa, source_xe, scratch);
+        let roms: [&Memory; 7] = [
+            &self.imm_rom,
+            &self.rs1_rom,
+            &self.rs2_rom,
+            &self.rd_rom,
+            &self.rdu_rom,
+            &self.mu_rom,
+            &self.pcu_rom,
+        ];
+
+        for (rom, data) in roms.iter().zip(self.rom_data(instructions).iter()) {
+            rom.encrypt_sk_compressed_to(
+                module,
+                data,
+                sk_prepared,
+                source_xa,
+                source_xe,
+                scratch,
+                writer,
+            )?;
+        }
+        Ok(())
     }
 
-    pub fn ram_encrypt_sk<M, S>(
+    /// Loads the seven ROMs written by [Interpreter::instructions_encrypt_sk_compressed_to],
+    /// followed by the read-only data bank written by
+    /// [Interpreter::rodata_encrypt_sk_compressed_to] if the interpreter has one.
+    pub fn rom_read_compressed_from<R: Read, M>(&mut self, module: &M, reader: &mut R) -> Result<()>
+    where
+        M: GLWEDecompress,
+    {
+        self.imm_rom.read_compressed_from(module, reader)?;
+        self.rs1_rom.read_compressed_from(module, reader)?;
+        self.rs2_rom.read_compressed_from(module, reader)?;
+        self.rd_rom.read_compressed_from(module, reader)?;
+        self.rdu_rom.read_compressed_from(module, reader)?;
+        self.mu_rom.read_compressed_from(module, reader)?;
+        self.pcu_rom.read_compressed_from(module, reader)?;
+        if let Some(rodata) = &mut self.rodata {
+            rodata.read_compressed_from(module, reader)?;
+        }
+        Ok(())
+    }
+
+    /// Encrypts `data` into the read-only data bank, see [Interpreter::set_rodata_size].
+    pub fn rodata_encrypt_sk<M, S>(
         &mut self,
         module: &M,
         data: &[u32],
@@ -388,74 +889,378 @@ impl<BE: Backend> Interpreter<BE> {
         M: ModuleN + GLWEEncryptSk<BE>,
         Scratch<BE>: ScratchTakeCore<BE>,
     {
-        assert!(data.len() <= self.ram.size());
+        let rodata: &mut Memory = self.rodata.as_mut().expect("no read-only data bank");
+        assert!(data.len() <= rodata.size());
 
         if let Some(vm_debug) = &mut self.vm_debug {
-            vm_debug.set_ram(data);
+            vm_debug.set_rodata(data);
         }
 
-        self.ram
-            .encrypt_sk(module, data, sk_prepared, source_xa, source_xe, scratch);
+        rodata.encrypt_sk(module, data, sk_prepared, source_xa, source_xe, scratch);
     }
 
-    pub fn ram_decrypt<M, S>(
-        &mut self,
+    /// Same as [Interpreter::rodata_encrypt_sk] with seed-compressed ciphertexts,
+    /// written after the ROMs by [Interpreter::instructions_encrypt_sk_compressed_to].
+    #[allow(clippy::too_many_arguments)]
+    pub fn rodata_encrypt_sk_compressed_to<W: Write, M, S>(
+        &self,
         module: &M,
-        data_decrypted: &mut [u32],
+        data: &[u32],
         sk_prepared: &S,
+        source_xa: &mut Source,
+        source_xe: &mut Source,
         scratch: &mut Scratch<BE>,
-    ) where
+        writer: &mut W,
+    ) -> Result<()>
+    where
         S: GLWESecretPreparedToRef<BE> + GLWEInfos,
-        M: ModuleN + GLWEDecrypt<BE>,
+        M: ModuleN + GLWECompressedEncryptSk<BE>,
         Scratch<BE>: ScratchTakeCore<BE>,
     {
-        assert_eq!(data_decrypted.len(), self.ram.size());
+        let rodata: &Memory = self.rodata.as_ref().expect("no read-only data bank");
+        assert!(data.len() <= rodata.size());
 
-        self.ram
-            .decrypt(module, data_decrypted, sk_prepared, scratch);
+        rodata.encrypt_sk_compressed_to(
+            module,
+            data,
+            sk_prepared,
+            source_xa,
+            source_xe,
+            scratch,
+            writer,
+        )
     }
 
-    pub fn cycle<M, DK, H, K, BRA>(&mut self, module: &M, keys: &H, scratch: &mut Scratch<BE>)
-    where
-        M: Sync
-            + GGSWPreparedFactory<BE>
-            + GLWEExternalProduct<BE>
-            + GLWEPackerOps<BE>
-            + GLWETrace<BE>
-            + GLWEPacking<BE>
-            + FheUintPreparedFactory<u32, BE>
-            + PrepareMultiple<BE, BRA>
-            + FheUintPrepare<BRA, BE>
-            + ExecuteBDDCircuit2WTo1W<BE>
-            + ExecuteBDDCircuit1WTo1W<BE>
-            + GLWEBlinSelection<u32, BE>
-            + GGSWBlindRotation<u32, BE>
-            + GLWENoise<BE>
-            + GGSWEncryptSk<BE>
-            + FheUintPreparedFactory<u32, BE>
-            + FheUintPreparedEncryptSk<u32, BE>
-            + GLWEBlindRetrieval<BE>,
+    pub fn rom_size(&self) -> usize {
+        self.rom_size
+    }
+
+    pub fn ram_size(&self) -> usize {
+        self.ram_size
+    }
+
+    /// Serializes the seven encrypted ROMs, as encrypted by [Interpreter::instructions_encrypt_sk],
+    /// followed by the read-only data bank, if any.
+    pub fn rom_write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.imm_rom.write_to(writer)?;
+        self.rs1_rom.write_to(writer)?;
+        self.rs2_rom.write_to(writer)?;
+        self.rd_rom.write_to(writer)?;
+        self.rdu_rom.write_to(writer)?;
+        self.mu_rom.write_to(writer)?;
+        self.pcu_rom.write_to(writer)?;
+        if let Some(rodata) = &self.rodata {
+            rodata.write_to(writer)?;
+        }
+        Ok(())
+    }
+
+    /// Loads the encrypted ROMs written by [Interpreter::rom_write_to].
+    /// The [Interpreter] must have been allocated with the same `rom_size`
+    /// and read-only data bank size.
+    pub fn rom_read_from<R: Read>(&mut self, reader: &mut R) -> Result<()> {
+        self.imm_rom.read_from(reader)?;
+        self.rs1_rom.read_from(reader)?;
+        self.rs2_rom.read_from(reader)?;
+        self.rd_rom.read_from(reader)?;
+        self.rdu_rom.read_from(reader)?;
+        self.mu_rom.read_from(reader)?;
+        self.pcu_rom.read_from(reader)?;
+        if let Some(rodata) = &mut self.rodata {
+            rodata.read_from(reader)?;
+        }
+        Ok(())
+    }
+
+    /// Serializes the state evolving across cycles, to resume the execution later
+    /// with [Interpreter::checkpoint_read_from], as:
+    /// [Header] | rom size | ram size | cycle | measurements | pc | halt status | registers | ram,
+    /// the halt status being written as status cycle | previous pc | status.
+    ///
+    /// Must be called at a cycle boundary. The ROMs are not included, nor the
+    /// plaintext state of the debug VM: a checkpoint only holds what the server
+    /// sees.
+    pub fn checkpoint_write_to<W: Write>(
+        &self,
+        params: &CryptographicParameters<BE>,
+        writer: &mut W,
+    ) -> Result<()> {
+        assert!(
+            self.cycle_in_progress.is_none(),
+            "checkpoint in the middle of cycle {}",
+            self.cycle
+        );
+        Header::new(CHECKPOINT_MAGIC, CHECKPOINT_VERSION, params.fingerprint()).write_to(writer)?;
+        write_u64(writer, self.rom_size as u64)?;
+        write_u64(writer, self.ram_size as u64)?;
+        write_u32(writer, self.cycle)?;
+        self.measurements.write_to(writer)?;
+        self.pc_fhe_uint.to_ref().write_to(writer)?;
+        write_u32(writer, self.status_cycle)?;
+        self.pc_prev_fhe_uint.to_ref().write_to(writer)?;
+        self.status_fhe_uint.to_ref().write_to(writer)?;
+        self.registers.write_to(writer)?;
+        self.ram.write_to(writer)
+    }
+
+    /// Restores the state written by [Interpreter::checkpoint_write_to].
+    ///
+    /// The [Interpreter] must have been allocated with the same `rom_size` and
+    /// `ram_size`, without debug VM, and its ROMs loaded with the same program.
+    pub fn checkpoint_read_from<R: Read>(
+        &mut self,
+        params: &CryptographicParameters<BE>,
+        reader: &mut R,
+    ) -> Result<()> {
+        if self.vm_debug.is_some() {
+            return Err(invalid_data(
+                "checkpoints do not hold the state of the debug VM".to_string(),
+            ));
+        }
+        Header::read_from(reader)?.check(&Header::new(
+            CHECKPOINT_MAGIC,
+            CHECKPOINT_VERSION,
+            params.fingerprint(),
+        ))?;
+        let rom_size: usize = read_u64(reader)? as usize;
+        let ram_size: usize = read_u64(reader)? as usize;
+        if rom_size != self.rom_size || ram_size != self.ram_size {
+            return Err(invalid_data(format!(
+                "interpreter (rom_size, ram_size)=({}, {}) != read ({}, {})",
+                self.rom_size, self.ram_size, rom_size, ram_size
+            )));
+        }
+        self.cycle = read_u32(reader)?;
+        self.measurements = Measurements::read_from(reader)?;
+        self.pc_fhe_uint.to_mut().read_from(reader)?;
+        self.status_cycle = read_u32(reader)?;
+        self.pc_prev_fhe_uint.to_mut().read_from(reader)?;
+        self.status_fhe_uint.to_mut().read_from(reader)?;
+        self.status_stale = true;
+        self.registers.read_from(reader)?;
+        self.ram.read_from(reader)
+    }
+
+    pub fn init_registers<M, S>(
+        &mut self,
+        module: &M,
+        data: &[u32],
+        sk_prepared: &S,
+        source_xa: &mut Source,
+        source_xe: &mut Source,
+        scratch: &mut Scratch<BE>,
+    ) where
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos,
+        M: ModuleN + GLWEEncryptSk<BE>,
         Scratch<BE>: ScratchTakeCore<BE>,
-        BRA: BlindRotationAlgo,
-        DK: DataRef,
-        H: Sync + BDDKeyHelper<DK, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
-        K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
     {
-        self.cycle_internal(
+        self.registers
+            .encrypt_sk(module, data, sk_prepared, source_xa, source_xe, scratch);
+    }
+
+    pub fn ram_encrypt_sk<M, S>(
+        &mut self,
+        module: &M,
+        data: &[u32],
+        sk_prepared: &S,
+        source_xa: &mut Source,
+        source_xe: &mut Source,
+        scratch: &mut Scratch<BE>,
+    ) where
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos,
+        M: ModuleN + GLWEEncryptSk<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        assert!(data.len() <= self.ram.size());
+
+        if let Some(vm_debug) = &mut self.vm_debug {
+            vm_debug.set_ram(data);
+        }
+
+        self.ram
+            .encrypt_sk(module, data, sk_prepared, source_xa, source_xe, scratch);
+    }
+
+    /// Same as [Interpreter::ram_encrypt_sk] but with the public key `pk`, so that
+    /// the RAM can be encrypted by a party that does not hold the secret-key.
+    pub fn ram_encrypt_pk<M, D>(
+        &mut self,
+        module: &M,
+        data: &[u32],
+        pk: &VMPublicKeyPrepared<D, BE>,
+        source_xu: &mut Source,
+        source_xe: &mut Source,
+        scratch: &mut Scratch<BE>,
+    ) where
+        D: DataRef,
+        M: ModuleN + GLWEEncryptPk<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        assert!(data.len() <= self.ram.size());
+
+        if let Some(vm_debug) = &mut self.vm_debug {
+            vm_debug.set_ram(data);
+        }
+
+        self.ram
+            .encrypt_pk(module, data, &pk.pk, source_xu, source_xe, scratch);
+    }
+
+    /// Injects `input` into the words [input.word_offset(), input.word_offset() + input.size())
+    /// of the encrypted RAM.
+    ///
+    /// The RAM ciphertexts entirely covered by the range are replaced by the ones of
+    /// the input. The (at most two) ciphertexts at the boundaries of the range also
+    /// hold words outside of it, so the input is homomorphically added to them: the
+    /// RAM must have been encrypted with zeros over the range, so that the same
+    /// encrypted boot RAM can be reused with different inputs. Use
+    /// [Interpreter::ram_inject_input_debug] to check it.
+    pub fn ram_inject_input<M>(
+        &mut self,
+        module: &M,
+        input: &EncryptedInput,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: ModuleN + GLWEAdd + GLWECopy + GLWENormalize<BE> + GLWEDecrypt<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        self.ram_inject_input_internal(
             module,
-            keys,
+            input,
             None::<&GLWESecretPrepared<Vec<u8>, BE>>,
             scratch,
         );
     }
 
-    pub fn cycle_debug<M, DK, H, BRA, K, S>(
+    /// Same as [Interpreter::ram_inject_input], decrypting `input` with `sk` into
+    /// the debug VM after checking that its range of the RAM is zero.
+    pub fn ram_inject_input_debug<M, S>(
         &mut self,
         module: &M,
-        keys: &H,
+        input: &EncryptedInput,
         sk: &S,
         scratch: &mut Scratch<BE>,
     ) where
+        M: ModuleN + GLWEAdd + GLWECopy + GLWENormalize<BE> + GLWEDecrypt<BE>,
+        S: GLWESecretPreparedToRef<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        self.ram_inject_input_internal(module, input, Some(sk), scratch);
+    }
+
+    fn ram_inject_input_internal<M, S>(
+        &mut self,
+        module: &M,
+        input: &EncryptedInput,
+        sk: Option<&S>,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: ModuleN + GLWEAdd + GLWECopy + GLWENormalize<BE> + GLWEDecrypt<BE>,
+        S: GLWESecretPreparedToRef<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        let range: Range<usize> = input.word_offset()..input.word_offset() + input.size();
+        assert!(
+            range.end <= self.ram.size(),
+            "input range [{}, {}) exceeds RAM size {}",
+            range.start,
+            range.end,
+            self.ram.size()
+        );
+
+        if let Some(vm_debug) = &mut self.vm_debug {
+            let sk: &S = sk.expect("injecting into a debug VM requires the secret-key");
+            assert!(
+                vm_debug.ram[range.clone()].iter().all(|word| *word == 0),
+                "input range [{}, {}) of the RAM is not zero",
+                range.start,
+                range.end
+            );
+            input.decrypt(module, &mut vm_debug.ram[range], sk, scratch);
+        }
+
+        self.ram
+            .inject_at(module, input.word_offset(), input.size(), &input.bits, scratch);
+    }
+
+    /// Encrypts `data` as RAM with seed-compressed ciphertexts and writes them.
+    /// Load them back with [Interpreter::ram_read_compressed_from].
+    #[allow(clippy::too_many_arguments)]
+    pub fn ram_encrypt_sk_compressed_to<W: Write, M, S>(
+        &self,
+        module: &M,
+        data: &[u32],
+        sk_prepared: &S,
+        source_xa: &mut Source,
+        source_xe: &mut Source,
+        scratch: &mut Scratch<BE>,
+        writer: &mut W,
+    ) -> Result<()>
+    where
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos,
+        M: ModuleN + GLWECompressedEncryptSk<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        assert!(data.len() <= self.ram.size());
+
+        self.ram.encrypt_sk_compressed_to(
+            module,
+            data,
+            sk_prepared,
+            source_xa,
+            source_xe,
+            scratch,
+            writer,
+        )
+    }
+
+    /// Loads the RAM written by [Interpreter::ram_encrypt_sk_compressed_to].
+    pub fn ram_read_compressed_from<R: Read, M>(&mut self, module: &M, reader: &mut R) -> Result<()>
+    where
+        M: GLWEDecompress,
+    {
+        self.ram.read_compressed_from(module, reader)
+    }
+
+    pub fn ram_decrypt<M, S>(
+        &self,
+        module: &M,
+        data_decrypted: &mut [u32],
+        sk_prepared: &S,
+        scratch: &mut Scratch<BE>,
+    ) where
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos,
+        M: ModuleN + GLWEDecrypt<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        assert_eq!(data_decrypted.len(), self.ram.size());
+
+        self.ram
+            .decrypt(module, data_decrypted, sk_prepared, scratch);
+    }
+
+    /// Decrypts the RAM words [offset, offset + data_decrypted.len()) into `data_decrypted`.
+    ///
+    /// Only the ciphertexts overlapping the range are decrypted and only the
+    /// words of the range are returned.
+    pub fn ram_decrypt_range<M, S>(
+        &self,
+        module: &M,
+        offset: usize,
+        data_decrypted: &mut [u32],
+        sk_prepared: &S,
+        scratch: &mut Scratch<BE>,
+    ) where
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos,
+        M: ModuleN + GLWEDecrypt<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        self.ram
+            .decrypt_range(module, offset, data_decrypted, sk_prepared, scratch);
+    }
+
+    pub fn cycle<M, DK, H, K, BRA>(&mut self, module: &M, keys: &H, scratch: &mut Scratch<BE>)
+    where
         M: Sync
             + GGSWPreparedFactory<BE>
             + GLWEExternalProduct<BE>
@@ -477,18 +1282,17 @@ impl<BE: Backend> Interpreter<BE> {
         Scratch<BE>: ScratchTakeCore<BE>,
         BRA: BlindRotationAlgo,
         DK: DataRef,
-        S: GLWESecretPreparedToRef<BE> + GLWEInfos + GetDistribution,
         H: Sync + BDDKeyHelper<DK, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
         K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
     {
-        self.cycle_internal(module, keys, Some(sk), scratch);
+        self.cycle_with_hook(module, keys, &mut (), scratch);
     }
 
-    fn cycle_internal<M, DK, H, BRA, K, S>(
+    pub fn cycle_debug<M, DK, H, BRA, K, S>(
         &mut self,
         module: &M,
         keys: &H,
-        sk: Option<&S>,
+        sk: &S,
         scratch: &mut Scratch<BE>,
     ) where
         M: Sync
@@ -516,124 +1320,16 @@ impl<BE: Backend> Interpreter<BE> {
         H: Sync + BDDKeyHelper<DK, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
         K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
     {
-        let threads = self.threads;
-        let mut this_cycle_measurement = Measurement::new();
-
-        let start_cycle_time = Instant::now();
-        // Retrive instructions components:
-        // - addresses=[rs1, rs2, rd]
-        // - imm
-        // - opids=[rdu, mu, pcu]
-        if self.verbose_timings {
-            println!();
-            println!(">>>>>>>>> CYCLE[{:03}] <<<<<<<<<<<", self.cycle);
-        }
-
-        // Skip preparing PC if first cycle
-        if self.cycle != 0 {
-            let start_time_pc_prepare = if self.verbose_timings {
-                Some(Instant::now())
-            } else {
-                None
-            };
-            self.pc_fhe_uint_prepared.prepare_custom_multi_thread(
-                threads,
-                module,
-                &self.pc_fhe_uint,
-                0,
-                self.rom_bits_size + 2, // PC is 4bytes aligned
-                keys,
-                scratch,
-            );
-            if self.verbose_timings {
-                this_cycle_measurement.time_prepare_pc =
-                    Instant::now().duration_since(start_time_pc_prepare.unwrap());
-            }
-        }
-
-        self.read_and_prepare_instruction_components(
-            threads,
-            module,
-            keys,
-            sk,
-            scratch,
-            &mut this_cycle_measurement,
-        );
-
-        // Reads Register[rs1] and Register[rs2]
-        self.read_and_prepare_registers(
-            threads,
-            module,
-            keys,
-            sk,
-            scratch,
-            &mut this_cycle_measurement,
-        );
-
-        self.read_ram(
-            threads,
-            module,
-            keys,
-            sk,
-            scratch,
-            &mut this_cycle_measurement,
-        );
-
-        // Evaluates arithmetic over Register[rs1], Register[rs2], imm and pc
-        match self.instruction_set {
-            InstructionSet::RV32M => unimplemented!(),
-            InstructionSet::RV32I => self.update_registers(
-                threads,
-                module,
-                RD_UPDATE_RV32I_OP_LIST,
-                keys,
-                sk,
-                scratch,
-                &mut this_cycle_measurement,
-            ),
-        };
-
-        // Stores value in Ram[rs2 + imm + offset]
-        self.update_ram(
-            threads,
-            module,
-            keys,
-            sk,
-            scratch,
-            &mut this_cycle_measurement,
-        );
-
-        // Updates PC
-        self.update_pc(
-            threads,
-            module,
-            keys,
-            sk,
-            scratch,
-            &mut this_cycle_measurement,
-        );
-
-        let end_cycle_time: Instant = Instant::now();
-        let total_cycle_time: std::time::Duration = end_cycle_time.duration_since(start_cycle_time);
-        this_cycle_measurement.total_cycle_time = total_cycle_time;
-
-        self.measurements
-            .cycle_measurements
-            .push(this_cycle_measurement);
-
-        self.print_timings(self.cycle as usize);
-
-        self.cycle += 1;
+        self.cycle_debug_with_hook(module, keys, sk, &mut (), scratch);
     }
 
-    pub(crate) fn read_and_prepare_instruction_components<M, D, BRA, H, K, S>(
+    /// Same as [Interpreter::cycle], calling `hook` before and after each [Phase].
+    pub fn cycle_with_hook<M, DK, H, K, BRA, C>(
         &mut self,
-        threads: usize,
         module: &M,
         keys: &H,
-        sk: Option<&S>,
+        hook: &mut C,
         scratch: &mut Scratch<BE>,
-        this_cycle_measurement: &mut Measurement,
     ) where
         M: Sync
             + GGSWPreparedFactory<BE>
@@ -641,254 +1337,1223 @@ impl<BE: Backend> Interpreter<BE> {
             + GLWEPackerOps<BE>
             + GLWETrace<BE>
             + GLWEPacking<BE>
+            + FheUintPreparedFactory<u32, BE>
+            + PrepareMultiple<BE, BRA>
             + FheUintPrepare<BRA, BE>
-            + ModuleN
+            + ExecuteBDDCircuit2WTo1W<BE>
+            + ExecuteBDDCircuit1WTo1W<BE>
+            + GLWEBlinSelection<u32, BE>
             + GGSWBlindRotation<u32, BE>
-            + GGSWPreparedFactory<BE>
-            + GLWEDecrypt<BE>
             + GLWENoise<BE>
             + GGSWEncryptSk<BE>
             + FheUintPreparedFactory<u32, BE>
             + FheUintPreparedEncryptSk<u32, BE>
-            + PrepareMultiple<BE, BRA>,
-        H: Sync + BDDKeyHelper<D, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
+            + GLWEBlindRetrieval<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+        BRA: BlindRotationAlgo,
+        DK: DataRef,
+        H: Sync + BDDKeyHelper<DK, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
+        K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+        C: CycleHook<BE>,
+    {
+        self.cycle_internal(
+            module,
+            keys,
+            None::<&GLWESecretPrepared<Vec<u8>, BE>>,
+            hook,
+            scratch,
+        );
+    }
+
+    /// Same as [Interpreter::cycle_debug], calling `hook` before and after each [Phase].
+    pub fn cycle_debug_with_hook<M, DK, H, BRA, K, S, C>(
+        &mut self,
+        module: &M,
+        keys: &H,
+        sk: &S,
+        hook: &mut C,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: Sync
+            + GGSWPreparedFactory<BE>
+            + GLWEExternalProduct<BE>
+            + GLWEPackerOps<BE>
+            + GLWETrace<BE>
+            + GLWEPacking<BE>
+            + FheUintPreparedFactory<u32, BE>
+            + PrepareMultiple<BE, BRA>
+            + FheUintPrepare<BRA, BE>
+            + ExecuteBDDCircuit2WTo1W<BE>
+            + ExecuteBDDCircuit1WTo1W<BE>
+            + GLWEBlinSelection<u32, BE>
+            + GGSWBlindRotation<u32, BE>
+            + GLWENoise<BE>
+            + GGSWEncryptSk<BE>
+            + FheUintPreparedFactory<u32, BE>
+            + FheUintPreparedEncryptSk<u32, BE>
+            + GLWEBlindRetrieval<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+        BRA: BlindRotationAlgo,
+        DK: DataRef,
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos + GetDistribution,
+        H: Sync + BDDKeyHelper<DK, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
+        K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+        C: CycleHook<BE>,
+    {
+        self.cycle_internal(module, keys, Some(sk), hook, scratch);
+    }
+
+    fn cycle_internal<M, DK, H, BRA, K, S, C>(
+        &mut self,
+        module: &M,
+        keys: &H,
+        sk: Option<&S>,
+        hook: &mut C,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: Sync
+            + GGSWPreparedFactory<BE>
+            + GLWEExternalProduct<BE>
+            + GLWEPackerOps<BE>
+            + GLWETrace<BE>
+            + GLWEPacking<BE>
+            + FheUintPreparedFactory<u32, BE>
+            + PrepareMultiple<BE, BRA>
+            + FheUintPrepare<BRA, BE>
+            + ExecuteBDDCircuit2WTo1W<BE>
+            + ExecuteBDDCircuit1WTo1W<BE>
+            + GLWEBlinSelection<u32, BE>
+            + GGSWBlindRotation<u32, BE>
+            + GLWENoise<BE>
+            + GGSWEncryptSk<BE>
+            + FheUintPreparedFactory<u32, BE>
+            + FheUintPreparedEncryptSk<u32, BE>
+            + GLWEBlindRetrieval<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+        BRA: BlindRotationAlgo,
+        DK: DataRef,
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos + GetDistribution,
+        H: Sync + BDDKeyHelper<DK, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
+        K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+        C: CycleHook<BE>,
+    {
+        let concurrent: bool = self.concurrent_updates && self.threads >= UPDATES;
+
+        self.begin_cycle();
+        for phase in Phase::ALL {
+            if concurrent && UPDATE_PHASES.contains(&phase) {
+                continue;
+            }
+            hook.before_phase(self, phase);
+            self.run_phase_internal(phase, module, keys, sk, scratch);
+            hook.after_phase(self, phase);
+        }
+        if concurrent {
+            for phase in UPDATE_PHASES {
+                hook.before_phase(self, phase);
+            }
+            self.run_updates_concurrently(module, keys, sk, scratch);
+            for phase in UPDATE_PHASES {
+                hook.after_phase(self, phase);
+            }
+        }
+        self.end_cycle();
+    }
+
+    /// Starts a cycle, whose phases are then run with [Interpreter::run_phase]
+    /// until [Interpreter::end_cycle], see [crate::pipeline].
+    ///
+    /// Panics if a cycle is already started.
+    pub fn begin_cycle(&mut self) {
+        assert!(
+            self.cycle_in_progress.is_none(),
+            "cycle {} already started",
+            self.cycle
+        );
+        if self.verbose_timings {
+            println!();
+            println!(">>>>>>>>> CYCLE[{:03}] <<<<<<<<<<<", self.cycle);
+        }
+        self.cycle_in_progress = Some((Measurement::new(), Instant::now()));
+    }
+
+    /// Ends the cycle started by [Interpreter::begin_cycle], recording its
+    /// timings, and moves to the next one.
+    ///
+    /// Panics if no cycle is started.
+    pub fn end_cycle(&mut self) {
+        let (mut this_cycle_measurement, start_cycle_time) = self
+            .cycle_in_progress
+            .take()
+            .unwrap_or_else(|| panic!("cycle {} not started", self.cycle));
+
+        this_cycle_measurement.total_cycle_time = start_cycle_time.elapsed();
+
+        self.measurements
+            .cycle_measurements
+            .push(this_cycle_measurement);
+
+        self.print_timings(self.cycle as usize);
+
+        self.cycle += 1;
+    }
+
+    /// Runs `phase` of the cycle started by [Interpreter::begin_cycle].
+    ///
+    /// Panics if no cycle is started.
+    pub fn run_phase<M, DK, H, K, BRA>(
+        &mut self,
+        phase: Phase,
+        module: &M,
+        keys: &H,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: Sync
+            + GGSWPreparedFactory<BE>
+            + GLWEExternalProduct<BE>
+            + GLWEPackerOps<BE>
+            + GLWETrace<BE>
+            + GLWEPacking<BE>
+            + FheUintPreparedFactory<u32, BE>
+            + PrepareMultiple<BE, BRA>
+            + FheUintPrepare<BRA, BE>
+            + ExecuteBDDCircuit2WTo1W<BE>
+            + ExecuteBDDCircuit1WTo1W<BE>
+            + GLWEBlinSelection<u32, BE>
+            + GGSWBlindRotation<u32, BE>
+            + GLWENoise<BE>
+            + GGSWEncryptSk<BE>
+            + FheUintPreparedFactory<u32, BE>
+            + FheUintPreparedEncryptSk<u32, BE>
+            + GLWEBlindRetrieval<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+        BRA: BlindRotationAlgo,
+        DK: DataRef,
+        H: Sync + BDDKeyHelper<DK, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
+        K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+    {
+        self.run_phase_internal(
+            phase,
+            module,
+            keys,
+            None::<&GLWESecretPrepared<Vec<u8>, BE>>,
+            scratch,
+        );
+    }
+
+    /// Same as [Interpreter::run_phase], checking the phase against the debug
+    /// interpreter as [Interpreter::cycle_debug] does.
+    pub fn run_phase_debug<M, DK, H, BRA, K, S>(
+        &mut self,
+        phase: Phase,
+        module: &M,
+        keys: &H,
+        sk: &S,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: Sync
+            + GGSWPreparedFactory<BE>
+            + GLWEExternalProduct<BE>
+            + GLWEPackerOps<BE>
+            + GLWETrace<BE>
+            + GLWEPacking<BE>
+            + FheUintPreparedFactory<u32, BE>
+            + PrepareMultiple<BE, BRA>
+            + FheUintPrepare<BRA, BE>
+            + ExecuteBDDCircuit2WTo1W<BE>
+            + ExecuteBDDCircuit1WTo1W<BE>
+            + GLWEBlinSelection<u32, BE>
+            + GGSWBlindRotation<u32, BE>
+            + GLWENoise<BE>
+            + GGSWEncryptSk<BE>
+            + FheUintPreparedFactory<u32, BE>
+            + FheUintPreparedEncryptSk<u32, BE>
+            + GLWEBlindRetrieval<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+        BRA: BlindRotationAlgo,
+        DK: DataRef,
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos + GetDistribution,
+        H: Sync + BDDKeyHelper<DK, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
+        K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+    {
+        self.run_phase_internal(phase, module, keys, Some(sk), scratch);
+    }
+
+    fn run_phase_internal<M, DK, H, BRA, K, S>(
+        &mut self,
+        phase: Phase,
+        module: &M,
+        keys: &H,
+        sk: Option<&S>,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: Sync
+            + GGSWPreparedFactory<BE>
+            + GLWEExternalProduct<BE>
+            + GLWEPackerOps<BE>
+            + GLWETrace<BE>
+            + GLWEPacking<BE>
+            + FheUintPreparedFactory<u32, BE>
+            + PrepareMultiple<BE, BRA>
+            + FheUintPrepare<BRA, BE>
+            + ExecuteBDDCircuit2WTo1W<BE>
+            + ExecuteBDDCircuit1WTo1W<BE>
+            + GLWEBlinSelection<u32, BE>
+            + GGSWBlindRotation<u32, BE>
+            + GLWENoise<BE>
+            + GGSWEncryptSk<BE>
+            + FheUintPreparedFactory<u32, BE>
+            + FheUintPreparedEncryptSk<u32, BE>
+            + GLWEBlindRetrieval<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+        BRA: BlindRotationAlgo,
+        DK: DataRef,
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos + GetDistribution,
+        H: Sync + BDDKeyHelper<DK, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
+        K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+    {
+        let threads = self.threads;
+        let (mut this_cycle_measurement, start_cycle_time) = self
+            .cycle_in_progress
+            .take()
+            .unwrap_or_else(|| panic!("cycle {} not started", self.cycle));
+
+        match phase {
+            // Prepares PC, skipped if first cycle, and updates the halt status
+            Phase::HaltStatus => {
+                self.update_halt_status(threads, module, keys, &mut this_cycle_measurement, scratch)
+            }
+            // Retrive instructions components:
+            // - addresses=[rs1, rs2, rd]
+            // - imm
+            // - opids=[rdu, mu, pcu]
+            Phase::ReadInstruction => self.read_and_prepare_instruction_components(
+                threads,
+                module,
+                keys,
+                sk,
+                scratch,
+                &mut this_cycle_measurement,
+            ),
+            // Reads Register[rs1] and Register[rs2]
+            Phase::ReadRegisters => self.read_and_prepare_registers(
+                threads,
+                module,
+                keys,
+                sk,
+                scratch,
+                &mut this_cycle_measurement,
+            ),
+            Phase::ReadRam => self.read_ram(
+                threads,
+                module,
+                keys,
+                sk,
+                scratch,
+                &mut this_cycle_measurement,
+            ),
+            // Evaluates arithmetic over Register[rs1], Register[rs2], imm and pc
+            Phase::UpdateRegisters => {
+                let rd_update_ops: Vec<RD_UPDATE> = self.rd_update_evaluated_ops();
+                self.update_registers(
+                    threads,
+                    module,
+                    &rd_update_ops,
+                    keys,
+                    sk,
+                    scratch,
+                    &mut this_cycle_measurement,
+                )
+            }
+            // Stores value in Ram[rs1 + imm + offset]
+            Phase::UpdateRam => self.update_ram(
+                threads,
+                module,
+                keys,
+                sk,
+                scratch,
+                &mut this_cycle_measurement,
+            ),
+            // Updates PC
+            Phase::UpdatePc => self.update_pc(
+                threads,
+                module,
+                keys,
+                sk,
+                scratch,
+                &mut this_cycle_measurement,
+            ),
+            // Bootstraps the RAM words due for a refresh
+            Phase::RefreshRam => self.refresh_ram(
+                threads,
+                module,
+                keys,
+                sk,
+                scratch,
+                &mut this_cycle_measurement,
+            ),
+        }
+
+        match phase {
+            Phase::UpdateRegisters => self.update_threads[0] = threads,
+            Phase::UpdateRam => self.update_threads[1] = threads,
+            Phase::UpdatePc => self.update_threads[2] = threads,
+            _ => {}
+        }
+        this_cycle_measurement.time_critical_path += this_cycle_measurement.phase_time(phase);
+
+        self.cycle_in_progress = Some((this_cycle_measurement, start_cycle_time));
+    }
+
+    pub(crate) fn read_and_prepare_instruction_components<M, D, BRA, H, K, S>(
+        &mut self,
+        threads: usize,
+        module: &M,
+        keys: &H,
+        sk: Option<&S>,
+        scratch: &mut Scratch<BE>,
+        this_cycle_measurement: &mut Measurement,
+    ) where
+        M: Sync
+            + GGSWPreparedFactory<BE>
+            + GLWEExternalProduct<BE>
+            + GLWEPackerOps<BE>
+            + GLWETrace<BE>
+            + GLWEPacking<BE>
+            + FheUintPrepare<BRA, BE>
+            + ModuleN
+            + GGSWBlindRotation<u32, BE>
+            + GGSWPreparedFactory<BE>
+            + GLWEDecrypt<BE>
+            + GLWENoise<BE>
+            + GGSWEncryptSk<BE>
+            + FheUintPreparedFactory<u32, BE>
+            + FheUintPreparedEncryptSk<u32, BE>
+            + PrepareMultiple<BE, BRA>
+            + Cmux<BE>,
+        H: Sync + BDDKeyHelper<D, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
         K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
         BRA: BlindRotationAlgo,
-        D: DataRef,
-        S: GLWESecretPreparedToRef<BE> + GLWEInfos + GetDistribution,
+        D: DataRef,
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos + GetDistribution,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        this_cycle_measurement.time_read_and_prepare_rom = measure_duration(|| {
+            this_cycle_measurement.time_read_rom = measure_duration(|| {
+                self.imm_rom.read_stateless(
+                    threads,
+                    module,
+                    &mut self.imm_val_fhe_uint,
+                    &self.pc_fhe_uint_prepared,
+                    2,
+                    keys,
+                    scratch,
+                );
+
+                self.rdu_rom.read_stateless(
+                    threads,
+                    module,
+                    &mut self.rdu_val_fhe_uint,
+                    &self.pc_fhe_uint_prepared,
+                    2,
+                    keys,
+                    scratch,
+                );
+
+                self.mu_rom.read_stateless(
+                    threads,
+                    module,
+                    &mut self.mu_val_fhe_uint,
+                    &self.pc_fhe_uint_prepared,
+                    2,
+                    keys,
+                    scratch,
+                );
+
+                self.pcu_rom.read_stateless(
+                    threads,
+                    module,
+                    &mut self.pcu_val_fhe_uint,
+                    &self.pc_fhe_uint_prepared,
+                    2,
+                    keys,
+                    scratch,
+                );
+
+                self.rs1_rom.read_stateless(
+                    threads,
+                    module,
+                    &mut self.rs1_addr_fhe_uint,
+                    &self.pc_fhe_uint_prepared,
+                    2,
+                    keys,
+                    scratch,
+                );
+                self.rs2_rom.read_stateless(
+                    threads,
+                    module,
+                    &mut self.rs2_addr_fhe_uint,
+                    &self.pc_fhe_uint_prepared,
+                    2,
+                    keys,
+                    scratch,
+                );
+                self.rd_rom.read_stateless(
+                    threads,
+                    module,
+                    &mut self.rd_addr_fhe_uint,
+                    &self.pc_fhe_uint_prepared,
+                    2,
+                    keys,
+                    scratch,
+                );
+            });
+
+            // Once halted, the instruction writes neither the registers nor the RAM:
+            // rd is set to x0 and the RAM update to NONE.
+            let zero: GLWE<Vec<u8>> = GLWE::alloc_from_infos(&self.rd_addr_fhe_uint);
+            let halted: GGSWPrepared<&[u8], BE> = self.status_fhe_uint_prepared.get_bit(0);
+            module.cmux_inplace_neg(&mut self.rd_addr_fhe_uint, &zero, &halted, scratch);
+            module.cmux_inplace_neg(&mut self.mu_val_fhe_uint, &zero, &halted, scratch);
+
+            this_cycle_measurement.time_prepare_rom = measure_duration(|| {
+                let rd_ops_bit_size: usize = rd_update_op_bit_size(&self.rd_update_ops());
+                let ram_ops_bit_size: usize =
+                    (usize::BITS - (RAM_UPDATE_OP_LIST.len() - 1).leading_zeros()) as usize;
+
+                module.prepare_multiple_fheuint(
+                    threads,
+                    &mut vec![
+                        &mut self.rdu_val_fhe_uint_prepared,
+                        &mut self.mu_val_fhe_uint_prepared,
+                        &mut self.pcu_val_fhe_uint_prepared,
+                        &mut self.imm_val_fhe_uint_prepared,
+                        &mut self.rs1_addr_fhe_uint_prepared,
+                        &mut self.rs2_addr_fhe_uint_prepared,
+                        &mut self.rd_addr_fhe_uint_prepared,
+                    ],
+                    vec![
+                        &self.rdu_val_fhe_uint,
+                        &self.mu_val_fhe_uint,
+                        &self.pcu_val_fhe_uint,
+                        &self.imm_val_fhe_uint,
+                        &self.rs1_addr_fhe_uint,
+                        &self.rs2_addr_fhe_uint,
+                        &self.rd_addr_fhe_uint,
+                    ],
+                    vec![
+                        rd_ops_bit_size,
+                        ram_ops_bit_size,
+                        4,
+                        32,
+                        self.reg_bit_size,
+                        self.reg_bit_size,
+                        self.reg_bit_size,
+                    ],
+                    keys,
+                    scratch,
+                );
+            });
+        });
+
+        if let (Some(sk), Some(vm_debug)) = (sk, &mut self.vm_debug) {
+            vm_debug.read_instructions();
+
+            let pc_have: u32 = self.pc_fhe_uint_prepared.decrypt(module, sk, keys, scratch);
+            let imm_have: u32 = self.imm_val_fhe_uint.decrypt(module, sk, scratch);
+            let rs1_have: u32 = self.rs1_addr_fhe_uint.decrypt(module, sk, scratch);
+            let rs2_have: u32 = self.rs2_addr_fhe_uint.decrypt(module, sk, scratch);
+            let rd_have: u32 = self.rd_addr_fhe_uint.decrypt(module, sk, scratch);
+            let rdu_have: u32 = self.rdu_val_fhe_uint.decrypt(module, sk, scratch);
+            let mu_have: u32 = self.mu_val_fhe_uint.decrypt(module, sk, scratch);
+            let pcu_have: u32 = self.pcu_val_fhe_uint.decrypt(module, sk, scratch);
+
+            let pc_want: u32 = vm_debug.pc;
+            let imm_want: u32 = vm_debug.imm;
+            let rs1_want: u32 = vm_debug.rs1_addr;
+            let rs2_want: u32 = vm_debug.rs2_addr;
+            let rd_want: u32 = vm_debug.rd_addr;
+            let rdu_want: u32 = vm_debug.rdu;
+            let mu_want: u32 = vm_debug.mu;
+            let pcu_want: u32 = vm_debug.pcu;
+
+            println!("READ ROM");
+            let pc_val_fhe_uint_noise = self
+                .pc_fhe_uint
+                .noise(module, pc_want, sk, scratch)
+                .max()
+                .log2();
+            println!(
+                "   pc_val  : {pc_have:08x} - {pc_want:08x} - {:.2}",
+                pc_val_fhe_uint_noise
+            );
+            this_cycle_measurement.pc_val_fhe_uint_noise = pc_val_fhe_uint_noise;
+
+            let imm_val_fhe_uint_noise = self
+                .imm_val_fhe_uint
+                .noise(module, imm_want, sk, scratch)
+                .max()
+                .log2();
+            println!(
+                "   imm_val : {imm_have:08x} - {imm_want:08x} - {:.2}",
+                imm_val_fhe_uint_noise
+            );
+            this_cycle_measurement.imm_val_fhe_uint_noise = imm_val_fhe_uint_noise;
+
+            let rs1_addr_fhe_uint_noise = self
+                .rs1_addr_fhe_uint
+                .noise(module, rs1_want, sk, scratch)
+                .max()
+                .log2();
+            println!(
+                "   rs1_addr: {rs1_have:08x} - {rs1_want:08x} - {:.2}",
+                rs1_addr_fhe_uint_noise
+            );
+
+            let rs2_addr_fhe_uint_noise = self
+                .rs2_addr_fhe_uint
+                .noise(module, rs2_want, sk, scratch)
+                .max()
+                .log2();
+            println!(
+                "   rs2_addr: {rs2_have:08x} - {rs2_want:08x} - {:.2}",
+                rs2_addr_fhe_uint_noise
+            );
+
+            let rd_addr_fhe_uint_noise = self
+                .rd_addr_fhe_uint
+                .noise(module, rd_want, sk, scratch)
+                .max()
+                .log2();
+            println!(
+                "   rd_addr : {rd_have:08x} - {rd_want:08x} - {:.2}",
+                rd_addr_fhe_uint_noise
+            );
+
+            let rdu_val_fhe_uint_noise = self
+                .rdu_val_fhe_uint
+                .noise(module, rdu_want, sk, scratch)
+                .max()
+                .log2();
+            println!(
+                "   rdu_val : {rdu_have:08x} - {rdu_want:08x} - {:.2}",
+                rdu_val_fhe_uint_noise
+            );
+
+            let mu_val_fhe_uint_noise = self
+                .mu_val_fhe_uint
+                .noise(module, mu_want, sk, scratch)
+                .max()
+                .log2();
+            println!(
+                "   mu_val  : {mu_have:08x} - {mu_want:08x} - {:.2}",
+                mu_val_fhe_uint_noise
+            );
+
+            let pcu_val_fhe_uint_noise = self
+                .pcu_val_fhe_uint
+                .noise(module, pcu_want, sk, scratch)
+                .max()
+                .log2();
+            println!(
+                "   pcu_val : {pcu_have:08x} - {pcu_want:08x} - {:.2}",
+                pcu_val_fhe_uint_noise
+            );
+            assert_eq!(pc_have, pc_want);
+            assert_eq!(imm_have, imm_want);
+            assert_eq!(rs1_have, rs1_want);
+            assert_eq!(rs2_have, rs2_want);
+            assert_eq!(rd_have, rd_want);
+            assert_eq!(rdu_have, rdu_want);
+            assert_eq!(mu_have, mu_want);
+            assert_eq!(pcu_have, pcu_want);
+        }
+    }
+
+    pub(crate) fn read_and_prepare_registers<M, DK, H, BRA, K, S>(
+        &mut self,
+        threads: usize,
+        module: &M,
+        keys: &H,
+        sk: Option<&S>,
+        scratch: &mut Scratch<BE>,
+        this_cycle_measurement: &mut Measurement,
+    ) where
+        BRA: BlindRotationAlgo,
+        DK: DataRef,
+        M: Sync
+            + FheUintPreparedFactory<u32, BE>
+            + FheUintPrepare<BRA, BE>
+            + GGSWPreparedFactory<BE>
+            + GLWEExternalProduct<BE>
+            + GLWEPackerOps<BE>
+            + GLWETrace<BE>
+            + GLWEPacking<BE>
+            + ModuleN
+            + FheUintPreparedFactory<u32, BE>
+            + PrepareMultiple<BE, BRA>
+            + FheUintPrepare<BRA, BE>
+            + GGSWBlindRotation<u32, BE>
+            + GLWEDecrypt<BE>
+            + GLWENoise<BE>,
+        H: Sync + BDDKeyHelper<DK, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
+        K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos,
         Scratch<BE>: ScratchTakeCore<BE>,
     {
-        this_cycle_measurement.time_read_and_prepare_rom = measure_duration(|| {
-            this_cycle_measurement.time_read_rom = measure_duration(|| {
-                self.imm_rom.read_stateless(
+        this_cycle_measurement.time_read_and_prepare_registers = measure_duration(|| {
+            this_cycle_measurement.time_read_registers = measure_duration(|| {
+                self.registers.read_stateless(
                     threads,
                     module,
-                    &mut self.imm_val_fhe_uint,
-                    &self.pc_fhe_uint_prepared,
-                    2,
+                    &mut self.rs1_val_fhe_uint,
+                    &self.rs1_addr_fhe_uint_prepared,
+                    0,
                     keys,
                     scratch,
                 );
 
-                self.rdu_rom.read_stateless(
+                self.registers.read_stateless(
                     threads,
                     module,
-                    &mut self.rdu_val_fhe_uint,
-                    &self.pc_fhe_uint_prepared,
-                    2,
+                    &mut self.rs2_val_fhe_uint,
+                    &self.rs2_addr_fhe_uint_prepared,
+                    0,
                     keys,
                     scratch,
                 );
+            });
 
-                self.mu_rom.read_stateless(
+            this_cycle_measurement.time_prepare_registers = measure_duration(|| {
+                module.prepare_multiple_fheuint(
                     threads,
-                    module,
-                    &mut self.mu_val_fhe_uint,
-                    &self.pc_fhe_uint_prepared,
-                    2,
+                    &mut vec![
+                        &mut self.rs1_val_fhe_uint_prepared,
+                        &mut self.rs2_val_fhe_uint_prepared,
+                    ],
+                    vec![&self.rs1_val_fhe_uint, &self.rs2_val_fhe_uint],
+                    vec![32, 32],
                     keys,
                     scratch,
                 );
+            });
+        });
 
-                self.pcu_rom.read_stateless(
-                    threads,
-                    module,
-                    &mut self.pcu_val_fhe_uint,
-                    &self.pc_fhe_uint_prepared,
-                    2,
-                    keys,
-                    scratch,
-                );
+        if let (Some(sk), Some(vm_debug)) = (sk, &mut self.vm_debug) {
+            vm_debug.read_registers();
+            let rs1_have: u32 = self.rs1_val_fhe_uint.decrypt(module, sk, scratch);
+            let rs2_have: u32 = self.rs2_val_fhe_uint.decrypt(module, sk, scratch);
+            let rs1_want: u32 = vm_debug.rs1_val;
+            let rs2_want: u32 = vm_debug.rs2_val;
+            println!("READ RD");
+            println!(
+                "   rs1_val : {rs1_have:08x} - {rs1_want:08x} - {:.2}",
+                self.rs1_val_fhe_uint
+                    .noise(module, rs1_want, sk, scratch)
+                    .max()
+                    .log2()
+            );
+            assert_eq!(rs1_have, rs1_want);
+            println!(
+                "   rs2_val : {rs2_have:08x} - {rs2_want:08x} - {:.2}",
+                self.rs2_val_fhe_uint
+                    .noise(module, rs2_want, sk, scratch)
+                    .max()
+                    .log2()
+            );
+            assert_eq!(rs2_have, rs2_want);
+        }
+    }
 
-                self.rs1_rom.read_stateless(
-                    threads,
-                    module,
-                    &mut self.rs1_addr_fhe_uint,
-                    &self.pc_fhe_uint_prepared,
-                    2,
-                    keys,
-                    scratch,
-                );
-                self.rs2_rom.read_stateless(
+    pub(crate) fn read_ram<D, M, H, BRA, K, S>(
+        &mut self,
+        threads: usize,
+        module: &M,
+        keys: &H,
+        sk: Option<&S>,
+        scratch: &mut Scratch<BE>,
+        this_cycle_measurement: &mut Measurement,
+    ) where
+        M: Sync
+            + GGSWPreparedFactory<BE>
+            + GLWEExternalProduct<BE>
+            + GLWEPackerOps<BE>
+            + GLWETrace<BE>
+            + GLWEPacking<BE>
+            + ModuleN
+            + GGSWBlindRotation<u32, BE>
+            + GGSWPreparedFactory<BE>
+            + ExecuteBDDCircuit2WTo1W<BE>
+            + FheUintPrepare<BRA, BE>
+            + GLWEBlinSelection<u32, BE>
+            + GLWENoise<BE>
+            + GLWEBlindRetrieval<BE>
+            + ExecuteBDDCircuit1WTo1W<BE>
+            + ExecuteBDDCircuit<BE>
+            + ModuleLogN
+            + GLWECopy
+            + PrepareMultiple<BE, BRA>
+            + Cmux<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+        H: Sync + BDDKeyHelper<D, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
+        K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+        BRA: BlindRotationAlgo,
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos,
+        D: DataRef,
+    {
+        this_cycle_measurement.time_read_ram = measure_duration(|| {
+            this_cycle_measurement.time_derive_ram_addr = measure_duration(|| {
+                // Derives ram address = [rs2 + imm + 2^18]
+                ram_offset(
                     threads,
                     module,
-                    &mut self.rs2_addr_fhe_uint,
-                    &self.pc_fhe_uint_prepared,
-                    2,
+                    &mut self.ram_addr_fhe_uint,
+                    &self.imm_val_fhe_uint_prepared,
+                    &self.rs1_val_fhe_uint_prepared,
                     keys,
                     scratch,
                 );
-                self.rd_rom.read_stateless(
+            });
+
+            let ram_addr_bit_size: usize = self.ram_addr_bit_size();
+            this_cycle_measurement.time_prepare_ram_addr = measure_duration(|| {
+                self.ram_addr_fhe_uint_prepared.prepare_custom_multi_thread(
                     threads,
                     module,
-                    &mut self.rd_addr_fhe_uint,
-                    &self.pc_fhe_uint_prepared,
-                    2,
+                    &self.ram_addr_fhe_uint,
+                    0,
+                    ram_addr_bit_size, // ram_bit_size is 4bytes alined
                     keys,
                     scratch,
                 );
             });
 
-            this_cycle_measurement.time_prepare_rom = measure_duration(|| {
-                let rd_ops_bit_size: usize = match self.instruction_set {
-                    InstructionSet::RV32M => unimplemented!(),
-                    InstructionSet::RV32I => {
-                        (usize::BITS - (RD_UPDATE_RV32I_OP_LIST.len() - 1).leading_zeros()) as usize
-                    }
-                };
-                let ram_ops_bit_size: usize =
-                    (usize::BITS - (RAM_UPDATE_OP_LIST.len() - 1).leading_zeros()) as usize;
-
-                module.prepare_multiple_fheuint(
+            // A misaligned access writes neither rd nor the RAM: rd is set to x0
+            // and the RAM update to NONE (see [crate::alignment]).
+            let rd_ops_bit_size: usize = rd_update_op_bit_size(&self.rd_update_ops());
+            let ram_ops_bit_size: usize =
+                (usize::BITS - (RAM_UPDATE_OP_LIST.len() - 1).leading_zeros()) as usize;
+            eval_misaligned(
+                threads,
+                module,
+                &mut self.misaligned_fhe_uint,
+                &self.ram_addr_fhe_uint_prepared,
+                &self.mu_val_fhe_uint_prepared,
+                &self.rdu_val_fhe_uint_prepared,
+                rd_ops_bit_size,
+                &misaligned_circuit(rd_ops_bit_size),
+                keys,
+                scratch,
+            );
+            self.misaligned_fhe_uint_prepared
+                .prepare_custom_multi_thread(
                     threads,
-                    &mut vec![
-                        &mut self.rdu_val_fhe_uint_prepared,
-                        &mut self.mu_val_fhe_uint_prepared,
-                        &mut self.pcu_val_fhe_uint_prepared,
-                        &mut self.imm_val_fhe_uint_prepared,
-                        &mut self.rs1_addr_fhe_uint_prepared,
-                        &mut self.rs2_addr_fhe_uint_prepared,
-                        &mut self.rd_addr_fhe_uint_prepared,
-                    ],
-                    vec![
-                        &self.rdu_val_fhe_uint,
-                        &self.mu_val_fhe_uint,
-                        &self.pcu_val_fhe_uint,
-                        &self.imm_val_fhe_uint,
-                        &self.rs1_addr_fhe_uint,
-                        &self.rs2_addr_fhe_uint,
-                        &self.rd_addr_fhe_uint,
-                    ],
-                    vec![
-                        rd_ops_bit_size,
-                        ram_ops_bit_size,
-                        4,
-                        32,
-                        self.reg_bit_size,
-                        self.reg_bit_size,
-                        self.reg_bit_size,
-                    ],
+                    module,
+                    &self.misaligned_fhe_uint,
+                    0,
+                    1,
+                    keys,
+                    scratch,
+                );
+            let zero: GLWE<Vec<u8>> = GLWE::alloc_from_infos(&self.rd_addr_fhe_uint);
+            let misaligned: GGSWPrepared<&[u8], BE> = self.misaligned_fhe_uint_prepared.get_bit(0);
+            module.cmux_inplace_neg(&mut self.rd_addr_fhe_uint, &zero, &misaligned, scratch);
+            module.cmux_inplace_neg(&mut self.mu_val_fhe_uint, &zero, &misaligned, scratch);
+            // Stores to the read-only data bank are dropped (see [crate::rodata]).
+            if let Some(bit) = self.rodata_bit() {
+                let in_rodata: GGSWPrepared<&[u8], BE> =
+                    self.ram_addr_fhe_uint_prepared.get_bit(bit);
+                module.cmux_inplace_neg(&mut self.mu_val_fhe_uint, &zero, &in_rodata, scratch);
+            }
+            module.prepare_multiple_fheuint(
+                threads,
+                &mut vec![
+                    &mut self.mu_val_fhe_uint_prepared,
+                    &mut self.rd_addr_fhe_uint_prepared,
+                ],
+                vec![&self.mu_val_fhe_uint, &self.rd_addr_fhe_uint],
+                vec![ram_ops_bit_size, self.reg_bit_size],
+                keys,
+                scratch,
+            );
+
+            this_cycle_measurement.time_prepare_ram_read_statefull = measure_duration(|| {
+                // Read ram_val_fhe_uint from Ram[rs2 + imm]
+                self.ram.read_statefull(
+                    threads,
+                    module,
+                    &mut self.ram_val_fhe_uint,
+                    &self.ram_addr_fhe_uint_prepared,
+                    2,
                     keys,
                     scratch,
                 );
             });
+
+            if let Some(rodata) = &mut self.rodata {
+                // Read rodata_val_fhe_uint from Rodata[rs2 + imm], selected
+                // over ram_val_fhe_uint by the loads from the bank
+                rodata.read_stateless(
+                    threads,
+                    module,
+                    &mut self.rodata_val_fhe_uint,
+                    &self.ram_addr_fhe_uint_prepared,
+                    2,
+                    keys,
+                    scratch,
+                );
+            }
         });
 
         if let (Some(sk), Some(vm_debug)) = (sk, &mut self.vm_debug) {
-            vm_debug.read_instructions();
-
-            let pc_have: u32 = self.pc_fhe_uint_prepared.decrypt(module, sk, keys, scratch);
-            let imm_have: u32 = self.imm_val_fhe_uint.decrypt(module, sk, scratch);
-            let rs1_have: u32 = self.rs1_addr_fhe_uint.decrypt(module, sk, scratch);
-            let rs2_have: u32 = self.rs2_addr_fhe_uint.decrypt(module, sk, scratch);
-            let rd_have: u32 = self.rd_addr_fhe_uint.decrypt(module, sk, scratch);
-            let rdu_have: u32 = self.rdu_val_fhe_uint.decrypt(module, sk, scratch);
-            let mu_have: u32 = self.mu_val_fhe_uint.decrypt(module, sk, scratch);
-            let pcu_have: u32 = self.pcu_val_fhe_uint.decrypt(module, sk, scratch);
-
-            let pc_want: u32 = vm_debug.pc;
-            let imm_want: u32 = vm_debug.imm;
-            let rs1_want: u32 = vm_debug.rs1_addr;
-            let rs2_want: u32 = vm_debug.rs2_addr;
-            let rd_want: u32 = vm_debug.rd_addr;
-            let rdu_want: u32 = vm_debug.rdu;
-            let mu_want: u32 = vm_debug.mu;
-            let pcu_want: u32 = vm_debug.pcu;
-
-            println!("READ ROM");
-            let pc_val_fhe_uint_noise = self
-                .pc_fhe_uint
-                .noise(module, pc_want, sk, scratch)
+            vm_debug.read_ram();
+            let ram_addr_have: u32 = self.ram_addr_fhe_uint.decrypt(module, sk, scratch);
+            let ram_val_have: u32 = self.ram_val_fhe_uint.decrypt(module, sk, scratch);
+            let ram_addr_want: u32 = vm_debug.ram_addr;
+            let ram_val_want: u32 = vm_debug.ram_val;
+            println!("READ RAM");
+            let ram_addr_read_noise = self
+                .ram_addr_fhe_uint
+                .noise(module, ram_addr_want, sk, scratch)
                 .max()
                 .log2();
             println!(
-                "   pc_val  : {pc_have:08x} - {pc_want:08x} - {:.2}",
-                pc_val_fhe_uint_noise
+                "   ram_addr: {ram_addr_have:08x} - {ram_addr_want:08x} - {:.2}",
+                ram_addr_read_noise
             );
-            this_cycle_measurement.pc_val_fhe_uint_noise = pc_val_fhe_uint_noise;
-
-            let imm_val_fhe_uint_noise = self
-                .imm_val_fhe_uint
-                .noise(module, imm_want, sk, scratch)
-                .max()
-                .log2();
-            println!(
-                "   imm_val : {imm_have:08x} - {imm_want:08x} - {:.2}",
-                imm_val_fhe_uint_noise
+            this_cycle_measurement.ram_addr_read_noise = ram_addr_read_noise;
+            assert_eq!(
+                ram_addr_have, ram_addr_want,
+                "ram addr noise {ram_addr_read_noise:.2}"
             );
-            this_cycle_measurement.imm_val_fhe_uint_noise = imm_val_fhe_uint_noise;
-
-            let rs1_addr_fhe_uint_noise = self
-                .rs1_addr_fhe_uint
-                .noise(module, rs1_want, sk, scratch)
+            let ram_val_read_noise = self
+                .ram_val_fhe_uint
+                .noise(module, ram_val_want, sk, scratch)
                 .max()
                 .log2();
             println!(
-                "   rs1_addr: {rs1_have:08x} - {rs1_want:08x} - {:.2}",
-                rs1_addr_fhe_uint_noise
+                "   ram_val : {ram_val_have:08x} - {ram_val_want:08x} - {:.2}",
+                ram_val_read_noise
             );
+            this_cycle_measurement.ram_val_read_noise = ram_val_read_noise;
+            assert_eq!(ram_val_have, ram_val_want);
+            let misaligned_have: bool =
+                self.misaligned_fhe_uint.decrypt(module, sk, scratch) & 1 == 1;
+            let misaligned_want: bool = vm_debug.misaligned;
+            println!("   misaligned: {misaligned_have} - {misaligned_want}");
+            assert_eq!(misaligned_have, misaligned_want);
+            if vm_debug.in_rodata {
+                let rodata_val_have: u32 = self.rodata_val_fhe_uint.decrypt(module, sk, scratch);
+                let rodata_val_want: u32 = vm_debug.rodata_val;
+                println!("   rodata_val: {rodata_val_have:08x} - {rodata_val_want:08x}");
+                assert_eq!(rodata_val_have, rodata_val_want);
+            }
+        }
+    }
 
-            let rs2_addr_fhe_uint_noise = self
-                .rs2_addr_fhe_uint
-                .noise(module, rs2_want, sk, scratch)
-                .max()
-                .log2();
-            println!(
-                "   rs2_addr: {rs2_have:08x} - {rs2_want:08x} - {:.2}",
-                rs2_addr_fhe_uint_noise
-            );
+    // Borrows the state of the register, RAM and PC updates apart from each
+    // other, so that they can run concurrently.
+    fn update_views(&mut self) -> (RegistersUpdate<'_, BE>, RamUpdate<'_, BE>, PcUpdate<'_, BE>) {
+        let rodata_bit: Option<usize> = self.rodata_bit();
+        let Self {
+            ref custom_instructions,
+            ref mut registers,
+            ref mut ram,
+            ref ram_addr_fhe_uint_prepared,
+            ref ram_val_fhe_uint,
+            ref mut ram_store_fhe_uint,
+            ref mut ram_val_fhe_uint_prepared,
+            ref rodata_val_fhe_uint,
+            ref mut pc_fhe_uint,
+            ref pc_fhe_uint_prepared,
+            ref mut pc_prev_fhe_uint,
+            ref status_fhe_uint_prepared,
+            ref misaligned_fhe_uint_prepared,
+            ref rs1_val_fhe_uint_prepared,
+            ref rs2_val_fhe_uint,
+            ref rs2_val_fhe_uint_prepared,
+            ref imm_val_fhe_uint,
+            ref imm_val_fhe_uint_prepared,
+            ref rd_addr_fhe_uint_prepared,
+            ref mut rd_val_fhe_uint,
+            ref mut rd_val_fhe_uint_prepared,
+            ref rdu_val_fhe_uint_prepared,
+            ref mu_val_fhe_uint_prepared,
+            ref pcu_val_fhe_uint_prepared,
+            ..
+        } = *self;
+
+        (
+            RegistersUpdate {
+                rodata_bit,
+                custom_instructions,
+                rs1_val_fhe_uint_prepared,
+                rs2_val_fhe_uint_prepared,
+                imm_val_fhe_uint,
+                imm_val_fhe_uint_prepared,
+                pc_fhe_uint_prepared,
+                ram_addr_fhe_uint_prepared,
+                ram_val_fhe_uint,
+                rodata_val_fhe_uint,
+                rdu_val_fhe_uint_prepared,
+                rd_addr_fhe_uint_prepared,
+                rd_val_fhe_uint,
+                rd_val_fhe_uint_prepared,
+                registers,
+            },
+            RamUpdate {
+                imm_val_fhe_uint,
+                rs2_val_fhe_uint,
+                ram_val_fhe_uint,
+                ram_addr_fhe_uint_prepared,
+                mu_val_fhe_uint_prepared,
+                ram_store_fhe_uint,
+                ram_val_fhe_uint_prepared,
+                ram,
+            },
+            PcUpdate {
+                rs1_val_fhe_uint_prepared,
+                rs2_val_fhe_uint_prepared,
+                imm_val_fhe_uint_prepared,
+                pc_fhe_uint_prepared,
+                pcu_val_fhe_uint_prepared,
+                status_fhe_uint_prepared,
+                misaligned_fhe_uint_prepared,
+                pc_fhe_uint,
+                pc_prev_fhe_uint,
+            },
+        )
+    }
 
-            let rd_addr_fhe_uint_noise = self
-                .rd_addr_fhe_uint
-                .noise(module, rd_want, sk, scratch)
-                .max()
-                .log2();
-            println!(
-                "   rd_addr : {rd_have:08x} - {rd_want:08x} - {:.2}",
-                rd_addr_fhe_uint_noise
-            );
+    // Work of the register, RAM and PC updates at the last cycle, i.e. their
+    // times multiplied by the threads they ran with (see [crate::scheduler]).
+    fn update_work(&self) -> [f64; UPDATES] {
+        let Some(last) = self.measurements.cycle_measurements.last() else {
+            return UPDATE_COSTS;
+        };
+        let times: [Duration; UPDATES] = [
+            last.time_update_registers,
+            last.time_update_ram + last.time_refresh_ram,
+            last.time_update_pc,
+        ];
+        let work: [f64; UPDATES] =
+            array::from_fn(|i| times[i].as_secs_f64() * self.update_threads[i] as f64);
+        if work.iter().all(|w| *w > 0.0) {
+            work
+        } else {
+            UPDATE_COSTS
+        }
+    }
 
-            let rdu_val_fhe_uint_noise = self
-                .rdu_val_fhe_uint
-                .noise(module, rdu_want, sk, scratch)
-                .max()
-                .log2();
-            println!(
-                "   rdu_val : {rdu_have:08x} - {rdu_want:08x} - {:.2}",
-                rdu_val_fhe_uint_noise
-            );
+    // Runs the register update, the RAM update followed by the refresh of the
+    // RAM, and the PC update at the same time, then checks them against the
+    // debug interpreter in the order they would have run one after the other.
+    fn run_updates_concurrently<M, DK, H, BRA, K, S>(
+        &mut self,
+        module: &M,
+        keys: &H,
+        sk: Option<&S>,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: Sync
+            + GGSWPreparedFactory<BE>
+            + GLWEExternalProduct<BE>
+            + GLWEPackerOps<BE>
+            + GLWETrace<BE>
+            + GLWEPacking<BE>
+            + FheUintPreparedFactory<u32, BE>
+            + PrepareMultiple<BE, BRA>
+            + FheUintPrepare<BRA, BE>
+            + ExecuteBDDCircuit2WTo1W<BE>
+            + ExecuteBDDCircuit1WTo1W<BE>
+            + GLWEBlinSelection<u32, BE>
+            + GGSWBlindRotation<u32, BE>
+            + GLWENoise<BE>
+            + GGSWEncryptSk<BE>
+            + FheUintPreparedFactory<u32, BE>
+            + FheUintPreparedEncryptSk<u32, BE>
+            + GLWEBlindRetrieval<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+        BRA: BlindRotationAlgo,
+        DK: DataRef,
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos + GetDistribution,
+        H: Sync + BDDKeyHelper<DK, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
+        K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+    {
+        let (mut this_cycle_measurement, start_cycle_time) = self
+            .cycle_in_progress
+            .take()
+            .unwrap_or_else(|| panic!("cycle {} not started", self.cycle));
+
+        let rd_update_ops: Vec<RD_UPDATE> = self.rd_update_evaluated_ops();
+        let rd_ops_bit_size: usize = rd_update_op_bit_size(&self.rd_update_ops());
+        let ram_update_ops: Vec<RAM_UPDATE> = self.ram_update_evaluated_ops();
+        let addrs: Vec<usize> = self.ram_refresh_addrs();
+        let threads: [usize; UPDATES] = split_threads(self.threads, self.update_work());
+
+        let mut measurements: [Measurement; UPDATES] = array::from_fn(|_| Measurement::new());
+        let time_updates: Duration = {
+            let [scratch_registers, scratch_ram, scratch_pc] = split_scratch(scratch, threads);
+            let [measurement_registers, measurement_ram, measurement_pc] = &mut measurements;
+            let (mut registers_update, mut ram_update, mut pc_update) = self.update_views();
+            measure_duration(|| {
+                thread::scope(|scope| {
+                    scope.spawn(|| {
+                        registers_update.run(
+                            threads[0],
+                            module,
+                            &rd_update_ops,
+                            rd_ops_bit_size,
+                            keys,
+                            scratch_registers,
+                            measurement_registers,
+                        )
+                    });
+                    scope.spawn(|| {
+                        ram_update.run(
+                            threads[1],
+                            module,
+                            &ram_update_ops,
+                            keys,
+                            scratch_ram,
+                            measurement_ram,
+                        );
+                        measurement_ram.time_refresh_ram = measure_duration(|| {
+                            ram_update.refresh(threads[1], module, &addrs, keys, scratch_ram)
+                        });
+                    });
+                    pc_update.run(threads[2], module, keys, scratch_pc, measurement_pc);
+                });
+            })
+        };
 
-            let mu_val_fhe_uint_noise = self
-                .mu_val_fhe_uint
-                .noise(module, mu_want, sk, scratch)
-                .max()
-                .log2();
-            println!(
-                "   mu_val  : {mu_have:08x} - {mu_want:08x} - {:.2}",
-                mu_val_fhe_uint_noise
-            );
+        for measurement in &measurements {
+            this_cycle_measurement.add_update_times(measurement);
+        }
+        this_cycle_measurement.time_critical_path += time_updates;
+        self.update_threads = threads;
 
-            let pcu_val_fhe_uint_noise = self
-                .pcu_val_fhe_uint
-                .noise(module, pcu_want, sk, scratch)
+        self.update_registers_debug(
+            module,
+            &rd_update_ops,
+            sk,
+            scratch,
+            &mut this_cycle_measurement,
+        );
+        self.update_ram_debug(module, sk, scratch);
+        self.update_pc_debug(module, sk, scratch);
+        self.refresh_ram_debug(module, &addrs, sk, scratch);
+
+        self.cycle_in_progress = Some((this_cycle_measurement, start_cycle_time));
+    }
+
+    // Mirrors the arguments of the register update, plus the debug secret-key.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn update_registers<M, H, D, BRA, K, S>(
+        &mut self,
+        threads: usize,
+        module: &M,
+        ops: &[RD_UPDATE],
+        keys: &H,
+        sk: Option<&S>,
+        scratch: &mut Scratch<BE>,
+        this_cycle_measurement: &mut Measurement,
+    ) where
+        M: Sync
+            + ExecuteBDDCircuit2WTo1W<BE>
+            + ExecuteBDDCircuit1WTo1W<BE>
+            + GLWEBlinSelection<u32, BE>
+            + ModuleLogN
+            + GLWERotate<BE>
+            + GLWETrace<BE>
+            + GLWESub
+            + GLWEAdd
+            + GLWECopy
+            + GGSWPreparedFactory<BE>
+            + ModuleN
+            + FheUintPreparedFactory<u32, BE>
+            + FheUintPrepare<BRA, BE>
+            + GGSWBlindRotation<u32, BE>
+            + GLWENormalize<BE>
+            + GLWEExternalProduct<BE>
+            + GLWENoise<BE>
+            + GLWEPackerOps<BE>
+            + GLWEBlindRetrieval<BE>
+            + GLWEPacking<BE>
+            + ExecuteBDDCircuit<BE>,
+        BRA: BlindRotationAlgo,
+        H: Sync + BDDKeyHelper<D, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
+        K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+        D: DataRef,
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        let ops_bit_size: usize = rd_update_op_bit_size(&self.rd_update_ops());
+        let (mut registers_update, _, _) = self.update_views();
+        registers_update.run(
+            threads,
+            module,
+            ops,
+            ops_bit_size,
+            keys,
+            scratch,
+            this_cycle_measurement,
+        );
+        self.update_registers_debug(module, ops, sk, scratch, this_cycle_measurement);
+    }
+
+    fn update_registers_debug<M, S>(
+        &mut self,
+        module: &M,
+        ops: &[RD_UPDATE],
+        sk: Option<&S>,
+        scratch: &mut Scratch<BE>,
+        this_cycle_measurement: &mut Measurement,
+    ) where
+        M: ModuleN + ModuleLogN + GLWEDecrypt<BE> + GLWENoise<BE> + GLWESub + GLWENormalize<BE>,
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        if let (Some(sk), Some(vm_debug)) = (sk, &mut self.vm_debug) {
+            vm_debug.update_registers(ops, &self.custom_instructions);
+
+            let rd_have: u32 = self.rd_val_fhe_uint.decrypt(module, sk, scratch);
+            let rd_want: u32 = vm_debug.rd_val;
+            println!("WRITE RD");
+            let rd_val_fhe_uint_noise = self
+                .rd_val_fhe_uint
+                .noise(module, rd_want, sk, scratch)
                 .max()
                 .log2();
             println!(
-                "   pcu_val : {pcu_have:08x} - {pcu_want:08x} - {:.2}",
-                pcu_val_fhe_uint_noise
+                "   rd_val  : {rd_have:08x} - {rd_want:08x} - {:.2}",
+                rd_val_fhe_uint_noise
             );
-            assert_eq!(pc_have, pc_want);
-            assert_eq!(imm_have, imm_want);
-            assert_eq!(rs1_have, rs1_want);
-            assert_eq!(rs2_have, rs2_want);
             assert_eq!(rd_have, rd_want);
-            assert_eq!(rdu_have, rdu_want);
-            assert_eq!(mu_have, mu_want);
-            assert_eq!(pcu_have, pcu_want);
+            this_cycle_measurement.rd_val_fhe_uint_noise = rd_val_fhe_uint_noise;
+
+            let mut registers_have: Vec<u32> = vec![0u32; self.registers.size()];
+            self.registers
+                .decrypt(module, &mut registers_have, sk, scratch);
+            let registers_want: &[u32] = &vm_debug.registers[..self.registers.size()];
+            println!("reg: {:?}", registers_have);
+            let reg_noise_vec: Vec<f64> = self.registers.noise(module, registers_want, sk, scratch);
+            if !reg_noise_vec.is_empty() {
+                let min: f64 = reg_noise_vec.iter().cloned().fold(f64::INFINITY, f64::min);
+                let max: f64 = reg_noise_vec
+                    .iter()
+                    .cloned()
+                    .fold(f64::NEG_INFINITY, f64::max);
+                println!("reg_noise: [min: {:.2}, max: {:.2}]", min, max);
+            } else {
+                println!("reg_noise: []");
+            }
+
+            assert_eq!(registers_have, registers_want);
         }
     }
 
-    pub(crate) fn read_and_prepare_registers<M, DK, H, BRA, K, S>(
+    pub(crate) fn update_ram<D, M, H, BRA, K, S>(
         &mut self,
         threads: usize,
         module: &M,
@@ -897,93 +2562,82 @@ impl<BE: Backend> Interpreter<BE> {
         scratch: &mut Scratch<BE>,
         this_cycle_measurement: &mut Measurement,
     ) where
-        BRA: BlindRotationAlgo,
-        DK: DataRef,
         M: Sync
-            + FheUintPreparedFactory<u32, BE>
-            + FheUintPrepare<BRA, BE>
             + GGSWPreparedFactory<BE>
             + GLWEExternalProduct<BE>
             + GLWEPackerOps<BE>
             + GLWETrace<BE>
             + GLWEPacking<BE>
             + ModuleN
-            + FheUintPreparedFactory<u32, BE>
-            + PrepareMultiple<BE, BRA>
-            + FheUintPrepare<BRA, BE>
             + GGSWBlindRotation<u32, BE>
-            + GLWEDecrypt<BE>
-            + GLWENoise<BE>,
-        H: Sync + BDDKeyHelper<DK, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
+            + GGSWPreparedFactory<BE>
+            + ExecuteBDDCircuit2WTo1W<BE>
+            + ExecuteBDDCircuit1WTo1W<BE>
+            + FheUintPrepare<BRA, BE>
+            + GLWEBlinSelection<u32, BE>
+            + GLWENoise<BE>
+            + GGSWEncryptSk<BE>
+            + GLWEBlindRetrieval<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+        H: Sync + BDDKeyHelper<D, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
         K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+        BRA: BlindRotationAlgo,
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos + GetDistribution,
+        D: DataRef,
+    {
+        let ops: Vec<RAM_UPDATE> = self.ram_update_evaluated_ops();
+        let (_, mut ram_update, _) = self.update_views();
+        ram_update.run(threads, module, &ops, keys, scratch, this_cycle_measurement);
+        self.update_ram_debug(module, sk, scratch);
+    }
+
+    fn update_ram_debug<M, S>(&mut self, module: &M, sk: Option<&S>, scratch: &mut Scratch<BE>)
+    where
+        M: ModuleN + ModuleLogN + GLWEDecrypt<BE> + GLWENoise<BE> + GLWESub + GLWENormalize<BE>,
         S: GLWESecretPreparedToRef<BE> + GLWEInfos,
         Scratch<BE>: ScratchTakeCore<BE>,
     {
-        this_cycle_measurement.time_read_and_prepare_registers = measure_duration(|| {
-            this_cycle_measurement.time_read_registers = measure_duration(|| {
-                self.registers.read_stateless(
-                    threads,
-                    module,
-                    &mut self.rs1_val_fhe_uint,
-                    &self.rs1_addr_fhe_uint_prepared,
-                    0,
-                    keys,
-                    scratch,
-                );
-
-                self.registers.read_stateless(
-                    threads,
-                    module,
-                    &mut self.rs2_val_fhe_uint,
-                    &self.rs2_addr_fhe_uint_prepared,
-                    0,
-                    keys,
-                    scratch,
-                );
-            });
-
-            this_cycle_measurement.time_prepare_registers = measure_duration(|| {
-                module.prepare_multiple_fheuint(
-                    threads,
-                    &mut vec![
-                        &mut self.rs1_val_fhe_uint_prepared,
-                        &mut self.rs2_val_fhe_uint_prepared,
-                    ],
-                    vec![&self.rs1_val_fhe_uint, &self.rs2_val_fhe_uint],
-                    vec![32, 32],
-                    keys,
-                    scratch,
-                );
-            });
-        });
-
         if let (Some(sk), Some(vm_debug)) = (sk, &mut self.vm_debug) {
-            vm_debug.read_registers();
-            let rs1_have: u32 = self.rs1_val_fhe_uint.decrypt(module, sk, scratch);
-            let rs2_have: u32 = self.rs2_val_fhe_uint.decrypt(module, sk, scratch);
-            let rs1_want: u32 = vm_debug.rs1_val;
-            let rs2_want: u32 = vm_debug.rs2_val;
-            println!("READ RD");
-            println!(
-                "   rs1_val : {rs1_have:08x} - {rs1_want:08x} - {:.2}",
-                self.rs1_val_fhe_uint
-                    .noise(module, rs1_want, sk, scratch)
-                    .max()
-                    .log2()
-            );
-            assert_eq!(rs1_have, rs1_want);
+            vm_debug.update_ram();
+            let ram_have: u32 = self.ram_store_fhe_uint.decrypt(module, sk, scratch);
+            let ram_want: u32 = vm_debug.ram_val;
+            println!("WRITE RAM");
             println!(
-                "   rs2_val : {rs2_have:08x} - {rs2_want:08x} - {:.2}",
-                self.rs2_val_fhe_uint
-                    .noise(module, rs2_want, sk, scratch)
+                "   ram_val : {ram_have:08x} - {ram_want:08x} - {:.2}",
+                self.ram_store_fhe_uint
+                    .noise(module, ram_want, sk, scratch)
                     .max()
                     .log2()
             );
-            assert_eq!(rs2_have, rs2_want);
+            assert_eq!(ram_have, ram_want);
+            let mut ram_have: Vec<u32> = vec![0u32; self.ram_size];
+            self.ram.decrypt(module, &mut ram_have, sk, scratch);
+            let ram_want: &Vec<u32> = &vm_debug.ram;
+            let ram_noise_vec = self.ram.noise(module, ram_want.as_slice(), sk, scratch);
+            if !ram_noise_vec.is_empty() {
+                let min = ram_noise_vec.iter().cloned().fold(f64::INFINITY, f64::min);
+                let max = ram_noise_vec
+                    .iter()
+                    .cloned()
+                    .fold(f64::NEG_INFINITY, f64::max);
+                println!("ram_noise: [{:.2}, {:.2}]", min, max);
+            } else {
+                println!("ram_noise: []");
+            }
+            assert_eq!(&ram_have, ram_want);
         }
     }
 
-    pub(crate) fn read_ram<D, M, H, BRA, K, S>(
+    // RAM words to refresh at the current cycle, following
+    // [Interpreter::ram_refresh_schedule].
+    fn ram_refresh_addrs(&self) -> Vec<usize> {
+        self.ram_refresh_schedule()
+            .map_or_else(Vec::new, |schedule| schedule.words_at(self.cycle as usize))
+    }
+
+    // Bootstraps the RAM words given by [Interpreter::ram_refresh_schedule]
+    // for the current cycle, which resets their noise.
+    pub(crate) fn refresh_ram<D, M, H, BRA, K, S>(
         &mut self,
         threads: usize,
         module: &M,
@@ -993,20 +2647,17 @@ impl<BE: Backend> Interpreter<BE> {
         this_cycle_measurement: &mut Measurement,
     ) where
         M: Sync
-            + GGSWPreparedFactory<BE>
-            + GLWEExternalProduct<BE>
-            + GLWEPackerOps<BE>
+            + ModuleLogN
+            + GLWERotate<BE>
             + GLWETrace<BE>
             + GLWEPacking<BE>
-            + ModuleN
-            + GGSWBlindRotation<u32, BE>
-            + GGSWPreparedFactory<BE>
-            + ExecuteBDDCircuit2WTo1W<BE>
+            + GLWECopy
+            + GLWEAdd
+            + GLWESub
+            + GLWENormalize<BE>
             + FheUintPrepare<BRA, BE>
-            + GLWEBlinSelection<u32, BE>
-            + GLWENoise<BE>
-            + GLWEBlindRetrieval<BE>
-            + ExecuteBDDCircuit1WTo1W<BE>,
+            + ExecuteBDDCircuit1WTo1W<BE>
+            + GLWEDecrypt<BE>,
         Scratch<BE>: ScratchTakeCore<BE>,
         H: Sync + BDDKeyHelper<D, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
         K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
@@ -1014,88 +2665,175 @@ impl<BE: Backend> Interpreter<BE> {
         S: GLWESecretPreparedToRef<BE> + GLWEInfos,
         D: DataRef,
     {
-        this_cycle_measurement.time_read_ram = measure_duration(|| {
-            this_cycle_measurement.time_derive_ram_addr = measure_duration(|| {
-                // Derives ram address = [rs2 + imm + 2^18]
-                ram_offset(
-                    threads,
-                    module,
-                    &mut self.ram_addr_fhe_uint,
-                    &self.imm_val_fhe_uint_prepared,
-                    &self.rs1_val_fhe_uint_prepared,
-                    keys,
-                    scratch,
-                );
-            });
+        let addrs: Vec<usize> = self.ram_refresh_addrs();
+        let (_, mut ram_update, _) = self.update_views();
+        this_cycle_measurement.time_refresh_ram = measure_duration(|| {
+            ram_update.refresh(threads, module, &addrs, keys, scratch);
+        });
+        self.refresh_ram_debug(module, &addrs, sk, scratch);
+    }
 
-            this_cycle_measurement.time_prepare_ram_addr = measure_duration(|| {
-                self.ram_addr_fhe_uint_prepared.prepare_custom_multi_thread(
-                    threads,
-                    module,
-                    &self.ram_addr_fhe_uint,
-                    0,
-                    self.ram_bit_size + 2, // ram_bit_size is 4bytes alined
-                    keys,
-                    scratch,
-                );
-            });
+    fn refresh_ram_debug<M, S>(
+        &self,
+        module: &M,
+        addrs: &[usize],
+        sk: Option<&S>,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: ModuleN + GLWEDecrypt<BE>,
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        if let (Some(sk), Some(vm_debug)) = (sk, &self.vm_debug) {
+            for &addr in addrs {
+                let mut have: [u32; 1] = [0];
+                self.ram.decrypt_range(module, addr, &mut have, sk, scratch);
+                assert_eq!(have[0], vm_debug.ram[addr], "refreshed RAM[{addr}]");
+            }
+        }
+    }
 
-            this_cycle_measurement.time_prepare_ram_read_statefull = measure_duration(|| {
-                // Read ram_val_fhe_uint from Ram[rs2 + imm]
-                self.ram.read_statefull(
-                    threads,
-                    module,
-                    &mut self.ram_val_fhe_uint,
-                    &self.ram_addr_fhe_uint_prepared,
-                    2,
-                    keys,
-                    scratch,
-                );
-            });
-        });
+    pub(crate) fn update_pc<M, K, S, H, BRA: BlindRotationAlgo, D>(
+        &mut self,
+        threads: usize,
+        module: &M,
+        keys: &H,
+        sk: Option<&S>,
+        scratch: &mut Scratch<BE>,
+        this_cycle_measurement: &mut Measurement,
+    ) where
+        M: ModuleLogN
+            + GLWEPacking<BE>
+            + GLWECopy
+            + ExecuteBDDCircuit<BE>
+            + FheUintPrepare<BRA, BE>
+            + Cmux<BE>
+            + GLWEDecrypt<BE>
+            + GLWENoise<BE>,
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos,
+        H: BDDKeyHelper<D, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
+        K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+        D: DataRef,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        let (_, _, mut pc_update) = self.update_views();
+        pc_update.run(threads, module, keys, scratch, this_cycle_measurement);
+        self.update_pc_debug(module, sk, scratch);
+    }
 
+    fn update_pc_debug<M, S>(&mut self, module: &M, sk: Option<&S>, scratch: &mut Scratch<BE>)
+    where
+        M: ModuleLogN + GLWEDecrypt<BE> + GLWENoise<BE>,
+        S: GLWESecretPreparedToRef<BE> + GLWEInfos,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
         if let (Some(sk), Some(vm_debug)) = (sk, &mut self.vm_debug) {
-            vm_debug.read_ram();
-            let ram_addr_have: u32 = self.ram_addr_fhe_uint.decrypt(module, sk, scratch);
-            let ram_val_have: u32 = self.ram_val_fhe_uint.decrypt(module, sk, scratch);
-            let ram_addr_want: u32 = vm_debug.ram_addr;
-            let ram_val_want: u32 = vm_debug.ram_val;
-            println!("READ RAM");
-            let ram_addr_read_noise = self
-                .ram_addr_fhe_uint
-                .noise(module, ram_addr_want, sk, scratch)
-                .max()
-                .log2();
+            vm_debug.update_pc();
+            let pc_have: u32 = self.pc_fhe_uint.decrypt(module, sk, scratch);
+            let pc_want: u32 = vm_debug.pc;
+            println!("UPDATE PC");
             println!(
-                "   ram_addr: {ram_addr_have:08x} - {ram_addr_want:08x} - {:.2}",
-                ram_addr_read_noise
-            );
-            this_cycle_measurement.ram_addr_read_noise = ram_addr_read_noise;
-            assert_eq!(
-                ram_addr_have, ram_addr_want,
-                "ram addr noise {ram_addr_read_noise:.2}"
+                "   pc_val  : {pc_have:08x} - {pc_want:08x} - {:.2}",
+                self.pc_fhe_uint
+                    .noise(module, pc_want, sk, scratch)
+                    .max()
+                    .log2()
             );
-            let ram_val_read_noise = self
-                .ram_val_fhe_uint
-                .noise(module, ram_val_want, sk, scratch)
-                .max()
-                .log2();
+            assert_eq!(pc_have, pc_want);
+        }
+    }
+
+    pub fn print_timings(&self, cycle: usize) {
+        if self.verbose_timings {
+            let this_cycle: &Measurement = &self.measurements.cycle_measurements[cycle];
+
             println!(
-                "   ram_val : {ram_val_have:08x} - {ram_val_want:08x} - {:.2}",
-                ram_val_read_noise
+                "
+Cycle Time: {:?}
+Critical path: {:?}
+  1. Prepare pc: {:?}
+  2. Read and prepare rom: {:?}
+     - Read rom: {:?}
+     - Prepare rom: {:?}
+  3. Read and prepare registers: {:?}
+     - Read registers: {:?}
+     - Prepare registers: {:?}
+  4. Read ram: {:?}
+     - Derive addr: {:?}
+     - Prepare addr: {:?}
+     - Read ram: {:?}
+  5. Update registers: {:?}
+     - Evaluate rd ops: {:?}
+     - Blind select rd: {:?}
+     - Refresh rd: {:?}
+     - Write rd: {:?}
+  6. Update ram: {:?}
+     - Evaluate store ops: {:?}
+     - Blind select value: {:?}
+     - Refresh value: {:?}
+     - Write ram: {:?}
+  7. Update pc: {:?}
+  8. Refresh ram: {:?}
+",
+                this_cycle.total_cycle_time,
+                this_cycle.time_critical_path,
+                this_cycle.time_prepare_pc,
+                this_cycle.time_read_and_prepare_rom,
+                this_cycle.time_read_rom,
+                this_cycle.time_prepare_rom,
+                this_cycle.time_read_and_prepare_registers,
+                this_cycle.time_read_registers,
+                this_cycle.time_prepare_registers,
+                this_cycle.time_read_ram,
+                this_cycle.time_derive_ram_addr,
+                this_cycle.time_prepare_ram_addr,
+                this_cycle.time_prepare_ram_read_statefull,
+                this_cycle.time_update_registers,
+                this_cycle.time_evaluate_rd_ops,
+                this_cycle.time_blind_select_rd,
+                this_cycle.time_refresh_rd,
+                this_cycle.time_write_rd,
+                this_cycle.time_update_ram,
+                this_cycle.time_ram_update_op_eval,
+                this_cycle.time_blind_select_ram_value,
+                this_cycle.time_refresh_ram_value,
+                this_cycle.time_write_ram,
+                this_cycle.time_update_pc,
+                this_cycle.time_refresh_ram,
             );
-            this_cycle_measurement.ram_val_read_noise = ram_val_read_noise;
-            assert_eq!(ram_val_have, ram_val_want);
         }
     }
+}
 
-    pub(crate) fn update_registers<M, H, D, BRA, K, S>(
+// State read and written by the register update, borrowed apart from the one
+// of the RAM and PC updates (see [Interpreter::update_views]).
+struct RegistersUpdate<'a, BE: Backend> {
+    rodata_bit: Option<usize>,
+    custom_instructions: &'a CustomInstructions,
+    rs1_val_fhe_uint_prepared: &'a FheUintPrepared<Vec<u8>, u32, BE>,
+    rs2_val_fhe_uint_prepared: &'a FheUintPrepared<Vec<u8>, u32, BE>,
+    imm_val_fhe_uint: &'a FheUint<Vec<u8>, u32>,
+    imm_val_fhe_uint_prepared: &'a FheUintPrepared<Vec<u8>, u32, BE>,
+    pc_fhe_uint_prepared: &'a FheUintPrepared<Vec<u8>, u32, BE>,
+    ram_addr_fhe_uint_prepared: &'a FheUintPrepared<Vec<u8>, u32, BE>,
+    ram_val_fhe_uint: &'a FheUint<Vec<u8>, u32>,
+    rodata_val_fhe_uint: &'a FheUint<Vec<u8>, u32>,
+    rdu_val_fhe_uint_prepared: &'a FheUintPrepared<Vec<u8>, u32, BE>,
+    rd_addr_fhe_uint_prepared: &'a FheUintPrepared<Vec<u8>, u32, BE>,
+    rd_val_fhe_uint: &'a mut FheUint<Vec<u8>, u32>,
+    rd_val_fhe_uint_prepared: &'a mut FheUintPrepared<Vec<u8>, u32, BE>,
+    registers: &'a mut Memory,
+}
+
+impl<BE: Backend> RegistersUpdate<'_, BE> {
+    #[allow(clippy::too_many_arguments)]
+    fn run<M, H, D, BRA, K>(
         &mut self,
         threads: usize,
         module: &M,
         ops: &[RD_UPDATE],
+        ops_bit_size: usize,
         keys: &H,
-        sk: Option<&S>,
         scratch: &mut Scratch<BE>,
         this_cycle_measurement: &mut Measurement,
     ) where
@@ -1118,31 +2856,82 @@ impl<BE: Backend> Interpreter<BE> {
             + GLWEExternalProduct<BE>
             + GLWENoise<BE>
             + GLWEPackerOps<BE>
-            + GLWEBlindRetrieval<BE>,
+            + GLWEBlindRetrieval<BE>
+            + GLWEPacking<BE>
+            + ExecuteBDDCircuit<BE>,
         BRA: BlindRotationAlgo,
         H: Sync + BDDKeyHelper<D, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
         K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
         D: DataRef,
-        S: GLWESecretPreparedToRef<BE> + GLWEInfos,
         Scratch<BE>: ScratchTakeCore<BE>,
     {
         this_cycle_measurement.time_update_registers = measure_duration(|| {
-            let rs1: &FheUintPrepared<Vec<u8>, u32, BE> = &self.rs1_val_fhe_uint_prepared;
-            let rs2: &FheUintPrepared<Vec<u8>, u32, BE> = &self.rs2_val_fhe_uint_prepared;
-            let imm: &FheUintPrepared<Vec<u8>, u32, BE> = &self.imm_val_fhe_uint_prepared;
-            let pc: &FheUintPrepared<Vec<u8>, u32, BE> = &self.pc_fhe_uint_prepared;
-            let ram_val: &FheUint<Vec<u8>, u32> = &self.ram_val_fhe_uint;
-
+            let rs1: &FheUintPrepared<Vec<u8>, u32, BE> = self.rs1_val_fhe_uint_prepared;
+            let rs2: &FheUintPrepared<Vec<u8>, u32, BE> = self.rs2_val_fhe_uint_prepared;
+            let imm: &FheUintPrepared<Vec<u8>, u32, BE> = self.imm_val_fhe_uint_prepared;
+            let pc: &FheUintPrepared<Vec<u8>, u32, BE> = self.pc_fhe_uint_prepared;
             let mut rd_map: HashMap<u32, FheUint<Vec<u8>, u32>> = HashMap::new();
 
             this_cycle_measurement.time_evaluate_rd_ops = measure_duration(|| {
+                // Loads from the read-only data bank read it instead of the RAM.
+                let mut loaded: FheUint<Vec<u8>, u32> =
+                    FheUint::alloc_from_infos(self.ram_val_fhe_uint);
+                match self.rodata_bit {
+                    Some(bit) => module.cmux(
+                        &mut loaded,
+                        self.rodata_val_fhe_uint,
+                        self.ram_val_fhe_uint,
+                        &self.ram_addr_fhe_uint_prepared.get_bit(bit),
+                        scratch,
+                    ),
+                    None => module.glwe_copy(&mut loaded, self.ram_val_fhe_uint),
+                }
+
+                // Loads read the low bits of the loaded word, so the accessed
+                // byte or halfword is first moved there.
+                let mut ram_val: FheUint<Vec<u8>, u32> =
+                    FheUint::alloc_from_infos(self.ram_val_fhe_uint);
+                eval_align_loaded(
+                    module,
+                    &mut ram_val,
+                    &loaded,
+                    self.ram_addr_fhe_uint_prepared,
+                    keys,
+                    scratch,
+                );
+
+                // RV32M operations share their multiplier and divider, so they
+                // are all evaluated at once.
+                let rv32m: Option<RV32MResults> = ops.iter().any(|op| op.is_rv32m()).then(|| {
+                    eval_rv32m(
+                        threads,
+                        module,
+                        self.imm_val_fhe_uint,
+                        rs1,
+                        rs2,
+                        self.rdu_val_fhe_uint_prepared,
+                        keys,
+                        scratch,
+                    )
+                });
+
                 // Evaluates arithmetic operations & store in map with respective op ID
                 for op in ops {
                     let mut tmp: FheUint<Vec<u8>, u32> =
-                        FheUint::alloc_from_infos(&self.imm_val_fhe_uint);
-                    op.eval_enc(
-                        threads, module, &mut tmp, rs1, rs2, imm, pc, ram_val, keys, scratch,
-                    );
+                        FheUint::alloc_from_infos(self.imm_val_fhe_uint);
+                    if let Some(inst) = self.custom_instructions.instruction(op) {
+                        eval_custom(
+                            threads, module, inst, &mut tmp, rs1, rs2, imm, keys, scratch,
+                        );
+                    } else {
+                        match rv32m.as_ref().and_then(|results| results.get(op)) {
+                            Some(res) => module.glwe_copy(&mut tmp, res),
+                            None => op.eval_enc(
+                                threads, module, &mut tmp, rs1, rs2, imm, pc, &ram_val, keys,
+                                scratch,
+                            ),
+                        }
+                    }
                     rd_map.insert(op.id(), tmp);
                 }
             });
@@ -1153,12 +2942,10 @@ impl<BE: Backend> Interpreter<BE> {
                     ops_ref.insert(*key as usize, object);
                 }
 
-                let ops_bit_size: usize = (usize::BITS - (ops.len() - 1).leading_zeros()) as usize;
-
                 module.glwe_blind_selection(
-                    &mut self.rd_val_fhe_uint,
+                    self.rd_val_fhe_uint,
                     ops_ref,
-                    &self.rdu_val_fhe_uint_prepared,
+                    self.rdu_val_fhe_uint_prepared,
                     0,
                     ops_bit_size,
                     scratch,
@@ -1169,7 +2956,7 @@ impl<BE: Backend> Interpreter<BE> {
                 self.rd_val_fhe_uint_prepared.prepare_custom_multi_thread(
                     threads,
                     module,
-                    &self.rd_val_fhe_uint,
+                    &*self.rd_val_fhe_uint,
                     0,
                     32,
                     keys,
@@ -1178,7 +2965,7 @@ impl<BE: Backend> Interpreter<BE> {
                 self.rd_val_fhe_uint.identity_multi_thread(
                     threads,
                     module,
-                    &self.rd_val_fhe_uint_prepared,
+                    &*self.rd_val_fhe_uint_prepared,
                     keys,
                     scratch,
                 );
@@ -1188,8 +2975,8 @@ impl<BE: Backend> Interpreter<BE> {
                 self.registers.write(
                     threads,
                     module,
-                    &self.rd_val_fhe_uint,
-                    &self.rd_addr_fhe_uint_prepared,
+                    &*self.rd_val_fhe_uint,
+                    self.rd_addr_fhe_uint_prepared,
                     0,
                     keys,
                     scratch,
@@ -1198,54 +2985,28 @@ impl<BE: Backend> Interpreter<BE> {
                 self.registers.zero(threads, module, 0, keys, scratch);
             });
         });
-
-        if let (Some(sk), Some(vm_debug)) = (sk, &mut self.vm_debug) {
-            vm_debug.update_registers(ops);
-
-            let rd_have: u32 = self.rd_val_fhe_uint.decrypt(module, sk, scratch);
-            let rd_want: u32 = vm_debug.rd_val;
-            println!("WRITE RD");
-            let rd_val_fhe_uint_noise = self
-                .rd_val_fhe_uint
-                .noise(module, rd_want, sk, scratch)
-                .max()
-                .log2();
-            println!(
-                "   rd_val  : {rd_have:08x} - {rd_want:08x} - {:.2}",
-                rd_val_fhe_uint_noise
-            );
-            assert_eq!(rd_have, rd_want);
-            this_cycle_measurement.rd_val_fhe_uint_noise = rd_val_fhe_uint_noise;
-
-            let mut registers_have: Vec<u32> = vec![0u32; 32];
-            self.registers
-                .decrypt(module, &mut registers_have, sk, scratch);
-            let registers_want: &[u32; 32] = &vm_debug.registers;
-            println!("reg: {:?}", registers_have);
-            let reg_noise_vec: Vec<f64> =
-                self.registers
-                    .noise(module, registers_want.as_slice(), sk, scratch);
-            if !reg_noise_vec.is_empty() {
-                let min: f64 = reg_noise_vec.iter().cloned().fold(f64::INFINITY, f64::min);
-                let max: f64 = reg_noise_vec
-                    .iter()
-                    .cloned()
-                    .fold(f64::NEG_INFINITY, f64::max);
-                println!("reg_noise: [min: {:.2}, max: {:.2}]", min, max);
-            } else {
-                println!("reg_noise: []");
-            }
-
-            assert_eq!(registers_have, registers_want);
-        }
     }
+}
 
-    pub(crate) fn update_ram<D, M, H, BRA, K, S>(
+// State read and written by the RAM update and the refresh of the RAM.
+struct RamUpdate<'a, BE: Backend> {
+    imm_val_fhe_uint: &'a FheUint<Vec<u8>, u32>,
+    rs2_val_fhe_uint: &'a FheUint<Vec<u8>, u32>,
+    ram_val_fhe_uint: &'a FheUint<Vec<u8>, u32>,
+    ram_addr_fhe_uint_prepared: &'a FheUintPrepared<Vec<u8>, u32, BE>,
+    mu_val_fhe_uint_prepared: &'a FheUintPrepared<Vec<u8>, u32, BE>,
+    ram_store_fhe_uint: &'a mut FheUint<Vec<u8>, u32>,
+    ram_val_fhe_uint_prepared: &'a mut FheUintPrepared<Vec<u8>, u32, BE>,
+    ram: &'a mut Memory,
+}
+
+impl<BE: Backend> RamUpdate<'_, BE> {
+    fn run<D, M, H, BRA, K>(
         &mut self,
         threads: usize,
         module: &M,
+        ops: &[RAM_UPDATE],
         keys: &H,
-        sk: Option<&S>,
         scratch: &mut Scratch<BE>,
         this_cycle_measurement: &mut Measurement,
     ) where
@@ -1269,7 +3030,6 @@ impl<BE: Backend> Interpreter<BE> {
         H: Sync + BDDKeyHelper<D, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
         K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
         BRA: BlindRotationAlgo,
-        S: GLWESecretPreparedToRef<BE> + GLWEInfos + GetDistribution,
         D: DataRef,
     {
         this_cycle_measurement.time_update_ram = measure_duration(|| {
@@ -1277,16 +3037,16 @@ impl<BE: Backend> Interpreter<BE> {
 
             this_cycle_measurement.time_ram_update_op_eval = measure_duration(|| {
                 // Constructs diffferent possible values that are stored back
-                for op in RAM_UPDATE_OP_LIST {
+                for op in ops {
                     let mut tmp: FheUint<Vec<u8>, u32> =
-                        FheUint::alloc_from_infos(&self.imm_val_fhe_uint);
+                        FheUint::alloc_from_infos(self.imm_val_fhe_uint);
                     op.eval_enc(
                         threads,
                         module,
                         &mut tmp,
-                        &self.rs2_val_fhe_uint,
-                        &self.ram_val_fhe_uint,
-                        &self.ram_addr_fhe_uint_prepared, // offset is the 2 LSB of [rs2 + imm]
+                        self.rs2_val_fhe_uint,
+                        self.ram_val_fhe_uint,
+                        self.ram_addr_fhe_uint_prepared, // offset is the 2 LSB of [rs2 + imm]
                         keys,
                         scratch,
                     );
@@ -1303,9 +3063,9 @@ impl<BE: Backend> Interpreter<BE> {
                 let ops_bit_size: usize =
                     (usize::BITS - (RAM_UPDATE_OP_LIST.len() - 1).leading_zeros()) as usize;
                 module.glwe_blind_selection(
-                    &mut self.ram_val_fhe_uint,
+                    self.ram_store_fhe_uint,
                     res_tmp_ref,
-                    &self.mu_val_fhe_uint_prepared,
+                    self.mu_val_fhe_uint_prepared,
                     0,
                     ops_bit_size,
                     scratch,
@@ -1316,16 +3076,16 @@ impl<BE: Backend> Interpreter<BE> {
                 self.ram_val_fhe_uint_prepared.prepare_custom_multi_thread(
                     threads,
                     module,
-                    &self.ram_val_fhe_uint,
+                    &*self.ram_store_fhe_uint,
                     0,
                     32,
                     keys,
                     scratch,
                 );
-                self.ram_val_fhe_uint.identity_multi_thread(
+                self.ram_store_fhe_uint.identity_multi_thread(
                     threads,
                     module,
-                    &self.ram_val_fhe_uint_prepared,
+                    &*self.ram_val_fhe_uint_prepared,
                     keys,
                     scratch,
                 );
@@ -1335,52 +3095,80 @@ impl<BE: Backend> Interpreter<BE> {
                 self.ram.read_statefull_rev(
                     threads,
                     module,
-                    &self.ram_val_fhe_uint,
-                    &self.ram_addr_fhe_uint_prepared,
+                    &*self.ram_store_fhe_uint,
+                    self.ram_addr_fhe_uint_prepared,
                     2,
                     keys,
                     scratch,
                 );
             });
         });
+    }
 
-        if let (Some(sk), Some(vm_debug)) = (sk, &mut self.vm_debug) {
-            vm_debug.update_ram();
-            let ram_have: u32 = self.ram_val_fhe_uint.decrypt(module, sk, scratch);
-            let ram_want: u32 = vm_debug.ram_val;
-            println!("WRITE RAM");
-            println!(
-                "   ram_val : {ram_have:08x} - {ram_want:08x} - {:.2}",
-                self.ram_val_fhe_uint
-                    .noise(module, ram_want, sk, scratch)
-                    .max()
-                    .log2()
+    // Bootstraps the words of the RAM at `addrs`.
+    fn refresh<D, M, H, BRA, K>(
+        &mut self,
+        threads: usize,
+        module: &M,
+        addrs: &[usize],
+        keys: &H,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: Sync
+            + ModuleLogN
+            + GLWERotate<BE>
+            + GLWETrace<BE>
+            + GLWEPacking<BE>
+            + GLWECopy
+            + GLWEAdd
+            + GLWESub
+            + GLWENormalize<BE>
+            + FheUintPrepare<BRA, BE>
+            + ExecuteBDDCircuit1WTo1W<BE>
+            + GLWEDecrypt<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+        H: Sync + BDDKeyHelper<D, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
+        K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+        BRA: BlindRotationAlgo,
+        D: DataRef,
+    {
+        let mut word: FheUint<Vec<u8>, u32> = FheUint::alloc_from_infos(self.ram_val_fhe_uint);
+        for &addr in addrs {
+            self.ram.read_at(module, &mut word, addr, keys, scratch);
+            self.ram_val_fhe_uint_prepared
+                .prepare_custom_multi_thread(threads, module, &word, 0, 32, keys, scratch);
+            word.identity_multi_thread(
+                threads,
+                module,
+                self.ram_val_fhe_uint_prepared,
+                keys,
+                scratch,
             );
-            assert_eq!(ram_have, ram_want);
-            let mut ram_have: Vec<u32> = vec![0u32; self.ram_size];
-            self.ram.decrypt(module, &mut ram_have, sk, scratch);
-            let ram_want: &Vec<u32> = &vm_debug.ram;
-            let ram_noise_vec = self.ram.noise(module, ram_want.as_slice(), sk, scratch);
-            if !ram_noise_vec.is_empty() {
-                let min = ram_noise_vec.iter().cloned().fold(f64::INFINITY, f64::min);
-                let max = ram_noise_vec
-                    .iter()
-                    .cloned()
-                    .fold(f64::NEG_INFINITY, f64::max);
-                println!("ram_noise: [{:.2}, {:.2}]", min, max);
-            } else {
-                println!("ram_noise: []");
-            }
-            assert_eq!(&ram_have, ram_want);
+            self.ram
+                .write_at(threads, module, &word, addr, keys, scratch);
         }
     }
+}
 
-    pub(crate) fn update_pc<M, K, S, H, BRA: BlindRotationAlgo, D>(
+// State read and written by the PC update.
+struct PcUpdate<'a, BE: Backend> {
+    rs1_val_fhe_uint_prepared: &'a FheUintPrepared<Vec<u8>, u32, BE>,
+    rs2_val_fhe_uint_prepared: &'a FheUintPrepared<Vec<u8>, u32, BE>,
+    imm_val_fhe_uint_prepared: &'a FheUintPrepared<Vec<u8>, u32, BE>,
+    pc_fhe_uint_prepared: &'a FheUintPrepared<Vec<u8>, u32, BE>,
+    pcu_val_fhe_uint_prepared: &'a FheUintPrepared<Vec<u8>, u32, BE>,
+    status_fhe_uint_prepared: &'a FheUintPrepared<Vec<u8>, u32, BE>,
+    misaligned_fhe_uint_prepared: &'a FheUintPrepared<Vec<u8>, u32, BE>,
+    pc_fhe_uint: &'a mut FheUint<Vec<u8>, u32>,
+    pc_prev_fhe_uint: &'a mut FheUint<Vec<u8>, u32>,
+}
+
+impl<BE: Backend> PcUpdate<'_, BE> {
+    fn run<M, K, H, BRA: BlindRotationAlgo, D>(
         &mut self,
         threads: usize,
         module: &M,
         keys: &H,
-        sk: Option<&S>,
         scratch: &mut Scratch<BE>,
         this_cycle_measurement: &mut Measurement,
     ) where
@@ -1392,97 +3180,40 @@ impl<BE: Backend> Interpreter<BE> {
             + Cmux<BE>
             + GLWEDecrypt<BE>
             + GLWENoise<BE>,
-        S: GLWESecretPreparedToRef<BE> + GLWEInfos,
         H: BDDKeyHelper<D, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
         K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
         D: DataRef,
         Scratch<BE>: ScratchTakeCore<BE>,
     {
         this_cycle_measurement.time_update_pc = measure_duration(|| {
+            module.glwe_copy(self.pc_prev_fhe_uint, &*self.pc_fhe_uint);
             update_pc(
                 threads,
                 module,
-                &mut self.pc_fhe_uint,
-                &self.rs1_val_fhe_uint_prepared,
-                &self.rs2_val_fhe_uint_prepared,
-                &self.pc_fhe_uint_prepared,
-                &self.imm_val_fhe_uint_prepared,
-                &self.pcu_val_fhe_uint_prepared,
+                self.pc_fhe_uint,
+                self.rs1_val_fhe_uint_prepared,
+                self.rs2_val_fhe_uint_prepared,
+                self.pc_fhe_uint_prepared,
+                self.imm_val_fhe_uint_prepared,
+                self.pcu_val_fhe_uint_prepared,
                 keys,
                 scratch,
             );
-        });
-
-        if let (Some(sk), Some(vm_debug)) = (sk, &mut self.vm_debug) {
-            vm_debug.update_pc();
-            let pc_have: u32 = self.pc_fhe_uint.decrypt(module, sk, scratch);
-            let pc_want: u32 = vm_debug.pc;
-            println!("UPDATE PC");
-            println!(
-                "   pc_val  : {pc_have:08x} - {pc_want:08x} - {:.2}",
-                self.pc_fhe_uint
-                    .noise(module, pc_want, sk, scratch)
-                    .max()
-                    .log2()
+            // Once halted, the PC is frozen.
+            module.cmux_inplace_neg(
+                self.pc_fhe_uint,
+                &*self.pc_prev_fhe_uint,
+                &self.status_fhe_uint_prepared.get_bit(0),
+                scratch,
             );
-            assert_eq!(pc_have, pc_want);
-        }
-    }
-
-    pub fn print_timings(&self, cycle: usize) {
-        if self.verbose_timings {
-            let this_cycle: &Measurement = &self.measurements.cycle_measurements[cycle];
-
-            println!(
-                "
-Cycle Time: {:?}
-  1. Prepare pc: {:?}
-  2. Read and prepare rom: {:?}
-     - Read rom: {:?}
-     - Prepare rom: {:?}
-  3. Read and prepare registers: {:?}
-     - Read registers: {:?}
-     - Prepare registers: {:?}
-  4. Read ram: {:?}
-     - Derive addr: {:?}
-     - Prepare addr: {:?}
-     - Read ram: {:?}
-  5. Update registers: {:?}
-     - Evaluate rd ops: {:?}
-     - Blind select rd: {:?}
-     - Refresh rd: {:?}
-     - Write rd: {:?}
-  6. Update ram: {:?}
-     - Evaluate store ops: {:?}
-     - Blind select value: {:?}
-     - Refresh value: {:?}
-     - Write ram: {:?}
-  7. Update pc: {:?}
-",
-                this_cycle.total_cycle_time,
-                this_cycle.time_prepare_pc,
-                this_cycle.time_read_and_prepare_rom,
-                this_cycle.time_read_rom,
-                this_cycle.time_prepare_rom,
-                this_cycle.time_read_and_prepare_registers,
-                this_cycle.time_read_registers,
-                this_cycle.time_prepare_registers,
-                this_cycle.time_read_ram,
-                this_cycle.time_derive_ram_addr,
-                this_cycle.time_prepare_ram_addr,
-                this_cycle.time_prepare_ram_read_statefull,
-                this_cycle.time_update_registers,
-                this_cycle.time_evaluate_rd_ops,
-                this_cycle.time_blind_select_rd,
-                this_cycle.time_refresh_rd,
-                this_cycle.time_write_rd,
-                this_cycle.time_update_ram,
-                this_cycle.time_ram_update_op_eval,
-                this_cycle.time_blind_select_ram_value,
-                this_cycle.time_refresh_ram_value,
-                this_cycle.time_write_ram,
-                this_cycle.time_update_pc,
+            // A misaligned access leaves the PC on itself, so that the program
+            // halts at the next cycle.
+            module.cmux_inplace_neg(
+                self.pc_fhe_uint,
+                &*self.pc_prev_fhe_uint,
+                &self.misaligned_fhe_uint_prepared.get_bit(0),
+                scratch,
             );
-        }
+        });
     }
 }
@@ -1,6 +1,8 @@
 use poulpy_hal::{
     api::{ScratchOwnedAlloc, ScratchOwnedBorrow},
-    layouts::{Backend, Data, DataMut, DataRef, Module, Scratch, ScratchOwned},
+    layouts::{
+        Backend, Data, DataMut, DataRef, Module, ReaderFrom, Scratch, ScratchOwned, WriterTo,
+    },
     source::Source,
 };
 use poulpy_schemes::bin_fhe::{
@@ -8,26 +10,53 @@ use poulpy_schemes::bin_fhe::{
         BDDKey, BDDKeyEncryptSk, BDDKeyHelper, BDDKeyInfos, BDDKeyLayout, BDDKeyPrepared,
         BDDKeyPreparedFactory,
     },
-    blind_rotation::{BlindRotationAlgo, BlindRotationKey, BlindRotationKeyFactory},
+    blind_rotation::{BlindRotationAlgo, BlindRotationKey, BlindRotationKeyFactory, CGGI},
     circuit_bootstrapping::{
         CircuitBootstrappingKeyLayout, CircuitBootstrappingKeyPrepared,
         CircuitBootstrappingKeyPreparedFactory,
     },
 };
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    io::{Read, Result, Write},
+};
 
 use poulpy_core::{
     layouts::{
-        GGLWELayout, GGLWEToGGSWKeyPreparedFactory, GLWEAutomorphismKey, GLWEAutomorphismKeyHelper,
-        GLWEAutomorphismKeyPrepared, GLWEAutomorphismKeyPreparedFactory, GLWEInfos,
+        compressed::GLWEAutomorphismKeyCompressed, GGLWELayout, GGLWEToGGSWKeyPreparedFactory,
+        GLWEAutomorphismKey, GLWEAutomorphismKeyDecompress, GLWEAutomorphismKeyHelper,
+        GLWEAutomorphismKeyPrepared, GLWEAutomorphismKeyPreparedFactory, GLWEInfos, GLWEPublicKey,
+        GLWEPublicKeyPrepared, GLWEPublicKeyPreparedFactory, GLWESecretPreparedToRef,
         GLWESecretToRef, GLWESwitchingKeyPrepared, GLWEToLWEKeyLayout, GLWEToLWEKeyPrepared,
         LWEInfos, LWESecretToRef, GLWE,
     },
-    GGLWEToGGSWKeyEncryptSk, GLWEAutomorphismKeyEncryptSk, GLWETrace, GetDistribution,
-    ScratchTakeCore,
+    GGLWEToGGSWKeyEncryptSk, GLWEAutomorphismKeyCompressedEncryptSk, GLWEAutomorphismKeyEncryptSk,
+    GLWEEncryptSk, GLWETrace, GetDistribution, GetDistributionMut, ScratchTakeCore,
 };
 
-use crate::parameters::CryptographicParameters;
+use crate::{
+    parameters::CryptographicParameters,
+    serialization::{invalid_data, read_u64, write_u64, Header},
+};
+
+/// Magic bytes of serialized [VMKeys].
+pub const VM_KEYS_MAGIC: [u8; 4] = *b"PZVK";
+
+/// Version of the serialized [VMKeys] layout.
+pub const VM_KEYS_VERSION: u32 = 1;
+
+/// Magic bytes of seed-compressed serialized [VMKeys].
+pub const VM_KEYS_COMPRESSED_MAGIC: [u8; 4] = *b"PZVC";
+
+/// Identifier of a [BlindRotationAlgo] in serialized [VMKeys].
+pub trait BlindRotationAlgoId: BlindRotationAlgo {
+    const ID: u64;
+}
+
+impl BlindRotationAlgoId for CGGI {
+    const ID: u64 = 0;
+}
 
 /// Struct storing the FHE evaluation keys for the read/write on FHE-RAM.
 pub struct VMKeys<D: Data, BRA: BlindRotationAlgo> {
@@ -159,26 +188,63 @@ impl<BRA: BlindRotationAlgo> VMKeys<Vec<u8>, BRA> {
 
         let evk_ram_infos: GGLWELayout = params.evk_ram_infos();
 
+        let mut evk_ram: HashMap<i64, GLWEAutomorphismKey<Vec<u8>>> = HashMap::new();
+        let Ok(bdd_key) = Self::encrypt_sk_internal::<_, _, _, Infallible>(
+            params,
+            sk_lwe,
+            sk_glwe,
+            source_xa,
+            source_xe,
+            GLWEAutomorphismKey::encrypt_sk_tmp_bytes(module, &evk_ram_infos),
+            |gal_el, source_xa, source_xe, scratch| {
+                let mut key: GLWEAutomorphismKey<Vec<u8>> =
+                    GLWEAutomorphismKey::alloc_from_infos(&evk_ram_infos);
+                key.encrypt_sk(module, gal_el, sk_glwe, source_xa, source_xe, scratch);
+                evk_ram.insert(gal_el, key);
+                Ok(())
+            },
+        );
+
+        VMKeys { evk_ram, bdd_key }
+    }
+
+    /// Key generation shared by [VMKeys::encrypt_sk] and
+    /// [VMKeys::encrypt_sk_compressed_to]: `encrypt_evk` encrypts the evk_ram key
+    /// of each Galois element, in increasing order, before the BDD key is
+    /// encrypted and returned.
+    fn encrypt_sk_internal<SL, SG, BE: Backend, E>(
+        params: &CryptographicParameters<BE>,
+        sk_lwe: &SL,
+        sk_glwe: &SG,
+        source_xa: &mut Source,
+        source_xe: &mut Source,
+        evk_ram_tmp_bytes: usize,
+        mut encrypt_evk: impl FnMut(
+            i64,
+            &mut Source,
+            &mut Source,
+            &mut Scratch<BE>,
+        ) -> std::result::Result<(), E>,
+    ) -> std::result::Result<BDDKey<Vec<u8>, BRA>, E>
+    where
+        SL: LWESecretToRef + GetDistribution + LWEInfos,
+        SG: GLWESecretToRef + GetDistribution + GLWEInfos,
+        Module<BE>: GGLWEToGGSWKeyEncryptSk<BE> + GLWETrace<BE> + BDDKeyEncryptSk<BRA, BE>,
+        BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+        ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        let module: &Module<BE> = params.module();
+
         let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(
-            GLWEAutomorphismKey::encrypt_sk_tmp_bytes(module, &evk_ram_infos)
-                .max(module.bdd_key_encrypt_sk_tmp_bytes(&params.bdd_key_layout())),
+            evk_ram_tmp_bytes.max(module.bdd_key_encrypt_sk_tmp_bytes(&params.bdd_key_layout())),
         );
 
-        let gal_els: Vec<i64> = GLWE::trace_galois_elements(module);
-        let evk_ram: HashMap<i64, GLWEAutomorphismKey<Vec<u8>>> =
-            HashMap::from_iter(gal_els.iter().map(|gal_el| {
-                let mut key: GLWEAutomorphismKey<Vec<u8>> =
-                    GLWEAutomorphismKey::alloc_from_infos(&evk_ram_infos);
-                key.encrypt_sk(
-                    module,
-                    *gal_el,
-                    sk_glwe,
-                    source_xa,
-                    source_xe,
-                    scratch.borrow(),
-                );
-                (*gal_el, key)
-            }));
+        let mut gal_els: Vec<i64> = GLWE::trace_galois_elements(module);
+        gal_els.sort();
+        for gal_el in gal_els {
+            encrypt_evk(gal_el, source_xa, source_xe, scratch.borrow())?;
+        }
 
         let mut bdd_key: BDDKey<Vec<u8>, BRA> = BDDKey::alloc_from_infos(&params.bdd_key_layout());
         bdd_key.encrypt_sk(
@@ -189,9 +255,207 @@ impl<BRA: BlindRotationAlgo> VMKeys<Vec<u8>, BRA> {
             source_xe,
             scratch.borrow(),
         );
+        Ok(bdd_key)
+    }
+}
 
-        VMKeys { evk_ram, bdd_key }
+impl<BRA: BlindRotationAlgoId> VMKeys<Vec<u8>, BRA> {
+    /// Serializes the keys as:
+    /// [Header] | blind-rotation algorithm | evk_ram | bdd_key.
+    pub fn write_to<W: Write, BE: Backend>(
+        &self,
+        params: &CryptographicParameters<BE>,
+        writer: &mut W,
+    ) -> Result<()> {
+        Header::new(VM_KEYS_MAGIC, VM_KEYS_VERSION, params.fingerprint()).write_to(writer)?;
+        write_u64(writer, BRA::ID)?;
+
+        // Sorted to make the output deterministic.
+        let mut gal_els: Vec<&i64> = self.evk_ram.keys().collect();
+        gal_els.sort();
+        write_u64(writer, gal_els.len() as u64)?;
+        for gal_el in gal_els {
+            write_u64(writer, *gal_el as u64)?;
+            self.evk_ram[gal_el].write_to(writer)?;
+        }
+
+        self.bdd_key.write_to(writer)
     }
+
+    /// Deserializes keys written by [VMKeys::write_to], checking that they were
+    /// generated for `params` and `BRA`.
+    pub fn read_from<R: Read, BE: Backend>(
+        params: &CryptographicParameters<BE>,
+        reader: &mut R,
+    ) -> Result<Self>
+    where
+        Module<BE>: GLWETrace<BE>,
+        BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+    {
+        Header::read_from(reader)?.check(&Header::new(
+            VM_KEYS_MAGIC,
+            VM_KEYS_VERSION,
+            params.fingerprint(),
+        ))?;
+
+        read_bra::<R, BRA>(reader)?;
+
+        let gal_els: Vec<i64> = GLWE::trace_galois_elements(params.module());
+        let evk_ram_infos: GGLWELayout = params.evk_ram_infos();
+        let len: usize = read_evk_ram_len(reader, &gal_els)?;
+        let mut evk_ram: HashMap<i64, GLWEAutomorphismKey<Vec<u8>>> = HashMap::new();
+        for _ in 0..len {
+            let gal_el: i64 = read_gal_el(reader, &gal_els, &evk_ram)?;
+            let mut key: GLWEAutomorphismKey<Vec<u8>> =
+                GLWEAutomorphismKey::alloc_from_infos(&evk_ram_infos);
+            key.read_from(reader)?;
+            evk_ram.insert(gal_el, key);
+        }
+
+        let mut bdd_key: BDDKey<Vec<u8>, BRA> = BDDKey::alloc_from_infos(&params.bdd_key_layout());
+        bdd_key.read_from(reader)?;
+
+        Ok(VMKeys { evk_ram, bdd_key })
+    }
+
+    /// Encrypts fresh keys and directly serializes them in seed-compressed form:
+    /// the uniform mask of each evk_ram ciphertext is replaced by the 32-byte
+    /// seed it was sampled from. The BDD key has no compressed form in
+    /// poulpy-schemes and is written as by [VMKeys::write_to].
+    ///
+    /// The layout mirrors [VMKeys::write_to] with a distinct magic.
+    pub fn encrypt_sk_compressed_to<W, SL, SG, BE: Backend>(
+        params: &CryptographicParameters<BE>,
+        sk_lwe: &SL,
+        sk_glwe: &SG,
+        source_xa: &mut Source,
+        source_xe: &mut Source,
+        writer: &mut W,
+    ) -> Result<()>
+    where
+        W: Write,
+        SL: LWESecretToRef + GetDistribution + LWEInfos,
+        SG: GLWESecretToRef + GetDistribution + GLWEInfos,
+        Module<BE>: GLWEAutomorphismKeyCompressedEncryptSk<BE>
+            + GGLWEToGGSWKeyEncryptSk<BE>
+            + GLWETrace<BE>
+            + BDDKeyEncryptSk<BRA, BE>,
+        BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+        ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        let module: &Module<BE> = params.module();
+
+        let evk_ram_infos: GGLWELayout = params.evk_ram_infos();
+
+        Header::new(
+            VM_KEYS_COMPRESSED_MAGIC,
+            VM_KEYS_VERSION,
+            params.fingerprint(),
+        )
+        .write_to(writer)?;
+        write_u64(writer, BRA::ID)?;
+        write_u64(writer, GLWE::trace_galois_elements(module).len() as u64)?;
+
+        let mut key: GLWEAutomorphismKeyCompressed<Vec<u8>> =
+            GLWEAutomorphismKeyCompressed::alloc_from_infos(&evk_ram_infos);
+        let bdd_key: BDDKey<Vec<u8>, BRA> = Self::encrypt_sk_internal(
+            params,
+            sk_lwe,
+            sk_glwe,
+            source_xa,
+            source_xe,
+            GLWEAutomorphismKeyCompressed::encrypt_sk_tmp_bytes(module, &evk_ram_infos),
+            |gal_el, source_xa, source_xe, scratch| {
+                key.encrypt_sk(
+                    module,
+                    gal_el,
+                    sk_glwe,
+                    source_xa.new_seed(),
+                    source_xe,
+                    scratch,
+                );
+                write_u64(writer, gal_el as u64)?;
+                key.write_to(writer)
+            },
+        )?;
+        bdd_key.write_to(writer)
+    }
+
+    /// Deserializes and decompresses keys written by
+    /// [VMKeys::encrypt_sk_compressed_to].
+    pub fn read_compressed_from<R: Read, BE: Backend>(
+        params: &CryptographicParameters<BE>,
+        reader: &mut R,
+    ) -> Result<Self>
+    where
+        Module<BE>: GLWETrace<BE> + GLWEAutomorphismKeyDecompress,
+        BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+    {
+        Header::read_from(reader)?.check(&Header::new(
+            VM_KEYS_COMPRESSED_MAGIC,
+            VM_KEYS_VERSION,
+            params.fingerprint(),
+        ))?;
+
+        read_bra::<R, BRA>(reader)?;
+
+        let module: &Module<BE> = params.module();
+        let gal_els: Vec<i64> = GLWE::trace_galois_elements(module);
+        let evk_ram_infos: GGLWELayout = params.evk_ram_infos();
+        let len: usize = read_evk_ram_len(reader, &gal_els)?;
+        let mut key_compressed: GLWEAutomorphismKeyCompressed<Vec<u8>> =
+            GLWEAutomorphismKeyCompressed::alloc_from_infos(&evk_ram_infos);
+        let mut evk_ram: HashMap<i64, GLWEAutomorphismKey<Vec<u8>>> = HashMap::new();
+        for _ in 0..len {
+            let gal_el: i64 = read_gal_el(reader, &gal_els, &evk_ram)?;
+            key_compressed.read_from(reader)?;
+            let mut key: GLWEAutomorphismKey<Vec<u8>> =
+                GLWEAutomorphismKey::alloc_from_infos(&evk_ram_infos);
+            key.decompress(module, &key_compressed);
+            evk_ram.insert(gal_el, key);
+        }
+
+        let mut bdd_key: BDDKey<Vec<u8>, BRA> = BDDKey::alloc_from_infos(&params.bdd_key_layout());
+        bdd_key.read_from(reader)?;
+
+        Ok(VMKeys { evk_ram, bdd_key })
+    }
+}
+
+fn read_bra<R: Read, BRA: BlindRotationAlgoId>(reader: &mut R) -> Result<()> {
+    let bra: u64 = read_u64(reader)?;
+    if bra != BRA::ID {
+        return Err(invalid_data(format!(
+            "blind rotation algorithm mismatch: {bra} != {}",
+            BRA::ID
+        )));
+    }
+    Ok(())
+}
+
+fn read_evk_ram_len<R: Read>(reader: &mut R, gal_els: &[i64]) -> Result<usize> {
+    let len: usize = read_u64(reader)? as usize;
+    if len != gal_els.len() {
+        return Err(invalid_data(format!(
+            "evk_ram.len()={} != read len={}",
+            gal_els.len(),
+            len
+        )));
+    }
+    Ok(len)
+}
+
+// Reads a galois element, rejecting those outside `gal_els` or already in `seen`.
+fn read_gal_el<R: Read, T>(reader: &mut R, gal_els: &[i64], seen: &HashMap<i64, T>) -> Result<i64> {
+    let gal_el: i64 = read_u64(reader)? as i64;
+    if !gal_els.contains(&gal_el) {
+        return Err(invalid_data(format!("unexpected galois element: {gal_el}")));
+    }
+    if seen.contains_key(&gal_el) {
+        return Err(invalid_data(format!("duplicate galois element: {gal_el}")));
+    }
+    Ok(gal_el)
 }
 
 impl<D: DataRef, BRA: BlindRotationAlgo, BE: Backend> BDDKeyHelper<D, BRA, BE>
@@ -207,3 +471,101 @@ impl<D: DataRef, BRA: BlindRotationAlgo, BE: Backend> BDDKeyHelper<D, BRA, BE>
         self.bdd_key.get_cbt_key()
     }
 }
+
+/// Magic bytes of a serialized [VMPublicKey].
+pub const VM_PUBLIC_KEY_MAGIC: [u8; 4] = *b"PZPK";
+
+/// Version of the serialized [VMPublicKey] layout.
+pub const VM_PUBLIC_KEY_VERSION: u32 = 1;
+
+/// Public encryption key generated alongside the [VMKeys], with which
+/// third parties can encrypt inputs without access to the secret-key.
+pub struct VMPublicKey<D: Data> {
+    pk: GLWEPublicKey<D>,
+}
+
+pub struct VMPublicKeyPrepared<D: Data, B: Backend> {
+    pub(crate) pk: GLWEPublicKeyPrepared<D, B>,
+}
+
+impl VMPublicKey<Vec<u8>> {
+    pub fn generate<S, BE: Backend>(
+        params: &CryptographicParameters<BE>,
+        sk_prepared: &S,
+        source_xa: &mut Source,
+        source_xe: &mut Source,
+    ) -> Self
+    where
+        S: GLWESecretPreparedToRef<BE> + GetDistribution,
+        Module<BE>: GLWEEncryptSk<BE>,
+        ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        let module: &Module<BE> = params.module();
+
+        // The key must match the layout of the RAM ciphertexts it encrypts.
+        let mut pk: GLWEPublicKey<Vec<u8>> = GLWEPublicKey::alloc_from_infos(&params.ram_infos());
+
+        let mut scratch: ScratchOwned<BE> =
+            ScratchOwned::alloc(GLWE::encrypt_sk_tmp_bytes(module, &pk));
+
+        // Not using GLWEPublicKey::generate, which in poulpy-core 0.4.1 encrypts
+        // into a temporary and leaves the key zeroed.
+        module.glwe_encrypt_zero_sk(&mut pk, sk_prepared, source_xa, source_xe, scratch.borrow());
+        *pk.dist_mut() = *sk_prepared.dist();
+
+        VMPublicKey { pk }
+    }
+
+    /// Serializes the key as: [Header] | key.
+    pub fn write_to<W: Write, BE: Backend>(
+        &self,
+        params: &CryptographicParameters<BE>,
+        writer: &mut W,
+    ) -> Result<()> {
+        Header::new(
+            VM_PUBLIC_KEY_MAGIC,
+            VM_PUBLIC_KEY_VERSION,
+            params.fingerprint(),
+        )
+        .write_to(writer)?;
+        self.pk.write_to(writer)
+    }
+
+    /// Deserializes a key written by [VMPublicKey::write_to], checking that it
+    /// was generated for `params`.
+    pub fn read_from<R: Read, BE: Backend>(
+        params: &CryptographicParameters<BE>,
+        reader: &mut R,
+    ) -> Result<Self> {
+        Header::read_from(reader)?.check(&Header::new(
+            VM_PUBLIC_KEY_MAGIC,
+            VM_PUBLIC_KEY_VERSION,
+            params.fingerprint(),
+        ))?;
+        let mut pk: GLWEPublicKey<Vec<u8>> = GLWEPublicKey::alloc_from_infos(&params.ram_infos());
+        pk.read_from(reader)?;
+        Ok(VMPublicKey { pk })
+    }
+}
+
+impl<B: Backend> VMPublicKeyPrepared<Vec<u8>, B> {
+    pub fn alloc(params: &CryptographicParameters<B>) -> Self
+    where
+        Module<B>: GLWEPublicKeyPreparedFactory<B>,
+    {
+        Self {
+            pk: GLWEPublicKeyPrepared::alloc_from_infos(params.module(), &params.ram_infos()),
+        }
+    }
+}
+
+impl<D: DataMut, B: Backend> VMPublicKeyPrepared<D, B> {
+    pub fn prepare<O, M>(&mut self, module: &M, other: &VMPublicKey<O>)
+    where
+        O: DataRef,
+        M: GLWEPublicKeyPreparedFactory<B>,
+    {
+        self.pk.prepare(module, &other.pk);
+    }
+}
@@ -1,22 +1,39 @@
+pub mod bdd;
+pub(crate) mod alignment;
 pub(crate) mod codegen;
+pub mod custom;
 pub(crate) mod debug;
+pub(crate) mod halt;
+pub mod input;
 pub mod instructions;
 pub mod interpreter;
 pub mod keys;
 pub(crate) mod measurements;
 pub(crate) mod memory;
+pub mod op_set;
 pub mod parameters;
 pub(crate) mod pc_update;
+pub mod pipeline;
 pub mod prepare;
 pub(crate) mod ram_offset;
 pub(crate) mod ram_update;
 pub(crate) mod rd_update;
+pub mod rodata;
+pub(crate) mod rv32m;
+pub(crate) mod scheduler;
+pub mod serialization;
+pub mod sources;
 
 // Re-export the main functionality
+pub use custom::{CustomInstruction, CustomInstructions};
+pub use halt::HaltStatus;
 pub(crate) use instructions::*;
 pub use interpreter::*;
 pub(crate) use measurements::*;
+pub use memory::{NoiseModel, RefreshSchedule};
+pub use op_set::OpSet;
 pub(crate) use pc_update::*;
+pub use pipeline::{CycleHook, Phase};
 
 #[cfg(test)]
 mod tests;
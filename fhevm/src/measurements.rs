@@ -1,4 +1,12 @@
-use std::time::{Duration, Instant};
+use std::{
+    io::{Read, Result, Write},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    serialization::{read_u64, write_u64},
+    Phase,
+};
 
 pub struct Measurements {
     pub cycle_measurements: Vec<Measurement>,
@@ -6,7 +14,7 @@ pub struct Measurements {
 
 #[allow(dead_code)]
 pub(crate) fn average_time(times: &[Duration]) -> Duration {
-    let total_time: Duration = times.iter().map(|c| c).sum::<Duration>();
+    let total_time: Duration = times.iter().sum::<Duration>();
     total_time / times.len() as u32
 }
 
@@ -17,6 +25,24 @@ impl Measurements {
         }
     }
 
+    /// Serializes the measurements as: count | measurements.
+    pub(crate) fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_u64(writer, self.cycle_measurements.len() as u64)?;
+        for measurement in &self.cycle_measurements {
+            measurement.write_to(writer)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn read_from<R: Read>(reader: &mut R) -> Result<Self> {
+        let len: usize = read_u64(reader)? as usize;
+        let mut cycle_measurements: Vec<Measurement> = Vec::new();
+        for _ in 0..len {
+            cycle_measurements.push(Measurement::read_from(reader)?);
+        }
+        Ok(Self { cycle_measurements })
+    }
+
     #[allow(dead_code)]
     pub fn average_cycle_time(&self) -> Duration {
         let total_cycle_time = self
@@ -27,6 +53,16 @@ impl Measurements {
         total_cycle_time / self.cycle_measurements.len() as u32
     }
 
+    #[allow(dead_code)]
+    pub fn average_time_critical_path(&self) -> Duration {
+        let total_cycle_time = self
+            .cycle_measurements
+            .iter()
+            .map(|measurement| measurement.time_critical_path)
+            .sum::<Duration>();
+        total_cycle_time / self.cycle_measurements.len() as u32
+    }
+
     #[allow(dead_code)]
     pub fn average_time_prepare_pc(&self) -> Duration {
         let total_cycle_time = self
@@ -247,6 +283,16 @@ impl Measurements {
         total_cycle_time / self.cycle_measurements.len() as u32
     }
 
+    #[allow(dead_code)]
+    pub fn average_time_refresh_ram(&self) -> Duration {
+        let total_cycle_time = self
+            .cycle_measurements
+            .iter()
+            .map(|measurement| measurement.time_refresh_ram)
+            .sum::<Duration>();
+        total_cycle_time / self.cycle_measurements.len() as u32
+    }
+
     #[allow(dead_code)]
     pub fn get_pc_val_fhe_uint_noise_list(&self) -> Vec<f64> {
         self.cycle_measurements
@@ -291,6 +337,10 @@ impl Measurements {
 pub struct Measurement {
     // Layer zero
     pub total_cycle_time: Duration,
+    // Time of the longest chain of stages run one after the other, which is
+    // the sum of the stages unless the updates run concurrently (see
+    // [crate::Interpreter::set_concurrent_updates]).
+    pub time_critical_path: Duration,
 
     // 1) Prepare PC
     pub time_prepare_pc: Duration,
@@ -328,6 +378,9 @@ pub struct Measurement {
     // 6) Update PC (dep 4)
     pub time_update_pc: Duration,
 
+    // 7) Refresh RAM (dep 5)
+    pub time_refresh_ram: Duration,
+
     pub pc_val_fhe_uint_noise: f64,
     pub imm_val_fhe_uint_noise: f64,
 
@@ -341,6 +394,7 @@ impl Measurement {
     pub fn new() -> Self {
         Self {
             total_cycle_time: Duration::from_secs(0),
+            time_critical_path: Duration::from_secs(0),
 
             // 1) Prepare PC
             time_prepare_pc: Duration::from_secs(0),
@@ -378,6 +432,9 @@ impl Measurement {
             // 6) Update PC (dep 4)
             time_update_pc: Duration::from_secs(0),
 
+            // 7) Refresh RAM (dep 5)
+            time_refresh_ram: Duration::from_secs(0),
+
             pc_val_fhe_uint_noise: 0.0,
             imm_val_fhe_uint_noise: 0.0,
             ram_addr_read_noise: 0.0,
@@ -387,6 +444,114 @@ impl Measurement {
     }
 }
 
+impl Measurement {
+    /// Returns the time taken by `phase`.
+    pub fn phase_time(&self, phase: Phase) -> Duration {
+        match phase {
+            Phase::HaltStatus => self.time_prepare_pc,
+            Phase::ReadInstruction => self.time_read_and_prepare_rom,
+            Phase::ReadRegisters => self.time_read_and_prepare_registers,
+            Phase::ReadRam => self.time_read_ram,
+            Phase::UpdateRegisters => self.time_update_registers,
+            Phase::UpdateRam => self.time_update_ram,
+            Phase::UpdatePc => self.time_update_pc,
+            Phase::RefreshRam => self.time_refresh_ram,
+        }
+    }
+
+    // Adds the times of the updates measured in `other`, e.g. by the thread
+    // running them apart from this cycle.
+    pub(crate) fn add_update_times(&mut self, other: &Measurement) {
+        self.time_update_registers += other.time_update_registers;
+        self.time_evaluate_rd_ops += other.time_evaluate_rd_ops;
+        self.time_blind_select_rd += other.time_blind_select_rd;
+        self.time_refresh_rd += other.time_refresh_rd;
+        self.time_write_rd += other.time_write_rd;
+        self.time_update_ram += other.time_update_ram;
+        self.time_ram_update_op_eval += other.time_ram_update_op_eval;
+        self.time_blind_select_ram_value += other.time_blind_select_ram_value;
+        self.time_refresh_ram_value += other.time_refresh_ram_value;
+        self.time_write_ram += other.time_write_ram;
+        self.time_update_pc += other.time_update_pc;
+        self.time_refresh_ram += other.time_refresh_ram;
+    }
+
+    pub(crate) fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_u64(writer, self.total_cycle_time.as_nanos() as u64)?;
+        write_u64(writer, self.time_critical_path.as_nanos() as u64)?;
+        write_u64(writer, self.time_prepare_pc.as_nanos() as u64)?;
+        write_u64(writer, self.time_read_and_prepare_rom.as_nanos() as u64)?;
+        write_u64(writer, self.time_read_rom.as_nanos() as u64)?;
+        write_u64(writer, self.time_prepare_rom.as_nanos() as u64)?;
+        write_u64(
+            writer,
+            self.time_read_and_prepare_registers.as_nanos() as u64,
+        )?;
+        write_u64(writer, self.time_read_registers.as_nanos() as u64)?;
+        write_u64(writer, self.time_prepare_registers.as_nanos() as u64)?;
+        write_u64(writer, self.time_read_ram.as_nanos() as u64)?;
+        write_u64(writer, self.time_derive_ram_addr.as_nanos() as u64)?;
+        write_u64(writer, self.time_prepare_ram_addr.as_nanos() as u64)?;
+        write_u64(
+            writer,
+            self.time_prepare_ram_read_statefull.as_nanos() as u64,
+        )?;
+        write_u64(writer, self.time_update_registers.as_nanos() as u64)?;
+        write_u64(writer, self.time_evaluate_rd_ops.as_nanos() as u64)?;
+        write_u64(writer, self.time_blind_select_rd.as_nanos() as u64)?;
+        write_u64(writer, self.time_refresh_rd.as_nanos() as u64)?;
+        write_u64(writer, self.time_write_rd.as_nanos() as u64)?;
+        write_u64(writer, self.time_update_ram.as_nanos() as u64)?;
+        write_u64(writer, self.time_ram_update_op_eval.as_nanos() as u64)?;
+        write_u64(writer, self.time_blind_select_ram_value.as_nanos() as u64)?;
+        write_u64(writer, self.time_refresh_ram_value.as_nanos() as u64)?;
+        write_u64(writer, self.time_write_ram.as_nanos() as u64)?;
+        write_u64(writer, self.time_update_pc.as_nanos() as u64)?;
+        write_u64(writer, self.time_refresh_ram.as_nanos() as u64)?;
+        write_u64(writer, self.pc_val_fhe_uint_noise.to_bits())?;
+        write_u64(writer, self.imm_val_fhe_uint_noise.to_bits())?;
+        write_u64(writer, self.ram_addr_read_noise.to_bits())?;
+        write_u64(writer, self.ram_val_read_noise.to_bits())?;
+        write_u64(writer, self.rd_val_fhe_uint_noise.to_bits())?;
+        Ok(())
+    }
+
+    pub(crate) fn read_from<R: Read>(reader: &mut R) -> Result<Self> {
+        Ok(Self {
+            total_cycle_time: Duration::from_nanos(read_u64(reader)?),
+            time_critical_path: Duration::from_nanos(read_u64(reader)?),
+            time_prepare_pc: Duration::from_nanos(read_u64(reader)?),
+            time_read_and_prepare_rom: Duration::from_nanos(read_u64(reader)?),
+            time_read_rom: Duration::from_nanos(read_u64(reader)?),
+            time_prepare_rom: Duration::from_nanos(read_u64(reader)?),
+            time_read_and_prepare_registers: Duration::from_nanos(read_u64(reader)?),
+            time_read_registers: Duration::from_nanos(read_u64(reader)?),
+            time_prepare_registers: Duration::from_nanos(read_u64(reader)?),
+            time_read_ram: Duration::from_nanos(read_u64(reader)?),
+            time_derive_ram_addr: Duration::from_nanos(read_u64(reader)?),
+            time_prepare_ram_addr: Duration::from_nanos(read_u64(reader)?),
+            time_prepare_ram_read_statefull: Duration::from_nanos(read_u64(reader)?),
+            time_update_registers: Duration::from_nanos(read_u64(reader)?),
+            time_evaluate_rd_ops: Duration::from_nanos(read_u64(reader)?),
+            time_blind_select_rd: Duration::from_nanos(read_u64(reader)?),
+            time_refresh_rd: Duration::from_nanos(read_u64(reader)?),
+            time_write_rd: Duration::from_nanos(read_u64(reader)?),
+            time_update_ram: Duration::from_nanos(read_u64(reader)?),
+            time_ram_update_op_eval: Duration::from_nanos(read_u64(reader)?),
+            time_blind_select_ram_value: Duration::from_nanos(read_u64(reader)?),
+            time_refresh_ram_value: Duration::from_nanos(read_u64(reader)?),
+            time_write_ram: Duration::from_nanos(read_u64(reader)?),
+            time_update_pc: Duration::from_nanos(read_u64(reader)?),
+            time_refresh_ram: Duration::from_nanos(read_u64(reader)?),
+            pc_val_fhe_uint_noise: f64::from_bits(read_u64(reader)?),
+            imm_val_fhe_uint_noise: f64::from_bits(read_u64(reader)?),
+            ram_addr_read_noise: f64::from_bits(read_u64(reader)?),
+            ram_val_read_noise: f64::from_bits(read_u64(reader)?),
+            rd_val_fhe_uint_noise: f64::from_bits(read_u64(reader)?),
+        })
+    }
+}
+
 pub fn measure_duration<F>(mut operation: F) -> Duration
 where
     F: FnMut(),
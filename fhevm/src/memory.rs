@@ -1,17 +1,21 @@
-use std::{f64, thread};
+use std::{
+    f64,
+    io::{Read, Result, Write},
+    thread,
+};
 
 use poulpy_core::{
     layouts::{
         GGLWEInfos, GGLWELayout, GGLWEPreparedToRef, GGSWInfos, GLWEAutomorphismKeyHelper,
-        GLWEInfos, GLWELayout, GLWESecretPreparedToRef, GLWEToMut, GetGaloisElement,
-        TorusPrecision, GLWE,
+        GLWECompressed, GLWEDecompress, GLWEInfos, GLWELayout, GLWEPreparedToRef,
+        GLWESecretPreparedToRef, GLWEToMut, GetGaloisElement, LWEInfos, TorusPrecision, GLWE,
     },
-    GLWEAdd, GLWECopy, GLWEDecrypt, GLWEEncryptSk, GLWENoise, GLWENormalize, GLWEPacking,
-    GLWERotate, GLWESub, GLWETrace, ScratchTakeCore,
+    GLWEAdd, GLWECompressedEncryptSk, GLWECopy, GLWEDecrypt, GLWEEncryptPk, GLWEEncryptSk,
+    GLWENormalize, GLWEPacking, GLWERotate, GLWESub, GLWETrace, GetDistribution, ScratchTakeCore,
 };
 use poulpy_hal::{
     api::{ModuleLogN, ModuleN, ScratchAvailable, TakeSlice},
-    layouts::{Backend, DataMut, DataRef, Scratch},
+    layouts::{Backend, DataMut, DataRef, ReaderFrom, Scratch, WriterTo},
     source::Source,
 };
 use poulpy_schemes::bin_fhe::bdd_arithmetic::{
@@ -19,6 +23,8 @@ use poulpy_schemes::bin_fhe::bdd_arithmetic::{
     GLWEBlindRotation, GetGGSWBit, ToBits,
 };
 
+use crate::serialization::{invalid_data, read_u64, write_u64};
+
 pub struct Memory {
     bits: Vec<BitArray>,
     size: usize,
@@ -45,6 +51,126 @@ impl Memory {
         self.size
     }
 
+    /// Layout of the ciphertexts of the memory.
+    pub(crate) fn glwe_layout(&self) -> GLWELayout {
+        self.bits[0].data[0].glwe_layout()
+    }
+
+    /// Reallocates the memory with `size` words of the same layout, discarding its content.
+    pub(crate) fn resize(&mut self, size: usize) {
+        *self = Self::alloc(&self.glwe_layout(), self.bits.len(), size);
+    }
+
+    /// Serializes the ciphertexts of the memory as: word size | size | bit arrays.
+    pub(crate) fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_u64(writer, self.bits.len() as u64)?;
+        write_u64(writer, self.size as u64)?;
+        for bits in &self.bits {
+            for ct in &bits.data {
+                ct.write_to(writer)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Overwrites the ciphertexts of the memory with the ones written by [Memory::write_to].
+    pub(crate) fn read_from<R: Read>(&mut self, reader: &mut R) -> Result<()> {
+        let word_size: usize = read_u64(reader)? as usize;
+        let size: usize = read_u64(reader)? as usize;
+        if word_size != self.bits.len() || size != self.size {
+            return Err(invalid_data(format!(
+                "memory (word_size, size)=({}, {}) != read ({}, {})",
+                self.bits.len(),
+                self.size,
+                word_size,
+                size
+            )));
+        }
+        for bits in &mut self.bits {
+            for ct in &mut bits.data {
+                ct.read_from(reader)?;
+            }
+        }
+        self.state = false;
+        Ok(())
+    }
+
+    /// Encrypts `data` with seed-compressed ciphertexts, which are directly
+    /// written with the layout of [Memory::write_to].
+    ///
+    /// Only the body and the 32-byte seed of the mask are stored, which makes
+    /// the output roughly (rank+1) times smaller.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn encrypt_sk_compressed_to<W: Write, M, S, BE: Backend>(
+        &self,
+        module: &M,
+        data: &[u32],
+        sk: &S,
+        source_xa: &mut Source,
+        source_xe: &mut Source,
+        scratch: &mut Scratch<BE>,
+        writer: &mut W,
+    ) -> Result<()>
+    where
+        M: ModuleN + GLWECompressedEncryptSk<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+        S: GLWESecretPreparedToRef<BE>,
+        u32: ToBits,
+    {
+        let size: usize = self.size;
+        let ram_chunks: usize = self.bits.len();
+
+        assert!(data.len() / ram_chunks <= size);
+
+        write_u64(writer, ram_chunks as u64)?;
+        write_u64(writer, size as u64)?;
+
+        let mut bits: Vec<u8> = vec![0u8; size];
+
+        for i in 0..ram_chunks {
+            for (x, y) in bits.iter_mut().zip(data.iter()) {
+                *x = y.bit(i);
+            }
+            self.bits[i].encrypt_sk_compressed_to(
+                module, &bits, sk, source_xa, source_xe, scratch, writer,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Overwrites the ciphertexts of the memory with the decompressed
+    /// ciphertexts written by [Memory::encrypt_sk_compressed_to].
+    pub(crate) fn read_compressed_from<R: Read, M>(
+        &mut self,
+        module: &M,
+        reader: &mut R,
+    ) -> Result<()>
+    where
+        M: GLWEDecompress,
+    {
+        let word_size: usize = read_u64(reader)? as usize;
+        let size: usize = read_u64(reader)? as usize;
+        if word_size != self.bits.len() || size != self.size {
+            return Err(invalid_data(format!(
+                "memory (word_size, size)=({}, {}) != read ({}, {})",
+                self.bits.len(),
+                self.size,
+                word_size,
+                size
+            )));
+        }
+        let mut ct_compressed: GLWECompressed<Vec<u8>> =
+            GLWECompressed::alloc_from_infos(&self.bits[0].data[0]);
+        for bits in &mut self.bits {
+            for ct in &mut bits.data {
+                ct_compressed.read_from(reader)?;
+                ct.decompress(module, &ct_compressed);
+            }
+        }
+        self.state = false;
+        Ok(())
+    }
+
     pub(crate) fn encrypt_sk<M, S, BE: Backend>(
         &mut self,
         module: &M,
@@ -74,6 +200,76 @@ impl Memory {
         }
     }
 
+    /// Same as [Memory::encrypt_sk] but with the public key `pk`.
+    pub(crate) fn encrypt_pk<M, K, BE: Backend>(
+        &mut self,
+        module: &M,
+        data: &[u32],
+        pk: &K,
+        source_xu: &mut Source,
+        source_xe: &mut Source,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: ModuleN + GLWEEncryptPk<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+        K: GLWEPreparedToRef<BE> + GetDistribution + GLWEInfos,
+        u32: ToBits,
+    {
+        let size: usize = self.size;
+        let ram_chunks: usize = self.bits.len();
+
+        assert!(data.len() / ram_chunks <= size);
+
+        let mut bits: Vec<u8> = vec![0u8; size];
+
+        for i in 0..ram_chunks {
+            for (x, y) in bits.iter_mut().zip(data.iter()) {
+                *x = y.bit(i);
+            }
+            self.bits[i].encrypt_pk(module, &bits, pk, source_xu, source_xe, scratch);
+        }
+    }
+
+    /// Injects `bits[i][j]`, encrypting the words [word_offset, word_offset + size)
+    /// of the j-th ciphertext overlapped by the range, into the i-th bit array.
+    ///
+    /// The ciphertexts entirely covered by the range are replaced. The ones at its
+    /// boundaries also hold words outside of the range, so `bits` is added to them
+    /// instead, which requires their words in the range to be zero.
+    pub(crate) fn inject_at<M, BE: Backend>(
+        &mut self,
+        module: &M,
+        word_offset: usize,
+        size: usize,
+        bits: &[Vec<GLWE<Vec<u8>>>],
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: GLWEAdd + GLWECopy + GLWENormalize<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        assert_eq!(bits.len(), self.bits.len());
+        assert!(!self.state);
+
+        for (bit_array, cts) in self.bits.iter_mut().zip(bits.iter()) {
+            let n: usize = bit_array.data[0].n().into();
+            let ct_offset: usize = word_offset / n;
+            assert!(ct_offset + cts.len() <= bit_array.data.len());
+            for (j, (res, a)) in bit_array.data[ct_offset..]
+                .iter_mut()
+                .zip(cts.iter())
+                .enumerate()
+            {
+                let start: usize = (ct_offset + j) * n;
+                if word_offset <= start && start + n <= word_offset + size {
+                    module.glwe_copy(res, a);
+                } else {
+                    module.glwe_add_inplace(res, a);
+                    module.glwe_normalize_inplace(res, scratch);
+                }
+            }
+        }
+    }
+
     #[allow(dead_code)]
     pub(crate) fn decrypt<M, S, BE: Backend>(
         &self,
@@ -107,6 +303,44 @@ impl Memory {
         }
     }
 
+    /// Decrypts the words [offset, offset + data.len()) into `data`.
+    ///
+    /// Only the ciphertexts overlapping the range are decrypted.
+    pub(crate) fn decrypt_range<M, S, BE: Backend>(
+        &self,
+        module: &M,
+        offset: usize,
+        data: &mut [u32],
+        sk: &S,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: ModuleN + GLWEDecrypt<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+        S: GLWESecretPreparedToRef<BE>,
+    {
+        assert!(
+            offset + data.len() <= self.size,
+            "range [{}, {}) exceeds memory size {}",
+            offset,
+            offset + data.len(),
+            self.size
+        );
+
+        let mut bits: Vec<u8> = vec![0u8; data.len()];
+
+        for i in 0..self.bits.len() {
+            self.bits[i].decrypt_range(module, offset, bits.as_mut_slice(), sk, scratch);
+
+            for (x, y) in bits.iter().zip(data.iter_mut()) {
+                if *x == 1 {
+                    *y |= 1 << i;
+                } else {
+                    *y &= !(1 << i);
+                }
+            }
+        }
+    }
+
     pub(crate) fn noise<M, S, BE: Backend>(
         &self,
         module: &M,
@@ -115,7 +349,7 @@ impl Memory {
         scratch: &mut Scratch<BE>,
     ) -> Vec<f64>
     where
-        M: ModuleN + GLWEDecrypt<BE> + GLWENoise<BE>,
+        M: ModuleN + GLWEDecrypt<BE> + GLWESub + GLWENormalize<BE>,
         Scratch<BE>: ScratchTakeCore<BE>,
         S: GLWESecretPreparedToRef<BE>,
         u32: FromBits,
@@ -126,15 +360,14 @@ impl Memory {
         assert!(data.len() / ram_chunks <= max_addr);
 
         let mut bits: Vec<u8> = vec![0u8; max_addr];
-        let mut noise: Vec<f64> = vec![0f64; self.bits.len()];
-        for i in 0..ram_chunks {
-            for (x, y) in bits.iter_mut().zip(data.iter()) {
-                *x = y.bit(i)
-            }
-            noise[i] = self.bits[i].noise(module, bits.as_slice(), sk, scratch);
-        }
-
-        noise
+        (0..ram_chunks)
+            .map(|i| {
+                for (x, y) in bits.iter_mut().zip(data.iter()) {
+                    *x = y.bit(i)
+                }
+                self.bits[i].noise(module, bits.as_slice(), sk, scratch)
+            })
+            .collect()
     }
 
     pub(crate) fn zero<M, BE: Backend, K, H>(
@@ -190,6 +423,7 @@ impl Memory {
         });
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn read_stateless<DR: DataMut, D: DataRef, H, M, K, BE: Backend>(
         &mut self,
         threads: usize,
@@ -210,7 +444,7 @@ impl Memory {
             "unitialized memory: self.data.len()=0"
         );
 
-        assert_eq!(self.state, false);
+        assert!(!self.state);
 
         let (mut tmp_res, scratch_1) = scratch.take_glwe_slice(self.bits.len(), res);
 
@@ -244,6 +478,7 @@ impl Memory {
         res.pack(module, tmp_res, keys, scratch_1);
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn read_statefull<DR: DataMut, A, H, M, K, BE: Backend>(
         &mut self,
         threads: usize,
@@ -265,7 +500,7 @@ impl Memory {
             "unitialized memory: self.data.len()=0"
         );
 
-        assert_eq!(self.state, false);
+        assert!(!self.state);
 
         let (mut tmp_res, scratch_1) = scratch.take_glwe_slice(self.bits.len(), res);
 
@@ -305,6 +540,7 @@ impl Memory {
         self.state = true;
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn read_statefull_rev<M, D, A, K, H, BE: Backend>(
         &mut self,
         threads: usize,
@@ -329,7 +565,7 @@ impl Memory {
         K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
         Scratch<BE>: ScratchTakeCore<BE>,
     {
-        assert_eq!(self.state, true);
+        assert!(self.state);
 
         let scratch_thread_size = BitArray::retrieve_statefull_rev_tmp_bytes(
             module,
@@ -370,6 +606,7 @@ impl Memory {
         self.state = false;
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn write<M, D, A, K, H, BE: Backend>(
         &mut self,
         threads: usize,
@@ -394,7 +631,7 @@ impl Memory {
         K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
         Scratch<BE>: ScratchTakeCore<BE>,
     {
-        assert_eq!(self.state, false);
+        assert!(!self.state);
 
         let scratch_thread_size = BitArray::write_tmp_bytes(
             module,
@@ -431,6 +668,231 @@ impl Memory {
             }
         });
     }
+
+    /// Reads the word at the public address `addr` into `res`.
+    pub(crate) fn read_at<DR: DataMut, H, M, K, BE: Backend>(
+        &self,
+        module: &M,
+        res: &mut FheUint<DR, u32>,
+        addr: usize,
+        keys: &H,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: ModuleLogN + GLWERotate<BE> + GLWEPacking<BE> + GLWECopy,
+        H: GLWEAutomorphismKeyHelper<K, BE>,
+        K: GGLWEPreparedToRef<BE> + GetGaloisElement + GGLWEInfos,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        assert!(!self.state);
+        assert!(addr < self.size, "addr={addr} >= size={}", self.size);
+
+        let (mut tmp_res, scratch_1) = scratch.take_glwe_slice(self.bits.len(), res);
+        for (subram, res) in self.bits.iter().zip(tmp_res.iter_mut()) {
+            subram.retrieve_at(module, res, addr);
+        }
+        res.pack(module, tmp_res, keys, scratch_1);
+    }
+
+    /// Overwrites the word at the public address `addr` with `w`.
+    pub(crate) fn write_at<M, D, K, H, BE: Backend>(
+        &mut self,
+        threads: usize,
+        module: &M,
+        w: &FheUint<D, u32>, // Must encrypt [w, 0, 0, ..., 0];
+        addr: usize,
+        keys: &H,
+        scratch: &mut Scratch<BE>,
+    ) where
+        D: DataRef,
+        M: Sync
+            + ModuleLogN
+            + GLWERotate<BE>
+            + GLWETrace<BE>
+            + GLWEAdd
+            + GLWESub
+            + GLWENormalize<BE>,
+        H: Sync + GLWEAutomorphismKeyHelper<K, BE>,
+        K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        assert!(!self.state);
+        assert!(addr < self.size, "addr={addr} >= size={}", self.size);
+
+        let glwe_infos: GLWELayout = self.glwe_layout();
+        let key_infos: GGLWELayout = keys.automorphism_key_infos();
+
+        let scratch_thread_size: usize =
+            BitArray::write_at_tmp_bytes(module, &glwe_infos, &key_infos)
+                + GLWE::bytes_of_from_infos(w);
+
+        assert!(
+            scratch.available() >= threads * scratch_thread_size,
+            "scratch.available(): {} < threads:{threads} * scratch_thread_size: {scratch_thread_size}",
+            scratch.available()
+        );
+
+        let (mut scratches, _) = scratch.split_mut(threads, scratch_thread_size);
+
+        let chunk_size: usize = self.bits.len().div_ceil(threads);
+
+        thread::scope(|scope| {
+            for (idx, (scratch_thread, subram_chunk)) in scratches
+                .iter_mut()
+                .zip(self.bits.chunks_mut(chunk_size))
+                .enumerate()
+            {
+                scope.spawn(move || {
+                    for (i, subram) in subram_chunk.iter_mut().enumerate() {
+                        let (mut bit, scratch_1) = scratch_thread.take_glwe(w);
+                        w.get_bit_glwe(module, chunk_size * idx + i, &mut bit, keys, scratch_1);
+                        subram.write_at(module, &bit, addr, keys, scratch_1);
+                    }
+                });
+            }
+        });
+    }
+
+    /// Returns the [RefreshSchedule] keeping the noise of the words of the
+    /// memory below [NoiseModel::max].
+    pub(crate) fn refresh_schedule(&self, model: &NoiseModel) -> RefreshSchedule {
+        let words_per_poly: usize = self.size.min(self.bits[0].data[0].n().into());
+        RefreshSchedule::new(model, self.size, words_per_poly)
+    }
+}
+
+/// Model of the noise of the words of a [Memory].
+///
+/// Noises are the base-2 logarithm of the maximum error of the bits of a
+/// word, as printed by [crate::Interpreter::cycle_debug]. The variance of the
+/// error grows linearly with the number of passes over the memory, i.e. of
+/// cycles of the interpreter, whose read and write at an encrypted address
+/// touch every word, until the word is written again.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NoiseModel {
+    /// Noise of a word right after it is written.
+    pub fresh: f64,
+    /// Noise added to every word by a pass over the memory.
+    pub pass: f64,
+    /// Maximum noise of a word, below the 2^-3 at which its bits no longer
+    /// decrypt, with some margin.
+    pub max: f64,
+}
+
+impl Default for NoiseModel {
+    /// Noise growth of the RAM measured with the default
+    /// [crate::parameters::CryptographicParameters].
+    fn default() -> Self {
+        Self {
+            fresh: RAM_FRESH_NOISE,
+            pass: RAM_PASS_NOISE,
+            max: RAM_MAX_NOISE,
+        }
+    }
+}
+
+// Noise of the RAM words with the default parameters, fitted by
+// `tests::refresh::test_ram_noise_model` to about -10.3 (fresh) and -10.5
+// (pass) over 8 passes, and rounded up.
+const RAM_FRESH_NOISE: f64 = -10.0;
+const RAM_PASS_NOISE: f64 = -10.0;
+// The bits are encoded with a torus precision of 2, i.e. at 2^-2, and no longer
+// decrypt past 2^-3: one bit of margin.
+const RAM_MAX_NOISE: f64 = -4.0;
+
+impl NoiseModel {
+    /// Fits the model, by least squares on the variances, to the noises
+    /// `noise[i]` of a word measured after `i` passes since it was written.
+    pub fn fit(noise: &[f64], max: f64) -> Self {
+        assert!(noise.len() >= 2, "at least two measurements are needed");
+        let n: f64 = noise.len() as f64;
+        let var: Vec<f64> = noise.iter().map(|x| (2.0 * x).exp2()).collect();
+        let mean_x: f64 = (n - 1.0) / 2.0;
+        let mean_y: f64 = var.iter().sum::<f64>() / n;
+        let (mut cov, mut var_x) = (0.0, 0.0);
+        for (i, y) in var.iter().enumerate() {
+            cov += (i as f64 - mean_x) * (y - mean_y);
+            var_x += (i as f64 - mean_x) * (i as f64 - mean_x);
+        }
+        let slope: f64 = cov / var_x;
+        assert!(slope > 0.0, "the noise does not grow with the passes");
+        let intercept: f64 = (mean_y - slope * mean_x).max(f64::MIN_POSITIVE);
+        Self {
+            fresh: intercept.log2() / 2.0,
+            pass: slope.log2() / 2.0,
+            max,
+        }
+    }
+
+    /// Number of passes after which the noise of a freshly written word
+    /// reaches [NoiseModel::max].
+    pub fn passes(&self) -> usize {
+        let budget: f64 = (2.0 * self.max).exp2() - (2.0 * self.fresh).exp2();
+        (budget / (2.0 * self.pass).exp2()).max(0.0) as usize
+    }
+}
+
+/// Round-robin refresh of the words of a [Memory]: every [RefreshSchedule::period]
+/// cycles, the next [RefreshSchedule::words] words are bootstrapped.
+///
+/// A cycle is one pass over the memory, and refreshing a word is itself a
+/// pass over the words sharing its polynomial, so the schedule refreshes every
+/// word before the cycles and the refreshes of a round exhaust the
+/// [NoiseModel::passes] budget.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RefreshSchedule {
+    size: usize,
+    period: usize,
+    words: usize,
+}
+
+impl RefreshSchedule {
+    /// Schedule of a memory of `size` words, with `words_per_poly` words per
+    /// polynomial, following `model`.
+    ///
+    /// Panics if the noise budget is too small for any schedule to keep up.
+    pub fn new(model: &NoiseModel, size: usize, words_per_poly: usize) -> Self {
+        let passes: usize = model.passes();
+        // Passes left to the cycles of a round, once the refreshes are accounted for.
+        let slack: usize = passes.saturating_sub(words_per_poly);
+        assert!(
+            slack > 0,
+            "noise budget of {passes} passes too small to refresh {words_per_poly} words per polynomial"
+        );
+        let (period, words) = if size <= slack {
+            (slack / size, 1)
+        } else {
+            (1, size.div_ceil(slack))
+        };
+        Self {
+            size,
+            period,
+            words,
+        }
+    }
+
+    /// Number of cycles between two refreshes.
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Number of words bootstrapped by a refresh.
+    pub fn words(&self) -> usize {
+        self.words
+    }
+
+    /// Addresses of the words to refresh at the end of the cycle `cycle`.
+    ///
+    /// Only depends on `cycle`, so that a resumed execution follows the same
+    /// schedule.
+    pub fn words_at(&self, cycle: usize) -> Vec<usize> {
+        if !(cycle + 1).is_multiple_of(self.period) {
+            return Vec::new();
+        }
+        let start: usize = (cycle / self.period) * self.words;
+        (start..start + self.words.min(self.size))
+            .map(|addr| addr % self.size)
+            .collect()
+    }
 }
 
 struct BitArray {
@@ -477,11 +939,82 @@ impl BitArray {
                 *y = *x as i64
             }
 
-            pt.encode_vec_i64(&data_i64, TorusPrecision(2));
+            pt.encode_vec_i64(data_i64, TorusPrecision(2));
             ct.encrypt_sk(module, &pt, sk_prepared, source_xa, source_xe, scratch_2);
         }
     }
 
+    fn encrypt_pk<M, BE: Backend, K>(
+        &mut self,
+        module: &M,
+        data: &[u8],
+        pk: &K,
+        source_xu: &mut Source,
+        source_xe: &mut Source,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: ModuleN + GLWEEncryptPk<BE>,
+        K: GLWEPreparedToRef<BE> + GetDistribution + GLWEInfos,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        let (mut pt, scratch_1) = scratch.take_glwe_plaintext(&self.data[0]);
+        let (data_i64, scratch_2) = scratch_1.take_slice(module.n());
+
+        for (chunk, ct) in data.chunks(module.n()).zip(self.data.iter_mut()) {
+            data_i64.fill(0);
+
+            for (y, x) in data_i64.iter_mut().zip(chunk.iter()) {
+                *y = *x as i64
+            }
+
+            pt.encode_vec_i64(data_i64, TorusPrecision(2));
+            ct.encrypt_pk(module, &pt, pk, source_xu, source_xe, scratch_2);
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn encrypt_sk_compressed_to<W: Write, M, BE: Backend, S>(
+        &self,
+        module: &M,
+        data: &[u8],
+        sk_prepared: &S,
+        source_xa: &mut Source,
+        source_xe: &mut Source,
+        scratch: &mut Scratch<BE>,
+        writer: &mut W,
+    ) -> Result<()>
+    where
+        M: ModuleN + GLWECompressedEncryptSk<BE>,
+        S: GLWESecretPreparedToRef<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        let mut ct_compressed: GLWECompressed<Vec<u8>> =
+            GLWECompressed::alloc_from_infos(&self.data[0]);
+
+        let (mut pt, scratch_1) = scratch.take_glwe_plaintext(&self.data[0]);
+        let (data_i64, scratch_2) = scratch_1.take_slice(module.n());
+
+        for chunk in data.chunks(module.n()) {
+            data_i64.fill(0);
+
+            for (y, x) in data_i64.iter_mut().zip(chunk.iter()) {
+                *y = *x as i64
+            }
+
+            pt.encode_vec_i64(data_i64, TorusPrecision(2));
+            ct_compressed.encrypt_sk(
+                module,
+                &pt,
+                sk_prepared,
+                source_xa.new_seed(),
+                source_xe,
+                scratch_2,
+            );
+            ct_compressed.write_to(writer)?;
+        }
+        Ok(())
+    }
+
     #[allow(dead_code)]
     fn decrypt<M, BE: Backend, S>(
         &self,
@@ -495,17 +1028,47 @@ impl BitArray {
         Scratch<BE>: ScratchTakeCore<BE>,
     {
         let (mut pt, scratch_1) = scratch.take_glwe_plaintext(&self.data[0]);
-        let (mut data_i64, scratch_2) = scratch_1.take_slice(module.n());
+        let (data_i64, scratch_2) = scratch_1.take_slice(module.n());
 
         for (chunk, ct) in data.chunks_mut(module.n()).zip(self.data.iter()) {
             ct.decrypt(module, &mut pt, sk_prepared, scratch_2);
-            pt.decode_vec_i64(&mut data_i64, TorusPrecision(2));
+            pt.decode_vec_i64(data_i64, TorusPrecision(2));
             for (y, x) in data_i64.iter_mut().zip(chunk.iter_mut()) {
                 *x = *y as u8;
             }
         }
     }
 
+    fn decrypt_range<M, BE: Backend, S>(
+        &self,
+        module: &M,
+        offset: usize,
+        data: &mut [u8],
+        sk_prepared: &S,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: ModuleN + GLWEDecrypt<BE>,
+        S: GLWESecretPreparedToRef<BE>,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        if data.is_empty() {
+            return;
+        }
+
+        let n: usize = module.n();
+        let (mut pt, scratch_1) = scratch.take_glwe_plaintext(&self.data[0]);
+        let (data_i64, scratch_2) = scratch_1.take_slice(n);
+
+        let end: usize = offset + data.len();
+        for j in offset / n..end.div_ceil(n) {
+            self.data[j].decrypt(module, &mut pt, sk_prepared, scratch_2);
+            pt.decode_vec_i64(data_i64, TorusPrecision(2));
+            for addr in (j * n).max(offset)..((j + 1) * n).min(end) {
+                data[addr - offset] = data_i64[addr % n] as u8;
+            }
+        }
+    }
+
     #[allow(dead_code)]
     fn noise<M, BE: Backend, S>(
         &self,
@@ -515,25 +1078,40 @@ impl BitArray {
         scratch: &mut Scratch<BE>,
     ) -> f64
     where
-        M: ModuleN + GLWEDecrypt<BE> + GLWENoise<BE>,
+        M: ModuleN + GLWEDecrypt<BE> + GLWESub + GLWENormalize<BE>,
         S: GLWESecretPreparedToRef<BE>,
         Scratch<BE>: ScratchTakeCore<BE>,
     {
-        let (mut pt, scratch_1) = scratch.take_glwe_plaintext(&self.data[0]);
-        let (data_i64, scratch_2) = scratch_1.take_slice(module.n());
+        let (mut pt_want, scratch_1) = scratch.take_glwe_plaintext(&self.data[0]);
+        let (mut pt_have, scratch_2) = scratch_1.take_glwe_plaintext(&self.data[0]);
+        let (data_i64, scratch_3) = scratch_2.take_slice(module.n());
+
+        // Precision at which the error is decoded.
+        let k: usize = pt_have.k().as_usize().min(60);
 
         let mut max_noise: f64 = f64::MIN;
 
         for (chunk, ct) in data.chunks(module.n()).zip(self.data.iter()) {
+            // The scratch is not zeroed and the last chunk can be partial.
+            data_i64.fill(0);
             for (y, x) in data_i64.iter_mut().zip(chunk.iter()) {
                 *y = *x as i64;
             }
-            pt.encode_vec_i64(&data_i64, TorusPrecision(2));
-            max_noise = max_noise.max(
-                ct.noise(module, &mut pt, sk_prepared, scratch_2)
-                    .max()
-                    .log2(),
-            );
+            pt_want.encode_vec_i64(data_i64, TorusPrecision(2));
+
+            ct.decrypt(module, &mut pt_have, sk_prepared, scratch_3);
+            module.glwe_sub_inplace(&mut pt_have, &pt_want);
+            module.glwe_normalize_inplace(&mut pt_have, scratch_3);
+            pt_have.decode_vec_i64(data_i64, TorusPrecision(k as u32));
+
+            // Only the coefficients holding a bit, the others are never
+            // written, nor refreshed, and keep accumulating noise.
+            let max: u64 = data_i64[..chunk.len()]
+                .iter()
+                .map(|e| e.unsigned_abs())
+                .max()
+                .unwrap_or(0);
+            max_noise = max_noise.max((max as f64).log2() - k as f64);
         }
 
         max_noise
@@ -634,7 +1212,7 @@ impl BitArray {
             0,
             scratch,
         );
-        module.glwe_copy(res, &mut self.data[0]);
+        module.glwe_copy(res, &self.data[0]);
     }
 
     fn retrieve_statefull_rev_tmp_bytes<M, R, A, K, BE: Backend>(
@@ -690,7 +1268,7 @@ impl BitArray {
     {
         {
             let (mut tmp, scratch_1) = scratch.take_glwe(&self.data[0]);
-            module.glwe_trace(&mut tmp, 0, &mut self.data[0], keys, scratch_1);
+            module.glwe_trace(&mut tmp, 0, &self.data[0], keys, scratch_1);
             module.glwe_sub_inplace(&mut self.data[0], &tmp);
         }
 
@@ -780,7 +1358,7 @@ impl BitArray {
 
         {
             let (mut tmp, scratch_1) = scratch.take_glwe(&self.data[0]);
-            module.glwe_trace(&mut tmp, 0, &mut self.data[0], keys, scratch_1);
+            module.glwe_trace(&mut tmp, 0, &self.data[0], keys, scratch_1);
             module.glwe_sub_inplace(&mut self.data[0], &tmp);
         }
 
@@ -807,4 +1385,55 @@ impl BitArray {
             );
         }
     }
+
+    fn retrieve_at<R, M, BE: Backend>(&self, module: &M, res: &mut R, addr: usize)
+    where
+        M: ModuleN + GLWERotate<BE>,
+        R: GLWEToMut,
+    {
+        let (poly, idx) = (addr / module.n(), addr % module.n());
+        module.glwe_rotate(-(idx as i64), res, &self.data[poly]);
+    }
+
+    fn write_at_tmp_bytes<M, R, K, BE: Backend>(module: &M, res: &R, key: &K) -> usize
+    where
+        M: ModuleN + GLWERotate<BE> + GLWETrace<BE> + GLWENormalize<BE>,
+        R: GLWEInfos,
+        K: GGLWEInfos,
+    {
+        (module.glwe_trace_tmp_bytes(res, res, key) + GLWE::bytes_of_from_infos(res))
+            .max(module.glwe_rotate_tmp_bytes())
+            .max(module.glwe_normalize_tmp_bytes())
+    }
+
+    fn write_at<R, M, H, K, BE: Backend>(
+        &mut self,
+        module: &M,
+        res: &R,
+        addr: usize,
+        keys: &H,
+        scratch: &mut Scratch<BE>,
+    ) where
+        M: ModuleN + GLWERotate<BE> + GLWETrace<BE> + GLWESub + GLWEAdd + GLWENormalize<BE>,
+        R: GLWEToMut,
+        H: GLWEAutomorphismKeyHelper<K, BE>,
+        K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+        Scratch<BE>: ScratchTakeCore<BE>,
+    {
+        let (poly, idx) = (addr / module.n(), addr % module.n());
+        let a: &mut GLWE<Vec<u8>> = &mut self.data[poly];
+
+        module.glwe_rotate_inplace(-(idx as i64), a, scratch);
+
+        {
+            let (mut tmp, scratch_1) = scratch.take_glwe(a);
+            module.glwe_trace(&mut tmp, 0, a, keys, scratch_1);
+            module.glwe_sub_inplace(a, &tmp);
+        }
+
+        module.glwe_add_inplace(a, res);
+        module.glwe_normalize_inplace(a, scratch);
+
+        module.glwe_rotate_inplace(idx as i64, a, scratch);
+    }
 }
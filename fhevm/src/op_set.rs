@@ -0,0 +1,86 @@
+//! Pruning of the operations evaluated by the [crate::Interpreter].
+//!
+//! Each cycle, the register update evaluates every operation of the
+//! instruction set, and the RAM update every store, before blindly selecting
+//! the one of the instruction. An [OpSet] restricts them to the operations
+//! present in the ROM, see [crate::Interpreter::set_op_set], so a program
+//! without shifts or halfword stores does not pay for them.
+//!
+//! This is a privacy/performance trade-off: the op set is public, as the
+//! server evaluates it in the clear, and reveals which operations the program
+//! uses (e.g. that it never divides, or only stores words). It does not reveal
+//! where or how often they are used, nor which one a cycle executes. Programs
+//! whose instruction mix is itself sensitive must not enable it.
+
+use std::io::{Read, Result, Write};
+
+use crate::{
+    serialization::{invalid_data, read_u64, write_u64},
+    InstructionsParser, RAM_UPDATE, RD_UPDATE,
+};
+
+/// Set of the register and RAM update operations used by a program.
+///
+/// The NONE operations are always part of the set, as the interpreter falls
+/// back to them on halted cycles and misaligned accesses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpSet {
+    // Bit i is set if the operation of id i is used
+    rd_update: u64,
+    ram_update: u64,
+}
+
+// The sets are masks of the operation ids, whose largest are CUSTOM7 and SW.
+const _: () = assert!((RD_UPDATE::CUSTOM7 as u32) < u64::BITS);
+const _: () = assert!((RAM_UPDATE::SW as u32) < u64::BITS);
+
+impl Default for OpSet {
+    fn default() -> Self {
+        OpSet {
+            rd_update: 1 << RD_UPDATE::NONE.id(),
+            ram_update: 1 << RAM_UPDATE::NONE.id(),
+        }
+    }
+}
+
+impl OpSet {
+    /// Returns the operations of the instructions added to `instructions`.
+    pub fn from_instructions(instructions: &InstructionsParser) -> Self {
+        let mut op_set: OpSet = OpSet::default();
+        for idx in 0..instructions.instructions.len() {
+            let (_, _, _, _, rd_w, mem_w, _) = instructions.get(idx);
+            op_set.rd_update |= 1 << rd_w;
+            op_set.ram_update |= 1 << mem_w;
+        }
+        op_set
+    }
+
+    pub(crate) fn contains_rd_update(&self, op: &RD_UPDATE) -> bool {
+        self.rd_update >> op.id() & 1 == 1
+    }
+
+    pub(crate) fn contains_ram_update(&self, op: &RAM_UPDATE) -> bool {
+        self.ram_update >> op.id() & 1 == 1
+    }
+
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_u64(writer, self.rd_update)?;
+        write_u64(writer, self.ram_update)
+    }
+
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self> {
+        let op_set: OpSet = OpSet {
+            rd_update: read_u64(reader)?,
+            ram_update: read_u64(reader)?,
+        };
+        let none: OpSet = OpSet::default();
+        if op_set.rd_update & none.rd_update == 0
+            || op_set.ram_update & none.ram_update == 0
+            || op_set.rd_update >> (RD_UPDATE::CUSTOM7.id() + 1) != 0
+            || op_set.ram_update >> (RAM_UPDATE::SW.id() + 1) != 0
+        {
+            return Err(invalid_data(format!("invalid op set: {op_set:?}")));
+        }
+        Ok(op_set)
+    }
+}
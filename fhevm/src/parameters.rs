@@ -261,6 +261,42 @@ impl<B: Backend> CryptographicParameters<B> {
     pub fn rank(&self) -> Rank {
         self.rank
     }
+
+    /// 64-bit FNV-1a digest of the parameter set.
+    ///
+    /// Stored in serialized keys and ciphertexts so that material produced
+    /// under a different parameter set is rejected on load.
+    pub fn fingerprint(&self) -> u64 {
+        let values: [u64; 20] = [
+            self.module.n() as u64,
+            self.n_lwe as u64,
+            self.lwe_block_size as u64,
+            self.base2k_fhe_uint.0 as u64,
+            self.base2k_cbt_brk.0 as u64,
+            self.base2k_cbt_atk.0 as u64,
+            self.base2k_cbt_tsk.0 as u64,
+            self.base2k_glwe_to_glwe_ksk.0 as u64,
+            self.base2k_glwe_to_lwe_ksk.0 as u64,
+            self.rank.0 as u64,
+            self.k_pt.0 as u64,
+            self.k_lwe.0 as u64,
+            self.k_fhe_uint_prepared.0 as u64,
+            self.k_fhe_uint.0 as u64,
+            self.k_rom.0 as u64,
+            self.k_ram.0 as u64,
+            self.k_evk_ram.0 as u64,
+            self.k_glwe_to_glwe_ksk.0 as u64,
+            self.k_glwe_to_lwe_ksk.0 as u64,
+            self.k_pbs.0 as u64,
+        ];
+
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in values.iter().flat_map(|v| v.to_le_bytes()) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        hash
+    }
 }
 
 impl<B: Backend> CryptographicParameters<B> {
@@ -17,6 +17,7 @@ use poulpy_schemes::bin_fhe::bdd_arithmetic::{
 #[cfg(test)]
 use crate::PC_UPDATE;
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn update_pc<R, OPID, PC, RS1, RS2, IMM, H, K, M, BE: Backend>(
     threads: usize,
     module: &M,
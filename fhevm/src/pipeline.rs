@@ -0,0 +1,80 @@
+//! Phases of a cycle of the [crate::Interpreter].
+//!
+//! [crate::Interpreter::cycle] runs the [Phase::ALL] phases in order. They can
+//! also be run one at a time with [crate::Interpreter::run_phase], between
+//! [crate::Interpreter::begin_cycle] and [crate::Interpreter::end_cycle], or
+//! with a [CycleHook] called around each of them by
+//! [crate::Interpreter::cycle_with_hook], e.g. to instrument or schedule them.
+//!
+//! Each phase reads the state written by the previous ones, as listed below,
+//! so running them in another order or skipping one gives a different, and in
+//! general wrong, execution.
+//!
+//! The update phases only read the state written by the read phases, so
+//! [crate::Interpreter::set_concurrent_updates] makes
+//! [crate::Interpreter::cycle] run them at the same time.
+
+use poulpy_hal::layouts::Backend;
+
+use crate::Interpreter;
+
+/// Phase of a cycle, see [crate::pipeline].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Prepares the PC and updates the halt status with the PC of the
+    /// previous cycle. Skipped at the first cycle.
+    HaltStatus,
+    /// Reads the components of the instruction at PC from the ROM: the register
+    /// addresses, the immediate and the operations, and prepares them.
+    ReadInstruction,
+    /// Reads x[rs1] and x[rs2] at the addresses read by
+    /// [Phase::ReadInstruction].
+    ReadRegisters,
+    /// Reads the RAM at x[rs1] + imm, or the read-only data bank.
+    ReadRam,
+    /// Evaluates the register update operations and writes x[rd].
+    UpdateRegisters,
+    /// Evaluates the RAM update operations and writes the RAM at x[rs1] + imm.
+    UpdateRam,
+    /// Evaluates the PC update operations and writes the PC.
+    UpdatePc,
+    /// Bootstraps the RAM words due for a refresh, see
+    /// [crate::Interpreter::ram_refresh_schedule].
+    RefreshRam,
+}
+
+impl Phase {
+    /// Phases of a cycle, in execution order.
+    pub const ALL: [Phase; 8] = [
+        Phase::HaltStatus,
+        Phase::ReadInstruction,
+        Phase::ReadRegisters,
+        Phase::ReadRam,
+        Phase::UpdateRegisters,
+        Phase::UpdateRam,
+        Phase::UpdatePc,
+        Phase::RefreshRam,
+    ];
+}
+
+/// Callbacks run around each [Phase] by [crate::Interpreter::cycle_with_hook].
+///
+/// When the updates run concurrently (see
+/// [crate::Interpreter::set_concurrent_updates]), the calls are grouped:
+/// [CycleHook::before_phase] is called for [Phase::UpdateRegisters],
+/// [Phase::UpdateRam], [Phase::UpdatePc] and [Phase::RefreshRam] before any of
+/// them starts, then [CycleHook::after_phase] for each of them, in the same
+/// order, once they are all done. The interpreter state seen by these calls is
+/// thus the one before, or after, all the updates.
+///
+/// `()` is the hook doing nothing.
+pub trait CycleHook<BE: Backend> {
+    /// Called before `phase` is run.
+    fn before_phase(&mut self, _interpreter: &Interpreter<BE>, _phase: Phase) {}
+
+    /// Called after `phase` is run.
+    fn after_phase(&mut self, _interpreter: &Interpreter<BE>, _phase: Phase) {}
+}
+
+impl<BE: Backend> CycleHook<BE> for () {}
@@ -15,6 +15,7 @@ use poulpy_schemes::bin_fhe::bdd_arithmetic::{
 use crate::RAM_UPDATE;
 
 pub trait Store<T: UnsignedInteger> {
+    #[allow(clippy::too_many_arguments)]
     fn eval_enc<R, D, A, B, H, K, M, BE: Backend>(
         &self,
         threads: usize,
@@ -96,6 +97,7 @@ impl Store<u32> for RAM_UPDATE {
                 module.glwe_blind_selection(res, cts_ref, offset, 0, 2, scratch);
             }
 
+            // Odd offsets are misaligned and trapped (see [crate::alignment]).
             Self::SH => {
                 let mut cts: HashMap<usize, FheUint<Vec<u8>, u32>> = HashMap::new();
                 for i in 0..2 {
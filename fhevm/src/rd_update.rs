@@ -43,7 +43,164 @@ impl_bdd_2w_to_1w_trait!(
     crate::codegen::codegen_lui::OUTPUT_CIRCUITS
 );
 
+// Zbb extension: the unary operations are evaluated on (rs1, rs1).
+
+define_bdd_2w_to_1w_trait!(pub Andn, andn);
+impl_bdd_2w_to_1w_trait!(
+    Andn,
+    andn,
+    u32,
+    crate::codegen::codegen_andn::AnyBitCircuit,
+    crate::codegen::codegen_andn::OUTPUT_CIRCUITS
+);
+
+define_bdd_2w_to_1w_trait!(pub Orn, orn);
+impl_bdd_2w_to_1w_trait!(
+    Orn,
+    orn,
+    u32,
+    crate::codegen::codegen_orn::AnyBitCircuit,
+    crate::codegen::codegen_orn::OUTPUT_CIRCUITS
+);
+
+define_bdd_2w_to_1w_trait!(pub Xnor, xnor);
+impl_bdd_2w_to_1w_trait!(
+    Xnor,
+    xnor,
+    u32,
+    crate::codegen::codegen_xnor::AnyBitCircuit,
+    crate::codegen::codegen_xnor::OUTPUT_CIRCUITS
+);
+
+define_bdd_2w_to_1w_trait!(pub Clz, clz);
+impl_bdd_2w_to_1w_trait!(
+    Clz,
+    clz,
+    u32,
+    crate::codegen::codegen_clz::AnyBitCircuit,
+    crate::codegen::codegen_clz::OUTPUT_CIRCUITS
+);
+
+define_bdd_2w_to_1w_trait!(pub Ctz, ctz);
+impl_bdd_2w_to_1w_trait!(
+    Ctz,
+    ctz,
+    u32,
+    crate::codegen::codegen_ctz::AnyBitCircuit,
+    crate::codegen::codegen_ctz::OUTPUT_CIRCUITS
+);
+
+define_bdd_2w_to_1w_trait!(pub Cpop, cpop);
+impl_bdd_2w_to_1w_trait!(
+    Cpop,
+    cpop,
+    u32,
+    crate::codegen::codegen_cpop::AnyBitCircuit,
+    crate::codegen::codegen_cpop::OUTPUT_CIRCUITS
+);
+
+define_bdd_2w_to_1w_trait!(pub Max, max);
+impl_bdd_2w_to_1w_trait!(
+    Max,
+    max,
+    u32,
+    crate::codegen::codegen_max::AnyBitCircuit,
+    crate::codegen::codegen_max::OUTPUT_CIRCUITS
+);
+
+define_bdd_2w_to_1w_trait!(pub Maxu, maxu);
+impl_bdd_2w_to_1w_trait!(
+    Maxu,
+    maxu,
+    u32,
+    crate::codegen::codegen_maxu::AnyBitCircuit,
+    crate::codegen::codegen_maxu::OUTPUT_CIRCUITS
+);
+
+define_bdd_2w_to_1w_trait!(pub Min, min);
+impl_bdd_2w_to_1w_trait!(
+    Min,
+    min,
+    u32,
+    crate::codegen::codegen_min::AnyBitCircuit,
+    crate::codegen::codegen_min::OUTPUT_CIRCUITS
+);
+
+define_bdd_2w_to_1w_trait!(pub Minu, minu);
+impl_bdd_2w_to_1w_trait!(
+    Minu,
+    minu,
+    u32,
+    crate::codegen::codegen_minu::AnyBitCircuit,
+    crate::codegen::codegen_minu::OUTPUT_CIRCUITS
+);
+
+define_bdd_2w_to_1w_trait!(pub SextB, sext_b);
+impl_bdd_2w_to_1w_trait!(
+    SextB,
+    sext_b,
+    u32,
+    crate::codegen::codegen_sext_b::AnyBitCircuit,
+    crate::codegen::codegen_sext_b::OUTPUT_CIRCUITS
+);
+
+define_bdd_2w_to_1w_trait!(pub SextH, sext_h);
+impl_bdd_2w_to_1w_trait!(
+    SextH,
+    sext_h,
+    u32,
+    crate::codegen::codegen_sext_h::AnyBitCircuit,
+    crate::codegen::codegen_sext_h::OUTPUT_CIRCUITS
+);
+
+define_bdd_2w_to_1w_trait!(pub ZextH, zext_h);
+impl_bdd_2w_to_1w_trait!(
+    ZextH,
+    zext_h,
+    u32,
+    crate::codegen::codegen_zext_h::AnyBitCircuit,
+    crate::codegen::codegen_zext_h::OUTPUT_CIRCUITS
+);
+
+define_bdd_2w_to_1w_trait!(pub Rol, rol);
+impl_bdd_2w_to_1w_trait!(
+    Rol,
+    rol,
+    u32,
+    crate::codegen::codegen_rol::AnyBitCircuit,
+    crate::codegen::codegen_rol::OUTPUT_CIRCUITS
+);
+
+define_bdd_2w_to_1w_trait!(pub Ror, ror);
+impl_bdd_2w_to_1w_trait!(
+    Ror,
+    ror,
+    u32,
+    crate::codegen::codegen_ror::AnyBitCircuit,
+    crate::codegen::codegen_ror::OUTPUT_CIRCUITS
+);
+
+define_bdd_2w_to_1w_trait!(pub Rev8, rev8);
+impl_bdd_2w_to_1w_trait!(
+    Rev8,
+    rev8,
+    u32,
+    crate::codegen::codegen_rev8::AnyBitCircuit,
+    crate::codegen::codegen_rev8::OUTPUT_CIRCUITS
+);
+
+define_bdd_2w_to_1w_trait!(pub OrcB, orc_b);
+impl_bdd_2w_to_1w_trait!(
+    OrcB,
+    orc_b,
+    u32,
+    crate::codegen::codegen_orc_b::AnyBitCircuit,
+    crate::codegen::codegen_orc_b::OUTPUT_CIRCUITS
+);
+
 pub trait Evaluate<T: UnsignedInteger, BE: Backend> {
+    // Takes every operand an operation may read, as they are selected blindly.
+    #[allow(clippy::too_many_arguments)]
     fn eval_enc<R, R1, R2, IM, PC, RA, H, K, M>(
         &self,
         threads: usize,
@@ -131,12 +288,30 @@ impl<BE: Backend> Evaluate<u32, BE> for RD_UPDATE {
             Self::SRLI => res.srl_multi_thread(threads, module, rs1, imm, keys, scratch),
             Self::SRAI => res.sra_multi_thread(threads, module, rs1, imm, keys, scratch),
             Self::SLTI => res.slt_multi_thread(threads, module, rs1, imm, keys, scratch),
+            Self::ANDN => res.andn_multi_thread(threads, module, rs1, rs2, keys, scratch),
+            Self::ORN => res.orn_multi_thread(threads, module, rs1, rs2, keys, scratch),
+            Self::XNOR => res.xnor_multi_thread(threads, module, rs1, rs2, keys, scratch),
+            Self::CLZ => res.clz_multi_thread(threads, module, rs1, rs1, keys, scratch),
+            Self::CTZ => res.ctz_multi_thread(threads, module, rs1, rs1, keys, scratch),
+            Self::CPOP => res.cpop_multi_thread(threads, module, rs1, rs1, keys, scratch),
+            Self::MAX => res.max_multi_thread(threads, module, rs1, rs2, keys, scratch),
+            Self::MAXU => res.maxu_multi_thread(threads, module, rs1, rs2, keys, scratch),
+            Self::MIN => res.min_multi_thread(threads, module, rs1, rs2, keys, scratch),
+            Self::MINU => res.minu_multi_thread(threads, module, rs1, rs2, keys, scratch),
+            Self::SEXTB => res.sext_b_multi_thread(threads, module, rs1, rs1, keys, scratch),
+            Self::SEXTH => res.sext_h_multi_thread(threads, module, rs1, rs1, keys, scratch),
+            Self::ZEXTH => res.zext_h_multi_thread(threads, module, rs1, rs1, keys, scratch),
+            Self::ROL => res.rol_multi_thread(threads, module, rs1, rs2, keys, scratch),
+            Self::ROR => res.ror_multi_thread(threads, module, rs1, rs2, keys, scratch),
+            Self::RORI => res.ror_multi_thread(threads, module, rs1, imm, keys, scratch),
+            Self::REV8 => res.rev8_multi_thread(threads, module, rs1, rs1, keys, scratch),
+            Self::ORCB => res.orc_b_multi_thread(threads, module, rs1, rs1, keys, scratch),
             Self::LB => {
                 module.glwe_copy(res, ram);
                 res.zero_byte(module, 1, keys, scratch);
                 res.zero_byte(module, 2, keys, scratch);
                 res.zero_byte(module, 3, keys, scratch);
-                //res.sext(module, 0, keys, scratch);
+                res.sext(module, 0, keys, scratch);
             }
             Self::LBU => {
                 module.glwe_copy(res, ram);
@@ -158,8 +333,11 @@ impl<BE: Backend> Evaluate<u32, BE> for RD_UPDATE {
             Self::LW => {
                 module.glwe_copy(res, ram);
             }
+            _ if self.custom_slot().is_some() => {
+                panic!("{self:?} is evaluated by custom::eval_custom")
+            }
             _ => {
-                unimplemented!()
+                panic!("{self:?} is evaluated by rv32m::eval_rv32m")
             }
         }
     }
@@ -0,0 +1,30 @@
+//! Read-only data bank of the [crate::Interpreter].
+//!
+//! Every cycle reads the RAM with a stateful blind retrieval and writes the
+//! accessed word back, so the cost of a cycle grows with the RAM size even
+//! for data that is never written. Constants, such as lookup tables, can
+//! instead be placed in a read-only bank, which is read with a stateless blind
+//! rotation, as the ROM, and never written.
+//!
+//! The bank is mapped at [rodata_offset] from the start of the RAM: the first
+//! power of two above both the RAM and the bank, so that the bit
+//! [rodata_bit] of the RAM address alone tells whether an access targets the
+//! bank. A load from the bank returns the word of the bank instead of the word
+//! of the RAM, and a store to the bank is dropped.
+
+/// Number of bits needed to index `size` words.
+fn bit_size(size: usize) -> usize {
+    (usize::BITS - size.saturating_sub(1).leading_zeros()) as usize
+}
+
+/// Bit of the RAM address selecting a read-only data bank of `rodata_size`
+/// words next to a RAM of `ram_size` words.
+pub fn rodata_bit(ram_size: usize, rodata_size: usize) -> usize {
+    2 + bit_size(ram_size).max(bit_size(rodata_size))
+}
+
+/// Offset in bytes, from the start of the RAM, of a read-only data bank of
+/// `rodata_size` words next to a RAM of `ram_size` words.
+pub fn rodata_offset(ram_size: usize, rodata_size: usize) -> usize {
+    1 << rodata_bit(ram_size, rodata_size)
+}
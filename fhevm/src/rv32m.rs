@@ -0,0 +1,526 @@
+//! Encrypted evaluation of the RV32M extension.
+//!
+//! Multiplications and divisions are far too large to be expressed as a single
+//! BDD, so they are evaluated as a sequence of steps (shift-and-add for the
+//! multiplications, restoring division for the divisions), each step being a
+//! small circuit built with [BDDBuilder] whose output is prepared again before
+//! being fed to the next step.
+//!
+//! All the operations of the extension share the same intermediate values, so
+//! they are evaluated together with [eval_rv32m].
+
+use std::sync::OnceLock;
+
+use poulpy_core::{
+    layouts::{
+        GGLWEInfos, GGLWEPreparedToRef, GLWEAutomorphismKeyHelper, GLWEInfos, GetGaloisElement,
+        GLWE,
+    },
+    GLWECopy, GLWEPacking, ScratchTakeCore,
+};
+use poulpy_hal::{
+    api::ModuleLogN,
+    layouts::{Backend, DataMut, DataRef, Scratch},
+};
+use poulpy_schemes::bin_fhe::{
+    bdd_arithmetic::{
+        BDDKeyHelper, BDDKeyInfos, ExecuteBDDCircuit, FheUint, FheUintPrepare, FheUintPrepared,
+        FheUintPreparedFactory, GetBitCircuitInfo,
+    },
+    blind_rotation::BlindRotationAlgo,
+};
+
+use crate::{
+    bdd::{BDDBuilder, BDDInputs, BDDRef, DynamicCircuit, FALSE},
+    RD_UPDATE, RD_UPDATE_RV32M_OP_LIST,
+};
+
+const WORD: usize = u32::BITS as usize;
+
+/// Number of bits of rs2 consumed by each step of the multiplication.
+pub(crate) const MUL_STEP_BITS: usize = 2;
+
+/// Circuits of the steps of the RV32M operations.
+///
+/// Inputs are given as `[word_0, word_1, ...]`, each word spanning [WORD] bits
+/// unless stated otherwise, and outputs as `[word, extra bits]`.
+pub(crate) struct RV32MCircuits {
+    /// [a, hi, b[MUL_STEP_BITS]] -> [(hi + a * b) >> MUL_STEP_BITS, low bits of hi + a * b].
+    pub(crate) mul_step: DynamicCircuit,
+    /// Same as [RV32MCircuits::mul_step] with hi = 0.
+    pub(crate) mul_first_step: DynamicCircuit,
+    /// [hi, a, b] -> [high word of a * b with a and b signed], hi being the unsigned high word.
+    pub(crate) mulh: DynamicCircuit,
+    /// [hi, a, b] -> [high word of a * b with a signed], hi being the unsigned high word.
+    pub(crate) mulhsu: DynamicCircuit,
+    /// [rs1, rs2, op] -> [|rs1| if op is signed else rs1].
+    pub(crate) div_rs1: DynamicCircuit,
+    /// [rs1, rs2, op] -> [|rs2| if op is signed else rs2].
+    pub(crate) div_rs2: DynamicCircuit,
+    /// [r, b, a[1]] -> [r' = x - q * b, q = x >= b] with x = 2 * r + a.
+    pub(crate) div_step: DynamicCircuit,
+    /// Same as [RV32MCircuits::div_step] with r = 0.
+    pub(crate) div_first_step: DynamicCircuit,
+    /// [q, r, rs1, rs2, op] -> [DIV/DIVU result].
+    pub(crate) div_quotient: DynamicCircuit,
+    /// [q, r, rs1, rs2, op] -> [REM/REMU result].
+    pub(crate) div_remainder: DynamicCircuit,
+}
+
+/// Number of bits of the prepared op id.
+pub(crate) fn op_bit_size() -> usize {
+    (usize::BITS - (RD_UPDATE_RV32M_OP_LIST.len() - 1).leading_zeros()) as usize
+}
+
+/// Returns the circuits, built on first use.
+pub(crate) fn rv32m_circuits() -> &'static RV32MCircuits {
+    static CIRCUITS: OnceLock<RV32MCircuits> = OnceLock::new();
+    CIRCUITS.get_or_init(|| RV32MCircuits {
+        mul_step: mul_step(false),
+        mul_first_step: mul_step(true),
+        mulh: mul_high(true),
+        mulhsu: mul_high(false),
+        div_rs1: div_abs(0),
+        div_rs2: div_abs(WORD),
+        div_step: div_step(false),
+        div_first_step: div_step(true),
+        div_quotient: div_quotient(),
+        div_remainder: div_remainder(),
+    })
+}
+
+fn mul_step(first: bool) -> DynamicCircuit {
+    let (a_in, hi_in, b_in) = (0, WORD, 2 * WORD);
+    let mut bdd: BDDBuilder = BDDBuilder::new();
+
+    let mut b: [BDDRef; MUL_STEP_BITS] = [FALSE; MUL_STEP_BITS];
+    for i in (0..MUL_STEP_BITS).rev() {
+        b[i] = bdd.var(b_in + i);
+    }
+    let mut a: Vec<BDDRef> = vec![FALSE; WORD];
+    let mut hi: Vec<BDDRef> = vec![FALSE; WORD + MUL_STEP_BITS];
+    for j in (0..WORD).rev() {
+        if !first {
+            hi[j] = bdd.var(hi_in + j);
+        }
+        a[j] = bdd.var(a_in + j);
+    }
+
+    // hi + a * b < 2^(WORD + MUL_STEP_BITS)
+    let mut acc: Vec<BDDRef> = hi;
+    for (i, &b_i) in b.iter().enumerate() {
+        let mut partial: Vec<BDDRef> = vec![FALSE; WORD + MUL_STEP_BITS];
+        for (j, &a_j) in a.iter().enumerate() {
+            partial[i + j] = bdd.and(b_i, a_j);
+        }
+        acc = bdd.add(&acc, &partial, FALSE);
+        acc.truncate(WORD + MUL_STEP_BITS);
+    }
+
+    let mut outputs: Vec<BDDRef> = acc[MUL_STEP_BITS..].to_vec();
+    outputs.extend_from_slice(&acc[..MUL_STEP_BITS]);
+    bdd.circuit(2 * WORD + MUL_STEP_BITS, &outputs)
+}
+
+// The signed high word is obtained from the unsigned one by subtracting
+// b if a < 0 and, if b is signed, a if b < 0.
+fn mul_high(signed_b: bool) -> DynamicCircuit {
+    let (hi_in, a_in, b_in) = (0, WORD, 2 * WORD);
+    let mut bdd: BDDBuilder = BDDBuilder::new();
+
+    let mut hi: Vec<BDDRef> = vec![FALSE; WORD];
+    let mut a: Vec<BDDRef> = vec![FALSE; WORD];
+    let mut b: Vec<BDDRef> = vec![FALSE; WORD];
+    a[WORD - 1] = bdd.var(a_in + WORD - 1);
+    b[WORD - 1] = bdd.var(b_in + WORD - 1);
+    for j in (0..WORD).rev() {
+        hi[j] = bdd.var(hi_in + j);
+        if j != WORD - 1 {
+            if signed_b {
+                a[j] = bdd.var(a_in + j);
+            }
+            b[j] = bdd.var(b_in + j);
+        }
+    }
+
+    let b_if_a_neg: Vec<BDDRef> = b.iter().map(|&b_j| bdd.and(a[WORD - 1], b_j)).collect();
+    let mut res: Vec<BDDRef> = bdd.sub(&hi, &b_if_a_neg);
+    res.truncate(WORD);
+    if signed_b {
+        let a_if_b_neg: Vec<BDDRef> = a.iter().map(|&a_j| bdd.and(b[WORD - 1], a_j)).collect();
+        res = bdd.sub(&res, &a_if_b_neg);
+        res.truncate(WORD);
+    }
+
+    bdd.circuit(3 * WORD, &res)
+}
+
+// Returns true iff op is DIV or REM.
+fn is_signed_div(bdd: &mut BDDBuilder, op: &[BDDRef]) -> BDDRef {
+    let div: BDDRef = bdd.eq_const(op, RD_UPDATE::DIV.id());
+    let rem: BDDRef = bdd.eq_const(op, RD_UPDATE::REM.id());
+    bdd.or(div, rem)
+}
+
+fn declare_op(bdd: &mut BDDBuilder, op_in: usize) -> Vec<BDDRef> {
+    let mut op: Vec<BDDRef> = vec![FALSE; op_bit_size()];
+    for i in (0..op.len()).rev() {
+        op[i] = bdd.var(op_in + i);
+    }
+    op
+}
+
+fn div_abs(x_in: usize) -> DynamicCircuit {
+    let op_in: usize = 2 * WORD;
+    let mut bdd: BDDBuilder = BDDBuilder::new();
+
+    let op: Vec<BDDRef> = declare_op(&mut bdd, op_in);
+    let mut x: Vec<BDDRef> = vec![FALSE; WORD];
+    for j in (0..WORD).rev() {
+        x[j] = bdd.var(x_in + j);
+    }
+
+    let signed: BDDRef = is_signed_div(&mut bdd, &op);
+    let negate: BDDRef = bdd.and(signed, x[WORD - 1]);
+    let neg_x: Vec<BDDRef> = bdd.neg(&x);
+    let res: Vec<BDDRef> = bdd.mux(negate, &neg_x, &x);
+
+    bdd.circuit(op_in + op.len(), &res)
+}
+
+// One step of the restoring division: r < b holds before and after the step.
+fn div_step(first: bool) -> DynamicCircuit {
+    let (r_in, b_in, a_in) = (0, WORD, 2 * WORD);
+    let mut bdd: BDDBuilder = BDDBuilder::new();
+
+    // x = 2 * r + a on WORD + 1 bits
+    let mut x: Vec<BDDRef> = vec![FALSE; WORD + 1];
+    let mut b: Vec<BDDRef> = vec![FALSE; WORD + 1];
+    if !first {
+        x[WORD] = bdd.var(r_in + WORD - 1);
+    }
+    for j in (1..WORD).rev() {
+        b[j] = bdd.var(b_in + j);
+        if !first {
+            x[j] = bdd.var(r_in + j - 1);
+        }
+    }
+    b[0] = bdd.var(b_in);
+    x[0] = bdd.var(a_in);
+
+    let diff: Vec<BDDRef> = bdd.sub(&x, &b);
+    let q: BDDRef = diff[WORD + 1];
+
+    let mut outputs: Vec<BDDRef> = bdd.mux(q, &diff[..WORD], &x[..WORD]);
+    outputs.push(q);
+    bdd.circuit(2 * WORD + 1, &outputs)
+}
+
+// Signed quotient: -q if rs1 and rs2 have different signs, except for a division
+// by zero which returns -1 (the unsigned quotient being already all ones).
+fn div_quotient() -> DynamicCircuit {
+    let (q_in, rs1_in, rs2_in, op_in) = (0, 2 * WORD, 3 * WORD, 4 * WORD);
+    let mut bdd: BDDBuilder = BDDBuilder::new();
+
+    let op: Vec<BDDRef> = declare_op(&mut bdd, op_in);
+    let rs1_sign: BDDRef = bdd.var(rs1_in + WORD - 1);
+    let mut rs2: Vec<BDDRef> = vec![FALSE; WORD];
+    for j in (0..WORD).rev() {
+        rs2[j] = bdd.var(rs2_in + j);
+    }
+    let mut q: Vec<BDDRef> = vec![FALSE; WORD];
+    for j in (0..WORD).rev() {
+        q[j] = bdd.var(q_in + j);
+    }
+
+    let signed: BDDRef = is_signed_div(&mut bdd, &op);
+    let rs2_is_zero: BDDRef = bdd.eq_const(&rs2, 0);
+    let rs2_non_zero: BDDRef = bdd.not(rs2_is_zero);
+    let signs_differ: BDDRef = bdd.xor(rs1_sign, rs2[WORD - 1]);
+    let negate: BDDRef = bdd.and(signed, rs2_non_zero);
+    let negate: BDDRef = bdd.and(negate, signs_differ);
+    let neg_q: Vec<BDDRef> = bdd.neg(&q);
+    let res: Vec<BDDRef> = bdd.mux(negate, &neg_q, &q);
+
+    bdd.circuit(op_in + op.len(), &res)
+}
+
+// Signed remainder: takes the sign of rs1.
+fn div_remainder() -> DynamicCircuit {
+    let (r_in, rs1_in, op_in) = (WORD, 2 * WORD, 4 * WORD);
+    let mut bdd: BDDBuilder = BDDBuilder::new();
+
+    let op: Vec<BDDRef> = declare_op(&mut bdd, op_in);
+    let rs1_sign: BDDRef = bdd.var(rs1_in + WORD - 1);
+    let mut r: Vec<BDDRef> = vec![FALSE; WORD];
+    for j in (0..WORD).rev() {
+        r[j] = bdd.var(r_in + j);
+    }
+
+    let signed: BDDRef = is_signed_div(&mut bdd, &op);
+    let negate: BDDRef = bdd.and(signed, rs1_sign);
+    let neg_r: Vec<BDDRef> = bdd.neg(&r);
+    let res: Vec<BDDRef> = bdd.mux(negate, &neg_r, &r);
+
+    bdd.circuit(op_in + op.len(), &res)
+}
+
+/// Encrypted results of the RV32M operations.
+pub(crate) struct RV32MResults {
+    pub(crate) mul: FheUint<Vec<u8>, u32>,
+    pub(crate) mulh: FheUint<Vec<u8>, u32>,
+    pub(crate) mulhsu: FheUint<Vec<u8>, u32>,
+    pub(crate) mulhu: FheUint<Vec<u8>, u32>,
+    /// Result of DIV or DIVU, depending on the evaluated op.
+    pub(crate) div: FheUint<Vec<u8>, u32>,
+    /// Result of REM or REMU, depending on the evaluated op.
+    pub(crate) rem: FheUint<Vec<u8>, u32>,
+}
+
+impl RV32MResults {
+    /// Returns the result of `op`, or None if `op` is not an RV32M operation.
+    pub(crate) fn get(&self, op: &RD_UPDATE) -> Option<&FheUint<Vec<u8>, u32>> {
+        match op {
+            RD_UPDATE::MUL => Some(&self.mul),
+            RD_UPDATE::MULH => Some(&self.mulh),
+            RD_UPDATE::MULHSU => Some(&self.mulhsu),
+            RD_UPDATE::MULHU => Some(&self.mulhu),
+            RD_UPDATE::DIV | RD_UPDATE::DIVU => Some(&self.div),
+            RD_UPDATE::REM | RD_UPDATE::REMU => Some(&self.rem),
+            _ => None,
+        }
+    }
+}
+
+/// Evaluates all the RV32M operations on rs1 and rs2.
+///
+/// `op` is the prepared op id of the instruction, which selects between the
+/// signed and unsigned divisions so that both share the same steps.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn eval_rv32m<A, R1, R2, OP, D, H, K, M, BRA, BE: Backend>(
+    threads: usize,
+    module: &M,
+    fhe_uint_infos: &A,
+    rs1: &FheUintPrepared<R1, u32, BE>,
+    rs2: &FheUintPrepared<R2, u32, BE>,
+    op: &FheUintPrepared<OP, u32, BE>,
+    keys: &H,
+    scratch: &mut Scratch<BE>,
+) -> RV32MResults
+where
+    A: GLWEInfos,
+    R1: DataRef,
+    R2: DataRef,
+    OP: DataRef,
+    D: DataRef,
+    BRA: BlindRotationAlgo,
+    H: BDDKeyHelper<D, BRA, BE> + BDDKeyInfos + GLWEAutomorphismKeyHelper<K, BE>,
+    K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+    M: ModuleLogN
+        + GLWEPacking<BE>
+        + GLWECopy
+        + ExecuteBDDCircuit<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + FheUintPrepare<BRA, BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+{
+    let circuits: &RV32MCircuits = rv32m_circuits();
+    let op_bits: usize = op_bit_size();
+
+    let fhe_uint = || FheUint::<Vec<u8>, u32>::alloc_from_infos(fhe_uint_infos);
+    let fhe_uint_prepared = || FheUintPrepared::<Vec<u8>, u32, BE>::alloc_from_infos(module, rs1);
+    let glwe_bits = |size: usize| -> Vec<GLWE<Vec<u8>>> {
+        (0..size)
+            .map(|_| GLWE::alloc_from_infos(fhe_uint_infos))
+            .collect()
+    };
+
+    // Multiplication: hi accumulates the high word while the low word
+    // is shifted out MUL_STEP_BITS bits at a time.
+    let mut hi: FheUint<Vec<u8>, u32> = fhe_uint();
+    let mut hi_prepared: FheUintPrepared<Vec<u8>, u32, BE> = fhe_uint_prepared();
+    let mut lo_bits: Vec<GLWE<Vec<u8>>> = glwe_bits(WORD);
+
+    for (step, bits) in lo_bits.chunks_exact_mut(MUL_STEP_BITS).enumerate() {
+        let mut inputs: BDDInputs<'_, BE> =
+            BDDInputs::new().word(rs1, WORD).word(&hi_prepared, WORD);
+        for i in 0..MUL_STEP_BITS {
+            inputs = inputs.bit(rs2, step * MUL_STEP_BITS + i);
+        }
+        let circuit: &DynamicCircuit = if step == 0 {
+            &circuits.mul_first_step
+        } else {
+            &circuits.mul_step
+        };
+        execute(
+            threads, module, &mut hi, bits, circuit, &inputs, keys, scratch,
+        );
+        hi_prepared.prepare_custom_multi_thread(threads, module, &hi, 0, WORD, keys, scratch);
+    }
+
+    let mut mul: FheUint<Vec<u8>, u32> = fhe_uint();
+    mul.pack(module, lo_bits, keys, scratch);
+
+    let inputs: BDDInputs<'_, BE> = BDDInputs::new()
+        .word(&hi_prepared, WORD)
+        .word(rs1, WORD)
+        .word(rs2, WORD);
+    let mut mulh: FheUint<Vec<u8>, u32> = fhe_uint();
+    execute(
+        threads,
+        module,
+        &mut mulh,
+        &mut [],
+        &circuits.mulh,
+        &inputs,
+        keys,
+        scratch,
+    );
+    let mut mulhsu: FheUint<Vec<u8>, u32> = fhe_uint();
+    execute(
+        threads,
+        module,
+        &mut mulhsu,
+        &mut [],
+        &circuits.mulhsu,
+        &inputs,
+        keys,
+        scratch,
+    );
+
+    // Division: restoring division of |rs1| by |rs2| for the signed ops
+    // and of rs1 by rs2 for the unsigned ones, from the most significant bit.
+    let inputs: BDDInputs<'_, BE> = BDDInputs::new()
+        .word(rs1, WORD)
+        .word(rs2, WORD)
+        .word(op, op_bits);
+    let mut a: FheUint<Vec<u8>, u32> = fhe_uint();
+    execute(
+        threads,
+        module,
+        &mut a,
+        &mut [],
+        &circuits.div_rs1,
+        &inputs,
+        keys,
+        scratch,
+    );
+    let mut b: FheUint<Vec<u8>, u32> = fhe_uint();
+    execute(
+        threads,
+        module,
+        &mut b,
+        &mut [],
+        &circuits.div_rs2,
+        &inputs,
+        keys,
+        scratch,
+    );
+
+    let mut a_prepared: FheUintPrepared<Vec<u8>, u32, BE> = fhe_uint_prepared();
+    a_prepared.prepare_custom_multi_thread(threads, module, &a, 0, WORD, keys, scratch);
+    let mut b_prepared: FheUintPrepared<Vec<u8>, u32, BE> = fhe_uint_prepared();
+    b_prepared.prepare_custom_multi_thread(threads, module, &b, 0, WORD, keys, scratch);
+
+    let mut r: FheUint<Vec<u8>, u32> = fhe_uint();
+    let mut r_prepared: FheUintPrepared<Vec<u8>, u32, BE> = fhe_uint_prepared();
+    let mut q_bits: Vec<GLWE<Vec<u8>>> = glwe_bits(WORD);
+
+    for i in (0..WORD).rev() {
+        let inputs: BDDInputs<'_, BE> = BDDInputs::new()
+            .word(&r_prepared, WORD)
+            .word(&b_prepared, WORD)
+            .bit(&a_prepared, i);
+        let circuit: &DynamicCircuit = if i == WORD - 1 {
+            &circuits.div_first_step
+        } else {
+            &circuits.div_step
+        };
+        execute(
+            threads,
+            module,
+            &mut r,
+            &mut q_bits[i..i + 1],
+            circuit,
+            &inputs,
+            keys,
+            scratch,
+        );
+        r_prepared.prepare_custom_multi_thread(threads, module, &r, 0, WORD, keys, scratch);
+    }
+
+    let mut q: FheUint<Vec<u8>, u32> = fhe_uint();
+    q.pack(module, q_bits, keys, scratch);
+    let mut q_prepared: FheUintPrepared<Vec<u8>, u32, BE> = fhe_uint_prepared();
+    q_prepared.prepare_custom_multi_thread(threads, module, &q, 0, WORD, keys, scratch);
+
+    let inputs: BDDInputs<'_, BE> = BDDInputs::new()
+        .word(&q_prepared, WORD)
+        .word(&r_prepared, WORD)
+        .word(rs1, WORD)
+        .word(rs2, WORD)
+        .word(op, op_bits);
+    let mut div: FheUint<Vec<u8>, u32> = fhe_uint();
+    execute(
+        threads,
+        module,
+        &mut div,
+        &mut [],
+        &circuits.div_quotient,
+        &inputs,
+        keys,
+        scratch,
+    );
+    let mut rem: FheUint<Vec<u8>, u32> = fhe_uint();
+    execute(
+        threads,
+        module,
+        &mut rem,
+        &mut [],
+        &circuits.div_remainder,
+        &inputs,
+        keys,
+        scratch,
+    );
+
+    RV32MResults {
+        mul,
+        mulh,
+        mulhsu,
+        mulhu: hi,
+        div,
+        rem,
+    }
+}
+
+// Evaluates `circuit`, packs its first WORD output bits into `res` and
+// copies the remaining ones into `extra`.
+#[allow(clippy::too_many_arguments)]
+fn execute<R, H, K, M, BE: Backend>(
+    threads: usize,
+    module: &M,
+    res: &mut FheUint<R, u32>,
+    extra: &mut [GLWE<Vec<u8>>],
+    circuit: &DynamicCircuit,
+    inputs: &BDDInputs<'_, BE>,
+    keys: &H,
+    scratch: &mut Scratch<BE>,
+) where
+    R: DataMut,
+    H: GLWEAutomorphismKeyHelper<K, BE>,
+    K: GGLWEPreparedToRef<BE> + GGLWEInfos + GetGaloisElement,
+    M: ModuleLogN + GLWEPacking<BE> + GLWECopy + ExecuteBDDCircuit<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+{
+    assert_eq!(circuit.output_size(), WORD + extra.len());
+
+    let (mut out_bits, scratch_1) = scratch.take_glwe_slice(circuit.output_size(), res);
+
+    module.execute_bdd_circuit_multi_thread(threads, &mut out_bits, inputs, circuit, scratch_1);
+
+    for (dst, src) in extra.iter_mut().zip(out_bits.drain(WORD..)) {
+        module.glwe_copy(dst, &src);
+    }
+
+    res.pack(module, out_bits, keys, scratch_1);
+}
@@ -0,0 +1,66 @@
+//! Concurrent updates of a cycle of the [crate::Interpreter].
+//!
+//! As shown by the dependency graph of `doc/costs.md`, the register, RAM and
+//! PC updates only read values produced by the phases before them, and each
+//! writes its own state, so they can run at the same time, each with a share
+//! of the threads. The refresh of the RAM only depends on the RAM update, and
+//! runs right after it. The cycle then takes the time of the slowest of the
+//! three instead of their sum.
+//!
+//! The threads are split so that the three updates finish together, from the
+//! work each did at the previous cycle, i.e. its time multiplied by the
+//! threads it ran with, or evenly at the first cycle (see [UPDATE_COSTS]).
+
+use poulpy_core::ScratchTakeCore;
+use poulpy_hal::{
+    api::ScratchAvailable,
+    layouts::{Backend, Scratch},
+};
+
+use crate::Phase;
+
+/// Number of updates run concurrently: registers, RAM and PC.
+pub(crate) const UPDATES: usize = 3;
+
+/// Phases run concurrently, the last ones of [Phase::ALL].
+pub(crate) const UPDATE_PHASES: [Phase; 4] = [
+    Phase::UpdateRegisters,
+    Phase::UpdateRam,
+    Phase::UpdatePc,
+    Phase::RefreshRam,
+];
+
+/// Work of the register, RAM and PC updates at the first cycle, before any is
+/// measured: the threads are split evenly.
+pub(crate) const UPDATE_COSTS: [f64; UPDATES] = [1.0; UPDATES];
+
+/// Splits `threads` between the updates doing `work`, at least one each, so
+/// as to minimize the largest work per thread. Ties go to the first update.
+pub(crate) fn split_threads(threads: usize, work: [f64; UPDATES]) -> [usize; UPDATES] {
+    assert!(threads >= UPDATES, "threads: {threads} < {UPDATES}");
+    let mut split: [usize; UPDATES] = [1; UPDATES];
+    for _ in UPDATES..threads {
+        let load = |i: usize| work[i] / split[i] as f64;
+        // max_by returns the last maximum
+        let i: usize = (0..UPDATES)
+            .rev()
+            .max_by(|&a, &b| load(a).total_cmp(&load(b)))
+            .unwrap();
+        split[i] += 1;
+    }
+    split
+}
+
+/// Splits `scratch` between the updates in proportion to their `threads`.
+pub(crate) fn split_scratch<BE: Backend>(
+    scratch: &mut Scratch<BE>,
+    threads: [usize; UPDATES],
+) -> [&mut Scratch<BE>; UPDATES]
+where
+    Scratch<BE>: ScratchTakeCore<BE>,
+{
+    let thread_size: usize = scratch.available() / threads.iter().sum::<usize>();
+    let (scratch_registers, scratch) = scratch.split_at_mut(threads[0] * thread_size);
+    let (scratch_ram, scratch_pc) = scratch.split_at_mut(threads[1] * thread_size);
+    [scratch_registers, scratch_ram, scratch_pc]
+}
@@ -0,0 +1,111 @@
+use std::io::{Error, ErrorKind, Read, Result, Write};
+
+/// Header prepended to every serialized Phantom object.
+///
+/// `magic` identifies the kind of object, `version` the layout of the payload
+/// and `fingerprint` the [crate::parameters::CryptographicParameters] it was
+/// produced with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub magic: [u8; 4],
+    pub version: u32,
+    pub fingerprint: u64,
+}
+
+impl Header {
+    pub fn new(magic: [u8; 4], version: u32, fingerprint: u64) -> Self {
+        Self {
+            magic,
+            version,
+            fingerprint,
+        }
+    }
+
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.magic)?;
+        write_u32(writer, self.version)?;
+        write_u64(writer, self.fingerprint)
+    }
+
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self> {
+        let mut magic: [u8; 4] = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        let version: u32 = read_u32(reader)?;
+        let fingerprint: u64 = read_u64(reader)?;
+        Ok(Self {
+            magic,
+            version,
+            fingerprint,
+        })
+    }
+
+    /// Returns an [ErrorKind::InvalidData] error if `self` does not match `expected`.
+    pub fn check(&self, expected: &Header) -> Result<()> {
+        if self.magic != expected.magic {
+            return Err(invalid_data(format!(
+                "invalid magic: {:?} != {:?}",
+                self.magic, expected.magic
+            )));
+        }
+        if self.version != expected.version {
+            return Err(invalid_data(format!(
+                "unsupported version: {} != {}",
+                self.version, expected.version
+            )));
+        }
+        if self.fingerprint != expected.fingerprint {
+            return Err(invalid_data(format!(
+                "parameters fingerprint mismatch: {:#018x} != {:#018x}",
+                self.fingerprint, expected.fingerprint
+            )));
+        }
+        Ok(())
+    }
+}
+
+pub fn invalid_data(msg: String) -> Error {
+    Error::new(ErrorKind::InvalidData, msg)
+}
+
+pub fn write_u32<W: Write>(writer: &mut W, value: u32) -> Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+pub fn read_u32<R: Read>(reader: &mut R) -> Result<u32> {
+    let mut bytes: [u8; 4] = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+pub fn write_u64<W: Write>(writer: &mut W, value: u64) -> Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+pub fn read_u64<R: Read>(reader: &mut R) -> Result<u64> {
+    let mut bytes: [u8; 8] = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Writes a length-prefixed byte string.
+pub fn write_bytes<W: Write>(writer: &mut W, bytes: &[u8]) -> Result<()> {
+    write_u64(writer, bytes.len() as u64)?;
+    writer.write_all(bytes)
+}
+
+/// Reads a length-prefixed byte string.
+///
+/// The untrusted length only bounds the read: the buffer grows with the bytes
+/// actually present, so a corrupted prefix cannot trigger a huge allocation.
+pub fn read_bytes<R: Read>(reader: &mut R) -> Result<Vec<u8>> {
+    let len: u64 = read_u64(reader)?;
+    let mut bytes: Vec<u8> = Vec::new();
+    reader.by_ref().take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(invalid_data(format!(
+            "byte string truncated: read {} of {len} bytes",
+            bytes.len()
+        )));
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,46 @@
+use poulpy_hal::source::Source;
+use rand_core::{OsRng, TryRngCore};
+
+/// Randomness used to generate secrets and to encrypt.
+///
+/// - `xs`: secret-key distribution.
+/// - `xa`: uniform masks of the ciphertexts.
+/// - `xe`: encryption noise.
+pub struct Sources {
+    pub xs: Source,
+    pub xa: Source,
+    pub xe: Source,
+}
+
+impl Sources {
+    /// Seeds each source independently from the OS entropy.
+    pub fn from_os_entropy() -> Self {
+        Self {
+            xs: Source::new(os_seed()),
+            xa: Source::new(os_seed()),
+            xe: Source::new(os_seed()),
+        }
+    }
+
+    /// Derives all sources from `seed`.
+    ///
+    /// INSECURE: anyone knowing `seed` recovers the secret-key and the noise.
+    /// Only meant for tests and reproducible benchmarks.
+    pub fn insecure_from_seed(seed: [u8; 32]) -> Self {
+        let mut source: Source = Source::new(seed);
+        Self {
+            xs: Source::new(source.new_seed()),
+            xa: Source::new(source.new_seed()),
+            xe: Source::new(source.new_seed()),
+        }
+    }
+}
+
+/// Returns a 32-byte seed drawn from the OS entropy.
+pub fn os_seed() -> [u8; 32] {
+    let mut seed: [u8; 32] = [0u8; 32];
+    OsRng
+        .try_fill_bytes(&mut seed)
+        .expect("failed to read OS entropy");
+    seed
+}
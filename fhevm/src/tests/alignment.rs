@@ -0,0 +1,182 @@
+use poulpy_core::{
+    layouts::{
+        GGLWEToGGSWKeyPreparedFactory, GGSWPreparedFactory, GLWEAutomorphismKeyPreparedFactory,
+        GLWEInfos, GLWESecret, GLWESecretPrepared, GLWESecretPreparedFactory, LWESecret,
+    },
+    GGLWEToGGSWKeyEncryptSk, GLWEAutomorphismKeyEncryptSk, GLWEDecrypt, GLWEEncryptSk,
+    GLWEExternalProduct, GLWEPackerOps, GLWEPacking, GLWETrace, ScratchTakeCore,
+};
+use poulpy_cpu_ref::FFT64Ref;
+use poulpy_hal::{
+    api::{ModuleN, ModuleNew, ScratchOwnedAlloc, ScratchOwnedBorrow},
+    layouts::{Backend, Module, Scratch, ScratchOwned},
+    source::Source,
+};
+use poulpy_schemes::bin_fhe::{
+    bdd_arithmetic::{
+        BDDKeyEncryptSk, BDDKeyPreparedFactory, FheUintPrepare, FheUintPreparedEncryptSk,
+        FheUintPreparedFactory, GGSWBlindRotation, GLWEBlindRetrieval,
+    },
+    blind_rotation::{BlindRotationAlgo, BlindRotationKey, BlindRotationKeyFactory, CGGI},
+};
+
+use super::rv32m::{bits, eval_plain_circuit};
+use crate::{
+    alignment::{is_misaligned, misaligned_circuit},
+    bdd::DynamicCircuit,
+    keys::{VMKeys, VMKeysPrepared},
+    parameters::CryptographicParameters,
+    prepare::PrepareMultiple,
+    HaltStatus, Instruction, InstructionsParser, Interpreter, RV32I,
+};
+
+#[test]
+fn test_misaligned_circuit() {
+    for rd_op_bits in [5, 6] {
+        let circuit: DynamicCircuit = misaligned_circuit(rd_op_bits);
+        for addr in 0..4 {
+            for mu in 0..4 {
+                for rdu in 0..1 << rd_op_bits {
+                    let out: Vec<bool> = eval_plain_circuit(
+                        &circuit,
+                        &bits(&[(addr, 2), (mu, 2), (rdu, rd_op_bits)]),
+                    );
+                    assert_eq!(
+                        out[0],
+                        is_misaligned(rdu, mu, addr),
+                        "addr: {addr} mu: {mu} rdu: {rdu}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_interpreter_misaligned_fft64_ref() {
+    test_interpreter_misaligned::<CGGI, FFT64Ref>()
+}
+
+fn test_interpreter_misaligned<BRA: BlindRotationAlgo, BE: Backend>()
+where
+    Module<BE>: ModuleNew<BE>
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + PrepareMultiple<BE, BRA>
+        + ModuleN
+        + GLWEEncryptSk<BE>
+        + FheUintPreparedEncryptSk<u32, BE>
+        + GLWEAutomorphismKeyEncryptSk<BE>
+        + GGLWEToGGSWKeyEncryptSk<BE>
+        + GLWETrace<BE>
+        + BDDKeyEncryptSk<BRA, BE>
+        + GGSWPreparedFactory<BE>
+        + GLWEExternalProduct<BE>
+        + GLWEPackerOps<BE>
+        + GLWEPacking<BE>
+        + FheUintPrepare<BRA, BE>
+        + GGSWBlindRotation<u32, BE>
+        + GLWEDecrypt<BE>
+        + GLWEAutomorphismKeyPreparedFactory<BE>
+        + GGLWEToGGSWKeyPreparedFactory<BE>
+        + BDDKeyPreparedFactory<BRA, BE>
+        + GLWEBlindRetrieval<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+    BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+{
+    let rom: Vec<Instruction> = vec![
+        // RD[31] <- 1<<18
+        RV32I::LUI.new().set_imm(1 << 6).set_rd(31),
+        // RD[1] <- sext(RAM[1<<18 + 1][7:0])
+        RV32I::LB.new().set_imm(1).set_rs1(31).set_rd(1),
+        // RD[2] <- RAM[1<<18 + 2][15:0]
+        RV32I::LHU.new().set_imm(2).set_rs1(31).set_rd(2),
+        // RAM[1<<18 + 6][15:0] <- RD[2]
+        RV32I::SH.new().set_imm(6).set_rs1(31).set_rs2(2),
+        // RD[3] <- RAM[1<<18 + 7][7:0]
+        RV32I::LBU.new().set_imm(7).set_rs1(31).set_rd(3),
+        // Misaligned, traps
+        RV32I::LW.new().set_imm(2).set_rs1(31).set_rd(4),
+        // RD[5] <- 7, never executed
+        RV32I::ADDI.new().set_imm(7).set_rd(5),
+    ];
+
+    let mut ram: Vec<u32> = vec![0u32; 64];
+    ram[0] = 0x8382_8180;
+
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+    let module: &Module<BE> = params.module();
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([0u8; 32]);
+    let mut source_xe: Source = Source::new([0u8; 32]);
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 24);
+
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_lwe: LWESecret<Vec<u8>> = LWESecret::alloc(params.n_lwe());
+    sk_lwe.fill_binary_block(params.lwe_block_size(), &mut source_xs);
+
+    let mut interpreter: Interpreter<BE> =
+        Interpreter::new_with_debug(&params, rom.len(), ram.len());
+
+    let mut instructions = InstructionsParser::new();
+    for inst in &rom {
+        instructions.add(*inst);
+    }
+
+    let mut sk_glwe_prepared: GLWESecretPrepared<Vec<u8>, BE> =
+        GLWESecretPrepared::alloc(module, sk_glwe.rank());
+    sk_glwe_prepared.prepare(module, &sk_glwe);
+
+    interpreter.instructions_encrypt_sk(
+        module,
+        &instructions,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    interpreter.ram_encrypt_sk(
+        module,
+        &ram,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 28);
+
+    let key: VMKeys<Vec<u8>, BRA> =
+        VMKeys::encrypt_sk(&params, &sk_lwe, &sk_glwe, &mut source_xa, &mut source_xe);
+
+    let mut key_prepared: VMKeysPrepared<Vec<u8>, BRA, BE> = VMKeysPrepared::alloc(&params);
+    key_prepared.prepare(module, &key, scratch.borrow());
+
+    // Runs past the trap: the debug VM checks that the PC, the registers and the RAM are frozen.
+    for _ in 0..rom.len() {
+        interpreter.cycle_debug(module, &key_prepared, &sk_glwe_prepared, scratch.borrow());
+    }
+
+    interpreter.halt_status_update(module, &key_prepared, scratch.borrow());
+    let status: HaltStatus =
+        interpreter.halt_status_decrypt(module, &sk_glwe_prepared, scratch.borrow());
+    assert_eq!(
+        status,
+        HaltStatus {
+            halted: true,
+            cycles: 5,
+        }
+    );
+
+    let registers: &[u32; 32] = &interpreter.vm_debug.as_ref().unwrap().registers;
+    assert_eq!(registers[1], 0xFFFF_FF81);
+    assert_eq!(registers[2], 0x8382);
+    assert_eq!(registers[3], 0x83);
+    assert_eq!(registers[4], 0);
+    assert_eq!(registers[5], 0);
+}
@@ -0,0 +1,303 @@
+use poulpy_core::{
+    layouts::{
+        GGLWEToGGSWKeyPreparedFactory, GGSWPreparedFactory, GLWEAutomorphismKeyPreparedFactory,
+        GLWEInfos, GLWESecret, GLWESecretPrepared, GLWESecretPreparedFactory, LWESecret,
+    },
+    GGLWEToGGSWKeyEncryptSk, GLWEAutomorphismKeyEncryptSk, GLWEDecrypt, GLWEEncryptSk,
+    GLWEExternalProduct, GLWEPackerOps, GLWEPacking, GLWETrace, ScratchTakeCore,
+};
+use poulpy_cpu_ref::FFT64Ref;
+use poulpy_hal::{
+    api::{ModuleN, ModuleNew, ScratchOwnedAlloc, ScratchOwnedBorrow},
+    layouts::{Backend, Module, Scratch, ScratchOwned},
+    source::Source,
+};
+use poulpy_schemes::bin_fhe::{
+    bdd_arithmetic::{
+        BDDKeyEncryptSk, BDDKeyPreparedFactory, FheUintPrepare, FheUintPreparedEncryptSk,
+        FheUintPreparedFactory, GGSWBlindRotation, GLWEBlindRetrieval, GetBitCircuitInfo,
+    },
+    blind_rotation::{BlindRotationAlgo, BlindRotationKey, BlindRotationKeyFactory, CGGI},
+};
+
+use crate::{
+    bdd::{BDDBuilder, BDDRef, DynamicCircuit, FALSE},
+    custom::{CustomInstruction, CustomInstructions, CUSTOM_0, CUSTOM_1},
+    keys::{VMKeys, VMKeysPrepared},
+    parameters::CryptographicParameters,
+    prepare::PrepareMultiple,
+    tests::rv32m::{bits, eval_plain_circuit, word},
+    Instruction, InstructionSet, InstructionsParser, Interpreter, RD_UPDATE, RV32I,
+};
+
+const WORD: usize = u32::BITS as usize;
+
+/// PRESENT S-box.
+const SBOX: [u32; 16] = [
+    0xC, 0x5, 0x6, 0xB, 0x9, 0x0, 0xA, 0xD, 0x3, 0xE, 0xF, 0x8, 0x4, 0x7, 0x1, 0x2,
+];
+
+/// x[rd] = (x[rs1] ^ x[rs2]) + imm, on custom-0 with funct3 = 0.
+struct XorAdd(DynamicCircuit);
+
+impl XorAdd {
+    fn new() -> Self {
+        let mut b: BDDBuilder = BDDBuilder::new();
+
+        // Declared from the high to the low bits, as the carry propagates from the low bits.
+        let mut vars: Vec<(BDDRef, BDDRef, BDDRef)> = (0..WORD)
+            .rev()
+            .map(|i| (b.var(i), b.var(WORD + i), b.var(2 * WORD + i)))
+            .collect();
+        vars.reverse();
+
+        let xor: Vec<BDDRef> = vars.iter().map(|&(a, c, _)| b.xor(a, c)).collect();
+        let imm: Vec<BDDRef> = vars.iter().map(|&(_, _, imm)| imm).collect();
+        let mut sum: Vec<BDDRef> = b.add(&xor, &imm, FALSE);
+        sum.truncate(WORD);
+
+        Self(b.circuit(3 * WORD, &sum))
+    }
+}
+
+impl CustomInstruction for XorAdd {
+    fn opcode(&self) -> u32 {
+        CUSTOM_0
+    }
+
+    fn funct3(&self) -> u32 {
+        0b000
+    }
+
+    fn circuit(&self) -> &dyn GetBitCircuitInfo {
+        &self.0
+    }
+
+    fn eval_plain(&self, rs1: u32, rs2: u32, imm: u32) -> u32 {
+        (rs1 ^ rs2).wrapping_add(imm)
+    }
+}
+
+/// x[rd] = [SBOX] applied to each nibble of x[rs1], on custom-1 with funct3 = 1.
+struct SBoxNibbles(DynamicCircuit);
+
+impl SBoxNibbles {
+    fn new() -> Self {
+        let mut b: BDDBuilder = BDDBuilder::new();
+        let rs1: Vec<BDDRef> = (0..WORD).map(|i| b.var(i)).collect();
+
+        let mut outputs: Vec<BDDRef> = Vec::with_capacity(WORD);
+        for nibble in rs1.chunks_exact(4) {
+            for bit in 0..4 {
+                let out: BDDRef = (0..16).fold(FALSE, |acc, x| {
+                    if (SBOX[x] >> bit) & 1 == 0 {
+                        return acc;
+                    }
+                    let is_x: BDDRef = b.eq_const(nibble, x as u32);
+                    b.or(acc, is_x)
+                });
+                outputs.push(out);
+            }
+        }
+
+        Self(b.circuit(WORD, &outputs))
+    }
+}
+
+impl CustomInstruction for SBoxNibbles {
+    fn opcode(&self) -> u32 {
+        CUSTOM_1
+    }
+
+    fn funct3(&self) -> u32 {
+        0b001
+    }
+
+    fn circuit(&self) -> &dyn GetBitCircuitInfo {
+        &self.0
+    }
+
+    fn eval_plain(&self, rs1: u32, _rs2: u32, _imm: u32) -> u32 {
+        (0..8).fold(0, |acc, i| {
+            acc | (SBOX[((rs1 >> (4 * i)) & 0xF) as usize] << (4 * i))
+        })
+    }
+}
+
+fn custom_instructions() -> CustomInstructions {
+    let mut custom: CustomInstructions = CustomInstructions::new();
+    custom.register(XorAdd::new());
+    custom.register(SBoxNibbles::new());
+    custom
+}
+
+fn custom_instruction(opcode: u32, funct3: u32) -> Instruction {
+    Instruction::new(opcode).set_funct3(funct3)
+}
+
+#[test]
+fn test_custom_circuits() {
+    let custom: CustomInstructions = custom_instructions();
+    let values: [u32; 6] = [0, 1, 0x7FFF_FFFF, 0x8000_0000, 0xFFFF_FFFF, 0x1234_5678];
+    for op in &RD_UPDATE::CUSTOM[..custom.len()] {
+        let inst: &dyn CustomInstruction = custom.instruction(op).unwrap();
+        for &rs1 in &values {
+            for &rs2 in &values {
+                for imm in [0, 5, 0xFFFF_F800] {
+                    let inputs: Vec<bool> = bits(&[(rs1, WORD), (rs2, WORD), (imm, WORD)]);
+                    let have: u32 = word(&eval_plain_circuit(inst.circuit(), &inputs));
+                    let want: u32 = inst.eval_plain(rs1, rs2, imm);
+                    assert_eq!(
+                        have, want,
+                        "{op:?} rs1: {rs1:#x} rs2: {rs2:#x} imm: {imm:#x}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_custom_decoding() {
+    let custom: CustomInstructions = custom_instructions();
+
+    let xor_add: Instruction = custom_instruction(CUSTOM_0, 0b000)
+        .set_imm(0xFFE)
+        .set_rs1(1)
+        .set_rd(3);
+    let sbox: Instruction = custom_instruction(CUSTOM_1, 0b001).set_rs1(4).set_rd(5);
+    assert!(custom.get(xor_add.get()).is_some());
+    assert!(custom
+        .get(custom_instruction(CUSTOM_0, 0b001).get())
+        .is_none());
+    assert!(custom.get(RV32I::ADD.new().get()).is_none());
+
+    let mut parser: InstructionsParser =
+        InstructionsParser::new().with_custom_instructions(&custom);
+    parser.add(xor_add);
+    parser.add(sbox);
+    parser.add(RV32I::ADD.new().set_rs1(1).set_rs2(2).set_rd(3));
+    // rs2 overlaps the low bits of the immediate.
+    parser.assert_instruction(0, 0xFFFF_FFFE, 0x1E, 1, 3, RD_UPDATE::CUSTOM0.id() as i64, 0, 1);
+    parser.assert_instruction(1, 0, 0, 4, 5, RD_UPDATE::CUSTOM1.id() as i64, 0, 1);
+    parser.assert_instruction(2, 0, 2, 1, 3, RD_UPDATE::ADD.id() as i64, 0, 1);
+}
+
+#[test]
+#[should_panic(expected = "unregistered custom instruction")]
+fn test_custom_decoding_unregistered() {
+    let mut parser: InstructionsParser =
+        InstructionsParser::new().with_custom_instructions(&custom_instructions());
+    parser.add(custom_instruction(CUSTOM_1, 0b000));
+}
+
+#[test]
+fn test_interpreter_custom_cycles_fft64_ref() {
+    test_interpreter_custom_cycles::<CGGI, FFT64Ref>()
+}
+
+fn test_interpreter_custom_cycles<BRA: BlindRotationAlgo, BE: Backend>()
+where
+    Module<BE>: ModuleNew<BE>
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + PrepareMultiple<BE, BRA>
+        + ModuleN
+        + GLWEEncryptSk<BE>
+        + FheUintPreparedEncryptSk<u32, BE>
+        + GLWEAutomorphismKeyEncryptSk<BE>
+        + GGLWEToGGSWKeyEncryptSk<BE>
+        + GLWETrace<BE>
+        + BDDKeyEncryptSk<BRA, BE>
+        + GGSWPreparedFactory<BE>
+        + GLWEExternalProduct<BE>
+        + GLWEPackerOps<BE>
+        + GLWEPacking<BE>
+        + FheUintPrepare<BRA, BE>
+        + GGSWBlindRotation<u32, BE>
+        + GLWEDecrypt<BE>
+        + GLWEAutomorphismKeyPreparedFactory<BE>
+        + GGLWEToGGSWKeyPreparedFactory<BE>
+        + BDDKeyPreparedFactory<BRA, BE>
+        + GLWEBlindRetrieval<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+    BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+{
+    let custom: CustomInstructions = custom_instructions();
+
+    let rom: Vec<Instruction> = vec![
+        // RD[1] <- -7
+        RV32I::ADDI.new().set_imm(0xFF9).set_rd(1),
+        // RD[2] <- (RD[1] ^ RD[0]) + 100
+        custom_instruction(CUSTOM_0, 0b000)
+            .set_imm(100)
+            .set_rs1(1)
+            .set_rd(2),
+        // RD[3] <- sbox(RD[2])
+        custom_instruction(CUSTOM_1, 0b001).set_rs1(2).set_rd(3),
+    ];
+
+    let ram: Vec<u32> = vec![0u32; 64];
+
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+    let module: &Module<BE> = params.module();
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([0u8; 32]);
+    let mut source_xe: Source = Source::new([0u8; 32]);
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 24);
+
+    // Generates a new secret-key along with the public evaluation keys.
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_lwe: LWESecret<Vec<u8>> = LWESecret::alloc(params.n_lwe());
+    sk_lwe.fill_binary_block(params.lwe_block_size(), &mut source_xs);
+
+    let mut interpreter: Interpreter<BE> =
+        Interpreter::new_with_debug(&params, rom.len(), ram.len());
+    interpreter.set_instruction_set(InstructionSet::RV32I);
+    interpreter.set_custom_instructions(&custom);
+
+    let mut instructions = InstructionsParser::new().with_custom_instructions(&custom);
+    for inst in &rom {
+        instructions.add(*inst);
+    }
+
+    let mut sk_glwe_prepared: GLWESecretPrepared<Vec<u8>, BE> =
+        GLWESecretPrepared::alloc(module, sk_glwe.rank());
+    sk_glwe_prepared.prepare(module, &sk_glwe);
+
+    interpreter.instructions_encrypt_sk(
+        module,
+        &instructions,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    interpreter.ram_encrypt_sk(
+        module,
+        &ram,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 28);
+
+    let key: VMKeys<Vec<u8>, BRA> =
+        VMKeys::encrypt_sk(&params, &sk_lwe, &sk_glwe, &mut source_xa, &mut source_xe);
+
+    let mut key_prepared: VMKeysPrepared<Vec<u8>, BRA, BE> = VMKeysPrepared::alloc(&params);
+    key_prepared.prepare(module, &key, scratch.borrow());
+
+    // The debug VM checks every decrypted register write against
+    // [CustomInstruction::eval_plain].
+    for _ in 0..rom.len() {
+        interpreter.cycle_debug(module, &key_prepared, &sk_glwe_prepared, scratch.borrow());
+    }
+}
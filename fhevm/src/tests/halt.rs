@@ -0,0 +1,179 @@
+use poulpy_core::{
+    layouts::{
+        GGLWEToGGSWKeyPreparedFactory, GGSWPreparedFactory, GLWEAutomorphismKeyPreparedFactory,
+        GLWEInfos, GLWESecret, GLWESecretPrepared, GLWESecretPreparedFactory, LWESecret,
+    },
+    GGLWEToGGSWKeyEncryptSk, GLWEAutomorphismKeyEncryptSk, GLWEDecrypt, GLWEEncryptSk,
+    GLWEExternalProduct, GLWEPackerOps, GLWEPacking, GLWETrace, ScratchTakeCore,
+};
+use poulpy_cpu_ref::FFT64Ref;
+use poulpy_hal::{
+    api::{ModuleN, ModuleNew, ScratchOwnedAlloc, ScratchOwnedBorrow},
+    layouts::{Backend, Module, Scratch, ScratchOwned},
+    source::Source,
+};
+use poulpy_schemes::bin_fhe::{
+    bdd_arithmetic::{
+        BDDKeyEncryptSk, BDDKeyPreparedFactory, FheUintPrepare, FheUintPreparedEncryptSk,
+        FheUintPreparedFactory, GGSWBlindRotation, GLWEBlindRetrieval, GetBitCircuitInfo,
+    },
+    blind_rotation::{BlindRotationAlgo, BlindRotationKey, BlindRotationKeyFactory, CGGI},
+};
+
+use super::rv32m::{bits, eval_plain_circuit, word};
+use crate::{
+    bdd::DynamicCircuit,
+    halt::halt_circuit,
+    keys::{VMKeys, VMKeysPrepared},
+    parameters::CryptographicParameters,
+    prepare::PrepareMultiple,
+    HaltStatus, Instruction, InstructionsParser, Interpreter, RV32I,
+};
+
+#[test]
+fn test_halt_circuit() {
+    let pc_bits: usize = 6;
+    let circuit: DynamicCircuit = halt_circuit(pc_bits);
+
+    for i in 0..circuit.output_size() {
+        let (_, state_size) = circuit.get_circuit(i);
+        assert!(state_size <= 32, "output {i} state size {state_size}");
+    }
+
+    let statuses: [HaltStatus; 4] = [
+        HaltStatus::default(),
+        HaltStatus::from_word(0x0000_0156),
+        HaltStatus::from_word(0x0000_0157),
+        HaltStatus::from_word(u32::MAX - 1),
+    ];
+
+    for status in statuses {
+        for (pc, pc_prev) in [(0, 0), (4, 0), (0x20, 0x20), (0x3C, 0x38), (0x38, 0x3C)] {
+            let out: Vec<bool> = eval_plain_circuit(
+                &circuit,
+                &bits(&[(status.to_word(), 32), (pc, pc_bits), (pc_prev, pc_bits)]),
+            );
+            assert_eq!(
+                HaltStatus::from_word(word(&out)),
+                status.update(pc, pc_prev),
+                "status: {status:?} pc: {pc:#x} pc_prev: {pc_prev:#x}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_interpreter_halt_fft64_ref() {
+    test_interpreter_halt::<CGGI, FFT64Ref>()
+}
+
+fn test_interpreter_halt<BRA: BlindRotationAlgo, BE: Backend>()
+where
+    Module<BE>: ModuleNew<BE>
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + PrepareMultiple<BE, BRA>
+        + ModuleN
+        + GLWEEncryptSk<BE>
+        + FheUintPreparedEncryptSk<u32, BE>
+        + GLWEAutomorphismKeyEncryptSk<BE>
+        + GGLWEToGGSWKeyEncryptSk<BE>
+        + GLWETrace<BE>
+        + BDDKeyEncryptSk<BRA, BE>
+        + GGSWPreparedFactory<BE>
+        + GLWEExternalProduct<BE>
+        + GLWEPackerOps<BE>
+        + GLWEPacking<BE>
+        + FheUintPrepare<BRA, BE>
+        + GGSWBlindRotation<u32, BE>
+        + GLWEDecrypt<BE>
+        + GLWEAutomorphismKeyPreparedFactory<BE>
+        + GGLWEToGGSWKeyPreparedFactory<BE>
+        + BDDKeyPreparedFactory<BRA, BE>
+        + GLWEBlindRetrieval<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+    BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+{
+    let rom: Vec<Instruction> = vec![
+        // RD[1] <- 5
+        RV32I::ADDI.new().set_imm(5).set_rd(1),
+        // Exits
+        RV32I::EBREAK.new(),
+        // RD[2] <- 7, never executed
+        RV32I::ADDI.new().set_imm(7).set_rd(2),
+    ];
+
+    let ram: Vec<u32> = vec![0u32; 64];
+
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+    let module: &Module<BE> = params.module();
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([0u8; 32]);
+    let mut source_xe: Source = Source::new([0u8; 32]);
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 24);
+
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_lwe: LWESecret<Vec<u8>> = LWESecret::alloc(params.n_lwe());
+    sk_lwe.fill_binary_block(params.lwe_block_size(), &mut source_xs);
+
+    let mut interpreter: Interpreter<BE> =
+        Interpreter::new_with_debug(&params, rom.len(), ram.len());
+
+    let mut instructions = InstructionsParser::new();
+    for inst in &rom {
+        instructions.add(*inst);
+    }
+
+    let mut sk_glwe_prepared: GLWESecretPrepared<Vec<u8>, BE> =
+        GLWESecretPrepared::alloc(module, sk_glwe.rank());
+    sk_glwe_prepared.prepare(module, &sk_glwe);
+
+    interpreter.instructions_encrypt_sk(
+        module,
+        &instructions,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    interpreter.ram_encrypt_sk(
+        module,
+        &ram,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 28);
+
+    let key: VMKeys<Vec<u8>, BRA> =
+        VMKeys::encrypt_sk(&params, &sk_lwe, &sk_glwe, &mut source_xa, &mut source_xe);
+
+    let mut key_prepared: VMKeysPrepared<Vec<u8>, BRA, BE> = VMKeysPrepared::alloc(&params);
+    key_prepared.prepare(module, &key, scratch.borrow());
+
+    // Runs past the exit: the debug VM checks that the PC, the registers and the RAM are frozen.
+    for cycle in 0..4 {
+        interpreter.cycle_debug(module, &key_prepared, &sk_glwe_prepared, scratch.borrow());
+        interpreter.halt_status_update(module, &key_prepared, scratch.borrow());
+        let status: HaltStatus =
+            interpreter.halt_status_decrypt(module, &sk_glwe_prepared, scratch.borrow());
+        let want: HaltStatus = match cycle {
+            0 => HaltStatus {
+                halted: false,
+                cycles: 1,
+            },
+            _ => HaltStatus {
+                halted: true,
+                cycles: 1,
+            },
+        };
+        assert_eq!(status, want, "cycle {cycle}");
+    }
+}
@@ -0,0 +1,127 @@
+use poulpy_core::{
+    layouts::{GLWESecret, GLWESecretPrepared, GLWESecretPreparedFactory},
+    GLWEAdd, GLWECopy, GLWEDecrypt, GLWEEncryptSk, GLWENormalize, ScratchTakeCore,
+};
+use poulpy_cpu_ref::FFT64Ref;
+use poulpy_hal::{
+    api::{ModuleN, ModuleNew, ScratchOwnedAlloc, ScratchOwnedBorrow},
+    layouts::{Backend, Module, Scratch, ScratchOwned},
+    source::Source,
+};
+use poulpy_schemes::bin_fhe::bdd_arithmetic::FheUintPreparedFactory;
+
+use crate::{input::EncryptedInput, parameters::CryptographicParameters, Interpreter};
+
+#[test]
+fn test_ram_inject_input_fft64_ref() {
+    test_ram_inject_input::<FFT64Ref>(false, false)
+}
+
+#[test]
+fn test_ram_inject_input_debug_fft64_ref() {
+    test_ram_inject_input::<FFT64Ref>(true, true)
+}
+
+#[test]
+#[should_panic(expected = "of the RAM is not zero")]
+fn test_ram_inject_input_debug_not_zero_fft64_ref() {
+    test_ram_inject_input::<FFT64Ref>(true, false)
+}
+
+// Injects an input covering the second of the three ciphertexts of each bit
+// array, and the end and the start of the first and third ones. Unless
+// `zero_range`, the boot RAM is only zero over the latter, as the covered
+// ciphertext is replaced.
+fn test_ram_inject_input<BE: Backend>(debug: bool, zero_range: bool)
+where
+    Module<BE>: ModuleNew<BE>
+        + ModuleN
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + GLWEEncryptSk<BE>
+        + GLWEDecrypt<BE>
+        + GLWEAdd
+        + GLWECopy
+        + GLWENormalize<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+{
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+    let module: &Module<BE> = params.module();
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 22);
+
+    let rom_size: usize = 1 << 4;
+    let n: usize = module.n();
+    let ram_size: usize = 3 * n;
+
+    let word_offset: usize = n - 3;
+    let size: usize = n + 7;
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([1u8; 32]);
+    let mut source_xe: Source = Source::new([2u8; 32]);
+
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_glwe_prepared: GLWESecretPrepared<Vec<u8>, BE> =
+        GLWESecretPrepared::alloc(module, params.rank());
+    sk_glwe_prepared.prepare(module, &sk_glwe);
+
+    let mut boot_ram: Vec<u32> = (0..ram_size as u32)
+        .map(|i| i.wrapping_mul(0x9E3779B9))
+        .collect();
+    if zero_range {
+        boot_ram[word_offset..word_offset + size].fill(0);
+    } else {
+        boot_ram[word_offset..n].fill(0);
+        boot_ram[2 * n..word_offset + size].fill(0);
+    }
+
+    let mut interpreter: Interpreter<BE> = if debug {
+        Interpreter::new_with_debug(&params, rom_size, ram_size)
+    } else {
+        Interpreter::new(&params, rom_size, ram_size)
+    };
+    interpreter.ram_encrypt_sk(
+        module,
+        &boot_ram,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    let data: Vec<u32> = (0..size as u32)
+        .map(|i| !i.wrapping_mul(0x01234567))
+        .collect();
+    let mut input: EncryptedInput = EncryptedInput::alloc(&params, word_offset, size);
+    input.encrypt_sk(
+        module,
+        &data,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    let mut bytes: Vec<u8> = Vec::new();
+    input.write_to(&params, &mut bytes).unwrap();
+    let input: EncryptedInput = EncryptedInput::read_from(&params, &mut bytes.as_slice()).unwrap();
+
+    if debug {
+        interpreter.ram_inject_input_debug(module, &input, &sk_glwe_prepared, scratch.borrow());
+    } else {
+        interpreter.ram_inject_input(module, &input, scratch.borrow());
+    }
+
+    let mut want: Vec<u32> = boot_ram.clone();
+    want[word_offset..word_offset + size].copy_from_slice(&data);
+    if debug {
+        assert_eq!(interpreter.vm_debug.as_ref().unwrap().ram, want);
+    }
+
+    let mut have: Vec<u32> = vec![0u32; ram_size];
+    interpreter.ram_decrypt(module, &mut have, &sk_glwe_prepared, scratch.borrow());
+    assert_eq!(have, want);
+}
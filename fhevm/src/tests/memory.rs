@@ -241,3 +241,43 @@ fn test_fhe_ram() {
         res.decrypt(params.module(), &sk_prep, scratch.borrow())
     );
 }
+
+#[test]
+fn test_fhe_ram_decrypt_range() {
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([1u8; 32]);
+    let mut source_xe: Source = Source::new([2u8; 32]);
+
+    let params: CryptographicParameters<FFT64Ref> = CryptographicParameters::<FFT64Ref>::new();
+    let module = params.module();
+
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_prep: GLWESecretPrepared<Vec<u8>, FFT64Ref> =
+        GLWESecretPrepared::alloc(module, sk_glwe.rank());
+    sk_prep.prepare(module, &sk_glwe);
+
+    let mut scratch: ScratchOwned<FFT64Ref> = ScratchOwned::alloc(1 << 22);
+
+    // Two ciphertexts per bit array.
+    let size: usize = module.n() << 1;
+    let data: Vec<u32> = (0..size as u32)
+        .map(|i| i.wrapping_mul(0x9E3779B9))
+        .collect();
+
+    let mut ram: Memory = Memory::alloc(&params.ram_infos(), 32, size);
+    ram.encrypt_sk(
+        module,
+        &data,
+        &sk_prep,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    for (offset, len) in [(0, 5), (module.n() - 2, 7), (size - 3, 3)] {
+        let mut have: Vec<u32> = vec![0u32; len];
+        ram.decrypt_range(module, offset, &mut have, &sk_prep, scratch.borrow());
+        assert_eq!(have, data[offset..offset + len]);
+    }
+}
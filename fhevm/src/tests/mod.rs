@@ -1,5 +1,21 @@
+mod alignment;
+mod custom;
 mod cycle;
+mod halt;
+mod input;
 mod interpreters;
 mod memory;
+mod op_set;
 mod pc_update;
+mod pipeline;
+mod public_key;
 mod ram_offset;
+mod refresh;
+mod rodata;
+mod rv32e;
+mod rv32m;
+mod scheduler;
+mod serialization;
+mod sources;
+mod syscall;
+mod zbb;
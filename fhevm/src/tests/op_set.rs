@@ -0,0 +1,214 @@
+use poulpy_core::{
+    layouts::{
+        GGLWEToGGSWKeyPreparedFactory, GGSWPreparedFactory, GLWEAutomorphismKeyPreparedFactory,
+        GLWEInfos, GLWESecret, GLWESecretPrepared, GLWESecretPreparedFactory, LWESecret,
+    },
+    GGLWEToGGSWKeyEncryptSk, GLWEAutomorphismKeyEncryptSk, GLWEDecrypt, GLWEEncryptSk,
+    GLWEExternalProduct, GLWEPackerOps, GLWEPacking, GLWETrace, ScratchTakeCore,
+};
+use poulpy_cpu_ref::FFT64Ref;
+use poulpy_hal::{
+    api::{ModuleN, ModuleNew, ScratchOwnedAlloc, ScratchOwnedBorrow},
+    layouts::{Backend, Module, Scratch, ScratchOwned},
+    source::Source,
+};
+use poulpy_schemes::bin_fhe::{
+    bdd_arithmetic::{
+        BDDKeyEncryptSk, BDDKeyPreparedFactory, FheUintPrepare, FheUintPreparedEncryptSk,
+        FheUintPreparedFactory, GGSWBlindRotation, GLWEBlindRetrieval,
+    },
+    blind_rotation::{BlindRotationAlgo, BlindRotationKey, BlindRotationKeyFactory, CGGI},
+};
+
+use crate::{
+    keys::{VMKeys, VMKeysPrepared},
+    parameters::CryptographicParameters,
+    prepare::PrepareMultiple,
+    Instruction, InstructionsParser, Interpreter, OpSet, RAM_UPDATE, RAM_UPDATE_OP_LIST, RD_UPDATE,
+    RD_UPDATE_RV32I_OP_LIST, RV32I,
+};
+
+// Program loading RAM[3], incrementing it and storing it to RAM[5] and RAM[6].
+fn rom() -> Vec<Instruction> {
+    vec![
+        // RD[31] <- 1<<18
+        RV32I::LUI.new().set_imm(1 << 6).set_rd(31),
+        // RD[1] <- RAM[3]
+        RV32I::LW.new().set_imm(12).set_rs1(31).set_rd(1),
+        // RD[1] <- RD[1] + 1
+        RV32I::ADDI.new().set_imm(1).set_rs1(1).set_rd(1),
+        // RAM[5] <- RD[1]
+        RV32I::SW.new().set_imm(20).set_rs1(31).set_rs2(1),
+        // RAM[6] <- RD[1]
+        RV32I::SW.new().set_imm(24).set_rs1(31).set_rs2(1),
+    ]
+}
+
+#[test]
+fn test_op_set() {
+    let mut instructions = InstructionsParser::new();
+    for inst in rom() {
+        instructions.add(inst);
+    }
+    let op_set: OpSet = OpSet::from_instructions(&instructions);
+
+    let rd_ops: Vec<u32> = RD_UPDATE_RV32I_OP_LIST
+        .iter()
+        .filter(|op| op_set.contains_rd_update(op))
+        .map(RD_UPDATE::id)
+        .collect();
+    let rd_want: Vec<u32> = [
+        RD_UPDATE::NONE,
+        RD_UPDATE::LUI,
+        RD_UPDATE::ADDI,
+        RD_UPDATE::LW,
+    ]
+    .iter()
+    .map(RD_UPDATE::id)
+    .collect();
+    assert_eq!(rd_ops.len(), rd_want.len());
+    assert!(rd_want.iter().all(|id| rd_ops.contains(id)));
+
+    let ram_ops: Vec<u32> = RAM_UPDATE_OP_LIST
+        .iter()
+        .filter(|op| op_set.contains_ram_update(op))
+        .map(RAM_UPDATE::id)
+        .collect();
+    assert_eq!(ram_ops, vec![RAM_UPDATE::NONE.id(), RAM_UPDATE::SW.id()]);
+
+    // An empty program keeps the NONE operations
+    let none: OpSet = OpSet::from_instructions(&InstructionsParser::new());
+    assert!(none.contains_rd_update(&RD_UPDATE::NONE));
+    assert!(none.contains_ram_update(&RAM_UPDATE::NONE));
+    assert!(!none.contains_ram_update(&RAM_UPDATE::SW));
+
+    let mut bytes: Vec<u8> = Vec::new();
+    op_set.write_to(&mut bytes).unwrap();
+    assert_eq!(OpSet::read_from(&mut bytes.as_slice()).unwrap(), op_set);
+
+    // Without the NONE operations
+    let mut bytes: Vec<u8> = Vec::new();
+    bytes.extend_from_slice(&2u64.to_le_bytes());
+    bytes.extend_from_slice(&1u64.to_le_bytes());
+    assert!(OpSet::read_from(&mut bytes.as_slice()).is_err());
+
+    // Operations up to CUSTOM7 and SW, but none above
+    let read = |rd_update: u64, ram_update: u64| {
+        let mut bytes: Vec<u8> = Vec::new();
+        bytes.extend_from_slice(&rd_update.to_le_bytes());
+        bytes.extend_from_slice(&ram_update.to_le_bytes());
+        OpSet::read_from(&mut bytes.as_slice())
+    };
+    let rd_max: u64 = 1 << RD_UPDATE::CUSTOM7.id();
+    let ram_max: u64 = 1 << RAM_UPDATE::SW.id();
+    let op_set: OpSet = read(1 | rd_max, 1 | ram_max).unwrap();
+    assert!(op_set.contains_rd_update(&RD_UPDATE::CUSTOM7));
+    assert!(op_set.contains_ram_update(&RAM_UPDATE::SW));
+    assert!(read(1 | rd_max << 1, 1).is_err());
+    assert!(read(1, 1 | ram_max << 1).is_err());
+}
+
+#[test]
+fn test_interpreter_op_set_fft64_ref() {
+    test_interpreter_op_set::<CGGI, FFT64Ref>()
+}
+
+fn test_interpreter_op_set<BRA: BlindRotationAlgo, BE: Backend>()
+where
+    Module<BE>: ModuleNew<BE>
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + PrepareMultiple<BE, BRA>
+        + ModuleN
+        + GLWEEncryptSk<BE>
+        + FheUintPreparedEncryptSk<u32, BE>
+        + GLWEAutomorphismKeyEncryptSk<BE>
+        + GGLWEToGGSWKeyEncryptSk<BE>
+        + GLWETrace<BE>
+        + BDDKeyEncryptSk<BRA, BE>
+        + GGSWPreparedFactory<BE>
+        + GLWEExternalProduct<BE>
+        + GLWEPackerOps<BE>
+        + GLWEPacking<BE>
+        + FheUintPrepare<BRA, BE>
+        + GGSWBlindRotation<u32, BE>
+        + GLWEDecrypt<BE>
+        + GLWEAutomorphismKeyPreparedFactory<BE>
+        + GGLWEToGGSWKeyPreparedFactory<BE>
+        + BDDKeyPreparedFactory<BRA, BE>
+        + GLWEBlindRetrieval<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+    BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+{
+    let rom: Vec<Instruction> = rom();
+
+    let ram: Vec<u32> = (0..16).map(|i| 0x0101_0101 * i).collect();
+
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+    let module: &Module<BE> = params.module();
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([0u8; 32]);
+    let mut source_xe: Source = Source::new([0u8; 32]);
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 24);
+
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_lwe: LWESecret<Vec<u8>> = LWESecret::alloc(params.n_lwe());
+    sk_lwe.fill_binary_block(params.lwe_block_size(), &mut source_xs);
+
+    let mut interpreter: Interpreter<BE> =
+        Interpreter::new_with_debug(&params, rom.len(), ram.len());
+
+    let mut instructions = InstructionsParser::new();
+    for inst in &rom {
+        instructions.add(*inst);
+    }
+
+    // Only evaluates NONE, LUI, LW and ADDI, and the NONE and SW stores
+    let op_set: OpSet = OpSet::from_instructions(&instructions);
+    interpreter.set_op_set(Some(op_set));
+    assert_eq!(interpreter.op_set(), Some(&op_set));
+
+    let mut sk_glwe_prepared: GLWESecretPrepared<Vec<u8>, BE> =
+        GLWESecretPrepared::alloc(module, sk_glwe.rank());
+    sk_glwe_prepared.prepare(module, &sk_glwe);
+
+    interpreter.instructions_encrypt_sk(
+        module,
+        &instructions,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    interpreter.ram_encrypt_sk(
+        module,
+        &ram,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 28);
+
+    let key: VMKeys<Vec<u8>, BRA> =
+        VMKeys::encrypt_sk(&params, &sk_lwe, &sk_glwe, &mut source_xa, &mut source_xe);
+
+    let mut key_prepared: VMKeysPrepared<Vec<u8>, BRA, BE> = VMKeysPrepared::alloc(&params);
+    key_prepared.prepare(module, &key, scratch.borrow());
+
+    // The debug VM checks the registers and the RAM after each cycle
+    for _ in 0..rom.len() {
+        interpreter.cycle_debug(module, &key_prepared, &sk_glwe_prepared, scratch.borrow());
+    }
+
+    let mut ram_have: Vec<u32> = vec![0u32; ram.len()];
+    interpreter.ram_decrypt(module, &mut ram_have, &sk_glwe_prepared, scratch.borrow());
+    assert_eq!(ram_have[5], 0x0303_0304);
+    assert_eq!(ram_have[6], 0x0303_0304);
+}
@@ -0,0 +1,182 @@
+use poulpy_core::{
+    layouts::{
+        GGLWEToGGSWKeyPreparedFactory, GGSWPreparedFactory, GLWEAutomorphismKeyPreparedFactory,
+        GLWEInfos, GLWESecret, GLWESecretPrepared, GLWESecretPreparedFactory, LWESecret,
+    },
+    GGLWEToGGSWKeyEncryptSk, GLWEAutomorphismKeyEncryptSk, GLWEDecrypt, GLWEEncryptSk,
+    GLWEExternalProduct, GLWEPackerOps, GLWEPacking, GLWETrace, ScratchTakeCore,
+};
+use poulpy_cpu_ref::FFT64Ref;
+use poulpy_hal::{
+    api::{ModuleN, ModuleNew, ScratchOwnedAlloc, ScratchOwnedBorrow},
+    layouts::{Backend, Module, Scratch, ScratchOwned},
+    source::Source,
+};
+use poulpy_schemes::bin_fhe::{
+    bdd_arithmetic::{
+        BDDKeyEncryptSk, BDDKeyPreparedFactory, FheUintPrepare, FheUintPreparedEncryptSk,
+        FheUintPreparedFactory, GGSWBlindRotation, GLWEBlindRetrieval,
+    },
+    blind_rotation::{BlindRotationAlgo, BlindRotationKey, BlindRotationKeyFactory, CGGI},
+};
+
+use crate::{
+    keys::{VMKeys, VMKeysPrepared},
+    parameters::CryptographicParameters,
+    prepare::PrepareMultiple,
+    CycleHook, Instruction, InstructionsParser, Interpreter, Phase, RV32I,
+};
+
+// Records the phases run, with the value of x1 after each of them.
+struct Recorder(Vec<(Phase, usize, u32)>);
+
+impl<BE: Backend> CycleHook<BE> for Recorder {
+    fn before_phase(&mut self, interpreter: &Interpreter<BE>, _phase: Phase) {
+        assert_eq!(interpreter.cycle_count(), 0);
+    }
+
+    fn after_phase(&mut self, interpreter: &Interpreter<BE>, phase: Phase) {
+        let x1: u32 = interpreter.vm_debug.as_ref().unwrap().registers[1];
+        self.0.push((phase, interpreter.cycle_count(), x1));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_end_cycle_not_started() {
+    let params: CryptographicParameters<FFT64Ref> = CryptographicParameters::<FFT64Ref>::new();
+    let mut interpreter: Interpreter<FFT64Ref> = Interpreter::new(&params, 4, 16);
+    interpreter.end_cycle();
+}
+
+#[test]
+fn test_interpreter_phases_fft64_ref() {
+    test_interpreter_phases::<CGGI, FFT64Ref>()
+}
+
+fn test_interpreter_phases<BRA: BlindRotationAlgo, BE: Backend>()
+where
+    Module<BE>: ModuleNew<BE>
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + PrepareMultiple<BE, BRA>
+        + ModuleN
+        + GLWEEncryptSk<BE>
+        + FheUintPreparedEncryptSk<u32, BE>
+        + GLWEAutomorphismKeyEncryptSk<BE>
+        + GGLWEToGGSWKeyEncryptSk<BE>
+        + GLWETrace<BE>
+        + BDDKeyEncryptSk<BRA, BE>
+        + GGSWPreparedFactory<BE>
+        + GLWEExternalProduct<BE>
+        + GLWEPackerOps<BE>
+        + GLWEPacking<BE>
+        + FheUintPrepare<BRA, BE>
+        + GGSWBlindRotation<u32, BE>
+        + GLWEDecrypt<BE>
+        + GLWEAutomorphismKeyPreparedFactory<BE>
+        + GGLWEToGGSWKeyPreparedFactory<BE>
+        + BDDKeyPreparedFactory<BRA, BE>
+        + GLWEBlindRetrieval<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+    BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+{
+    let rom: Vec<Instruction> = vec![
+        // RD[1] <- 5
+        RV32I::ADDI.new().set_imm(5).set_rd(1),
+        // RD[2] <- RD[1] + 3
+        RV32I::ADDI.new().set_imm(3).set_rs1(1).set_rd(2),
+    ];
+
+    let ram: Vec<u32> = vec![0u32; 16];
+
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+    let module: &Module<BE> = params.module();
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([0u8; 32]);
+    let mut source_xe: Source = Source::new([0u8; 32]);
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 24);
+
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_lwe: LWESecret<Vec<u8>> = LWESecret::alloc(params.n_lwe());
+    sk_lwe.fill_binary_block(params.lwe_block_size(), &mut source_xs);
+
+    let mut interpreter: Interpreter<BE> =
+        Interpreter::new_with_debug(&params, rom.len(), ram.len());
+
+    let mut instructions = InstructionsParser::new();
+    for inst in &rom {
+        instructions.add(*inst);
+    }
+
+    let mut sk_glwe_prepared: GLWESecretPrepared<Vec<u8>, BE> =
+        GLWESecretPrepared::alloc(module, sk_glwe.rank());
+    sk_glwe_prepared.prepare(module, &sk_glwe);
+
+    interpreter.instructions_encrypt_sk(
+        module,
+        &instructions,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    interpreter.ram_encrypt_sk(
+        module,
+        &ram,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 28);
+
+    let key: VMKeys<Vec<u8>, BRA> =
+        VMKeys::encrypt_sk(&params, &sk_lwe, &sk_glwe, &mut source_xa, &mut source_xe);
+
+    let mut key_prepared: VMKeysPrepared<Vec<u8>, BRA, BE> = VMKeysPrepared::alloc(&params);
+    key_prepared.prepare(module, &key, scratch.borrow());
+
+    // x1 is written by the register update
+    let mut recorder: Recorder = Recorder(Vec::new());
+    interpreter.cycle_debug_with_hook(
+        module,
+        &key_prepared,
+        &sk_glwe_prepared,
+        &mut recorder,
+        scratch.borrow(),
+    );
+    let phases: Vec<Phase> = recorder.0.iter().map(|(phase, _, _)| *phase).collect();
+    assert_eq!(phases, Phase::ALL);
+    for (phase, cycle, x1) in &recorder.0 {
+        assert_eq!(*cycle, 0);
+        let written: bool = Phase::ALL.iter().position(|p| p == phase)
+            >= Phase::ALL.iter().position(|p| *p == Phase::UpdateRegisters);
+        assert_eq!(*x1, if written { 5 } else { 0 }, "{phase:?}");
+    }
+    assert_eq!(interpreter.cycle_count(), 1);
+
+    // Next cycle, run phase by phase
+    interpreter.begin_cycle();
+    for phase in Phase::ALL {
+        interpreter.run_phase_debug(
+            phase,
+            module,
+            &key_prepared,
+            &sk_glwe_prepared,
+            scratch.borrow(),
+        );
+    }
+    interpreter.end_cycle();
+    assert_eq!(interpreter.cycle_count(), 2);
+
+    let registers: &[u32; 32] = &interpreter.vm_debug.as_ref().unwrap().registers;
+    assert_eq!(registers[1], 5);
+    assert_eq!(registers[2], 8);
+}
@@ -0,0 +1,86 @@
+use poulpy_core::{
+    layouts::{
+        GLWEPublicKeyPreparedFactory, GLWESecret, GLWESecretPrepared, GLWESecretPreparedFactory,
+    },
+    GLWEDecrypt, GLWEEncryptPk, GLWEEncryptSk, ScratchTakeCore,
+};
+use poulpy_cpu_ref::FFT64Ref;
+use poulpy_hal::{
+    api::{ModuleN, ModuleNew, ScratchOwnedAlloc, ScratchOwnedBorrow},
+    layouts::{Backend, Module, Scratch, ScratchOwned},
+    source::Source,
+};
+use poulpy_schemes::bin_fhe::bdd_arithmetic::FheUintPreparedFactory;
+
+use crate::{
+    keys::{VMPublicKey, VMPublicKeyPrepared},
+    parameters::CryptographicParameters,
+    Interpreter,
+};
+
+#[test]
+fn test_ram_encrypt_pk_fft64_ref() {
+    test_ram_encrypt_pk::<FFT64Ref>()
+}
+
+fn test_ram_encrypt_pk<BE: Backend>()
+where
+    Module<BE>: ModuleNew<BE>
+        + ModuleN
+        + GLWESecretPreparedFactory<BE>
+        + GLWEPublicKeyPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + GLWEEncryptSk<BE>
+        + GLWEEncryptPk<BE>
+        + GLWEDecrypt<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+{
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+    let module: &Module<BE> = params.module();
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 22);
+
+    let rom_size: usize = 1 << 4;
+    let ram_size: usize = 1 << 4;
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([1u8; 32]);
+    let mut source_xe: Source = Source::new([2u8; 32]);
+
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_glwe_prepared: GLWESecretPrepared<Vec<u8>, BE> =
+        GLWESecretPrepared::alloc(module, params.rank());
+    sk_glwe_prepared.prepare(module, &sk_glwe);
+
+    let pk: VMPublicKey<Vec<u8>> =
+        VMPublicKey::generate(&params, &sk_glwe_prepared, &mut source_xa, &mut source_xe);
+
+    // Round-trip through the serialized form handed to input providers.
+    let mut bytes: Vec<u8> = Vec::new();
+    pk.write_to(&params, &mut bytes).unwrap();
+    let pk: VMPublicKey<Vec<u8>> = VMPublicKey::read_from(&params, &mut bytes.as_slice()).unwrap();
+
+    let mut pk_prepared: VMPublicKeyPrepared<Vec<u8>, BE> = VMPublicKeyPrepared::alloc(&params);
+    pk_prepared.prepare(module, &pk);
+
+    let data: Vec<u32> = (0..ram_size as u32)
+        .map(|i| i.wrapping_mul(0x9E3779B9))
+        .collect();
+
+    let mut source_xu: Source = Source::new([3u8; 32]);
+    let mut interpreter: Interpreter<BE> = Interpreter::new(&params, rom_size, ram_size);
+    interpreter.ram_encrypt_pk(
+        module,
+        &data,
+        &pk_prepared,
+        &mut source_xu,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    let mut have: Vec<u32> = vec![0u32; ram_size];
+    interpreter.ram_decrypt(module, &mut have, &sk_glwe_prepared, scratch.borrow());
+    assert_eq!(have, data);
+}
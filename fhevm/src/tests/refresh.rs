@@ -0,0 +1,354 @@
+use poulpy_core::{
+    layouts::{
+        GGLWEToGGSWKeyPreparedFactory, GGSWPreparedFactory, GLWEAutomorphismKeyPreparedFactory,
+        GLWEInfos, GLWESecret, GLWESecretPrepared, GLWESecretPreparedFactory, LWESecret,
+    },
+    GGLWEToGGSWKeyEncryptSk, GLWEAutomorphismKeyEncryptSk, GLWEDecrypt, GLWEEncryptSk,
+    GLWEExternalProduct, GLWEPackerOps, GLWEPacking, GLWETrace, ScratchTakeCore,
+};
+use poulpy_cpu_ref::FFT64Ref;
+use poulpy_hal::{
+    api::{ModuleN, ModuleNew, ScratchOwnedAlloc, ScratchOwnedBorrow},
+    layouts::{Backend, Module, Scratch, ScratchOwned},
+    source::Source,
+};
+use poulpy_schemes::bin_fhe::{
+    bdd_arithmetic::{
+        BDDKeyEncryptSk, BDDKeyPreparedFactory, FheUintPrepare, FheUintPreparedEncryptSk,
+        FheUintPreparedFactory, GGSWBlindRotation, GLWEBlindRetrieval,
+    },
+    blind_rotation::{BlindRotationAlgo, BlindRotationKey, BlindRotationKeyFactory, CGGI},
+};
+
+use crate::{
+    keys::{VMKeys, VMKeysPrepared},
+    parameters::CryptographicParameters,
+    prepare::PrepareMultiple,
+    Instruction, InstructionsParser, Interpreter, NoiseModel, RefreshSchedule, RV32I,
+};
+
+#[test]
+fn test_noise_model() {
+    // Variances 2^-20 * (1 + i)
+    let noise: Vec<f64> = (0..8)
+        .map(|i| (-20.0 + (1.0 + i as f64).log2()) / 2.0)
+        .collect();
+    let model: NoiseModel = NoiseModel::fit(&noise, -4.0);
+    assert!((model.fresh + 10.0).abs() < 1e-9, "{model:?}");
+    assert!((model.pass + 10.0).abs() < 1e-9, "{model:?}");
+
+    // (2^-8 - 2^-20) / 2^-20 = 4095
+    let model: NoiseModel = NoiseModel {
+        fresh: -10.0,
+        pass: -10.0,
+        max: -4.0,
+    };
+    assert_eq!(model.passes(), 4095);
+}
+
+#[test]
+fn test_refresh_schedule() {
+    let model: NoiseModel = NoiseModel {
+        fresh: -20.0,
+        pass: -10.0,
+        max: -10.0 + 0.5 * 100.5f64.log2(),
+    };
+    assert_eq!(model.passes(), 100);
+
+    // 20 words: one every 80 / 20 = 4 cycles
+    let schedule: RefreshSchedule = RefreshSchedule::new(&model, 20, 20);
+    assert_eq!((schedule.period(), schedule.words()), (4, 1));
+    assert!(schedule.words_at(0).is_empty());
+    assert_eq!(schedule.words_at(3), vec![0]);
+    assert_eq!(schedule.words_at(7), vec![1]);
+    assert_eq!(schedule.words_at(83), vec![0]);
+
+    // 1000 words, 60 per polynomial: ceil(1000 / 40) = 25 every cycle
+    let schedule: RefreshSchedule = RefreshSchedule::new(&model, 1000, 60);
+    assert_eq!((schedule.period(), schedule.words()), (1, 25));
+    assert_eq!(schedule.words_at(0), (0..25).collect::<Vec<usize>>());
+    assert_eq!(schedule.words_at(39), (975..1000).collect::<Vec<usize>>());
+    assert_eq!(schedule.words_at(40), (0..25).collect::<Vec<usize>>());
+
+    // Every word refreshed every cycle
+    let schedule: RefreshSchedule = RefreshSchedule::new(&model, 3, 99);
+    assert_eq!(schedule.words_at(5), vec![0, 1, 2]);
+}
+
+#[test]
+#[should_panic]
+fn test_refresh_schedule_budget_too_small() {
+    let model: NoiseModel = NoiseModel {
+        fresh: -20.0,
+        pass: -10.0,
+        max: -10.0 + 0.5 * 100.5f64.log2(),
+    };
+    RefreshSchedule::new(&model, 1024, 1024);
+}
+
+#[test]
+fn test_interpreter_ram_refresh_fft64_ref() {
+    test_interpreter_ram_refresh::<CGGI, FFT64Ref>()
+}
+
+fn test_interpreter_ram_refresh<BRA: BlindRotationAlgo, BE: Backend>()
+where
+    Module<BE>: ModuleNew<BE>
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + PrepareMultiple<BE, BRA>
+        + ModuleN
+        + GLWEEncryptSk<BE>
+        + FheUintPreparedEncryptSk<u32, BE>
+        + GLWEAutomorphismKeyEncryptSk<BE>
+        + GGLWEToGGSWKeyEncryptSk<BE>
+        + GLWETrace<BE>
+        + BDDKeyEncryptSk<BRA, BE>
+        + GGSWPreparedFactory<BE>
+        + GLWEExternalProduct<BE>
+        + GLWEPackerOps<BE>
+        + GLWEPacking<BE>
+        + FheUintPrepare<BRA, BE>
+        + GGSWBlindRotation<u32, BE>
+        + GLWEDecrypt<BE>
+        + GLWEAutomorphismKeyPreparedFactory<BE>
+        + GGLWEToGGSWKeyPreparedFactory<BE>
+        + BDDKeyPreparedFactory<BRA, BE>
+        + GLWEBlindRetrieval<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+    BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+{
+    let rom: Vec<Instruction> = vec![
+        // RD[31] <- 1<<18
+        RV32I::LUI.new().set_imm(1 << 6).set_rd(31),
+        // RD[1] <- RAM[3]
+        RV32I::LW.new().set_imm(12).set_rs1(31).set_rd(1),
+        // RD[1] <- RD[1] + 1
+        RV32I::ADDI.new().set_imm(1).set_rs1(1).set_rd(1),
+        // RAM[5] <- RD[1]
+        RV32I::SW.new().set_imm(20).set_rs1(31).set_rs2(1),
+        // RAM[6] <- RD[1]
+        RV32I::SW.new().set_imm(24).set_rs1(31).set_rs2(1),
+    ];
+
+    let ram: Vec<u32> = (0..16).map(|i| 0x0101_0101 * i).collect();
+
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+    let module: &Module<BE> = params.module();
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([0u8; 32]);
+    let mut source_xe: Source = Source::new([0u8; 32]);
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 24);
+
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_lwe: LWESecret<Vec<u8>> = LWESecret::alloc(params.n_lwe());
+    sk_lwe.fill_binary_block(params.lwe_block_size(), &mut source_xs);
+
+    let mut interpreter: Interpreter<BE> =
+        Interpreter::new_with_debug(&params, rom.len(), ram.len());
+
+    let mut instructions = InstructionsParser::new();
+    for inst in &rom {
+        instructions.add(*inst);
+    }
+
+    let mut sk_glwe_prepared: GLWESecretPrepared<Vec<u8>, BE> =
+        GLWESecretPrepared::alloc(module, sk_glwe.rank());
+    sk_glwe_prepared.prepare(module, &sk_glwe);
+
+    interpreter.instructions_encrypt_sk(
+        module,
+        &instructions,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    interpreter.ram_encrypt_sk(
+        module,
+        &ram,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 28);
+
+    let key: VMKeys<Vec<u8>, BRA> =
+        VMKeys::encrypt_sk(&params, &sk_lwe, &sk_glwe, &mut source_xa, &mut source_xe);
+
+    let mut key_prepared: VMKeysPrepared<Vec<u8>, BRA, BE> = VMKeysPrepared::alloc(&params);
+    key_prepared.prepare(module, &key, scratch.borrow());
+
+    // Lets the noise of the RAM grow, the refresh being disabled by default...
+    assert!(interpreter.ram_refresh_schedule().is_none());
+    for _ in 0..rom.len() - 1 {
+        interpreter.cycle_debug(module, &key_prepared, &sk_glwe_prepared, scratch.borrow());
+    }
+
+    let ram_noise_max = |interpreter: &Interpreter<BE>, scratch: &mut Scratch<BE>| -> f64 {
+        let ram_want: &Vec<u32> = &interpreter.vm_debug.as_ref().unwrap().ram;
+        interpreter
+            .ram
+            .noise(module, ram_want, &sk_glwe_prepared, scratch)
+            .into_iter()
+            .fold(f64::NEG_INFINITY, f64::max)
+    };
+    let noise_before: f64 = ram_noise_max(&interpreter, scratch.borrow());
+
+    // ...then refreshes every word, with a budget of 17 passes leaving a
+    // single one to the cycles.
+    interpreter.set_ram_noise_model(Some(NoiseModel {
+        fresh: -20.0,
+        pass: -10.0,
+        max: -10.0 + 0.5 * 17.5f64.log2(),
+    }));
+    let schedule: RefreshSchedule = interpreter.ram_refresh_schedule().unwrap();
+    assert_eq!((schedule.period(), schedule.words()), (1, ram.len()));
+
+    // The debug VM checks the refreshed words
+    interpreter.cycle_debug(module, &key_prepared, &sk_glwe_prepared, scratch.borrow());
+
+    let noise_after: f64 = ram_noise_max(&interpreter, scratch.borrow());
+    println!("ram_noise: {noise_before:.2} -> {noise_after:.2}");
+    assert!(noise_after < noise_before);
+
+    let mut ram_have: Vec<u32> = vec![0u32; ram.len()];
+    interpreter.ram_decrypt(module, &mut ram_have, &sk_glwe_prepared, scratch.borrow());
+    assert_eq!(ram_have[5], 0x0303_0304);
+    assert_eq!(ram_have[6], 0x0303_0304);
+}
+
+#[test]
+fn test_ram_noise_model_fft64_ref() {
+    test_ram_noise_model::<CGGI, FFT64Ref>()
+}
+
+/// Measures the noise growth of the RAM with the default parameters, from
+/// which the [NoiseModel::default] is derived.
+fn test_ram_noise_model<BRA: BlindRotationAlgo, BE: Backend>()
+where
+    Module<BE>: ModuleNew<BE>
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + PrepareMultiple<BE, BRA>
+        + ModuleN
+        + GLWEEncryptSk<BE>
+        + FheUintPreparedEncryptSk<u32, BE>
+        + GLWEAutomorphismKeyEncryptSk<BE>
+        + GGLWEToGGSWKeyEncryptSk<BE>
+        + GLWETrace<BE>
+        + BDDKeyEncryptSk<BRA, BE>
+        + GGSWPreparedFactory<BE>
+        + GLWEExternalProduct<BE>
+        + GLWEPackerOps<BE>
+        + GLWEPacking<BE>
+        + FheUintPrepare<BRA, BE>
+        + GGSWBlindRotation<u32, BE>
+        + GLWEDecrypt<BE>
+        + GLWEAutomorphismKeyPreparedFactory<BE>
+        + GGLWEToGGSWKeyPreparedFactory<BE>
+        + BDDKeyPreparedFactory<BRA, BE>
+        + GLWEBlindRetrieval<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+    BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+{
+    const PASSES: usize = 8;
+
+    // Loads only, so that no word is written again and every cycle is a pass
+    // over the fresh RAM.
+    let mut rom: Vec<Instruction> = vec![
+        // RD[31] <- 1<<18
+        RV32I::LUI.new().set_imm(1 << 6).set_rd(31),
+    ];
+    for i in 0..PASSES - 1 {
+        // RD[1] <- RAM[i]
+        rom.push(RV32I::LW.new().set_imm(4 * i as u32).set_rs1(31).set_rd(1));
+    }
+
+    let ram: Vec<u32> = (0..16).map(|i| 0x0101_0101 * i).collect();
+
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+    let module: &Module<BE> = params.module();
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([0u8; 32]);
+    let mut source_xe: Source = Source::new([0u8; 32]);
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 24);
+
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_lwe: LWESecret<Vec<u8>> = LWESecret::alloc(params.n_lwe());
+    sk_lwe.fill_binary_block(params.lwe_block_size(), &mut source_xs);
+
+    let mut interpreter: Interpreter<BE> =
+        Interpreter::new_with_debug(&params, rom.len(), ram.len());
+
+    let mut instructions = InstructionsParser::new();
+    for inst in &rom {
+        instructions.add(*inst);
+    }
+
+    let mut sk_glwe_prepared: GLWESecretPrepared<Vec<u8>, BE> =
+        GLWESecretPrepared::alloc(module, sk_glwe.rank());
+    sk_glwe_prepared.prepare(module, &sk_glwe);
+
+    interpreter.instructions_encrypt_sk(
+        module,
+        &instructions,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    interpreter.ram_encrypt_sk(
+        module,
+        &ram,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 28);
+
+    let key: VMKeys<Vec<u8>, BRA> =
+        VMKeys::encrypt_sk(&params, &sk_lwe, &sk_glwe, &mut source_xa, &mut source_xe);
+
+    let mut key_prepared: VMKeysPrepared<Vec<u8>, BRA, BE> = VMKeysPrepared::alloc(&params);
+    key_prepared.prepare(module, &key, scratch.borrow());
+
+    let ram_noise_max = |interpreter: &Interpreter<BE>, scratch: &mut Scratch<BE>| -> f64 {
+        let ram_want: &Vec<u32> = &interpreter.vm_debug.as_ref().unwrap().ram;
+        interpreter
+            .ram
+            .noise(module, ram_want, &sk_glwe_prepared, scratch)
+            .into_iter()
+            .fold(f64::NEG_INFINITY, f64::max)
+    };
+
+    let mut noise: Vec<f64> = vec![ram_noise_max(&interpreter, scratch.borrow())];
+    for _ in 0..PASSES {
+        interpreter.cycle_debug(module, &key_prepared, &sk_glwe_prepared, scratch.borrow());
+        noise.push(ram_noise_max(&interpreter, scratch.borrow()));
+    }
+
+    // A pass rewrites every word, so the noise of the encryption is dropped:
+    // the words are fresh after the first one.
+    let model: NoiseModel = NoiseModel::fit(&noise[1..], NoiseModel::default().max);
+    println!("ram_noise: {noise:.2?}");
+    println!("{model:?}");
+
+    // The default model is at least as pessimistic as the measured one.
+    assert!(model.fresh <= NoiseModel::default().fresh, "{model:?}");
+    assert!(model.pass <= NoiseModel::default().pass, "{model:?}");
+}
@@ -0,0 +1,179 @@
+use poulpy_core::{
+    layouts::{
+        GGLWEToGGSWKeyPreparedFactory, GGSWPreparedFactory, GLWEAutomorphismKeyPreparedFactory,
+        GLWEInfos, GLWESecret, GLWESecretPrepared, GLWESecretPreparedFactory, LWESecret,
+    },
+    GGLWEToGGSWKeyEncryptSk, GLWEAutomorphismKeyEncryptSk, GLWEDecrypt, GLWEEncryptSk,
+    GLWEExternalProduct, GLWEPackerOps, GLWEPacking, GLWETrace, ScratchTakeCore,
+};
+use poulpy_cpu_ref::FFT64Ref;
+use poulpy_hal::{
+    api::{ModuleN, ModuleNew, ScratchOwnedAlloc, ScratchOwnedBorrow},
+    layouts::{Backend, Module, Scratch, ScratchOwned},
+    source::Source,
+};
+use poulpy_schemes::bin_fhe::{
+    bdd_arithmetic::{
+        BDDKeyEncryptSk, BDDKeyPreparedFactory, FheUintPrepare, FheUintPreparedEncryptSk,
+        FheUintPreparedFactory, GGSWBlindRotation, GLWEBlindRetrieval,
+    },
+    blind_rotation::{BlindRotationAlgo, BlindRotationKey, BlindRotationKeyFactory, CGGI},
+};
+
+use crate::{
+    keys::{VMKeys, VMKeysPrepared},
+    parameters::CryptographicParameters,
+    prepare::PrepareMultiple,
+    rodata::{rodata_bit, rodata_offset},
+    Instruction, InstructionsParser, Interpreter, RV32I,
+};
+
+#[test]
+fn test_rodata_offset() {
+    // Above both the RAM and the bank
+    assert_eq!(rodata_offset(16, 8), 64);
+    assert_eq!(rodata_offset(16, 16), 64);
+    assert_eq!(rodata_offset(16, 17), 128);
+    assert_eq!(rodata_offset(1024, 4096), 1 << 14);
+    assert_eq!(rodata_offset(1000, 1), 1 << 12);
+    assert_eq!(rodata_bit(1000, 1), 12);
+}
+
+#[test]
+fn test_interpreter_rodata_fft64_ref() {
+    test_interpreter_rodata::<CGGI, FFT64Ref>()
+}
+
+fn test_interpreter_rodata<BRA: BlindRotationAlgo, BE: Backend>()
+where
+    Module<BE>: ModuleNew<BE>
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + PrepareMultiple<BE, BRA>
+        + ModuleN
+        + GLWEEncryptSk<BE>
+        + FheUintPreparedEncryptSk<u32, BE>
+        + GLWEAutomorphismKeyEncryptSk<BE>
+        + GGLWEToGGSWKeyEncryptSk<BE>
+        + GLWETrace<BE>
+        + BDDKeyEncryptSk<BRA, BE>
+        + GGSWPreparedFactory<BE>
+        + GLWEExternalProduct<BE>
+        + GLWEPackerOps<BE>
+        + GLWEPacking<BE>
+        + FheUintPrepare<BRA, BE>
+        + GGSWBlindRotation<u32, BE>
+        + GLWEDecrypt<BE>
+        + GLWEAutomorphismKeyPreparedFactory<BE>
+        + GGLWEToGGSWKeyPreparedFactory<BE>
+        + BDDKeyPreparedFactory<BRA, BE>
+        + GLWEBlindRetrieval<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+    BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+{
+    // The bank is mapped at 1<<18 + rodata_offset(16, 8) = 1<<18 + 64
+    let rom: Vec<Instruction> = vec![
+        // RD[31] <- 1<<18
+        RV32I::LUI.new().set_imm(1 << 6).set_rd(31),
+        // RD[1] <- RODATA[1]
+        RV32I::LW.new().set_imm(64 + 4).set_rs1(31).set_rd(1),
+        // RD[2] <- RODATA[2][15:8]
+        RV32I::LBU.new().set_imm(64 + 9).set_rs1(31).set_rd(2),
+        // Dropped: RODATA[0] <- RD[1]
+        RV32I::SW.new().set_imm(64).set_rs1(31).set_rs2(1),
+        // RD[3] <- RODATA[0]
+        RV32I::LW.new().set_imm(64).set_rs1(31).set_rd(3),
+        // RD[4] <- RAM[0]
+        RV32I::LW.new().set_imm(0).set_rs1(31).set_rd(4),
+        // RAM[1] <- RD[1]
+        RV32I::SW.new().set_imm(4).set_rs1(31).set_rs2(1),
+    ];
+
+    let mut ram: Vec<u32> = vec![0u32; 16];
+    ram[0] = 0x1234_5678;
+
+    let mut rodata: Vec<u32> = vec![0u32; 8];
+    rodata[0] = 0x1111_1111;
+    rodata[1] = 0xCAFE_BABE;
+    rodata[2] = 0x0000_AB00;
+
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+    let module: &Module<BE> = params.module();
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([0u8; 32]);
+    let mut source_xe: Source = Source::new([0u8; 32]);
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 24);
+
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_lwe: LWESecret<Vec<u8>> = LWESecret::alloc(params.n_lwe());
+    sk_lwe.fill_binary_block(params.lwe_block_size(), &mut source_xs);
+
+    let mut interpreter: Interpreter<BE> =
+        Interpreter::new_with_debug(&params, rom.len(), ram.len());
+    interpreter.set_rodata_size(rodata.len());
+
+    let mut instructions = InstructionsParser::new();
+    for inst in &rom {
+        instructions.add(*inst);
+    }
+
+    let mut sk_glwe_prepared: GLWESecretPrepared<Vec<u8>, BE> =
+        GLWESecretPrepared::alloc(module, sk_glwe.rank());
+    sk_glwe_prepared.prepare(module, &sk_glwe);
+
+    interpreter.instructions_encrypt_sk(
+        module,
+        &instructions,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    interpreter.ram_encrypt_sk(
+        module,
+        &ram,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    interpreter.rodata_encrypt_sk(
+        module,
+        &rodata,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 28);
+
+    let key: VMKeys<Vec<u8>, BRA> =
+        VMKeys::encrypt_sk(&params, &sk_lwe, &sk_glwe, &mut source_xa, &mut source_xe);
+
+    let mut key_prepared: VMKeysPrepared<Vec<u8>, BRA, BE> = VMKeysPrepared::alloc(&params);
+    key_prepared.prepare(module, &key, scratch.borrow());
+
+    // The debug VM checks the RAM at each cycle, in particular that the
+    // store to the bank left the RAM word aliasing it unchanged.
+    for _ in 0..rom.len() {
+        interpreter.cycle_debug(module, &key_prepared, &sk_glwe_prepared, scratch.borrow());
+    }
+
+    let registers: &[u32; 32] = &interpreter.vm_debug.as_ref().unwrap().registers;
+    assert_eq!(registers[1], 0xCAFE_BABE);
+    assert_eq!(registers[2], 0xAB);
+    assert_eq!(registers[3], 0x1111_1111);
+    assert_eq!(registers[4], 0x1234_5678);
+
+    let mut ram_have: Vec<u32> = vec![0u32; ram.len()];
+    interpreter.ram_decrypt(module, &mut ram_have, &sk_glwe_prepared, scratch.borrow());
+    assert_eq!(ram_have[0], 0x1234_5678);
+    assert_eq!(ram_have[1], 0xCAFE_BABE);
+}
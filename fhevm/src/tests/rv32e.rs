@@ -0,0 +1,175 @@
+use poulpy_core::{
+    layouts::{
+        GGLWEToGGSWKeyPreparedFactory, GGSWPreparedFactory, GLWEAutomorphismKeyPreparedFactory,
+        GLWEInfos, GLWESecret, GLWESecretPrepared, GLWESecretPreparedFactory, LWESecret,
+    },
+    GGLWEToGGSWKeyEncryptSk, GLWEAutomorphismKeyEncryptSk, GLWEDecrypt, GLWEEncryptSk,
+    GLWEExternalProduct, GLWEPackerOps, GLWEPacking, GLWETrace, ScratchTakeCore,
+};
+use poulpy_cpu_ref::FFT64Ref;
+use poulpy_hal::{
+    api::{ModuleN, ModuleNew, ScratchOwnedAlloc, ScratchOwnedBorrow},
+    layouts::{Backend, Module, Scratch, ScratchOwned},
+    source::Source,
+};
+use poulpy_schemes::bin_fhe::{
+    bdd_arithmetic::{
+        BDDKeyEncryptSk, BDDKeyPreparedFactory, FheUintPrepare, FheUintPreparedEncryptSk,
+        FheUintPreparedFactory, GGSWBlindRotation, GLWEBlindRetrieval,
+    },
+    blind_rotation::{BlindRotationAlgo, BlindRotationKey, BlindRotationKeyFactory, CGGI},
+};
+
+use crate::{
+    keys::{VMKeys, VMKeysPrepared},
+    parameters::CryptographicParameters,
+    prepare::PrepareMultiple,
+    HaltStatus, Instruction, InstructionsParser, Interpreter, RD_UPDATE, RV32I, SYSCALL_REGISTER,
+    SYSCALL_REGISTER_RV32E,
+};
+
+#[test]
+fn test_rv32e_decoding() {
+    let mut parser: InstructionsParser = InstructionsParser::new().with_rv32e();
+    parser.add(RV32I::ADD.new().set_rs1(14).set_rs2(15).set_rd(13));
+    parser.add(RV32I::ECALL.new());
+    parser.assert_instruction(0, 0, 15, 14, 13, RD_UPDATE::ADD.id() as i64, 0, 1);
+    assert_eq!(parser.get_registers(1), (0, SYSCALL_REGISTER_RV32E, 0));
+
+    let mut parser: InstructionsParser = InstructionsParser::new();
+    parser.add(RV32I::ECALL.new());
+    assert_eq!(parser.get_registers(0), (0, SYSCALL_REGISTER, 0));
+}
+
+#[test]
+#[should_panic(expected = "not available in RV32E")]
+fn test_rv32e_decoding_invalid_register() {
+    let mut parser: InstructionsParser = InstructionsParser::new().with_rv32e();
+    parser.add(RV32I::ADDI.new().set_imm(1).set_rd(16));
+}
+
+#[test]
+fn test_interpreter_rv32e_fft64_ref() {
+    test_interpreter_rv32e::<CGGI, FFT64Ref>()
+}
+
+fn test_interpreter_rv32e<BRA: BlindRotationAlgo, BE: Backend>()
+where
+    Module<BE>: ModuleNew<BE>
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + PrepareMultiple<BE, BRA>
+        + ModuleN
+        + GLWEEncryptSk<BE>
+        + FheUintPreparedEncryptSk<u32, BE>
+        + GLWEAutomorphismKeyEncryptSk<BE>
+        + GGLWEToGGSWKeyEncryptSk<BE>
+        + GLWETrace<BE>
+        + BDDKeyEncryptSk<BRA, BE>
+        + GGSWPreparedFactory<BE>
+        + GLWEExternalProduct<BE>
+        + GLWEPackerOps<BE>
+        + GLWEPacking<BE>
+        + FheUintPrepare<BRA, BE>
+        + GGSWBlindRotation<u32, BE>
+        + GLWEDecrypt<BE>
+        + GLWEAutomorphismKeyPreparedFactory<BE>
+        + GGLWEToGGSWKeyPreparedFactory<BE>
+        + BDDKeyPreparedFactory<BRA, BE>
+        + GLWEBlindRetrieval<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+    BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+{
+    let rom: Vec<Instruction> = vec![
+        // RD[1] <- 5
+        RV32I::ADDI.new().set_imm(5).set_rd(1),
+        // RD[15] <- RD[1] + RD[1]
+        RV32I::ADD.new().set_rs1(1).set_rs2(1).set_rd(15),
+        // t0 <- SYS_EXIT
+        RV32I::ADDI.new().set_imm(0xFFF).set_rd(5),
+        // Exits
+        RV32I::ECALL.new(),
+        // RD[2] <- 7, never executed
+        RV32I::ADDI.new().set_imm(7).set_rd(2),
+    ];
+
+    let ram: Vec<u32> = vec![0u32; 64];
+
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+    let module: &Module<BE> = params.module();
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([0u8; 32]);
+    let mut source_xe: Source = Source::new([0u8; 32]);
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 24);
+
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_lwe: LWESecret<Vec<u8>> = LWESecret::alloc(params.n_lwe());
+    sk_lwe.fill_binary_block(params.lwe_block_size(), &mut source_xs);
+
+    let mut interpreter: Interpreter<BE> =
+        Interpreter::new_with_debug(&params, rom.len(), ram.len());
+    interpreter.set_rv32e(true);
+    assert!(interpreter.is_rv32e());
+    assert_eq!(interpreter.reg_bit_size, 4);
+
+    let mut instructions = InstructionsParser::new().with_rv32e();
+    for inst in &rom {
+        instructions.add(*inst);
+    }
+
+    let mut sk_glwe_prepared: GLWESecretPrepared<Vec<u8>, BE> =
+        GLWESecretPrepared::alloc(module, sk_glwe.rank());
+    sk_glwe_prepared.prepare(module, &sk_glwe);
+
+    interpreter.instructions_encrypt_sk(
+        module,
+        &instructions,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    interpreter.ram_encrypt_sk(
+        module,
+        &ram,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 28);
+
+    let key: VMKeys<Vec<u8>, BRA> =
+        VMKeys::encrypt_sk(&params, &sk_lwe, &sk_glwe, &mut source_xa, &mut source_xe);
+
+    let mut key_prepared: VMKeysPrepared<Vec<u8>, BRA, BE> = VMKeysPrepared::alloc(&params);
+    key_prepared.prepare(module, &key, scratch.borrow());
+
+    for _ in 0..rom.len() {
+        interpreter.cycle_debug(module, &key_prepared, &sk_glwe_prepared, scratch.borrow());
+    }
+
+    interpreter.halt_status_update(module, &key_prepared, scratch.borrow());
+    let status: HaltStatus =
+        interpreter.halt_status_decrypt(module, &sk_glwe_prepared, scratch.borrow());
+    assert_eq!(
+        status,
+        HaltStatus {
+            halted: true,
+            cycles: 3,
+        }
+    );
+
+    // The debug VM checks the encrypted registers against its own at each cycle.
+    let registers: &[u32; 32] = &interpreter.vm_debug.as_ref().unwrap().registers;
+    assert_eq!(registers[1], 5);
+    assert_eq!(registers[15], 10);
+    assert_eq!(registers[5], u32::MAX);
+    assert_eq!(registers[2], 0);
+}
@@ -0,0 +1,298 @@
+use poulpy_core::{
+    layouts::{
+        GGLWEToGGSWKeyPreparedFactory, GGSWPreparedFactory, GLWEAutomorphismKeyPreparedFactory,
+        GLWEInfos, GLWESecret, GLWESecretPrepared, GLWESecretPreparedFactory, LWESecret,
+    },
+    GGLWEToGGSWKeyEncryptSk, GLWEAutomorphismKeyEncryptSk, GLWEDecrypt, GLWEEncryptSk,
+    GLWEExternalProduct, GLWEPackerOps, GLWEPacking, GLWETrace, ScratchTakeCore,
+};
+use poulpy_cpu_ref::FFT64Ref;
+use poulpy_hal::{
+    api::{ModuleN, ModuleNew, ScratchOwnedAlloc, ScratchOwnedBorrow},
+    layouts::{Backend, Module, Scratch, ScratchOwned},
+    source::Source,
+};
+use poulpy_schemes::bin_fhe::{
+    bdd_arithmetic::{
+        BDDKeyEncryptSk, BDDKeyPreparedFactory, FheUintPrepare, FheUintPreparedEncryptSk,
+        FheUintPreparedFactory, GGSWBlindRotation, GLWEBlindRetrieval, GetBitCircuitInfo, Node,
+    },
+    blind_rotation::{BlindRotationAlgo, BlindRotationKey, BlindRotationKeyFactory, CGGI},
+};
+
+use crate::{
+    bdd::DynamicCircuit,
+    keys::{VMKeys, VMKeysPrepared},
+    parameters::CryptographicParameters,
+    prepare::PrepareMultiple,
+    rv32m::{op_bit_size, rv32m_circuits, RV32MCircuits, MUL_STEP_BITS},
+    Instruction, InstructionSet, InstructionsParser, Interpreter, RD_UPDATE,
+    RD_UPDATE_RV32M_OP_LIST, RV32I, RV32M,
+};
+
+// Mirrors the evaluation of a BDD circuit, with the same state layout.
+pub(super) fn eval_plain_circuit<C: GetBitCircuitInfo + ?Sized>(
+    circuit: &C,
+    inputs: &[bool],
+) -> Vec<bool> {
+    (0..circuit.output_size())
+        .map(|i| {
+            let (nodes, state_size) = circuit.get_circuit(i);
+            if state_size == 0 {
+                return false;
+            }
+            let mut prev: Vec<bool> = vec![false; state_size];
+            let mut next: Vec<bool> = vec![false; state_size];
+            prev[1] = true;
+            let (all_but_last, last) = nodes.split_at(nodes.len() - state_size);
+            for level in all_but_last.chunks_exact(state_size) {
+                for (j, node) in level.iter().enumerate() {
+                    match node {
+                        Node::Cmux(input, hi, lo) => {
+                            next[j] = if inputs[*input] { prev[*hi] } else { prev[*lo] }
+                        }
+                        Node::Copy => next[j] = prev[j],
+                        Node::None => {}
+                    }
+                }
+                std::mem::swap(&mut prev, &mut next);
+            }
+            match last[0] {
+                Node::Cmux(input, hi, lo) => {
+                    if inputs[input] {
+                        prev[hi]
+                    } else {
+                        prev[lo]
+                    }
+                }
+                _ => panic!("invalid last node"),
+            }
+        })
+        .collect()
+}
+
+pub(super) fn bits(words: &[(u32, usize)]) -> Vec<bool> {
+    words
+        .iter()
+        .flat_map(|&(word, size)| (0..size).map(move |i| (word >> i) & 1 == 1))
+        .collect()
+}
+
+pub(super) fn word(bits: &[bool]) -> u32 {
+    bits.iter()
+        .enumerate()
+        .fold(0, |acc, (i, &bit)| acc | ((bit as u32) << i))
+}
+
+// Composes the steps of the circuits the same way as [crate::rv32m::eval_rv32m].
+fn eval_plain_rv32m(circuits: &RV32MCircuits, rs1: u32, rs2: u32, op: u32) -> [u32; 6] {
+    let op_bits: usize = op_bit_size();
+
+    let mut hi: u32 = 0;
+    let mut lo: u32 = 0;
+    for step in 0..32 / MUL_STEP_BITS {
+        let b: u32 = (rs2 >> (step * MUL_STEP_BITS)) & ((1 << MUL_STEP_BITS) - 1);
+        let circuit: &DynamicCircuit = if step == 0 {
+            &circuits.mul_first_step
+        } else {
+            &circuits.mul_step
+        };
+        let out: Vec<bool> =
+            eval_plain_circuit(circuit, &bits(&[(rs1, 32), (hi, 32), (b, MUL_STEP_BITS)]));
+        hi = word(&out[..32]);
+        lo |= word(&out[32..]) << (step * MUL_STEP_BITS);
+    }
+    let inputs: Vec<bool> = bits(&[(hi, 32), (rs1, 32), (rs2, 32)]);
+    let mulh: u32 = word(&eval_plain_circuit(&circuits.mulh, &inputs));
+    let mulhsu: u32 = word(&eval_plain_circuit(&circuits.mulhsu, &inputs));
+
+    let inputs: Vec<bool> = bits(&[(rs1, 32), (rs2, 32), (op, op_bits)]);
+    let a: u32 = word(&eval_plain_circuit(&circuits.div_rs1, &inputs));
+    let b: u32 = word(&eval_plain_circuit(&circuits.div_rs2, &inputs));
+    let mut r: u32 = 0;
+    let mut q: u32 = 0;
+    for i in (0..32).rev() {
+        let circuit: &DynamicCircuit = if i == 31 {
+            &circuits.div_first_step
+        } else {
+            &circuits.div_step
+        };
+        let out: Vec<bool> =
+            eval_plain_circuit(circuit, &bits(&[(r, 32), (b, 32), ((a >> i) & 1, 1)]));
+        r = word(&out[..32]);
+        q |= (out[32] as u32) << i;
+    }
+    let inputs: Vec<bool> = bits(&[(q, 32), (r, 32), (rs1, 32), (rs2, 32), (op, op_bits)]);
+    let div: u32 = word(&eval_plain_circuit(&circuits.div_quotient, &inputs));
+    let rem: u32 = word(&eval_plain_circuit(&circuits.div_remainder, &inputs));
+
+    [lo, mulh, mulhsu, hi, div, rem]
+}
+
+#[test]
+fn test_rv32m_circuits() {
+    let circuits: &RV32MCircuits = rv32m_circuits();
+
+    // Keeps the evaluation state within the scratch space of the interpreter.
+    for circuit in [
+        &circuits.mul_step,
+        &circuits.mul_first_step,
+        &circuits.mulh,
+        &circuits.mulhsu,
+        &circuits.div_rs1,
+        &circuits.div_rs2,
+        &circuits.div_step,
+        &circuits.div_first_step,
+        &circuits.div_quotient,
+        &circuits.div_remainder,
+    ] {
+        for i in 0..circuit.output_size() {
+            assert!(circuit.get_circuit(i).1 <= 32);
+        }
+    }
+
+    let mut values: Vec<u32> = vec![
+        0,
+        1,
+        2,
+        3,
+        7,
+        0x7FFF_FFFF,
+        0x8000_0000,
+        0xFFFF_FFFF,
+        0xFFFF_FFFE,
+        0x1234_5678,
+    ];
+    let mut seed: u64 = 0x9E37_79B9_7F4A_7C15;
+    for _ in 0..6 {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        values.push(seed as u32);
+    }
+
+    for &rs1 in &values {
+        for &rs2 in &values {
+            for op in RD_UPDATE_RV32M_OP_LIST.iter().filter(|op| op.is_rv32m()) {
+                let [mul, mulh, mulhsu, mulhu, div, rem] =
+                    eval_plain_rv32m(circuits, rs1, rs2, op.id());
+                let have: u32 = match op {
+                    RD_UPDATE::MUL => mul,
+                    RD_UPDATE::MULH => mulh,
+                    RD_UPDATE::MULHSU => mulhsu,
+                    RD_UPDATE::MULHU => mulhu,
+                    RD_UPDATE::DIV | RD_UPDATE::DIVU => div,
+                    _ => rem,
+                };
+                let want: u32 = op.eval_plain(0, rs1, rs2, 0, 0);
+                assert_eq!(have, want, "{op:?} rs1: {rs1:#x} rs2: {rs2:#x}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_interpreter_rv32m_cycles_fft64_ref() {
+    test_interpreter_rv32m_cycles::<CGGI, FFT64Ref>()
+}
+
+fn test_interpreter_rv32m_cycles<BRA: BlindRotationAlgo, BE: Backend>()
+where
+    Module<BE>: ModuleNew<BE>
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + PrepareMultiple<BE, BRA>
+        + ModuleN
+        + GLWEEncryptSk<BE>
+        + FheUintPreparedEncryptSk<u32, BE>
+        + GLWEAutomorphismKeyEncryptSk<BE>
+        + GGLWEToGGSWKeyEncryptSk<BE>
+        + GLWETrace<BE>
+        + BDDKeyEncryptSk<BRA, BE>
+        + GGSWPreparedFactory<BE>
+        + GLWEExternalProduct<BE>
+        + GLWEPackerOps<BE>
+        + GLWEPacking<BE>
+        + FheUintPrepare<BRA, BE>
+        + GGSWBlindRotation<u32, BE>
+        + GLWEDecrypt<BE>
+        + GLWEAutomorphismKeyPreparedFactory<BE>
+        + GGLWEToGGSWKeyPreparedFactory<BE>
+        + BDDKeyPreparedFactory<BRA, BE>
+        + GLWEBlindRetrieval<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+    BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+{
+    let rom: Vec<Instruction> = vec![
+        // RD[1] <- -7
+        RV32I::ADDI.new().set_imm(0xFF9).set_rd(1),
+        // RD[2] <- 3
+        RV32I::ADDI.new().set_imm(3).set_rd(2),
+        // RD[3] <- RD[1] * RD[2]
+        RV32M::MUL.new().set_rs1(1).set_rs2(2).set_rd(3),
+        // RD[4] <- RD[1] / RD[2]
+        RV32M::DIV.new().set_rs1(1).set_rs2(2).set_rd(4),
+    ];
+
+    let ram: Vec<u32> = vec![0u32; 64];
+
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+    let module: &Module<BE> = params.module();
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([0u8; 32]);
+    let mut source_xe: Source = Source::new([0u8; 32]);
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 24);
+
+    // Generates a new secret-key along with the public evaluation keys.
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_lwe: LWESecret<Vec<u8>> = LWESecret::alloc(params.n_lwe());
+    sk_lwe.fill_binary_block(params.lwe_block_size(), &mut source_xs);
+
+    let mut interpreter: Interpreter<BE> =
+        Interpreter::new_with_debug(&params, rom.len(), ram.len());
+    interpreter.set_instruction_set(InstructionSet::RV32M);
+
+    let mut instructions = InstructionsParser::new();
+    for inst in &rom {
+        instructions.add(*inst);
+    }
+
+    let mut sk_glwe_prepared: GLWESecretPrepared<Vec<u8>, BE> =
+        GLWESecretPrepared::alloc(module, sk_glwe.rank());
+    sk_glwe_prepared.prepare(module, &sk_glwe);
+
+    interpreter.instructions_encrypt_sk(
+        module,
+        &instructions,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    interpreter.ram_encrypt_sk(
+        module,
+        &ram,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 28);
+
+    let key: VMKeys<Vec<u8>, BRA> =
+        VMKeys::encrypt_sk(&params, &sk_lwe, &sk_glwe, &mut source_xa, &mut source_xe);
+
+    let mut key_prepared: VMKeysPrepared<Vec<u8>, BRA, BE> = VMKeysPrepared::alloc(&params);
+    key_prepared.prepare(module, &key, scratch.borrow());
+
+    // The debug VM checks every decrypted register write against [RD_UPDATE::eval_plain].
+    for _ in 0..rom.len() {
+        interpreter.cycle_debug(module, &key_prepared, &sk_glwe_prepared, scratch.borrow());
+    }
+}
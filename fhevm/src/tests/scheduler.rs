@@ -0,0 +1,214 @@
+use poulpy_core::{
+    layouts::{
+        GGLWEToGGSWKeyPreparedFactory, GGSWPreparedFactory, GLWEAutomorphismKeyPreparedFactory,
+        GLWEInfos, GLWESecret, GLWESecretPrepared, GLWESecretPreparedFactory, LWESecret,
+    },
+    GGLWEToGGSWKeyEncryptSk, GLWEAutomorphismKeyEncryptSk, GLWEDecrypt, GLWEEncryptSk,
+    GLWEExternalProduct, GLWEPackerOps, GLWEPacking, GLWETrace, ScratchTakeCore,
+};
+use poulpy_cpu_ref::FFT64Ref;
+use poulpy_hal::{
+    api::{ModuleN, ModuleNew, ScratchOwnedAlloc, ScratchOwnedBorrow},
+    layouts::{Backend, Module, Scratch, ScratchOwned},
+    source::Source,
+};
+use poulpy_schemes::bin_fhe::{
+    bdd_arithmetic::{
+        BDDKeyEncryptSk, BDDKeyPreparedFactory, FheUintPrepare, FheUintPreparedEncryptSk,
+        FheUintPreparedFactory, GGSWBlindRotation, GLWEBlindRetrieval,
+    },
+    blind_rotation::{BlindRotationAlgo, BlindRotationKey, BlindRotationKeyFactory, CGGI},
+};
+
+use crate::{
+    keys::{VMKeys, VMKeysPrepared},
+    parameters::CryptographicParameters,
+    prepare::PrepareMultiple,
+    scheduler::{split_threads, UPDATE_COSTS},
+    CycleHook, Instruction, InstructionsParser, Interpreter, NoiseModel, Phase, RV32I,
+};
+
+// Records the order in which the hooks are called.
+struct Recorder(Vec<(bool, Phase)>);
+
+impl<BE: Backend> CycleHook<BE> for Recorder {
+    fn before_phase(&mut self, _interpreter: &Interpreter<BE>, phase: Phase) {
+        self.0.push((false, phase));
+    }
+
+    fn after_phase(&mut self, _interpreter: &Interpreter<BE>, phase: Phase) {
+        self.0.push((true, phase));
+    }
+}
+
+#[test]
+fn test_split_threads() {
+    // 210 : 81 : 81, the register update gets the extra threads first
+    let work: [f64; 3] = [210.0, 81.0, 81.0];
+    assert_eq!(split_threads(3, work), [1, 1, 1]);
+    assert_eq!(split_threads(5, work), [3, 1, 1]);
+    assert_eq!(split_threads(8, work), [4, 2, 2]);
+    assert_eq!(split_threads(32, work), [18, 7, 7]);
+
+    // Even split at the first cycle, the remainder going to the first updates
+    assert_eq!(split_threads(3, UPDATE_COSTS), [1, 1, 1]);
+    assert_eq!(split_threads(5, UPDATE_COSTS), [2, 2, 1]);
+    assert_eq!(split_threads(8, UPDATE_COSTS), [3, 3, 2]);
+    assert_eq!(split_threads(32, UPDATE_COSTS), [11, 11, 10]);
+
+    // Every update keeps a thread, even without work
+    assert_eq!(split_threads(6, [1.0, 0.0, 0.0]), [4, 1, 1]);
+}
+
+#[test]
+#[should_panic]
+fn test_split_threads_too_few() {
+    split_threads(2, UPDATE_COSTS);
+}
+
+#[test]
+fn test_interpreter_concurrent_updates_fft64_ref() {
+    test_interpreter_concurrent_updates::<CGGI, FFT64Ref>()
+}
+
+fn test_interpreter_concurrent_updates<BRA: BlindRotationAlgo, BE: Backend>()
+where
+    Module<BE>: ModuleNew<BE>
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + PrepareMultiple<BE, BRA>
+        + ModuleN
+        + GLWEEncryptSk<BE>
+        + FheUintPreparedEncryptSk<u32, BE>
+        + GLWEAutomorphismKeyEncryptSk<BE>
+        + GGLWEToGGSWKeyEncryptSk<BE>
+        + GLWETrace<BE>
+        + BDDKeyEncryptSk<BRA, BE>
+        + GGSWPreparedFactory<BE>
+        + GLWEExternalProduct<BE>
+        + GLWEPackerOps<BE>
+        + GLWEPacking<BE>
+        + FheUintPrepare<BRA, BE>
+        + GGSWBlindRotation<u32, BE>
+        + GLWEDecrypt<BE>
+        + GLWEAutomorphismKeyPreparedFactory<BE>
+        + GGLWEToGGSWKeyPreparedFactory<BE>
+        + BDDKeyPreparedFactory<BRA, BE>
+        + GLWEBlindRetrieval<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+    BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+{
+    let rom: Vec<Instruction> = vec![
+        // RD[31] <- 1<<18
+        RV32I::LUI.new().set_imm(1 << 6).set_rd(31),
+        // RD[1] <- RAM[3]
+        RV32I::LW.new().set_imm(12).set_rs1(31).set_rd(1),
+        // RD[1] <- RD[1] + 1
+        RV32I::ADDI.new().set_imm(1).set_rs1(1).set_rd(1),
+        // RAM[5] <- RD[1]
+        RV32I::SW.new().set_imm(20).set_rs1(31).set_rs2(1),
+        // RAM[6] <- RD[1]
+        RV32I::SW.new().set_imm(24).set_rs1(31).set_rs2(1),
+    ];
+
+    let ram: Vec<u32> = (0..16).map(|i| 0x0101_0101 * i).collect();
+
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+    let module: &Module<BE> = params.module();
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([0u8; 32]);
+    let mut source_xe: Source = Source::new([0u8; 32]);
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 24);
+
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_lwe: LWESecret<Vec<u8>> = LWESecret::alloc(params.n_lwe());
+    sk_lwe.fill_binary_block(params.lwe_block_size(), &mut source_xs);
+
+    let mut interpreter: Interpreter<BE> =
+        Interpreter::new_with_debug(&params, rom.len(), ram.len());
+
+    let mut instructions = InstructionsParser::new();
+    for inst in &rom {
+        instructions.add(*inst);
+    }
+
+    let mut sk_glwe_prepared: GLWESecretPrepared<Vec<u8>, BE> =
+        GLWESecretPrepared::alloc(module, sk_glwe.rank());
+    sk_glwe_prepared.prepare(module, &sk_glwe);
+
+    interpreter.instructions_encrypt_sk(
+        module,
+        &instructions,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    interpreter.ram_encrypt_sk(
+        module,
+        &ram,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 28);
+
+    let key: VMKeys<Vec<u8>, BRA> =
+        VMKeys::encrypt_sk(&params, &sk_lwe, &sk_glwe, &mut source_xa, &mut source_xe);
+
+    let mut key_prepared: VMKeysPrepared<Vec<u8>, BRA, BE> = VMKeysPrepared::alloc(&params);
+    key_prepared.prepare(module, &key, scratch.borrow());
+
+    // Refreshes a word every cycle, next to the updates
+    interpreter.set_ram_noise_model(Some(NoiseModel {
+        fresh: -20.0,
+        pass: -10.0,
+        max: -10.0 + 0.5 * 17.5f64.log2(),
+    }));
+    interpreter.set_threads(4);
+    interpreter.set_concurrent_updates(true);
+
+    // The debug VM checks every update
+    let mut recorder: Recorder = Recorder(Vec::new());
+    interpreter.cycle_debug_with_hook(
+        module,
+        &key_prepared,
+        &sk_glwe_prepared,
+        &mut recorder,
+        scratch.borrow(),
+    );
+    let updates: Vec<(bool, Phase)> = recorder.0[8..].to_vec();
+    assert_eq!(
+        updates,
+        [false, true]
+            .into_iter()
+            .flat_map(|done| Phase::ALL[4..].iter().map(move |phase| (done, *phase)))
+            .collect::<Vec<(bool, Phase)>>()
+    );
+
+    for _ in 1..rom.len() {
+        interpreter.cycle_debug(module, &key_prepared, &sk_glwe_prepared, scratch.borrow());
+    }
+
+    let mut ram_have: Vec<u32> = vec![0u32; ram.len()];
+    interpreter.ram_decrypt(module, &mut ram_have, &sk_glwe_prepared, scratch.borrow());
+    assert_eq!(ram_have[5], 0x0303_0304);
+    assert_eq!(ram_have[6], 0x0303_0304);
+
+    // The updates overlap, so the critical path is shorter than their sum
+    for measurement in &interpreter.measurements.cycle_measurements {
+        let sum = Phase::ALL
+            .iter()
+            .map(|phase| measurement.phase_time(*phase))
+            .sum();
+        assert!(measurement.time_critical_path < sum);
+        assert!(measurement.time_critical_path <= measurement.total_cycle_time);
+    }
+}
@@ -0,0 +1,576 @@
+use std::{io::ErrorKind, time::Duration};
+
+use poulpy_core::{
+    layouts::{
+        GGLWEToGGSWKeyPreparedFactory, GGSWPreparedFactory, GLWEAutomorphismKeyDecompress,
+        GLWEAutomorphismKeyPreparedFactory, GLWEDecompress, GLWEInfos, GLWESecret,
+        GLWESecretPrepared, GLWESecretPreparedFactory, LWESecret,
+    },
+    GGLWEToGGSWKeyEncryptSk, GLWEAutomorphismKeyCompressedEncryptSk, GLWEAutomorphismKeyEncryptSk,
+    GLWECompressedEncryptSk, GLWEDecrypt, GLWEEncryptSk, GLWEExternalProduct, GLWEPackerOps,
+    GLWEPacking, GLWETrace, ScratchTakeCore,
+};
+use poulpy_cpu_ref::FFT64Ref;
+use poulpy_hal::{
+    api::{ModuleN, ModuleNew, ScratchOwnedAlloc, ScratchOwnedBorrow},
+    layouts::{Backend, Module, Scratch, ScratchOwned, WriterTo},
+    source::Source,
+};
+use poulpy_schemes::bin_fhe::{
+    bdd_arithmetic::{
+        BDDKeyEncryptSk, BDDKeyPreparedFactory, FheUintPrepare, FheUintPreparedEncryptSk,
+        FheUintPreparedFactory, GGSWBlindRotation, GLWEBlindRetrieval,
+    },
+    blind_rotation::{BlindRotationKey, BlindRotationKeyFactory, CGGI},
+};
+
+use crate::{
+    keys::{BlindRotationAlgoId, VMKeys, VMKeysPrepared, VM_KEYS_MAGIC, VM_KEYS_VERSION},
+    parameters::CryptographicParameters,
+    prepare::PrepareMultiple,
+    serialization::{read_bytes, write_bytes, Header},
+    Instruction, InstructionsParser, Interpreter, Measurement, RV32I,
+};
+
+#[test]
+fn test_header() {
+    let params: CryptographicParameters<FFT64Ref> = CryptographicParameters::new();
+    let header: Header = Header::new(VM_KEYS_MAGIC, VM_KEYS_VERSION, params.fingerprint());
+
+    let mut bytes: Vec<u8> = Vec::new();
+    header.write_to(&mut bytes).unwrap();
+    let have: Header = Header::read_from(&mut bytes.as_slice()).unwrap();
+    assert_eq!(have, header);
+    have.check(&header).unwrap();
+
+    let other_version: Header = Header::new(VM_KEYS_MAGIC, VM_KEYS_VERSION + 1, header.fingerprint);
+    assert_eq!(
+        other_version.check(&header).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+
+    let other_params: Header = Header::new(VM_KEYS_MAGIC, VM_KEYS_VERSION, !header.fingerprint);
+    assert_eq!(
+        other_params.check(&header).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+
+    let other_magic: Header = Header::new(*b"XXXX", VM_KEYS_VERSION, header.fingerprint);
+    assert_eq!(
+        other_magic.check(&header).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+}
+
+#[test]
+fn test_bytes() {
+    let mut bytes: Vec<u8> = Vec::new();
+    write_bytes(&mut bytes, b"phantom").unwrap();
+    assert_eq!(read_bytes(&mut bytes.as_slice()).unwrap(), b"phantom");
+
+    // A length prefix beyond the available bytes is rejected without
+    // allocating it.
+    let mut bytes: Vec<u8> = u64::MAX.to_le_bytes().to_vec();
+    bytes.extend_from_slice(b"phantom");
+    assert_eq!(
+        read_bytes(&mut bytes.as_slice()).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+}
+
+#[test]
+fn test_vm_keys_serialization_fft64_ref() {
+    test_vm_keys_serialization::<CGGI, FFT64Ref>()
+}
+
+fn test_vm_keys_serialization<BRA: BlindRotationAlgoId, BE: Backend>()
+where
+    Module<BE>: ModuleNew<BE>
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + PrepareMultiple<BE, BRA>
+        + ModuleN
+        + GLWEEncryptSk<BE>
+        + FheUintPreparedEncryptSk<u32, BE>
+        + GLWEAutomorphismKeyEncryptSk<BE>
+        + GGLWEToGGSWKeyEncryptSk<BE>
+        + GLWETrace<BE>
+        + BDDKeyEncryptSk<BRA, BE>
+        + GGSWPreparedFactory<BE>
+        + GLWEExternalProduct<BE>
+        + GLWEPackerOps<BE>
+        + GLWEPacking<BE>
+        + FheUintPrepare<BRA, BE>
+        + GGSWBlindRotation<u32, BE>
+        + GLWEDecrypt<BE>
+        + GLWEAutomorphismKeyPreparedFactory<BE>
+        + GGLWEToGGSWKeyPreparedFactory<BE>
+        + BDDKeyPreparedFactory<BRA, BE>
+        + GLWEBlindRetrieval<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+    BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+{
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([0u8; 32]);
+    let mut source_xe: Source = Source::new([0u8; 32]);
+
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_lwe: LWESecret<Vec<u8>> = LWESecret::alloc(params.n_lwe());
+    sk_lwe.fill_binary_block(params.lwe_block_size(), &mut source_xs);
+
+    let keys: VMKeys<Vec<u8>, BRA> =
+        VMKeys::encrypt_sk(&params, &sk_lwe, &sk_glwe, &mut source_xa, &mut source_xe);
+    let mut bytes: Vec<u8> = Vec::new();
+    keys.write_to(&params, &mut bytes).unwrap();
+    drop(keys);
+
+    let loaded: VMKeys<Vec<u8>, BRA> = VMKeys::read_from(&params, &mut bytes.as_slice()).unwrap();
+    let mut rewritten: Vec<u8> = Vec::new();
+    loaded.write_to(&params, &mut rewritten).unwrap();
+    assert!(rewritten == bytes);
+
+    // Truncated keys are rejected.
+    assert!(VMKeys::<Vec<u8>, BRA>::read_from(&params, &mut &bytes[..bytes.len() / 2]).is_err());
+
+    // So are keys repeating a galois element: overwrite the second element
+    // with the first, which follow the header, the algorithm id and the count.
+    let mut header: Vec<u8> = Vec::new();
+    Header::new(VM_KEYS_MAGIC, VM_KEYS_VERSION, params.fingerprint())
+        .write_to(&mut header)
+        .unwrap();
+    let mut evk: Vec<u8> = Vec::new();
+    loaded
+        .evk_ram()
+        .values()
+        .next()
+        .unwrap()
+        .write_to(&mut evk)
+        .unwrap();
+    let first: usize = header.len() + 16;
+    let second: usize = first + 8 + evk.len();
+    let mut duplicated: Vec<u8> = bytes.clone();
+    duplicated.copy_within(first..first + 8, second);
+    let err = VMKeys::<Vec<u8>, BRA>::read_from(&params, &mut duplicated.as_slice())
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert!(err.to_string().contains("duplicate galois element"));
+
+    // The loaded keys evaluate a cycle
+    check_keys_evaluate(&params, &loaded, &sk_glwe, &mut source_xa, &mut source_xe);
+}
+
+#[test]
+fn test_vm_keys_compressed_serialization_fft64_ref() {
+    test_vm_keys_compressed_serialization::<CGGI, FFT64Ref>()
+}
+
+fn test_vm_keys_compressed_serialization<BRA: BlindRotationAlgoId, BE: Backend>()
+where
+    Module<BE>: ModuleNew<BE>
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + PrepareMultiple<BE, BRA>
+        + ModuleN
+        + GLWEEncryptSk<BE>
+        + FheUintPreparedEncryptSk<u32, BE>
+        + GLWEAutomorphismKeyCompressedEncryptSk<BE>
+        + GLWEAutomorphismKeyDecompress
+        + GGLWEToGGSWKeyEncryptSk<BE>
+        + GLWETrace<BE>
+        + BDDKeyEncryptSk<BRA, BE>
+        + GGSWPreparedFactory<BE>
+        + GLWEExternalProduct<BE>
+        + GLWEPackerOps<BE>
+        + GLWEPacking<BE>
+        + FheUintPrepare<BRA, BE>
+        + GGSWBlindRotation<u32, BE>
+        + GLWEDecrypt<BE>
+        + GLWEAutomorphismKeyPreparedFactory<BE>
+        + GGLWEToGGSWKeyPreparedFactory<BE>
+        + BDDKeyPreparedFactory<BRA, BE>
+        + GLWEBlindRetrieval<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+    BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+{
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([0u8; 32]);
+    let mut source_xe: Source = Source::new([0u8; 32]);
+
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_lwe: LWESecret<Vec<u8>> = LWESecret::alloc(params.n_lwe());
+    sk_lwe.fill_binary_block(params.lwe_block_size(), &mut source_xs);
+
+    let mut compressed: Vec<u8> = Vec::new();
+    VMKeys::<Vec<u8>, BRA>::encrypt_sk_compressed_to(
+        &params,
+        &sk_lwe,
+        &sk_glwe,
+        &mut source_xa,
+        &mut source_xe,
+        &mut compressed,
+    )
+    .unwrap();
+
+    // The magics keep the two formats apart
+    assert!(VMKeys::<Vec<u8>, BRA>::read_from(&params, &mut compressed.as_slice()).is_err());
+
+    let loaded: VMKeys<Vec<u8>, BRA> =
+        VMKeys::read_compressed_from(&params, &mut compressed.as_slice()).unwrap();
+    let mut uncompressed: Vec<u8> = Vec::new();
+    loaded.write_to(&params, &mut uncompressed).unwrap();
+    assert!(compressed.len() < uncompressed.len());
+
+    // The decompressed keys evaluate a cycle
+    check_keys_evaluate(&params, &loaded, &sk_glwe, &mut source_xa, &mut source_xe);
+}
+
+// Runs a program loading, incrementing and storing a RAM word with `keys`.
+fn check_keys_evaluate<BRA: BlindRotationAlgoId, BE: Backend>(
+    params: &CryptographicParameters<BE>,
+    keys: &VMKeys<Vec<u8>, BRA>,
+    sk_glwe: &GLWESecret<Vec<u8>>,
+    source_xa: &mut Source,
+    source_xe: &mut Source,
+) where
+    Module<BE>: ModuleNew<BE>
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + PrepareMultiple<BE, BRA>
+        + ModuleN
+        + GLWEEncryptSk<BE>
+        + FheUintPreparedEncryptSk<u32, BE>
+        + GLWETrace<BE>
+        + GGSWPreparedFactory<BE>
+        + GLWEExternalProduct<BE>
+        + GLWEPackerOps<BE>
+        + GLWEPacking<BE>
+        + FheUintPrepare<BRA, BE>
+        + GGSWBlindRotation<u32, BE>
+        + GLWEDecrypt<BE>
+        + GLWEAutomorphismKeyPreparedFactory<BE>
+        + GGLWEToGGSWKeyPreparedFactory<BE>
+        + BDDKeyPreparedFactory<BRA, BE>
+        + GLWEBlindRetrieval<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+    BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+{
+    let module: &Module<BE> = params.module();
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 28);
+
+    let mut sk_glwe_prepared: GLWESecretPrepared<Vec<u8>, BE> =
+        GLWESecretPrepared::alloc(module, sk_glwe.rank());
+    sk_glwe_prepared.prepare(module, sk_glwe);
+
+    let mut key_prepared: VMKeysPrepared<Vec<u8>, BRA, BE> = VMKeysPrepared::alloc(params);
+    key_prepared.prepare(module, keys, scratch.borrow());
+
+    let rom: Vec<Instruction> = vec![
+        // RD[31] <- 1<<18
+        RV32I::LUI.new().set_imm(1 << 6).set_rd(31),
+        // RD[1] <- RAM[3]
+        RV32I::LW.new().set_imm(12).set_rs1(31).set_rd(1),
+        // RD[1] <- RD[1] + 1
+        RV32I::ADDI.new().set_imm(1).set_rs1(1).set_rd(1),
+        // RAM[5] <- RD[1]
+        RV32I::SW.new().set_imm(20).set_rs1(31).set_rs2(1),
+    ];
+    let ram: Vec<u32> = (0..16).map(|i| 0x0101_0101 * i).collect();
+
+    let mut instructions: InstructionsParser = InstructionsParser::new();
+    for inst in &rom {
+        instructions.add(*inst);
+    }
+
+    let mut interpreter: Interpreter<BE> =
+        Interpreter::new_with_debug(params, rom.len(), ram.len());
+    interpreter.instructions_encrypt_sk(
+        module,
+        &instructions,
+        &sk_glwe_prepared,
+        source_xa,
+        source_xe,
+        scratch.borrow(),
+    );
+    interpreter.ram_encrypt_sk(
+        module,
+        &ram,
+        &sk_glwe_prepared,
+        source_xa,
+        source_xe,
+        scratch.borrow(),
+    );
+
+    // The debug VM checks the registers and the RAM after each cycle
+    for _ in 0..rom.len() {
+        interpreter.cycle_debug(module, &key_prepared, &sk_glwe_prepared, scratch.borrow());
+    }
+
+    let mut ram_have: Vec<u32> = vec![0u32; ram.len()];
+    interpreter.ram_decrypt(module, &mut ram_have, &sk_glwe_prepared, scratch.borrow());
+    assert_eq!(ram_have[5], 0x0303_0304);
+}
+
+#[test]
+fn test_rom_serialization_fft64_ref() {
+    test_rom_serialization::<FFT64Ref>()
+}
+
+fn test_rom_serialization<BE: Backend>()
+where
+    Module<BE>: ModuleNew<BE>
+        + ModuleN
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + GLWEEncryptSk<BE>
+        + GLWEDecrypt<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+{
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+    let module: &Module<BE> = params.module();
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 22);
+
+    let rom_size: usize = 1 << 4;
+    let ram_size: usize = 1 << 4;
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([0u8; 32]);
+    let mut source_xe: Source = Source::new([0u8; 32]);
+
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_glwe_prepared: GLWESecretPrepared<Vec<u8>, BE> =
+        GLWESecretPrepared::alloc(module, params.rank());
+    sk_glwe_prepared.prepare(module, &sk_glwe);
+
+    let mut instructions: InstructionsParser = InstructionsParser::new();
+    instructions.add(Instruction::new(258455));
+    instructions.add(Instruction::new(0x00a00093)); // addi x1, x0, 10
+
+    let mut interpreter: Interpreter<BE> = Interpreter::new(&params, rom_size, ram_size);
+    interpreter.instructions_encrypt_sk(
+        module,
+        &instructions,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    let mut bytes: Vec<u8> = Vec::new();
+    interpreter.rom_write_to(&mut bytes).unwrap();
+
+    let mut loaded: Interpreter<BE> = Interpreter::new(&params, rom_size, ram_size);
+    loaded.rom_read_from(&mut bytes.as_slice()).unwrap();
+
+    let mut imm_have: Vec<u32> = vec![0u32; rom_size];
+    loaded
+        .imm_rom
+        .decrypt(module, &mut imm_have, &sk_glwe_prepared, scratch.borrow());
+    let mut rd_have: Vec<u32> = vec![0u32; rom_size];
+    loaded
+        .rd_rom
+        .decrypt(module, &mut rd_have, &sk_glwe_prepared, scratch.borrow());
+
+    for i in 0..instructions.instructions.len() {
+        let instruction: Instruction = instructions.get_raw(i);
+        let (_, _, rd) = instruction.get_registers();
+        assert_eq!(imm_have[i], instruction.get_imm());
+        assert_eq!(rd_have[i], rd);
+    }
+
+    // ROM of a different size is rejected.
+    let mut other: Interpreter<BE> = Interpreter::new(&params, rom_size << 1, ram_size);
+    assert_eq!(
+        other
+            .rom_read_from(&mut bytes.as_slice())
+            .unwrap_err()
+            .kind(),
+        ErrorKind::InvalidData
+    );
+}
+
+#[test]
+fn test_ram_compressed_serialization_fft64_ref() {
+    test_ram_compressed_serialization::<FFT64Ref>()
+}
+
+fn test_ram_compressed_serialization<BE: Backend>()
+where
+    Module<BE>: ModuleNew<BE>
+        + ModuleN
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + GLWEEncryptSk<BE>
+        + GLWECompressedEncryptSk<BE>
+        + GLWEDecompress
+        + GLWEDecrypt<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+{
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+    let module: &Module<BE> = params.module();
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 22);
+
+    let rom_size: usize = 1 << 4;
+    let ram_size: usize = 1 << 4;
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([0u8; 32]);
+    let mut source_xe: Source = Source::new([0u8; 32]);
+
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_glwe_prepared: GLWESecretPrepared<Vec<u8>, BE> =
+        GLWESecretPrepared::alloc(module, params.rank());
+    sk_glwe_prepared.prepare(module, &sk_glwe);
+
+    let data: Vec<u32> = (0..ram_size as u32)
+        .map(|i| i.wrapping_mul(0x9E3779B9))
+        .collect();
+
+    let mut interpreter: Interpreter<BE> = Interpreter::new(&params, rom_size, ram_size);
+    let mut compressed: Vec<u8> = Vec::new();
+    interpreter
+        .ram_encrypt_sk_compressed_to(
+            module,
+            &data,
+            &sk_glwe_prepared,
+            &mut source_xa,
+            &mut source_xe,
+            scratch.borrow(),
+            &mut compressed,
+        )
+        .unwrap();
+
+    interpreter.ram_encrypt_sk(
+        module,
+        &data,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+    let mut uncompressed: Vec<u8> = Vec::new();
+    interpreter.ram.write_to(&mut uncompressed).unwrap();
+    assert!(compressed.len() < uncompressed.len());
+
+    let mut loaded: Interpreter<BE> = Interpreter::new(&params, rom_size, ram_size);
+    loaded
+        .ram_read_compressed_from(module, &mut compressed.as_slice())
+        .unwrap();
+
+    let mut have: Vec<u32> = vec![0u32; ram_size];
+    loaded.ram_decrypt(module, &mut have, &sk_glwe_prepared, scratch.borrow());
+    assert_eq!(have, data);
+}
+
+#[test]
+fn test_checkpoint_fft64_ref() {
+    test_checkpoint::<FFT64Ref>()
+}
+
+fn test_checkpoint<BE: Backend>()
+where
+    Module<BE>: ModuleNew<BE>
+        + ModuleN
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + GLWEEncryptSk<BE>
+        + GLWEDecrypt<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+{
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+    let module: &Module<BE> = params.module();
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 22);
+
+    let rom_size: usize = 1 << 4;
+    let ram_size: usize = 1 << 4;
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([0u8; 32]);
+    let mut source_xe: Source = Source::new([0u8; 32]);
+
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_glwe_prepared: GLWESecretPrepared<Vec<u8>, BE> =
+        GLWESecretPrepared::alloc(module, params.rank());
+    sk_glwe_prepared.prepare(module, &sk_glwe);
+
+    let ram: Vec<u32> = (0..ram_size as u32).map(|i| i * 3 + 1).collect();
+
+    let mut interpreter: Interpreter<BE> = Interpreter::new(&params, rom_size, ram_size);
+    interpreter.ram_encrypt_sk(
+        module,
+        &ram,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+    interpreter.cycle = 7;
+    interpreter.status_cycle = 6;
+    let mut measurement: Measurement = Measurement::new();
+    measurement.time_update_pc = Duration::from_micros(1234);
+    measurement.rd_val_fhe_uint_noise = -42.5;
+    interpreter
+        .measurements
+        .cycle_measurements
+        .push(measurement);
+
+    let mut bytes: Vec<u8> = Vec::new();
+    interpreter
+        .checkpoint_write_to(&params, &mut bytes)
+        .unwrap();
+
+    let mut resumed: Interpreter<BE> = Interpreter::new(&params, rom_size, ram_size);
+    resumed
+        .checkpoint_read_from(&params, &mut bytes.as_slice())
+        .unwrap();
+
+    assert_eq!(resumed.cycle_count(), 7);
+    assert_eq!(resumed.status_cycle, 6);
+    assert!(resumed.status_stale);
+    let measurements: &[Measurement] = &resumed.measurements.cycle_measurements;
+    assert_eq!(measurements.len(), 1);
+    assert_eq!(measurements[0].time_update_pc, Duration::from_micros(1234));
+    assert_eq!(measurements[0].rd_val_fhe_uint_noise, -42.5);
+
+    let mut have: Vec<u32> = vec![0u32; ram_size];
+    resumed.ram_decrypt(module, &mut have, &sk_glwe_prepared, scratch.borrow());
+    assert_eq!(have, ram);
+
+    // Checkpoint of an interpreter with a different RAM size is rejected.
+    let mut other: Interpreter<BE> = Interpreter::new(&params, rom_size, ram_size << 1);
+    assert_eq!(
+        other
+            .checkpoint_read_from(&params, &mut bytes.as_slice())
+            .unwrap_err()
+            .kind(),
+        ErrorKind::InvalidData
+    );
+
+    // The plaintext of the debug VM is not part of the checkpoint.
+    let mut debug: Interpreter<BE> = Interpreter::new_with_debug(&params, rom_size, ram_size);
+    assert_eq!(
+        debug
+            .checkpoint_read_from(&params, &mut bytes.as_slice())
+            .unwrap_err()
+            .kind(),
+        ErrorKind::InvalidData
+    );
+}
@@ -0,0 +1,15 @@
+use crate::sources::{os_seed, Sources};
+
+#[test]
+fn test_sources() {
+    let mut a: Sources = Sources::insecure_from_seed([7u8; 32]);
+    let mut b: Sources = Sources::insecure_from_seed([7u8; 32]);
+    assert_eq!(a.xs.new_seed(), b.xs.new_seed());
+    assert_eq!(a.xa.new_seed(), b.xa.new_seed());
+    assert_eq!(a.xe.new_seed(), b.xe.new_seed());
+
+    // Sources derived from the same seed must still be independent.
+    assert_ne!(a.xa.new_seed(), a.xe.new_seed());
+
+    assert_ne!(os_seed(), os_seed());
+}
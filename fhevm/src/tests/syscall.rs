@@ -0,0 +1,37 @@
+use crate::{InstructionsParser, RV32I, SYSCALL_REGISTER};
+
+#[test]
+fn test_syscall_lowering() {
+    let pc: u32 = 0x100;
+
+    let mut parser: InstructionsParser = InstructionsParser::new();
+    parser.add(RV32I::ECALL.new());
+    parser.add(RV32I::EBREAK.new());
+
+    // ECALL: BLT a7, x0, 0
+    let (imm, rs2, rs1, rd, rd_w, mem_w, pc_w) = parser.get(0);
+    assert_eq!((imm, rs2, rs1, rd), (0, 0, SYSCALL_REGISTER as i64, 0));
+    let (rd_op, ram_op, pc_op) = parser.get_raw(0).get_opid();
+    assert_eq!(rd_w, rd_op.id() as i64);
+    assert_eq!(mem_w, ram_op.id() as i64);
+    assert_eq!(pc_w, pc_op.id() as i64);
+
+    // Negative syscall numbers freeze the PC, others fall through.
+    for (a7, pc_want) in [
+        (-1i32 as u32, pc),
+        (-2i32 as u32, pc),
+        (0, pc + 4),
+        (1, pc + 4),
+    ] {
+        assert_eq!(pc_op.eval_plain(imm as u32, a7, 0, pc), pc_want);
+        assert_eq!(rd_op.eval_plain(imm as u32, a7, 0, pc, 0), 0);
+        assert_eq!(ram_op.eval_plain(0, 0xDEAD_BEEF, 0), 0xDEAD_BEEF);
+    }
+
+    // EBREAK: unconditionally freezes the PC.
+    let (imm, rs2, rs1, rd, _, _, _) = parser.get(1);
+    assert_eq!((imm, rs2, rs1, rd), (0, 0, 0, 0));
+    let (rd_op, _, pc_op) = parser.get_raw(1).get_opid();
+    assert_eq!(pc_op.eval_plain(imm as u32, 0, 0, pc), pc);
+    assert_eq!(rd_op.eval_plain(imm as u32, 0, 0, pc, 0), 0);
+}
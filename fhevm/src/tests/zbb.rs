@@ -0,0 +1,250 @@
+use poulpy_core::{
+    layouts::{
+        GGLWEToGGSWKeyPreparedFactory, GGSWPreparedFactory, GLWEAutomorphismKeyPreparedFactory,
+        GLWEInfos, GLWESecret, GLWESecretPrepared, GLWESecretPreparedFactory, LWESecret,
+    },
+    GGLWEToGGSWKeyEncryptSk, GLWEAutomorphismKeyEncryptSk, GLWEDecrypt, GLWEEncryptSk,
+    GLWEExternalProduct, GLWEPackerOps, GLWEPacking, GLWETrace, ScratchTakeCore,
+};
+use poulpy_cpu_ref::FFT64Ref;
+use poulpy_hal::{
+    api::{ModuleN, ModuleNew, ScratchOwnedAlloc, ScratchOwnedBorrow},
+    layouts::{Backend, Module, Scratch, ScratchOwned},
+    source::Source,
+};
+use poulpy_schemes::bin_fhe::{
+    bdd_arithmetic::{
+        BDDKeyEncryptSk, BDDKeyPreparedFactory, FheUintPrepare, FheUintPreparedEncryptSk,
+        FheUintPreparedFactory, GGSWBlindRotation, GLWEBlindRetrieval, GetBitCircuitInfo,
+    },
+    blind_rotation::{BlindRotationAlgo, BlindRotationKey, BlindRotationKeyFactory, CGGI},
+};
+
+use crate::{
+    codegen,
+    keys::{VMKeys, VMKeysPrepared},
+    parameters::CryptographicParameters,
+    prepare::PrepareMultiple,
+    tests::rv32m::{bits, eval_plain_circuit, word},
+    Instruction, InstructionSet, InstructionsParser, Interpreter, Zbb, RD_UPDATE,
+    RD_UPDATE_RV32I_OP_LIST, RD_UPDATE_RV32I_ZBB_OP_LIST, RV32I,
+};
+
+// Circuit evaluating `op` and its inputs, as wired in [crate::rd_update].
+fn zbb_circuit(
+    op: &RD_UPDATE,
+    rs1: u32,
+    rs2: u32,
+    imm: u32,
+) -> (&'static dyn GetBitCircuitInfo, [u32; 2]) {
+    match op {
+        RD_UPDATE::ANDN => (&codegen::codegen_andn::OUTPUT_CIRCUITS, [rs1, rs2]),
+        RD_UPDATE::ORN => (&codegen::codegen_orn::OUTPUT_CIRCUITS, [rs1, rs2]),
+        RD_UPDATE::XNOR => (&codegen::codegen_xnor::OUTPUT_CIRCUITS, [rs1, rs2]),
+        RD_UPDATE::CLZ => (&codegen::codegen_clz::OUTPUT_CIRCUITS, [rs1, rs1]),
+        RD_UPDATE::CTZ => (&codegen::codegen_ctz::OUTPUT_CIRCUITS, [rs1, rs1]),
+        RD_UPDATE::CPOP => (&codegen::codegen_cpop::OUTPUT_CIRCUITS, [rs1, rs1]),
+        RD_UPDATE::MAX => (&codegen::codegen_max::OUTPUT_CIRCUITS, [rs1, rs2]),
+        RD_UPDATE::MAXU => (&codegen::codegen_maxu::OUTPUT_CIRCUITS, [rs1, rs2]),
+        RD_UPDATE::MIN => (&codegen::codegen_min::OUTPUT_CIRCUITS, [rs1, rs2]),
+        RD_UPDATE::MINU => (&codegen::codegen_minu::OUTPUT_CIRCUITS, [rs1, rs2]),
+        RD_UPDATE::SEXTB => (&codegen::codegen_sext_b::OUTPUT_CIRCUITS, [rs1, rs1]),
+        RD_UPDATE::SEXTH => (&codegen::codegen_sext_h::OUTPUT_CIRCUITS, [rs1, rs1]),
+        RD_UPDATE::ZEXTH => (&codegen::codegen_zext_h::OUTPUT_CIRCUITS, [rs1, rs1]),
+        RD_UPDATE::ROL => (&codegen::codegen_rol::OUTPUT_CIRCUITS, [rs1, rs2]),
+        RD_UPDATE::ROR => (&codegen::codegen_ror::OUTPUT_CIRCUITS, [rs1, rs2]),
+        RD_UPDATE::RORI => (&codegen::codegen_ror::OUTPUT_CIRCUITS, [rs1, imm]),
+        RD_UPDATE::REV8 => (&codegen::codegen_rev8::OUTPUT_CIRCUITS, [rs1, rs1]),
+        RD_UPDATE::ORCB => (&codegen::codegen_orc_b::OUTPUT_CIRCUITS, [rs1, rs1]),
+        _ => panic!("{op:?} is not a Zbb operation"),
+    }
+}
+
+#[test]
+fn test_zbb_circuits() {
+    let mut values: Vec<u32> = vec![
+        0,
+        1,
+        0x80,
+        0x8000,
+        0x7FFF_FFFF,
+        0x8000_0000,
+        0xFFFF_FFFF,
+        0x00FF_0100,
+        0x1234_5678,
+    ];
+    let mut seed: u64 = 0x9E37_79B9_7F4A_7C15;
+    for _ in 0..6 {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        values.push(seed as u32);
+    }
+
+    for &rs1 in &values {
+        for &rs2 in &values {
+            let imm: u32 = rs2 & 0x1F;
+            for op in &RD_UPDATE_RV32I_ZBB_OP_LIST[RD_UPDATE_RV32I_OP_LIST.len()..] {
+                let (circuit, [a, b]) = zbb_circuit(op, rs1, rs2, imm);
+                let have: u32 = word(&eval_plain_circuit(circuit, &bits(&[(a, 32), (b, 32)])));
+                let want: u32 = op.eval_plain(imm, rs1, rs2, 0, 0);
+                assert_eq!(have, want, "{op:?} rs1: {rs1:#x} rs2: {rs2:#x}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_zbb_decoding() {
+    let cases: [(Instruction, RD_UPDATE); 18] = [
+        (Zbb::ANDN.new(), RD_UPDATE::ANDN),
+        (Zbb::ORN.new(), RD_UPDATE::ORN),
+        (Zbb::XNOR.new(), RD_UPDATE::XNOR),
+        (Zbb::CLZ.new(), RD_UPDATE::CLZ),
+        (Zbb::CTZ.new(), RD_UPDATE::CTZ),
+        (Zbb::CPOP.new(), RD_UPDATE::CPOP),
+        (Zbb::MAX.new(), RD_UPDATE::MAX),
+        (Zbb::MAXU.new(), RD_UPDATE::MAXU),
+        (Zbb::MIN.new(), RD_UPDATE::MIN),
+        (Zbb::MINU.new(), RD_UPDATE::MINU),
+        (Zbb::SEXTB.new(), RD_UPDATE::SEXTB),
+        (Zbb::SEXTH.new(), RD_UPDATE::SEXTH),
+        (Zbb::ZEXTH.new(), RD_UPDATE::ZEXTH),
+        (Zbb::ROL.new(), RD_UPDATE::ROL),
+        (Zbb::ROR.new(), RD_UPDATE::ROR),
+        (Zbb::RORI.new().set_shamt(7), RD_UPDATE::RORI),
+        (Zbb::REV8.new(), RD_UPDATE::REV8),
+        (Zbb::ORCB.new(), RD_UPDATE::ORCB),
+    ];
+    for (instruction, want) in cases {
+        let (rd_op, _, _) = instruction.get_opid();
+        assert_eq!(rd_op.id(), want.id(), "{instruction:?}");
+    }
+
+    // Base instructions sharing funct3 with the Zbb ones are still decoded as such.
+    for (instruction, want) in [
+        (RV32I::SLLI.new().set_shamt(5), RD_UPDATE::SLLI),
+        (RV32I::SRLI.new().set_shamt(5), RD_UPDATE::SRLI),
+        (RV32I::SRAI.new().set_shamt(5), RD_UPDATE::SRAI),
+        (RV32I::AND.new(), RD_UPDATE::AND),
+        (RV32I::SUB.new(), RD_UPDATE::SUB),
+    ] {
+        let (rd_op, _, _) = instruction.get_opid();
+        assert_eq!(rd_op.id(), want.id(), "{instruction:?}");
+    }
+
+    // The rotation amount of RORI is carried by the immediate.
+    let mut parser: InstructionsParser = InstructionsParser::new();
+    parser.add(Zbb::RORI.new().set_rs1(1).set_rd(2).set_shamt(7));
+    parser.assert_instruction(0, 7, 0, 1, 2, RD_UPDATE::RORI.id() as i64, 0, 1);
+}
+
+#[test]
+fn test_interpreter_zbb_cycles_fft64_ref() {
+    test_interpreter_zbb_cycles::<CGGI, FFT64Ref>()
+}
+
+fn test_interpreter_zbb_cycles<BRA: BlindRotationAlgo, BE: Backend>()
+where
+    Module<BE>: ModuleNew<BE>
+        + GLWESecretPreparedFactory<BE>
+        + FheUintPreparedFactory<u32, BE>
+        + PrepareMultiple<BE, BRA>
+        + ModuleN
+        + GLWEEncryptSk<BE>
+        + FheUintPreparedEncryptSk<u32, BE>
+        + GLWEAutomorphismKeyEncryptSk<BE>
+        + GGLWEToGGSWKeyEncryptSk<BE>
+        + GLWETrace<BE>
+        + BDDKeyEncryptSk<BRA, BE>
+        + GGSWPreparedFactory<BE>
+        + GLWEExternalProduct<BE>
+        + GLWEPackerOps<BE>
+        + GLWEPacking<BE>
+        + FheUintPrepare<BRA, BE>
+        + GGSWBlindRotation<u32, BE>
+        + GLWEDecrypt<BE>
+        + GLWEAutomorphismKeyPreparedFactory<BE>
+        + GGLWEToGGSWKeyPreparedFactory<BE>
+        + BDDKeyPreparedFactory<BRA, BE>
+        + GLWEBlindRetrieval<BE>,
+    ScratchOwned<BE>: ScratchOwnedAlloc<BE> + ScratchOwnedBorrow<BE>,
+    Scratch<BE>: ScratchTakeCore<BE>,
+    BlindRotationKey<Vec<u8>, BRA>: BlindRotationKeyFactory<BRA>,
+{
+    let rom: Vec<Instruction> = vec![
+        // RD[1] <- -7
+        RV32I::ADDI.new().set_imm(0xFF9).set_rd(1),
+        // RD[2] <- 3
+        RV32I::ADDI.new().set_imm(3).set_rd(2),
+        // RD[3] <- RD[1] rotr RD[2]
+        Zbb::ROR.new().set_rs1(1).set_rs2(2).set_rd(3),
+        // RD[4] <- RD[1] rotr 4
+        Zbb::RORI.new().set_imm(4).set_rs1(1).set_rd(4),
+        // RD[5] <- clz(RD[2])
+        Zbb::CLZ.new().set_rs1(2).set_rd(5),
+        // RD[6] <- min(RD[1], RD[2])
+        Zbb::MIN.new().set_rs1(1).set_rs2(2).set_rd(6),
+    ];
+
+    let ram: Vec<u32> = vec![0u32; 64];
+
+    let params: CryptographicParameters<BE> = CryptographicParameters::<BE>::new();
+    let module: &Module<BE> = params.module();
+
+    let mut source_xs: Source = Source::new([0u8; 32]);
+    let mut source_xa: Source = Source::new([0u8; 32]);
+    let mut source_xe: Source = Source::new([0u8; 32]);
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 24);
+
+    // Generates a new secret-key along with the public evaluation keys.
+    let mut sk_glwe: GLWESecret<Vec<u8>> = GLWESecret::alloc(params.n_glwe(), params.rank());
+    sk_glwe.fill_ternary_prob(0.5, &mut source_xs);
+    let mut sk_lwe: LWESecret<Vec<u8>> = LWESecret::alloc(params.n_lwe());
+    sk_lwe.fill_binary_block(params.lwe_block_size(), &mut source_xs);
+
+    let mut interpreter: Interpreter<BE> =
+        Interpreter::new_with_debug(&params, rom.len(), ram.len());
+    interpreter.set_instruction_set(InstructionSet::RV32IZbb);
+
+    let mut instructions = InstructionsParser::new();
+    for inst in &rom {
+        instructions.add(*inst);
+    }
+
+    let mut sk_glwe_prepared: GLWESecretPrepared<Vec<u8>, BE> =
+        GLWESecretPrepared::alloc(module, sk_glwe.rank());
+    sk_glwe_prepared.prepare(module, &sk_glwe);
+
+    interpreter.instructions_encrypt_sk(
+        module,
+        &instructions,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    interpreter.ram_encrypt_sk(
+        module,
+        &ram,
+        &sk_glwe_prepared,
+        &mut source_xa,
+        &mut source_xe,
+        scratch.borrow(),
+    );
+
+    let mut scratch: ScratchOwned<BE> = ScratchOwned::alloc(1 << 28);
+
+    let key: VMKeys<Vec<u8>, BRA> =
+        VMKeys::encrypt_sk(&params, &sk_lwe, &sk_glwe, &mut source_xa, &mut source_xe);
+
+    let mut key_prepared: VMKeysPrepared<Vec<u8>, BRA, BE> = VMKeysPrepared::alloc(&params);
+    key_prepared.prepare(module, &key, scratch.borrow());
+
+    // The debug VM checks every decrypted register write against [RD_UPDATE::eval_plain].
+    for _ in 0..rom.len() {
+        interpreter.cycle_debug(module, &key_prepared, &sk_glwe_prepared, scratch.borrow());
+    }
+}
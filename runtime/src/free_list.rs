@@ -0,0 +1,300 @@
+// Freeing allocator, enabled by the `free-list` feature.
+//
+// Freed blocks are kept in a list sorted by address, and merged with their
+// free neighbours so that the small RAM does not fragment into unusable
+// pieces. An allocation takes the first free block large enough, and only
+// grows the heap with `sys_alloc_aligned`, which checks that the heap does not
+// clash with the stack, if there is none.
+//
+// Blocks have no header: their size is given back by the `Layout` passed to
+// `dealloc`. Every block is rounded up to, and aligned on, the size of a
+// `FreeBlock`, so that any freed block, or any remainder of a split block,
+// can hold one.
+
+use core::{alloc::Layout, mem::size_of, ptr::null_mut};
+
+#[cfg(not(test))]
+use crate::alloc::sys_alloc_aligned;
+#[cfg(test)]
+use tests::sys_alloc_aligned;
+
+// Header written in a free block.
+struct FreeBlock {
+    size: usize,
+    next: *mut FreeBlock,
+}
+
+const BLOCK: usize = size_of::<FreeBlock>();
+
+// First free block, with the lowest address, or null if there is none.
+static mut FREE_LIST: *mut FreeBlock = null_mut();
+
+fn round_up(value: usize, align: usize) -> usize {
+    (value + align - 1) & !(align - 1)
+}
+
+// Size and alignment of the block holding `layout`.
+fn block_layout(layout: Layout) -> (usize, usize) {
+    (
+        round_up(layout.size().max(1), BLOCK),
+        layout.align().max(BLOCK),
+    )
+}
+
+// Writes a free block of `size` bytes at `addr`, followed by `next`.
+unsafe fn write_block(addr: usize, size: usize, next: *mut FreeBlock) -> *mut FreeBlock {
+    let block = addr as *mut FreeBlock;
+    block.write(FreeBlock { size, next });
+    block
+}
+
+pub(crate) unsafe fn alloc(layout: Layout) -> *mut u8 {
+    let (size, align) = block_layout(layout);
+
+    // SAFETY: Single threaded, so nothing else can touch the list while we're working.
+    let mut prev: *mut FreeBlock = null_mut();
+    let mut block: *mut FreeBlock = FREE_LIST;
+    while !block.is_null() {
+        let FreeBlock {
+            size: block_size,
+            next,
+        } = block.read();
+        let (start, end) = (block as usize, block as usize + block_size);
+        let ptr = round_up(start, align);
+        if ptr + size <= end {
+            // The bytes of the block before and after the allocation stay free.
+            let mut rest = next;
+            if ptr + size < end {
+                rest = write_block(ptr + size, end - ptr - size, rest);
+            }
+            if ptr > start {
+                rest = write_block(start, ptr - start, rest);
+            }
+            if prev.is_null() {
+                FREE_LIST = rest;
+            } else {
+                (*prev).next = rest;
+            }
+            return ptr as *mut u8;
+        }
+        prev = block;
+        block = next;
+    }
+
+    sys_alloc_aligned(size, align)
+}
+
+pub(crate) unsafe fn dealloc(ptr: *mut u8, layout: Layout) {
+    let (size, _) = block_layout(layout);
+    let start = ptr as usize;
+
+    // Finds the free blocks before and after the freed one.
+    let mut prev: *mut FreeBlock = null_mut();
+    let mut next: *mut FreeBlock = FREE_LIST;
+    while !next.is_null() && (next as usize) < start {
+        prev = next;
+        next = (*next).next;
+    }
+
+    // Merges the freed block with the next one if they are adjacent...
+    let block = if next as usize == start + size {
+        write_block(start, size + (*next).size, (*next).next)
+    } else {
+        write_block(start, size, next)
+    };
+
+    // ...and with the previous one.
+    if prev.is_null() {
+        FREE_LIST = block;
+    } else if prev as usize + (*prev).size == start {
+        (*prev).size += (*block).size;
+        (*prev).next = (*block).next;
+    } else {
+        (*prev).next = block;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{alloc::GlobalAlloc, ptr::addr_of_mut, slice};
+    use std::sync::{Mutex, MutexGuard};
+
+    const ARENA_SIZE: usize = 1024;
+
+    #[repr(align(64))]
+    struct Arena([u8; ARENA_SIZE]);
+
+    static mut ARENA: Arena = Arena([0; ARENA_SIZE]);
+
+    // The free list and the arena are global
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    // The heap never grows: allocations must fit in the arena
+    pub(super) unsafe fn sys_alloc_aligned(_bytes: usize, _align: usize) -> *mut u8 {
+        null_mut()
+    }
+
+    struct FreeList;
+
+    unsafe impl GlobalAlloc for FreeList {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            alloc(layout)
+        }
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            dealloc(ptr, layout)
+        }
+    }
+
+    // Empties the free list and frees the whole arena, returning its address.
+    fn reset() -> (MutexGuard<'static, ()>, usize) {
+        let guard = LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        unsafe {
+            FREE_LIST = null_mut();
+            let arena = addr_of_mut!(ARENA.0) as *mut u8;
+            dealloc(arena, Layout::from_size_align(ARENA_SIZE, 64).unwrap());
+            (guard, arena as usize)
+        }
+    }
+
+    // Returns the (address, size) of the free blocks.
+    fn free_blocks() -> Vec<(usize, usize)> {
+        let mut blocks = Vec::new();
+        unsafe {
+            let mut block = FREE_LIST;
+            while !block.is_null() {
+                blocks.push((block as usize, (*block).size));
+                block = (*block).next;
+            }
+        }
+        blocks
+    }
+
+    fn layout(size: usize, align: usize) -> Layout {
+        Layout::from_size_align(size, align).unwrap()
+    }
+
+    #[test]
+    fn test_alloc_dealloc() {
+        let (_guard, arena) = reset();
+        unsafe {
+            // Rounded up to a multiple of BLOCK
+            let a = alloc(layout(BLOCK + 1, 1));
+            let b = alloc(layout(1, 1));
+            assert_eq!(a as usize, arena);
+            assert_eq!(b as usize, arena + 2 * BLOCK);
+            assert_eq!(free_blocks(), [(arena + 3 * BLOCK, ARENA_SIZE - 3 * BLOCK)]);
+
+            dealloc(a, layout(BLOCK + 1, 1));
+            assert_eq!(
+                free_blocks(),
+                [
+                    (arena, 2 * BLOCK),
+                    (arena + 3 * BLOCK, ARENA_SIZE - 3 * BLOCK)
+                ]
+            );
+
+            // First fit: reuses the freed block and splits it
+            let c = alloc(layout(BLOCK, 1));
+            assert_eq!(c as usize, arena);
+            assert_eq!(
+                free_blocks(),
+                [
+                    (arena + BLOCK, BLOCK),
+                    (arena + 3 * BLOCK, ARENA_SIZE - 3 * BLOCK)
+                ]
+            );
+
+            // Too large for any free block, and the heap cannot grow
+            assert!(alloc(layout(ARENA_SIZE, 1)).is_null());
+        }
+    }
+
+    #[test]
+    fn test_coalescing() {
+        let (_guard, arena) = reset();
+        unsafe {
+            let blocks: Vec<*mut u8> = (0..4).map(|_| alloc(layout(BLOCK, 1))).collect();
+
+            // Not adjacent: kept apart
+            dealloc(blocks[0], layout(BLOCK, 1));
+            dealloc(blocks[2], layout(BLOCK, 1));
+            assert_eq!(
+                free_blocks(),
+                [
+                    (arena, BLOCK),
+                    (arena + 2 * BLOCK, BLOCK),
+                    (arena + 4 * BLOCK, ARENA_SIZE - 4 * BLOCK)
+                ]
+            );
+
+            // Merged with both neighbours
+            dealloc(blocks[1], layout(BLOCK, 1));
+            assert_eq!(
+                free_blocks(),
+                [
+                    (arena, 3 * BLOCK),
+                    (arena + 4 * BLOCK, ARENA_SIZE - 4 * BLOCK)
+                ]
+            );
+
+            // Merged with the previous and the next one, back to the whole arena
+            dealloc(blocks[3], layout(BLOCK, 1));
+            assert_eq!(free_blocks(), [(arena, ARENA_SIZE)]);
+        }
+    }
+
+    #[test]
+    fn test_alignment() {
+        let (_guard, arena) = reset();
+        unsafe {
+            let a = alloc(layout(1, 1));
+            let b = alloc(layout(1, 64));
+            assert_eq!(a as usize, arena);
+            assert_eq!(b as usize, arena + 64);
+
+            // The bytes skipped to align b stay free
+            assert_eq!(
+                free_blocks(),
+                [
+                    (arena + BLOCK, 64 - BLOCK),
+                    (arena + 64 + BLOCK, ARENA_SIZE - 64 - BLOCK)
+                ]
+            );
+            let c = alloc(layout(1, 1));
+            assert_eq!(c as usize, arena + BLOCK);
+
+            // Blocks are at least aligned on BLOCK
+            let d = alloc(layout(3, 2));
+            assert_eq!(d as usize % BLOCK, 0);
+        }
+    }
+
+    #[test]
+    fn test_realloc() {
+        let (_guard, arena) = reset();
+        unsafe {
+            let a = FreeList.alloc(layout(BLOCK, 8));
+            slice::from_raw_parts_mut(a, BLOCK).fill(0xAB);
+            let b = FreeList.alloc(layout(BLOCK, 8));
+
+            // Moved past b, with its content
+            let a = FreeList.realloc(a, layout(BLOCK, 8), 4 * BLOCK);
+            assert_eq!(a as usize, arena + 2 * BLOCK);
+            assert!(slice::from_raw_parts(a, BLOCK).iter().all(|&x| x == 0xAB));
+
+            // The old block is freed and reused
+            assert_eq!(
+                free_blocks(),
+                [(arena, BLOCK), (arena + 6 * BLOCK, ARENA_SIZE - 6 * BLOCK)]
+            );
+            let c = FreeList.alloc(layout(BLOCK, 8));
+            assert_eq!(c as usize, arena);
+
+            FreeList.dealloc(a, layout(4 * BLOCK, 8));
+            FreeList.dealloc(b, layout(BLOCK, 8));
+            FreeList.dealloc(c, layout(BLOCK, 8));
+            assert_eq!(free_blocks(), [(arena, ARENA_SIZE)]);
+        }
+    }
+}
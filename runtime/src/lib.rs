@@ -1,8 +1,13 @@
-#![no_std]
+// The free list is tested on the host, with std
+#![cfg_attr(not(test), no_std)]
 
+#[cfg(target_arch = "riscv32")]
 use core::alloc::{GlobalAlloc, Layout};
 
+#[cfg(target_arch = "riscv32")]
 mod alloc;
+#[cfg(any(test, all(target_arch = "riscv32", feature = "free-list")))]
+mod free_list;
 
 // RISC-V assembly to start the program
 
@@ -16,6 +21,7 @@ mod alloc;
 //     1. directly jumps to "_start_rust" symbol.
 //     2. Notice that "_start_rust" is linked to the "main" function defined in "/guest/src/main"
 //
+#[cfg(target_arch = "riscv32")]
 core::arch::global_asm!(
     ".option nopic
     .section .init, \"ax\"
@@ -41,20 +47,53 @@ run:
     "
 );
 
-#[cfg(target_arch = "riscv32")]
+// Syscall numbers, passed in a7 (t0 in RV32E, which has no a7). Negative numbers
+// terminate the program: the encrypted VM freezes its state on them, while the
+// others are no-ops.
+pub const SYS_PRINT: i32 = 1;
+pub const SYS_EXIT: i32 = -1;
+pub const SYS_ABORT: i32 = -2;
+
+#[cfg(all(target_arch = "riscv32", not(target_feature = "e")))]
+unsafe fn syscall(id: i32, a0: usize, a1: usize) {
+    core::arch::asm!("ecall", in("a0") a0, in("a1") a1, in("a7") id);
+}
+
+#[cfg(all(target_arch = "riscv32", target_feature = "e"))]
+unsafe fn syscall(id: i32, a0: usize, a1: usize) {
+    core::arch::asm!("ecall", in("a0") a0, in("a1") a1, in("t0") id);
+}
 
+#[cfg(target_arch = "riscv32")]
 pub fn println(v: &str) {
-    let v_addr = v.as_ptr().addr();
-    let v_len = v.len();
     unsafe {
-        core::arch::asm!("ecall", in("a0") v_addr, in("a1") v_len);
+        syscall(SYS_PRINT, v.as_ptr().addr(), v.len());
     }
 }
 
+/// Terminates the program with exit `code`.
+#[cfg(target_arch = "riscv32")]
+pub fn exit(code: u32) -> ! {
+    unsafe {
+        syscall(SYS_EXIT, code as usize, 0);
+    }
+    loop {}
+}
+
+/// Terminates the program abnormally, e.g. from a panic handler.
+#[cfg(target_arch = "riscv32")]
+pub fn abort() -> ! {
+    unsafe {
+        syscall(SYS_ABORT, 0, 0);
+    }
+    loop {}
+}
+
 /// Expose "_start_rust" symbol in binary.
 ///
 /// The function calls "main" function declared in "guest/src/main"
 #[doc(hidden)]
+#[cfg(target_arch = "riscv32")]
 #[link_section = ".init.rust"]
 #[export_name = "_start_rust"]
 pub unsafe extern "C" fn start_rust(a0: u32, a1: u32, a2: u32) -> u32 {
@@ -64,23 +103,39 @@ pub unsafe extern "C" fn start_rust(a0: u32, a1: u32, a2: u32) -> u32 {
     main(a0, a1, a2)
 }
 
-/// Following code is taken from nexus-zkvm which itself refers to risc-v rt
+// Following code is taken from nexus-zkvm which itself refers to risc-v rt
 
 #[export_name = "error: rt appears more than once"]
 #[doc(hidden)]
 pub static __ONCE__: () = ();
 
+#[cfg(target_arch = "riscv32")]
 struct Heap;
 
+#[cfg(target_arch = "riscv32")]
 #[global_allocator]
 static HEAP: Heap = Heap;
 
 // This trivial allocate will always expand the heap, and never
 // deallocates. This should be fine for small programs.
 
+#[cfg(all(target_arch = "riscv32", not(feature = "free-list")))]
 unsafe impl GlobalAlloc for Heap {
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
         alloc::sys_alloc_aligned(layout.size(), layout.align())
     }
     unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
 }
+
+// Programs allocating in a loop, e.g. with `String` or `Vec`, exhaust the heap
+// of the trivial allocator: the `free-list` feature reuses the freed memory.
+
+#[cfg(all(target_arch = "riscv32", feature = "free-list"))]
+unsafe impl GlobalAlloc for Heap {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        free_list::alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        free_list::dealloc(ptr, layout)
+    }
+}